@@ -0,0 +1,124 @@
+//! Parse-time benchmark for deep subcommand trees
+//!
+//! Run with `cargo bench --bench parse`. Uses only `std`: each case is timed
+//! over many iterations and reported as the mean time per parse.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use zfish::command::{App, Arg, Command};
+
+/// Levels below the root, and subcommands at each level
+const DEPTH: usize = 5;
+const WIDTH: usize = 10;
+
+/// A command with a few flags and options, like a real tool's subcommand
+fn leaf(name: &str) -> Command {
+    Command::new(name.to_string())
+        .about("A subcommand")
+        .arg(
+            Arg::new("force")
+                .short('f')
+                .long("force")
+                .takes_value(false),
+        )
+        .arg(Arg::new("jobs").short('j').long("jobs").default_value("4"))
+        .arg(Arg::new("target").long("target"))
+        .arg(Arg::new("feature").long("feature").multiple(true))
+        .arg(Arg::new("input").index(0))
+}
+
+/// `WIDTH` subcommands per level, `DEPTH` levels: 50 commands in all
+fn tree(level: usize) -> Command {
+    let mut cmd = leaf(&format!("cmd{}", level));
+    if level < DEPTH {
+        for i in 1..WIDTH {
+            cmd = cmd.subcommand(leaf(&format!("sib{}-{}", level, i)));
+        }
+        cmd = cmd.subcommand(tree(level + 1));
+    }
+    cmd
+}
+
+fn app() -> App {
+    let mut app = App::new("bench")
+        .version("1.0.0")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .takes_value(false)
+                .global(true),
+        )
+        .arg(Arg::new("config").long("config").global(true));
+    for i in 1..WIDTH {
+        app = app.subcommand(leaf(&format!("top{}", i)));
+    }
+    app.subcommand(tree(1))
+}
+
+/// argv that walks all the way down, with flags at every level
+fn deep_argv() -> Vec<String> {
+    let mut argv = vec!["bench".to_string(), "--config".into(), "a.toml".into()];
+    for level in 1..=DEPTH {
+        argv.push(format!("cmd{}", level));
+        argv.extend(
+            ["-f", "--jobs", "8", "--feature", "x", "--feature=y", "-v"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+    }
+    argv.push("file.txt".into());
+    argv
+}
+
+/// argv with many flags on a single command
+fn wide_argv(flags: usize) -> Vec<String> {
+    let mut argv = vec!["bench".to_string(), "top1".into()];
+    for i in 0..flags {
+        argv.push("--feature".into());
+        argv.push(format!("f{}", i));
+    }
+    argv
+}
+
+fn bench(name: &str, iterations: u32, mut f: impl FnMut()) {
+    // Warm up caches and the allocator
+    for _ in 0..iterations / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let per_iter = start.elapsed() / iterations;
+    println!("{:<40} {:>12?} / iter", name, per_iter);
+}
+
+fn main() {
+    let app = app();
+    let command = app.as_ref();
+    let deep = deep_argv();
+    let wide = wide_argv(10_000);
+
+    bench("build 50-command tree", 2_000, || {
+        black_box(self::app());
+    });
+    bench("parse 5 levels deep", 20_000, || {
+        black_box(command.try_parse_from(black_box(&deep)).unwrap());
+    });
+    bench("parse 10k options on one command", 50, || {
+        black_box(command.try_parse_from(black_box(&wide)).unwrap());
+    });
+
+    let start = Instant::now();
+    let mut flags = 0;
+    while start.elapsed() < Duration::from_millis(500) {
+        black_box(command.try_parse_from(black_box(&wide)).unwrap());
+        flags += 10_000;
+    }
+    println!(
+        "{:<40} {:>12.0} / s",
+        "options parsed",
+        flags as f64 / start.elapsed().as_secs_f64()
+    );
+}
@@ -0,0 +1,37 @@
+//! Picks the terminal backend for the target
+//!
+//! Emits `zfish_termios` for Unix targets whose `struct termios` layout
+//! `src/os/unix.rs` knows, plus `zfish_termios = "linux"` or `"bsd"` naming
+//! the layout. Windows uses `src/os/windows.rs`; every other target gets
+//! `src/os/fallback.rs`.
+
+use std::env;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rustc-check-cfg=cfg(zfish_termios, values(none(), \"linux\", \"bsd\"))");
+
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    let os = var("CARGO_CFG_TARGET_OS");
+    let arch = var("CARGO_CFG_TARGET_ARCH");
+    let vendor = var("CARGO_CFG_TARGET_VENDOR");
+
+    // Linux on MIPS, PowerPC and SPARC uses different flag values and layout
+    let generic_abi = !matches!(
+        arch.as_str(),
+        "mips" | "mips64" | "powerpc" | "powerpc64" | "sparc" | "sparc64"
+    );
+    let termios = if matches!(os.as_str(), "linux" | "android") && generic_abi {
+        Some("linux")
+    } else if vendor == "apple"
+        || matches!(os.as_str(), "freebsd" | "dragonfly" | "netbsd" | "openbsd")
+    {
+        Some("bsd")
+    } else {
+        None
+    };
+    if let Some(termios) = termios {
+        println!("cargo::rustc-cfg=zfish_termios");
+        println!("cargo::rustc-cfg=zfish_termios=\"{}\"", termios);
+    }
+}
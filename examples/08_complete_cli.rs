@@ -2,10 +2,12 @@
 // Example: Complete CLI - Putting it all together
 
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use zfish::summary::Report;
 use zfish::{Args, Color, Level, Logger, ProgressBar, Style};
 
 fn main() {
+    let started = Instant::now();
     let args = Args::parse();
 
     // Setup logger based on verbosity
@@ -75,19 +77,10 @@ fn main() {
         "Results saved to: {}",
         Color::Cyan.paint(output_file)
     ));
-    logger.info(&format!(
-        "{}",
-        Color::Green.paint("✓ Done!").style(Style::Bold)
-    ));
+    logger.info(&format!("Processed {} items", iterations));
 
-    // Summary
-    println!(
-        "\n{}",
-        Color::Magenta.paint("=== Summary ===").style(Style::Bold)
-    );
-    println!("Processed: {} items", iterations);
-    println!("Output: {}", output_file);
-    println!("Status: {}", Color::Green.paint("Success"));
+    // Summary footer: "Finished in 2.6s", plus any warnings or errors logged
+    Report::since(started).print();
 }
 
 fn show_help() {
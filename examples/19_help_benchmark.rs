@@ -0,0 +1,87 @@
+//! Help generation benchmark
+//!
+//! Times help rendering for a 200-argument command, comparing a fresh
+//! `generate_help()` String per render with `help_bytes()` into one reused
+//! buffer. Run in release mode for meaningful numbers:
+//!
+//! ```text
+//! cargo run --release --example 19_help_benchmark
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use zfish::command::{Arg, Command};
+
+const ARGS: usize = 200;
+const ITERATIONS: u32 = 2_000;
+
+fn build_command() -> Command {
+    let mut cmd = Command::new("bench")
+        .about("A command with many arguments")
+        .version("1.0.0");
+
+    for i in 0..ARGS {
+        let arg = match i % 4 {
+            0 => Arg::new(format!("input{}", i))
+                .index(i / 4)
+                .about("A positional input"),
+            1 => Arg::new(format!("flag{}", i))
+                .long(format!("flag-{}", i))
+                .takes_value(false)
+                .about("Toggles a behavior"),
+            2 => Arg::new(format!("opt{}", i))
+                .long(format!("opt-{}", i))
+                .default_value("auto")
+                .about("An option with a default"),
+            _ => Arg::new(format!("req{}", i))
+                .long(format!("req-{}", i))
+                .required(true)
+                .about("A required option"),
+        };
+        cmd = cmd.arg(arg);
+    }
+
+    for i in 0..20 {
+        cmd = cmd.subcommand(Command::new(format!("sub{}", i)).about("A subcommand"));
+    }
+    cmd
+}
+
+fn time(label: &str, mut render: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    let mut bytes = 0;
+    for _ in 0..ITERATIONS {
+        bytes = black_box(render());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<28} {:>10.2?} total  {:>8.2?}/render  ({} bytes)",
+        label,
+        elapsed,
+        elapsed / ITERATIONS,
+        bytes
+    );
+    elapsed
+}
+
+fn main() {
+    let cmd = build_command();
+    println!(
+        "Rendering help for {} args x {} iterations\n",
+        ARGS, ITERATIONS
+    );
+
+    let fresh = time("generate_help()", || cmd.generate_help().len());
+
+    let mut buf = Vec::new();
+    let reused = time("help_bytes() reused buffer", || {
+        buf.clear();
+        cmd.help_bytes(&mut buf);
+        buf.len()
+    });
+
+    println!(
+        "\nReusing a buffer: {:.2}x",
+        fresh.as_secs_f64() / reused.as_secs_f64()
+    );
+}
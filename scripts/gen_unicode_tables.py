@@ -0,0 +1,172 @@
+#!/usr/bin/env python3
+"""Generates src/unicode_tables.rs from the Unicode Character Database.
+
+Usage:
+
+    python3 scripts/gen_unicode_tables.py UCD_DIR > src/unicode_tables.rs
+
+UCD_DIR holds these files from https://www.unicode.org/Public/14.0.0/ucd/,
+keeping their layout:
+
+    UnicodeData.txt
+    DerivedNormalizationProps.txt
+    emoji/emoji-data.txt
+
+Bump UNICODE_VERSION together with the downloaded files. Only the standard
+library is used.
+"""
+
+import os
+import sys
+
+UNICODE_VERSION = "14.0.0"
+
+# Keycap bases stay text: they are ordinary ASCII unless followed by U+20E3
+KEYCAP_BASES = {ord("#"), ord("*")} | set(range(ord("0"), ord("9") + 1))
+
+
+def records(path):
+    """Yields the `;`-separated fields of each non-comment line"""
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if line:
+                yield [field.strip() for field in line.split(";")]
+
+
+def code_points(field):
+    """Expands `0041` or `0041..005A` into a range"""
+    first, _, last = field.partition("..")
+    return range(int(first, 16), int(last or first, 16) + 1)
+
+
+def read_unicode_data(ucd):
+    """Returns canonical combining classes and canonical decompositions"""
+    classes = {}
+    decompositions = {}
+    for fields in records(os.path.join(ucd, "UnicodeData.txt")):
+        cp = int(fields[0], 16)
+        if int(fields[3]):
+            classes[cp] = int(fields[3])
+        # Compatibility decompositions start with a `<tag>` and are skipped
+        if fields[5] and not fields[5].startswith("<"):
+            decompositions[cp] = [int(part, 16) for part in fields[5].split()]
+    return classes, decompositions
+
+
+def read_property(path, name):
+    """Returns the code points listed with property `name`"""
+    found = set()
+    for fields in records(path):
+        if fields[1] == name:
+            found.update(code_points(fields[0]))
+    return found
+
+
+def ranges(points):
+    """Merges sorted code points (or `(cp, value)` pairs) into runs"""
+    runs = []
+    for cp, value in points:
+        if runs and runs[-1][1] == cp - 1 and runs[-1][2] == value:
+            runs[-1][1] = cp
+        else:
+            runs.append([cp, cp, value])
+    return runs
+
+
+def table(name, doc, rust_type, rows, per_line):
+    lines = ["/// " + line for line in doc]
+    lines.append("#[rustfmt::skip]")
+    lines.append("pub(crate) const {}: &[{}] = &[".format(name, rust_type))
+    for i in range(0, len(rows), per_line):
+        lines.append("    " + " ".join(row + "," for row in rows[i : i + per_line]))
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def hex_cp(cp):
+    return "0x{:04X}".format(cp)
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit(__doc__)
+    ucd = sys.argv[1]
+
+    classes, decompositions = read_unicode_data(ucd)
+    exclusions = read_property(
+        os.path.join(ucd, "DerivedNormalizationProps.txt"), "Full_Composition_Exclusion"
+    )
+    emoji_data = os.path.join(ucd, "emoji", "emoji-data.txt")
+    emoji = read_property(emoji_data, "Emoji")
+    emoji_presentation = read_property(emoji_data, "Emoji_Presentation")
+
+    combining_rows = [
+        "({}, {}, {})".format(hex_cp(first), hex_cp(last), value)
+        for first, last, value in ranges(sorted(classes.items()))
+    ]
+    decomposition_rows = [
+        "({}, {}, {})".format(hex_cp(cp), hex_cp(parts[0]), hex_cp(parts[1] if len(parts) > 1 else 0))
+        for cp, parts in sorted(decompositions.items())
+    ]
+    compositions = sorted(
+        (parts[0], parts[1], cp)
+        for cp, parts in decompositions.items()
+        if len(parts) == 2 and cp not in exclusions
+    )
+    composition_rows = ["({}, {}, {})".format(*map(hex_cp, row)) for row in compositions]
+    text_emoji = sorted(emoji - emoji_presentation - KEYCAP_BASES)
+    emoji_rows = [
+        "({}, {})".format(hex_cp(first), hex_cp(last))
+        for first, last, _ in ranges((cp, None) for cp in text_emoji)
+    ]
+
+    header = [
+        "//! Unicode {} tables for NFC normalization and emoji widths".format(UNICODE_VERSION),
+        "//!",
+        "//! Generated by `scripts/gen_unicode_tables.py` from the Unicode Character",
+        "//! Database; do not edit by hand.",
+    ]
+    sections = [
+        "\n".join(header),
+        table(
+            "COMBINING_CLASSES",
+            ["Canonical combining classes as `(first, last, class)` ranges, sorted"],
+            "(u32, u32, u8)",
+            combining_rows,
+            4,
+        ),
+        table(
+            "DECOMPOSITIONS",
+            [
+                "Canonical decompositions as `(code point, first, second)`, sorted; a",
+                "second of 0 means a singleton decomposition",
+            ],
+            "(u32, u32, u32)",
+            decomposition_rows,
+            4,
+        ),
+        table(
+            "COMPOSITIONS",
+            ["Primary composites as `(first, second, composite)`, sorted by the pair"],
+            "(u32, u32, u32)",
+            composition_rows,
+            4,
+        ),
+        table(
+            "TEXT_PRESENTATION_EMOJI",
+            [
+                "Emoji shown as text unless followed by VS-16 (`Emoji=Yes`,",
+                "`Emoji_Presentation=No`), as `(first, last)` ranges, sorted; the ASCII",
+                "keycap bases `#`, `*` and `0`-`9` are left out",
+            ],
+            "(u32, u32)",
+            emoji_rows,
+            6,
+        ),
+    ]
+    sys.stdout.write("\n\n".join(sections) + "\n")
+
+
+if __name__ == "__main__":
+    main()
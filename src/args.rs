@@ -49,6 +49,7 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::ffi::OsString;
 
 /// Represents parsed command-line arguments.
 #[derive(Debug, Clone)]
@@ -66,7 +67,24 @@ pub struct Args {
 impl Args {
     /// Parse command-line arguments from the environment.
     pub fn parse() -> Self {
-        let mut args: Vec<String> = env::args().collect();
+        Self::parse_from(env::args_os())
+    }
+
+    /// Parse arguments from an iterator whose first item is the program name.
+    ///
+    /// Fields are plain strings, so arguments that are not valid UTF-8 are
+    /// converted lossily (`U+FFFD` for bad bytes). Use
+    /// [`ArgMatches::value_of_os`](crate::command::ArgMatches::value_of_os)
+    /// when such values must be kept exactly.
+    pub fn parse_from<I, T>(args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let mut args: Vec<String> = args
+            .into_iter()
+            .map(|arg| arg.into().to_string_lossy().into_owned())
+            .collect();
         let command = if !args.is_empty() {
             args.remove(0)
         } else {
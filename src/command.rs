@@ -7,19 +7,45 @@
 //! ## Features (v0.2.1)
 //!
 //! - **Subcommands**: Multi-level command hierarchies with aliases
+//! - **Abbreviations**: Unique prefixes of long flags and subcommands (`.infer_long_args(true)`)
 //! - **Positional Arguments**: Required and optional positional args (`<FILE>`, `[OUTPUT]`)
 //! - **Variadic Arguments**: Capture multiple values (`[FILES]...`)
 //! - **Flags & Options**: Short/long flags with values (`-v`, `--verbose`, `--output file.txt`)
 //! - **Environment Variables**: Automatic fallback to env vars (`.env("APP_CONFIG")`)
+//! - **Config Files**: Defaults below env vars from a file (`App::config_file("~/.myapp/config")`)
+//! - **Conditional Defaults**: Defaults that depend on other args (`.default_value_if("format", Some("json"), "pretty")`)
 //! - **Value Delimiters**: Parse comma-separated values (`--tags rust,cli,tool`)
 //! - **Argument Dependencies**: Require other arguments (`.requires("format")`)
+//! - **Conditional Requirements**: `.required_unless_present("anonymous")`, `.required_if_eq("mode", "remote")`
 //! - **Conflict Detection**: Prevent incompatible args (`.conflicts_with("quiet")`)
 //! - **Argument Groups**: Mutually exclusive argument sets
 //! - **Auto Help**: Automatic `--help` generation for all commands
-//! - **Validation**: Required arguments, possible values, custom validators
+//! - **Validation**: Required arguments, possible values, custom validators (closures too)
+//! - **Typed Values**: Parse while parsing the command line (`.value_parser(...)`, `get_one::<u16>("port")`)
 //! - **Error Handling**: Detailed error messages for debugging
+//! - **Interactive Fallback**: Prompt for missing required values on a TTY
+//! - **Global Arguments**: Args visible to every subcommand (`.global(true)`, `--dry-run`)
 //! - **Cross-Platform**: Works on Linux, macOS, Windows
 //!
+//! ## Parsing Without Side Effects
+//!
+//! [`Command::try_parse_from`] is the parsing core: it never prints, prompts
+//! or exits, so it can be embedded in GUI apps, servers and tests. [`App`] is
+//! the terminal frontend on top of it — interactive prompting, the shared
+//! verbosity, and (in `get_matches*`) printing help/errors and exiting.
+//! To present a failure yourself, render it with [`Command::format_error`]:
+//!
+//! ```
+//! use zfish::command::{Arg, Command};
+//!
+//! let cmd = Command::new("serve").version("1.0").arg(Arg::new("port").long("port"));
+//! let reply = match cmd.try_parse_from(["serve", "--version"]) {
+//!     Ok(matches) => format!("port {:?}", matches.value_of("port")),
+//!     Err(err) => cmd.format_error(err).rendered().to_string(),
+//! };
+//! assert_eq!(reply, "serve 1.0\n");
+//! ```
+//!
 //! ## Example
 //!
 //! ```rust
@@ -56,8 +82,79 @@
 //! }
 //! ```
 
+#[cfg(feature = "interactive")]
+use crate::completion::PathCompleter;
+use crate::config::ConfigFile;
+#[cfg(feature = "interactive")]
+use crate::line_editor::LineEditor;
+#[cfg(feature = "interactive")]
+use crate::prompt::Prompt;
+use crate::shell::{CommandSpec, OptionSpec, PositionalSpec, Shell};
+use crate::style::{Color, Style, strip_ansi};
+use crate::summary::Report;
+use crate::table::{OutputFormat, Table};
+use crate::text::similarity::levenshtein;
+use crate::value_parser::describe_range;
+use std::any::Any;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
+use std::io;
+use std::io::IsTerminal;
+use std::ops::{Bound, RangeBounds};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+/// Name and long flag of the argument registered by [`App::dry_run_flag`]
+const DRY_RUN_ARG: &str = "dry-run";
+
+/// Name and long flag of the argument registered by [`App::yes_flag`]
+const YES_ARG: &str = "yes";
+
+/// Name and long flag of the argument registered by [`App::output_format_arg`]
+const FORMAT_ARG: &str = "format";
+
+/// Names of the arguments registered by [`App::verbosity_args`]
+const QUIET_ARG: &str = "quiet";
+const VERBOSE_ARG: &str = "verbose";
+
+/// The kind of value an argument expects, used for completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ValueHint {
+    /// No particular kind of value
+    #[default]
+    Unknown,
+    /// A path to a file or directory
+    AnyPath,
+    /// A path to a file
+    FilePath,
+    /// A path to a directory
+    DirPath,
+    /// A network host name
+    Hostname,
+    /// The name of a program on `PATH` (or a shell command)
+    CommandName,
+}
+
+/// What the parser does each time it meets an argument, see [`Arg::action`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ArgAction {
+    /// Store the value that follows (`--out dir`)
+    #[default]
+    Set,
+    /// Collect the value of every occurrence, like [`Arg::multiple`]
+    Append,
+    /// A flag without a value (`--force`), like `takes_value(false)`
+    SetTrue,
+    /// A flag counted per occurrence, so `-vvv` gives
+    /// [`ArgMatches::get_count`] `== 3`
+    Count,
+}
 
 /// Represents a parsed command-line argument value
 #[derive(Debug, Clone, PartialEq)]
@@ -68,6 +165,9 @@ pub enum ArgValue {
     Multiple(Vec<String>),
     /// A flag (present/absent)
     Flag(bool),
+    /// Values of an [`Arg::allow_invalid_utf8`] argument of which at least
+    /// one is not valid UTF-8, one per value
+    Os(Vec<OsString>),
 }
 
 impl ArgValue {
@@ -94,10 +194,25 @@ impl ArgValue {
             _ => None,
         }
     }
+
+    /// Returns the values as OS strings, for every kind but a flag
+    pub fn as_os_strs(&self) -> Option<Vec<&OsStr>> {
+        match self {
+            ArgValue::Single(s) => Some(vec![OsStr::new(s)]),
+            ArgValue::Multiple(v) => Some(v.iter().map(OsStr::new).collect()),
+            ArgValue::Os(v) => Some(v.iter().map(OsString::as_os_str).collect()),
+            ArgValue::Flag(_) => None,
+        }
+    }
 }
 
 /// Errors that can occur during command parsing
+///
+/// This enum is `#[non_exhaustive]`: new variants may be added in minor
+/// releases. Match on [`CommandError::kind`] when you need to branch on the
+/// category of an error.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum CommandError {
     /// An argument is missing
     MissingArgument(String),
@@ -105,6 +220,18 @@ pub enum CommandError {
     UnknownArgument(String),
     /// An unknown subcommand was provided
     UnknownSubcommand(String),
+    /// A bare word was left over after every positional was filled (see
+    /// [`Setting::StrictPositionals`])
+    UnexpectedArgument(String), // (value)
+    /// An unknown argument was provided that is a likely typo of a known one
+    MisspelledArgument(String, String), // (arg_name, suggestion)
+    /// An unknown subcommand was provided that is a likely typo of a known one
+    MisspelledSubcommand(String, String), // (subcommand, suggestion)
+    /// An abbreviated long flag matches more than one flag (see [`Setting::InferLongArgs`])
+    AmbiguousArgument(String, Vec<String>), // (arg_name, candidates)
+    /// An abbreviated subcommand matches more than one subcommand (see
+    /// [`Setting::InferSubcommands`])
+    AmbiguousSubcommand(String, Vec<String>), // (subcommand, candidates)
     /// An argument validation failed
     ValidationError(String, String), // (arg_name, error_message)
     /// Invalid value for an argument
@@ -117,37 +244,273 @@ pub enum CommandError {
     ArgumentConflict(String, String), // (arg1, arg2)
     /// Required dependency is missing
     MissingDependency(String, String), // (arg, required_arg)
+    /// A subcommand is required but none was given (see [`Setting::SubcommandRequired`])
+    MissingSubcommand(Vec<String>), // (command path from the root)
+    /// A single-value argument was given more than once
+    DuplicateArgument(String), // (arg_name)
+    /// The config or `.env` file could not be read or parsed (see
+    /// [`App::config_file`] and [`App::dotenv`])
+    InvalidConfig(String, String), // (path, error_message)
+    /// A value is not valid UTF-8 (see [`Arg::allow_invalid_utf8`])
+    InvalidUtf8(String), // (arg_name)
+    /// Several errors reported together, e.g. by [`ArgErrors`]
+    Multiple(Vec<CommandError>),
+    /// An error raised while parsing a subcommand; [`Command::format_error`]
+    /// renders it with that subcommand's usage and help
+    InSubcommand(Vec<String>, Box<CommandError>), // (command path from the root, error)
+    /// An error raised by application code after parsing (see [`Command::error`])
+    Custom(ErrorKind, String), // (kind, message)
 }
 
-impl fmt::Display for CommandError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// The category of a [`CommandError`]
+///
+/// Application code uses this with [`Command::error`] to raise errors that are
+/// presented exactly like the parser's own errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A required argument is missing
+    MissingArgument,
+    /// An unknown argument was provided
+    UnknownArgument,
+    /// An unknown subcommand was provided
+    UnknownSubcommand,
+    /// A value failed validation
+    ValidationError,
+    /// A value is not acceptable for an argument
+    InvalidValue,
+    /// Help was requested
+    HelpRequested,
+    /// Version was requested
+    VersionRequested,
+    /// Arguments conflict with each other
+    ArgumentConflict,
+    /// A required dependency is missing
+    MissingDependency,
+    /// A required subcommand is missing
+    MissingSubcommand,
+    /// A single-value argument was given more than once
+    DuplicateArgument,
+    /// The config or `.env` file could not be read or parsed
+    InvalidConfig,
+    /// A value is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl CommandError {
+    /// Returns the category of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            CommandError::MissingArgument(_) => ErrorKind::MissingArgument,
+            CommandError::UnknownArgument(_) => ErrorKind::UnknownArgument,
+            CommandError::UnknownSubcommand(_) => ErrorKind::UnknownSubcommand,
+            CommandError::UnexpectedArgument(_) => ErrorKind::UnknownArgument,
+            CommandError::MisspelledArgument(_, _) => ErrorKind::UnknownArgument,
+            CommandError::MisspelledSubcommand(_, _) => ErrorKind::UnknownSubcommand,
+            CommandError::AmbiguousArgument(_, _) => ErrorKind::UnknownArgument,
+            CommandError::AmbiguousSubcommand(_, _) => ErrorKind::UnknownSubcommand,
+            CommandError::ValidationError(_, _) => ErrorKind::ValidationError,
+            CommandError::InvalidValue(_, _) => ErrorKind::InvalidValue,
+            CommandError::HelpRequested => ErrorKind::HelpRequested,
+            CommandError::VersionRequested => ErrorKind::VersionRequested,
+            CommandError::ArgumentConflict(_, _) => ErrorKind::ArgumentConflict,
+            CommandError::MissingDependency(_, _) => ErrorKind::MissingDependency,
+            CommandError::MissingSubcommand(_) => ErrorKind::MissingSubcommand,
+            CommandError::DuplicateArgument(_) => ErrorKind::DuplicateArgument,
+            CommandError::InvalidConfig(_, _) => ErrorKind::InvalidConfig,
+            CommandError::InvalidUtf8(_) => ErrorKind::InvalidUtf8,
+            CommandError::Multiple(errors) => errors
+                .first()
+                .map_or(ErrorKind::ValidationError, CommandError::kind),
+            CommandError::Custom(kind, _) => *kind,
+            CommandError::InSubcommand(_, error) => error.kind(),
+        }
+    }
+
+    /// Returns the error itself, looking through [`InSubcommand`](CommandError::InSubcommand)
+    ///
+    /// Match on this rather than on the error directly when the failure may
+    /// come from a subcommand.
+    pub fn inner(&self) -> &CommandError {
+        match self {
+            CommandError::InSubcommand(_, error) => error.inner(),
+            error => error,
+        }
+    }
+
+    /// Returns the command path from the root to the command that failed
+    ///
+    /// Empty when the error is not tied to a subcommand.
+    pub fn command_path(&self) -> &[String] {
+        match self {
+            CommandError::InSubcommand(path, _) => path,
+            _ => &[],
+        }
+    }
+
+    /// Records that this error was raised while parsing the subcommand at `path`
+    ///
+    /// The innermost path wins, so outer commands leave it alone.
+    fn in_subcommand(self, path: impl FnOnce() -> Vec<String>) -> CommandError {
+        match self {
+            CommandError::InSubcommand(..) => self,
+            error => CommandError::InSubcommand(path(), Box::new(error)),
+        }
+    }
+
+    /// Returns the argument names and values this error refers to, in order
+    ///
+    /// For example `ArgumentConflict("quiet", "verbose")` yields
+    /// `["quiet", "verbose"]` and `InvalidValue("port", "http")` yields
+    /// `["port", "http"]`. Errors without context return an empty list.
+    pub fn context(&self) -> Vec<&str> {
+        match self {
+            CommandError::MissingArgument(name)
+            | CommandError::UnknownArgument(name)
+            | CommandError::UnknownSubcommand(name)
+            | CommandError::UnexpectedArgument(name)
+            | CommandError::DuplicateArgument(name)
+            | CommandError::InvalidUtf8(name) => vec![name.as_str()],
+            CommandError::MissingSubcommand(path) => path.iter().map(String::as_str).collect(),
+            CommandError::ValidationError(name, _) | CommandError::InvalidConfig(name, _) => {
+                vec![name.as_str()]
+            }
+            CommandError::InvalidValue(name, value) => vec![name.as_str(), value.as_str()],
+            CommandError::ArgumentConflict(first, second)
+            | CommandError::MissingDependency(first, second)
+            | CommandError::MisspelledArgument(first, second)
+            | CommandError::MisspelledSubcommand(first, second) => {
+                vec![first.as_str(), second.as_str()]
+            }
+            CommandError::AmbiguousArgument(name, candidates)
+            | CommandError::AmbiguousSubcommand(name, candidates) => std::iter::once(name)
+                .chain(candidates)
+                .map(String::as_str)
+                .collect(),
+            CommandError::Multiple(errors) => {
+                errors.iter().flat_map(CommandError::context).collect()
+            }
+            CommandError::InSubcommand(_, error) => error.context(),
+            CommandError::HelpRequested
+            | CommandError::VersionRequested
+            | CommandError::Custom(_, _) => Vec::new(),
+        }
+    }
+
+    /// Returns the known name an unknown argument or subcommand was probably
+    /// meant to be, such as `--verbose` for `--verbos`
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            CommandError::MisspelledArgument(_, suggestion)
+            | CommandError::MisspelledSubcommand(_, suggestion) => Some(suggestion),
+            CommandError::InSubcommand(_, error) => error.suggestion(),
+            _ => None,
+        }
+    }
+
+    /// The error message without the `error:` prefix
+    ///
+    /// [`Multiple`](CommandError::Multiple) errors give one message per line.
+    pub fn message(&self) -> String {
         match self {
             CommandError::MissingArgument(name) => {
-                write!(f, "error: the argument '{}' is required", name)
+                format!("the argument '{}' is required", name)
+            }
+            CommandError::UnknownArgument(name) => format!("unknown argument '{}'", name),
+            CommandError::UnknownSubcommand(name) => format!("unknown subcommand '{}'", name),
+            CommandError::UnexpectedArgument(value) => format!("unexpected argument '{}'", value),
+            CommandError::MisspelledArgument(name, suggestion) => {
+                format!(
+                    "unknown argument '{}'; did you mean '{}'?",
+                    name, suggestion
+                )
+            }
+            CommandError::MisspelledSubcommand(name, suggestion) => {
+                format!(
+                    "unknown subcommand '{}'; did you mean '{}'?",
+                    name, suggestion
+                )
             }
-            CommandError::UnknownArgument(name) => {
-                write!(f, "error: unknown argument '{}'", name)
+            CommandError::AmbiguousArgument(name, candidates) => {
+                format!(
+                    "ambiguous argument '{}'; could be {}",
+                    name,
+                    quoted_list(candidates)
+                )
+            }
+            CommandError::AmbiguousSubcommand(name, candidates) => {
+                format!(
+                    "ambiguous subcommand '{}'; could be {}",
+                    name,
+                    quoted_list(candidates)
+                )
             }
-            CommandError::UnknownSubcommand(name) => {
-                write!(f, "error: unknown subcommand '{}'", name)
+            CommandError::MissingSubcommand(path) => {
+                format!("'{}' requires a subcommand", path.join(" "))
             }
             CommandError::ValidationError(name, msg) => {
-                write!(f, "error: validation failed for '{}': {}", name, msg)
+                format!("validation failed for '{}': {}", name, msg)
             }
             CommandError::InvalidValue(name, value) => {
-                write!(f, "error: invalid value '{}' for '{}'", value, name)
+                format!("invalid value '{}' for '{}'", value, name)
             }
-            CommandError::HelpRequested => write!(f, "help requested"),
-            CommandError::VersionRequested => write!(f, "version requested"),
+            CommandError::HelpRequested => "help requested".to_string(),
+            CommandError::VersionRequested => "version requested".to_string(),
             CommandError::ArgumentConflict(arg1, arg2) => {
-                write!(
-                    f,
-                    "error: the argument '{}' cannot be used with '{}'",
-                    arg1, arg2
-                )
+                format!("the argument '{}' cannot be used with '{}'", arg1, arg2)
             }
             CommandError::MissingDependency(arg, required) => {
-                write!(f, "error: the argument '{}' requires '{}'", arg, required)
+                format!("the argument '{}' requires '{}'", arg, required)
+            }
+            CommandError::DuplicateArgument(name) => {
+                format!("the argument '{}' was given more than once", name)
+            }
+            CommandError::InvalidConfig(path, msg) => {
+                format!("invalid config file '{}': {}", path, msg)
+            }
+            CommandError::InvalidUtf8(name) => {
+                format!("the value of '{}' is not valid UTF-8", name)
+            }
+            CommandError::Multiple(_) => self.messages().join("\n"),
+            CommandError::Custom(_, message) => message.clone(),
+            CommandError::InSubcommand(_, error) => error.message(),
+        }
+    }
+
+    /// One message per error, for rendering each after its own `error:`
+    fn messages(&self) -> Vec<String> {
+        match self {
+            CommandError::Multiple(errors) => {
+                errors.iter().flat_map(CommandError::messages).collect()
+            }
+            CommandError::InSubcommand(_, error) => error.messages(),
+            _ => vec![self.message()],
+        }
+    }
+}
+
+/// `'a', 'b' or 'c'`
+fn quoted_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| format!("'{}'", item)).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.concat(),
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner() {
+            CommandError::HelpRequested | CommandError::VersionRequested => {
+                write!(f, "{}", self.message())
+            }
+            _ => {
+                let lines: Vec<String> = self
+                    .messages()
+                    .iter()
+                    .map(|message| format!("error: {}", message))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
             }
         }
     }
@@ -158,12 +521,165 @@ impl std::error::Error for CommandError {}
 /// Result type for command operations
 pub type CommandResult<T> = Result<T, CommandError>;
 
-/// Represents a group of mutually exclusive arguments
+/// Colors used when rendering help and error output
+///
+/// Each role is optional; `None` leaves that part of the output plain. The
+/// default keeps help text plain and only colors the `error:` label. Colors
+/// are left out anyway under `NO_COLOR`, and when help or an error is printed
+/// by [`FormattedError::exit`] to a stream that is not a terminal.
+/// Example: `App::new("app").styles(HelpStyles::plain())`
+#[derive(Debug, Clone, Copy)]
+pub struct HelpStyles {
+    /// Section headings such as `USAGE:` and `OPTIONS:` (rendered bold)
+    pub heading: Option<Color>,
+    /// Flags (`-v, --verbose`) and subcommand names
+    pub flag: Option<Color>,
+    /// Annotations such as `[required]` and `[default: ...]`
+    pub value: Option<Color>,
+    /// Value placeholders such as `<FILE>`
+    pub placeholder: Option<Color>,
+    /// The `error:` label in error output (rendered bold)
+    pub error: Option<Color>,
+}
+
+impl HelpStyles {
+    /// No colors anywhere, useful for tests and snapshot comparisons
+    pub fn plain() -> Self {
+        Self {
+            heading: None,
+            flag: None,
+            value: None,
+            placeholder: None,
+            error: None,
+        }
+    }
+
+    /// A colored palette in the spirit of clap's defaults
+    pub fn colored() -> Self {
+        Self {
+            heading: Some(Color::BrightYellow),
+            flag: Some(Color::BrightGreen),
+            value: Some(Color::BrightBlack),
+            placeholder: Some(Color::BrightCyan),
+            error: Some(Color::BrightRed),
+        }
+    }
+
+    fn paint(color: Option<Color>, text: &str, bold: bool) -> Cow<'_, str> {
+        match color {
+            Some(color) if bold => color.paint(text).style(Style::Bold).to_string().into(),
+            Some(color) => color.paint(text).to_string().into(),
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    fn paint_heading<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Self::paint(self.heading, text, true)
+    }
+
+    fn paint_flag<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Self::paint(self.flag, text, false)
+    }
+
+    fn paint_value<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Self::paint(self.value, text, false)
+    }
+
+    fn paint_placeholder<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Self::paint(self.placeholder, text, false)
+    }
+
+    fn paint_error<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Self::paint(self.error, text, true)
+    }
+}
+
+impl Default for HelpStyles {
+    fn default() -> Self {
+        Self {
+            error: Some(Color::BrightRed),
+            ..Self::plain()
+        }
+    }
+}
+
+/// A [`CommandError`] rendered for a specific command
+///
+/// Produced by [`Command::error`] and [`Command::format_error`]. The rendered
+/// text includes a colorized `error:` label, the command's usage line and a
+/// help hint — the same presentation the parser uses for its own errors.
+/// Help and version requests render as the help text and version line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormattedError {
+    error: CommandError,
+    rendered: String,
+    /// Exit status for real errors, see [`Command::error_exit_code`]
+    error_code: i32,
+}
+
+impl FormattedError {
+    /// Returns the underlying error
+    pub fn error(&self) -> &CommandError {
+        &self.error
+    }
+
+    /// Returns the rendered error text
+    pub fn rendered(&self) -> &str {
+        &self.rendered
+    }
+
+    /// Returns the process exit status for this outcome
+    ///
+    /// `0` for help and version requests; for real errors the command's
+    /// [`error_exit_code`](Command::error_exit_code), `1` unless set.
+    pub fn exit_code(&self) -> i32 {
+        match self.error.inner() {
+            CommandError::HelpRequested | CommandError::VersionRequested => 0,
+            _ => self.error_code,
+        }
+    }
+
+    /// Prints the rendered text and exits the process with [`exit_code`](Self::exit_code)
+    ///
+    /// Help and version go to stdout, errors to stderr.
+    pub fn exit(&self) -> ! {
+        self.print();
+        std::process::exit(self.exit_code());
+    }
+
+    /// Prints the rendered text where [`exit`](Self::exit) would
+    ///
+    /// Colors are dropped when that stream is not a terminal, so
+    /// `myapp --help > help.txt` stays free of escape sequences.
+    fn print(&self) {
+        if self.exit_code() == 0 {
+            print!("{}", for_stream(&self.rendered, io::stdout().is_terminal()));
+        } else {
+            eprint!("{}", for_stream(&self.rendered, io::stderr().is_terminal()));
+        }
+    }
+}
+
+impl fmt::Display for FormattedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.rendered)
+    }
+}
+
+impl std::error::Error for FormattedError {}
+
+/// Represents a group of related arguments
+///
+/// Members are mutually exclusive unless the group allows
+/// [`multiple`](ArgGroup::multiple) or [`requires_all`](ArgGroup::requires_all).
 #[derive(Debug, Clone)]
 pub struct ArgGroup {
     name: String,
     args: Vec<String>,
     required: bool,
+    multiple: bool,
+    requires_all: bool,
+    conflicts_with: Vec<String>,
 }
 
 impl ArgGroup {
@@ -173,6 +689,9 @@ impl ArgGroup {
             name: name.into(),
             args: Vec::new(),
             required: false,
+            multiple: false,
+            requires_all: false,
+            conflicts_with: Vec::new(),
         }
     }
 
@@ -195,6 +714,63 @@ impl ArgGroup {
         self.required = required;
         self
     }
+
+    /// Allows several members of this group to be used together
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Requires every member once any one is used, e.g. `--user` and
+    /// `--password` must be given together
+    pub fn requires_all(mut self, requires_all: bool) -> Self {
+        self.requires_all = requires_all;
+        self
+    }
+
+    /// Rejects any member of this group together with `other`, the name of
+    /// an argument or of another group
+    pub fn conflicts_with(mut self, other: impl Into<String>) -> Self {
+        self.conflicts_with.push(other.into());
+        self
+    }
+}
+
+/// A validator, value parser or summary closure, shared by clones of an
+/// [`Arg`] or [`App`]
+struct Callback<F: ?Sized>(Arc<F>);
+
+impl<F: ?Sized> Clone for Callback<F> {
+    fn clone(&self) -> Self {
+        Callback(Arc::clone(&self.0))
+    }
+}
+
+// Keeps `Command` usable across `catch_unwind`, as it was with plain fn pointers
+impl<F: ?Sized> std::panic::RefUnwindSafe for Callback<F> {}
+impl<F: ?Sized> std::panic::UnwindSafe for Callback<F> {}
+
+impl<F: ?Sized> fmt::Debug for Callback<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<closure>")
+    }
+}
+
+type ValidatorFn = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+type ValueParserFn = dyn Fn(&str) -> Result<TypedValue, String> + Send + Sync;
+type SummaryFn = dyn Fn(&Report) + Send + Sync;
+
+/// A value produced by an [`Arg::value_parser`]
+#[derive(Clone)]
+struct TypedValue(Arc<dyn Any + Send + Sync>);
+
+impl std::panic::RefUnwindSafe for TypedValue {}
+impl std::panic::UnwindSafe for TypedValue {}
+
+impl fmt::Debug for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TypedValue(..)")
+    }
 }
 
 /// Represents a single command-line argument definition
@@ -207,17 +783,30 @@ pub struct Arg {
     required: bool,
     takes_value: bool,
     multiple: bool,
+    overrides_self: bool,
     default_value: Option<String>,
+    default_value_ifs: Vec<(String, Option<String>, String)>, // (arg, value, default)
     possible_values: Option<Vec<String>>,
-    #[allow(clippy::type_complexity)]
-    validator: Option<fn(&str) -> Result<(), String>>,
+    value_range: Option<(Bound<i64>, Bound<i64>)>, // Allowed integer values
+    validator: Option<Callback<ValidatorFn>>,
+    value_parser: Option<Callback<ValueParserFn>>,
     // New fields for v0.2.1+
-    index: Option<usize>,          // Position for positional arguments
-    env: Option<String>,           // Environment variable name
-    requires: Vec<String>,         // Arguments this arg depends on
-    conflicts_with: Vec<String>,   // Arguments this arg conflicts with
-    value_delimiter: Option<char>, // Delimiter for splitting values (e.g., ',')
-    last: bool,                    // Variadic positional (FILES...)
+    index: Option<usize>,               // Position for positional arguments
+    env: Option<String>,                // Environment variable name
+    requires: Vec<String>,              // Arguments this arg depends on
+    required_unless: Vec<String>,       // Required unless one of these is present
+    required_if: Vec<(String, String)>, // Required if an arg has a value
+    conflicts_with: Vec<String>,        // Arguments this arg conflicts with
+    value_delimiter: Option<char>,      // Delimiter for splitting values (e.g., ',')
+    last: bool,                         // Variadic positional (FILES...)
+    global: bool,                       // Propagated to all subcommands
+    value_hint: ValueHint,              // What kind of value, for completion
+    allow_stdin_dash: bool,             // Accept `-` (stdin) as a value
+    allow_hyphen_values: bool,          // Accept values starting with `-`
+    normalize_unicode: bool,            // NFC-normalize values
+    ignore_case: bool,                  // Case-insensitive long flag and values
+    allow_invalid_utf8: bool,           // Keep non-UTF-8 values as OsStrings
+    help_heading: Option<String>,       // Help section for this option
 }
 
 impl Arg {
@@ -231,15 +820,29 @@ impl Arg {
             required: false,
             takes_value: true,
             multiple: false,
+            overrides_self: false,
             default_value: None,
+            default_value_ifs: Vec::new(),
             possible_values: Some(Vec::new()),
+            value_range: None,
             validator: None,
+            value_parser: None,
             index: None,
             env: None,
             requires: Vec::new(),
+            required_unless: Vec::new(),
+            required_if: Vec::new(),
             conflicts_with: Vec::new(),
             value_delimiter: None,
             last: false,
+            global: false,
+            value_hint: ValueHint::Unknown,
+            allow_stdin_dash: false,
+            allow_hyphen_values: false,
+            normalize_unicode: false,
+            ignore_case: false,
+            allow_invalid_utf8: false,
+            help_heading: None,
         }
     }
 
@@ -274,26 +877,106 @@ impl Arg {
     }
 
     /// Allows this argument to be specified multiple times
+    ///
+    /// Values accumulate in order. Without this (or
+    /// [`overrides_with_self`](Arg::overrides_with_self)), giving an option
+    /// twice is a [`CommandError::DuplicateArgument`].
     pub fn multiple(mut self, multiple: bool) -> Self {
         self.multiple = multiple;
         self
     }
 
+    /// Sets what parsing does with each occurrence of this argument
+    ///
+    /// A shorthand for [`takes_value`](Arg::takes_value) and
+    /// [`multiple`](Arg::multiple). Example: `Arg::new("verbose").short('v').action(ArgAction::Count)`
+    pub fn action(mut self, action: ArgAction) -> Self {
+        (self.takes_value, self.multiple) = match action {
+            ArgAction::Set => (true, false),
+            ArgAction::Append => (true, true),
+            ArgAction::SetTrue | ArgAction::Count => (false, false),
+        };
+        self
+    }
+
+    /// Lets a repeated occurrence replace the earlier value (last one wins)
+    ///
+    /// Useful for options that aliases or wrapper scripts may already pass,
+    /// e.g. `--color auto ... --color never`.
+    pub fn overrides_with_self(mut self, overrides: bool) -> Self {
+        self.overrides_self = overrides;
+        self
+    }
+
     /// Sets the default value for this argument
     pub fn default_value(mut self, value: impl Into<String>) -> Self {
         self.default_value = Some(value.into());
         self
     }
 
+    /// Uses `default` when `arg` has the value `value`, or with `None`, when
+    /// `arg` is present at all
+    ///
+    /// Conditions are tried in the order added and win over
+    /// [`default_value`](Arg::default_value); a value from the command line or
+    /// [`env`](Arg::env) wins over both. The other argument's own default
+    /// counts, unless it is itself conditional.
+    /// Example: `Arg::new("style").default_value_if("format", Some("json"), "pretty")`
+    pub fn default_value_if(
+        mut self,
+        arg: impl Into<String>,
+        value: Option<&str>,
+        default: impl Into<String>,
+    ) -> Self {
+        self.default_value_ifs
+            .push((arg.into(), value.map(String::from), default.into()));
+        self
+    }
+
     /// Sets the possible values for this argument
     pub fn possible_values(mut self, values: &[&str]) -> Self {
         self.possible_values = Some(values.iter().map(|s| s.to_string()).collect());
         self
     }
 
-    /// Sets a custom validator function
-    pub fn validator(mut self, validator: fn(&str) -> Result<(), String>) -> Self {
-        self.validator = Some(validator);
+    /// Only accepts whole numbers within `range`
+    ///
+    /// Other values fail with a message such as "must be between 1 and
+    /// 65535", and help shows the range next to the argument.
+    /// Example: `Arg::new("port").long("port").value_range(1..=65535)`
+    pub fn value_range(mut self, range: impl RangeBounds<i64>) -> Self {
+        self.value_range = Some((range.start_bound().cloned(), range.end_bound().cloned()));
+        self
+    }
+
+    /// Sets a custom validator, a function or closure that rejects a value
+    /// with an error message
+    /// Example: `Arg::new("name").validator(move |v| if reserved.contains(v) { Err("reserved".into()) } else { Ok(()) })`
+    pub fn validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validator = Some(Callback(Arc::new(validator)));
+        self
+    }
+
+    /// Parses every value of this argument into a `T` while the command line
+    /// is parsed, for [`ArgMatches::get_one`] and [`ArgMatches::get_many`]
+    ///
+    /// Values from the command line, the environment and defaults all go
+    /// through the parser; one it rejects fails with
+    /// [`CommandError::ValidationError`]. The string value stays available
+    /// through [`ArgMatches::value_of`]. The functions in
+    /// [`value_parser`](crate::value_parser) fit here.
+    /// Example: `Arg::new("port").value_parser(value_parser::in_range(1..=65535u16))`
+    pub fn value_parser<T, F>(mut self, parser: F) -> Self
+    where
+        T: Any + Send + Sync,
+        F: Fn(&str) -> Result<T, String> + Send + Sync + 'static,
+    {
+        self.value_parser = Some(Callback(Arc::new(move |value: &str| {
+            parser(value).map(|parsed| TypedValue(Arc::new(parsed)))
+        })));
         self
     }
 
@@ -305,6 +988,7 @@ impl Arg {
     }
 
     /// Sets an environment variable to read from if the argument is not provided
+    /// (the value is validated like one given on the command line)
     /// Example: `Arg::new("config").env("APP_CONFIG")`
     pub fn env(mut self, env: impl Into<String>) -> Self {
         self.env = Some(env.into());
@@ -318,6 +1002,25 @@ impl Arg {
         self
     }
 
+    /// Makes this argument required unless `arg` is present
+    ///
+    /// Call it again to name alternatives; any one of them being present is
+    /// enough. Example: `Arg::new("token").required_unless_present("anonymous")`
+    pub fn required_unless_present(mut self, arg: impl Into<String>) -> Self {
+        self.required_unless.push(arg.into());
+        self
+    }
+
+    /// Makes this argument required when `arg` has the value `value`
+    ///
+    /// Defaults and environment variables count as values. Call it again to
+    /// add conditions; any one of them holding is enough.
+    /// Example: `Arg::new("host").required_if_eq("mode", "remote")`
+    pub fn required_if_eq(mut self, arg: impl Into<String>, value: impl Into<String>) -> Self {
+        self.required_if.push((arg.into(), value.into()));
+        self
+    }
+
     /// Specifies arguments that conflict with this argument
     /// Example: `Arg::new("quiet").conflicts_with("verbose")`
     pub fn conflicts_with(mut self, arg: impl Into<String>) -> Self {
@@ -344,116 +1047,661 @@ impl Arg {
         self
     }
 
+    /// Describes what kind of value this argument takes
+    ///
+    /// Path hints enable Tab completion from the filesystem when the value is
+    /// asked for interactively (see [`Command::interactive_fallback`]).
+    /// Example: `Arg::new("config").value_hint(ValueHint::FilePath)`
+    pub fn value_hint(mut self, hint: ValueHint) -> Self {
+        self.value_hint = hint;
+        self
+    }
+
+    /// Accepts a lone `-` as this argument's value, meaning stdin
+    ///
+    /// Parse the value with `value_of_t::<FileOrStdin>` (see [`crate::io`]).
+    /// Example: `Arg::new("file").index(0).allow_stdin_dash(true)`
+    pub fn allow_stdin_dash(mut self, allow: bool) -> Self {
+        self.allow_stdin_dash = allow;
+        self
+    }
+
+    /// Takes the next argument as this option's value even if it starts with
+    /// `-`, so `--offset -5` and `-o -` parse instead of failing
+    ///
+    /// [`Setting::AllowHyphenValues`] does the same for every option of a
+    /// command. Example: `Arg::new("offset").long("offset").allow_hyphen_values(true)`
+    pub fn allow_hyphen_values(mut self, allow: bool) -> Self {
+        self.allow_hyphen_values = allow;
+        self
+    }
+
+    /// Makes this argument available to every subcommand
+    ///
+    /// A global argument may appear before or after the subcommand name, and
+    /// its value is visible in both the parent's and the subcommand's matches.
+    /// Example: `Arg::new("verbose").long("verbose").global(true)`
+    pub fn global(mut self, global: bool) -> Self {
+        self.global = global;
+        self
+    }
+
+    /// Normalizes values to Unicode NFC before they are validated and stored
+    ///
+    /// An accent typed as a separate combining character then matches the
+    /// precomposed form, in [`possible_values`](Self::possible_values) and in
+    /// the stored value (see [`unicode::nfc`](crate::unicode::nfc)).
+    /// Example: `Arg::new("label").long("label").normalize_unicode(true)`
+    pub fn normalize_unicode(mut self, normalize: bool) -> Self {
+        self.normalize_unicode = normalize;
+        self
+    }
+
+    /// Matches this argument's long flag and
+    /// [`possible_values`](Self::possible_values) regardless of case
+    ///
+    /// `--LEVEL Debug` then parses as `--level debug`: the value is stored as
+    /// it was defined, so code checking `value_of` only sees canonical values.
+    /// Example: `Arg::new("level").long("level").ignore_case(true)`
+    pub fn ignore_case(mut self, ignore: bool) -> Self {
+        self.ignore_case = ignore;
+        self
+    }
+
+    /// Accepts values that are not valid UTF-8, such as Unix file names
+    ///
+    /// Such values are stored as [`ArgValue::Os`] and read back with
+    /// [`ArgMatches::value_of_os`]; [`value_of`](ArgMatches::value_of) gives
+    /// `None` for them. Without this, they fail with
+    /// [`CommandError::InvalidUtf8`].
+    /// Example: `Arg::new("path").index(0).allow_invalid_utf8(true)`
+    pub fn allow_invalid_utf8(mut self, allow: bool) -> Self {
+        self.allow_invalid_utf8 = allow;
+        self
+    }
+
+    /// Lists this option in help under its own section instead of `OPTIONS`
+    ///
+    /// Options sharing a heading are grouped together, and sections appear in
+    /// the order their first option was added. Give the title without a colon.
+    /// Example: `Arg::new("proxy").long("proxy").help_heading("NETWORK OPTIONS")`
+    pub fn help_heading(mut self, heading: impl Into<String>) -> Self {
+        self.help_heading = Some(heading.into());
+        self
+    }
+
     /// Gets the name of this argument
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Applies [`normalize_unicode`](Self::normalize_unicode) to a value
+    fn normalized<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.normalize_unicode {
+            crate::unicode::nfc(value)
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Checks whether `next` can be taken as this option's value
+    fn accepts_value(&self, next: &str, ctx: &ParseContext) -> bool {
+        !next.starts_with('-')
+            || (next == "-" && self.allow_stdin_dash)
+            || self.allow_hyphen_values
+            || ctx.settings.contains(Setting::AllowHyphenValues)
+    }
+
     /// Checks if this argument matches a short flag
     fn matches_short(&self, c: char) -> bool {
         self.short == Some(c)
     }
 
-    /// Validates a value against this argument's constraints
-    fn validate(&self, value: &str) -> Result<(), String> {
+    /// Validates a value against this argument's constraints, returning the
+    /// value to store (the matching possible value under
+    /// [`ignore_case`](Self::ignore_case))
+    fn validate(&self, value: &str) -> Result<String, String> {
+        let mut value = value.to_string();
+
         // Check possible values
         if let Some(ref possible) = self.possible_values
             && !possible.is_empty()
-            && !possible.contains(&value.to_string())
         {
-            return Err(format!(
-                "invalid value '{}', expected one of: {}",
-                value,
-                possible.join(", ")
-            ));
+            let found = possible.iter().find(|p| {
+                let p = self.normalized(p);
+                p == value || (self.ignore_case && p.to_lowercase() == value.to_lowercase())
+            });
+            match found {
+                Some(p) => value = self.normalized(p).into_owned(),
+                None => {
+                    return Err(format!(
+                        "invalid value '{}', expected one of: {}",
+                        value,
+                        possible.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if let Some(range) = self.value_range {
+            let number: i64 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a whole number", value))?;
+            if !range.contains(&number) {
+                return Err(format!("{} must be {}", value, describe_range(&range)));
+            }
         }
 
         // Run custom validator
-        if let Some(validator) = self.validator {
-            validator(value)?;
+        if let Some(ref validator) = self.validator {
+            (validator.0)(&value)?;
         }
 
-        Ok(())
+        Ok(value)
     }
 }
 
+/// Where the value of an argument came from (see [`ArgMatches::value_source`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueSource {
+    /// Given on the command line
+    CommandLine,
+    /// Read from the argument's environment variable ([`Arg::env`])
+    EnvVar,
+    /// Filled in from [`Arg::default_value`] or [`Arg::default_value_if`]
+    Default,
+    /// Typed in answer to an interactive prompt ([`Command::interactive_fallback`])
+    Prompt,
+    /// Read from the app's config file ([`App::config_file`])
+    ConfigFile,
+}
+
 /// Represents the result of parsing a command
-#[derive(Debug, Clone)]
+///
+/// The matches of a command and of every subcommand below it share one
+/// [`Vec`] of levels, outermost first; a subcommand's matches are the same
+/// path viewed from a deeper index.
+#[derive(Clone)]
 pub struct ArgMatches {
+    path: Arc<Vec<Level>>,
+    depth: usize,
+    /// The view one level down, made on first use by [`ArgMatches::subcommand`]
+    next: OnceLock<Box<ArgMatches>>,
+}
+
+/// What one command on the subcommand path parsed
+#[derive(Debug, Clone)]
+struct Level {
+    name: String, // As matched: the typed alias or the inferred name
     command_name: String,
     args: HashMap<String, ArgValue>,
-    subcommand: Option<Box<(String, ArgMatches)>>,
+    indices: HashMap<String, Vec<usize>>,
+    sources: HashMap<String, ValueSource>, // Values not from the command line
+    typed: HashMap<String, Vec<TypedValue>>, // Results of `Arg::value_parser`
+    argv_offset: usize,
+}
+
+impl Level {
+    fn new(name: String, command_name: &str) -> Self {
+        Self {
+            name,
+            command_name: command_name.to_string(),
+            args: HashMap::new(),
+            indices: HashMap::new(),
+            sources: HashMap::new(),
+            typed: HashMap::new(),
+            argv_offset: 0,
+        }
+    }
+}
+
+impl fmt::Debug for ArgMatches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = self.level();
+        f.debug_struct("ArgMatches")
+            .field("command_name", &level.command_name)
+            .field("args", &level.args)
+            .field("indices", &level.indices)
+            .field("sources", &level.sources)
+            .field("typed", &level.typed)
+            .field("subcommand", &self.subcommand())
+            .finish()
+    }
 }
 
 impl ArgMatches {
     /// Creates a new empty ArgMatches
     fn new(command_name: impl Into<String>) -> Self {
+        let command_name = command_name.into();
+        let level = Level::new(command_name.clone(), &command_name);
         Self {
-            command_name: command_name.into(),
-            args: HashMap::new(),
-            subcommand: None,
+            path: Arc::new(vec![level]),
+            depth: 0,
+            next: OnceLock::new(),
         }
     }
 
+    /// This command's own level of the path
+    fn level(&self) -> &Level {
+        &self.path[self.depth]
+    }
+
+    /// This command's own level, for the parser to fill in
+    ///
+    /// Nothing else holds the path while parsing, so this does not copy it.
+    fn level_mut(&mut self) -> &mut Level {
+        self.next = OnceLock::new();
+        &mut Arc::make_mut(&mut self.path)[self.depth]
+    }
+
     /// Gets the name of this command
     pub fn command_name(&self) -> &str {
-        &self.command_name
+        &self.level().command_name
     }
 
     /// Checks if an argument is present
     pub fn is_present(&self, name: &str) -> bool {
-        self.args.contains_key(name)
+        self.level().args.contains_key(name)
     }
 
     /// Gets the value of an argument as a string
     pub fn value_of(&self, name: &str) -> Option<&str> {
-        self.args.get(name).and_then(|v| v.as_str())
+        self.level().args.get(name).and_then(|v| v.as_str())
+    }
+
+    /// Gets the value of an argument as an OS string, which may not be valid
+    /// UTF-8 (see [`Arg::allow_invalid_utf8`]); the first one if there are several
+    pub fn value_of_os(&self, name: &str) -> Option<&OsStr> {
+        self.level()
+            .args
+            .get(name)
+            .and_then(ArgValue::as_os_strs)
+            .and_then(|values| values.first().copied())
+    }
+
+    /// Gets every value of an argument as OS strings (see [`value_of_os`](Self::value_of_os))
+    pub fn values_of_os(&self, name: &str) -> Option<Vec<&OsStr>> {
+        self.level().args.get(name).and_then(ArgValue::as_os_strs)
     }
 
     /// Gets the value of an argument as a boolean
     pub fn is_flag_set(&self, name: &str) -> bool {
-        self.args
+        self.level()
+            .args
             .get(name)
             .and_then(|v| v.as_bool())
             .unwrap_or(false)
     }
 
-    /// Gets multiple values for an argument
-    pub fn values_of(&self, name: &str) -> Option<&[String]> {
-        self.args.get(name).and_then(|v| v.as_vec())
+    /// Checks whether `--dry-run` was given (see [`App::dry_run_flag`])
+    pub fn is_dry_run(&self) -> bool {
+        self.is_flag_set(DRY_RUN_ARG)
     }
 
-    /// Gets the subcommand, if any
-    pub fn subcommand(&self) -> Option<(&str, &ArgMatches)> {
-        self.subcommand
-            .as_ref()
-            .map(|boxed| (boxed.0.as_str(), &boxed.1))
+    /// Checks whether `--yes` was given (see [`App::yes_flag`])
+    pub fn assume_yes(&self) -> bool {
+        self.is_flag_set(YES_ARG)
     }
 
-    /// Gets the subcommand name, if any
+    /// Gets the `--format` value registered by [`App::output_format_arg`]
+    ///
+    /// Returns `None` if the option is absent or names an unknown format.
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        self.value_of(FORMAT_ARG).and_then(|v| v.parse().ok())
+    }
+
+    /// Counts how many times an argument was given on the command line
+    ///
+    /// Values from defaults and environment variables are not occurrences,
+    /// so this tells "absent" (0), "given once" and "given repeatedly" apart
+    /// whatever the value. Example: `-vvv` gives `occurrences_of("verbose") == 3`
+    pub fn occurrences_of(&self, name: &str) -> usize {
+        self.level().indices.get(name).map_or(0, Vec::len)
+    }
+
+    /// Gets how many times a counted flag ([`ArgAction::Count`]) was given, 0
+    /// if never
+    ///
+    /// Saturates at `u8::MAX`. Example: `-vvv` gives `get_count("verbose") == 3`
+    pub fn get_count(&self, name: &str) -> u8 {
+        u8::try_from(self.occurrences_of(name)).unwrap_or(u8::MAX)
+    }
+
+    /// Tells where an argument's value came from, `None` if it has none
+    ///
+    /// Lets an app treat an explicit `--token` differently from one picked up
+    /// from the environment. A `--opt` given without its value counts as
+    /// [`ValueSource::CommandLine`] even though it stores the default.
+    pub fn value_source(&self, name: &str) -> Option<ValueSource> {
+        self.level().args.contains_key(name).then(|| {
+            self.level()
+                .sources
+                .get(name)
+                .copied()
+                .unwrap_or(ValueSource::CommandLine)
+        })
+    }
+
+    /// Gets the argv position of each occurrence of an argument, in order
+    ///
+    /// Index 0 is the program name, so `app -I a -L b -I c` gives
+    /// `indices_of("include") == [2, 6]`. Flags record the flag's position,
+    /// options record their value's position (or the `--opt=value` token).
+    /// Comparing indices of different arguments recovers their relative order.
+    pub fn indices_of(&self, name: &str) -> Option<&[usize]> {
+        self.level().indices.get(name).map(Vec::as_slice)
+    }
+
+    /// Gets the verbosity registered by [`App::verbosity_args`]
+    ///
+    /// `-q` gives `-1`, otherwise the number of `-v` flags (0 when neither is
+    /// given). Feed it to [`Logger::from_verbosity`](crate::log::Logger::from_verbosity).
+    pub fn verbosity(&self) -> i8 {
+        if self.is_flag_set(QUIET_ARG) {
+            -1
+        } else {
+            i8::try_from(self.get_count(VERBOSE_ARG)).unwrap_or(i8::MAX)
+        }
+    }
+
+    /// Gets multiple values for an argument
+    pub fn values_of(&self, name: &str) -> Option<&[String]> {
+        self.level().args.get(name).and_then(|v| v.as_vec())
+    }
+
+    /// Gets the value of an argument parsed into any `FromStr` type
+    ///
+    /// Returns `MissingArgument` if the argument is absent and `InvalidValue`
+    /// if the value cannot be parsed.
+    /// Example: `let port: u16 = matches.value_of_t("port")?;`
+    pub fn value_of_t<T: FromStr>(&self, name: &str) -> CommandResult<T> {
+        let value = self
+            .value_of(name)
+            .ok_or_else(|| CommandError::MissingArgument(name.to_string()))?;
+        value
+            .parse::<T>()
+            .map_err(|_| CommandError::InvalidValue(name.to_string(), value.to_string()))
+    }
+
+    /// Like [`value_of_t`](Self::value_of_t), but an absent argument gives `Ok(None)`
+    pub fn value_of_t_opt<T: FromStr>(&self, name: &str) -> CommandResult<Option<T>> {
+        if self.value_of(name).is_none() {
+            return Ok(None);
+        }
+        self.value_of_t(name).map(Some)
+    }
+
+    /// Gets every value of an argument parsed into any `FromStr` type
+    ///
+    /// An absent argument gives an empty list; the first value that cannot be
+    /// parsed fails with `InvalidValue`.
+    pub fn values_of_t<T: FromStr>(&self, name: &str) -> CommandResult<Vec<T>> {
+        let values: &[String] = match self.level().args.get(name) {
+            Some(ArgValue::Multiple(values)) => values,
+            Some(ArgValue::Single(value)) => std::slice::from_ref(value),
+            _ => &[],
+        };
+        values
+            .iter()
+            .map(|value| {
+                value
+                    .parse::<T>()
+                    .map_err(|_| CommandError::InvalidValue(name.to_string(), value.clone()))
+            })
+            .collect()
+    }
+
+    /// Gets the value an argument's [`Arg::value_parser`] produced
+    ///
+    /// Returns `None` if the argument is absent, has no value parser, or its
+    /// parser produces a type other than `T`. For several values, this is the
+    /// first. Example: `let port: Option<&u16> = matches.get_one("port");`
+    pub fn get_one<T: Any>(&self, name: &str) -> Option<&T> {
+        self.level()
+            .typed
+            .get(name)?
+            .first()
+            .and_then(|value| value.0.downcast_ref())
+    }
+
+    /// Gets every value an argument's [`Arg::value_parser`] produced, in order
+    ///
+    /// Empty in the cases where [`get_one`](Self::get_one) gives `None`.
+    pub fn get_many<T: Any>(&self, name: &str) -> Vec<&T> {
+        self.level()
+            .typed
+            .get(name)
+            .map_or_else(Vec::new, |values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.0.downcast_ref())
+                    .collect()
+            })
+    }
+
+    /// Builds a settings struct from these matches
+    /// Example: `let config: Config = matches.populate()?;`
+    pub fn populate<T: FromArgMatches>(&self) -> CommandResult<T> {
+        T::from_arg_matches(self)
+    }
+
+    /// Gets the subcommand, if any
+    pub fn subcommand(&self) -> Option<(&str, &ArgMatches)> {
+        let level = self.path.get(self.depth + 1)?;
+        let matches = self.next.get_or_init(|| {
+            Box::new(ArgMatches {
+                path: Arc::clone(&self.path),
+                depth: self.depth + 1,
+                next: OnceLock::new(),
+            })
+        });
+        Some((level.name.as_str(), matches))
+    }
+
+    /// Gets the subcommand name, if any
     pub fn subcommand_name(&self) -> Option<&str> {
-        self.subcommand.as_ref().map(|boxed| boxed.0.as_str())
+        self.path
+            .get(self.depth + 1)
+            .map(|level| level.name.as_str())
     }
 
     /// Gets the subcommand matches, if any
     pub fn subcommand_matches(&self, name: &str) -> Option<&ArgMatches> {
-        self.subcommand.as_ref().and_then(|boxed| {
-            if boxed.0 == name {
-                Some(&boxed.1)
-            } else {
-                None
-            }
-        })
+        match self.subcommand() {
+            Some((matched, matches)) if matched == name => Some(matches),
+            _ => None,
+        }
     }
 
     /// Inserts an argument value
     fn insert(&mut self, name: String, value: ArgValue) {
-        self.args.insert(name, value);
+        self.level_mut().args.insert(name, value);
+    }
+
+    /// Inserts an argument value that did not come from the command line
+    fn insert_from(&mut self, source: ValueSource, name: String, value: ArgValue) {
+        self.level_mut().sources.insert(name.clone(), source);
+        self.insert(name, value);
+    }
+
+    /// Records one command-line occurrence of an argument at `index` in this command's args
+    fn record_occurrence(&mut self, name: &str, index: usize) {
+        let level = self.level_mut();
+        level
+            .indices
+            .entry(name.to_string())
+            .or_default()
+            .push(level.argv_offset + index);
+    }
+
+    /// How often `name` occurred in this command's own part of argv
+    ///
+    /// Global values inherited from a parent command are not counted, so a
+    /// subcommand can still override them.
+    fn local_occurrences(&self, name: &str) -> usize {
+        let level = self.level();
+        level.indices.get(name).map_or(0, |indices| {
+            indices.iter().filter(|&&i| i >= level.argv_offset).count()
+        })
+    }
+
+    /// Starts the level of a subcommand matched as `name`, below this one
+    ///
+    /// The parser fills it in through these same matches, then steps back up
+    /// with [`leave_subcommand`](Self::leave_subcommand).
+    fn enter_subcommand(&mut self, name: String, command: &Command) {
+        let path = Arc::make_mut(&mut self.path);
+        path.truncate(self.depth + 1);
+        path.push(Level::new(name, &command.name));
+        self.depth += 1;
+        self.next = OnceLock::new();
+    }
+
+    /// Steps back up from a subcommand, taking over the values it parsed
+    /// for `globals`
+    fn leave_subcommand(&mut self, globals: &[Arg]) {
+        self.depth -= 1;
+        let (levels, below) = Arc::make_mut(&mut self.path).split_at_mut(self.depth + 1);
+        let (level, sub) = (&mut levels[self.depth], &below[0]);
+        for global in globals {
+            if let Some(value) = sub.args.get(&global.name) {
+                level.args.insert(global.name.clone(), value.clone());
+            }
+            if let Some(indices) = sub.indices.get(&global.name) {
+                level.indices.insert(global.name.clone(), indices.clone());
+            }
+            if let Some(source) = sub.sources.get(&global.name) {
+                level.sources.insert(global.name.clone(), *source);
+            }
+        }
+    }
+}
+
+/// Maps parsed matches into an application's settings struct
+///
+/// Implement it with the typed getters — [`ArgMatches::value_of_t`] for
+/// required values, [`value_of_t_opt`](ArgMatches::value_of_t_opt) for
+/// `Option<T>`, [`values_of_t`](ArgMatches::values_of_t) for `Vec<T>` and
+/// [`is_flag_set`](ArgMatches::is_flag_set) for `bool` — and call
+/// [`ArgMatches::populate`], or parse and convert in one go with
+/// [`App::parse_into`]. Errors are ordinary [`CommandError`]s, so
+/// [`Command::format_error`] reports them like any parse error; collect them
+/// in [`ArgErrors`] to report every bad argument at once.
+///
+/// ```
+/// use zfish::command::{App, Arg, ArgMatches, CommandResult, FromArgMatches};
+///
+/// struct Config {
+///     port: u16,
+///     host: Option<String>,
+///     tags: Vec<String>,
+///     verbose: bool,
+/// }
+///
+/// impl FromArgMatches for Config {
+///     fn from_arg_matches(matches: &ArgMatches) -> CommandResult<Self> {
+///         Ok(Config {
+///             port: matches.value_of_t("port")?,
+///             host: matches.value_of_t_opt("host")?,
+///             tags: matches.values_of_t("tag")?,
+///             verbose: matches.is_flag_set("verbose"),
+///         })
+///     }
+/// }
+///
+/// let matches = App::new("serve")
+///     .arg(Arg::new("port").long("port").default_value("8080"))
+///     .arg(Arg::new("host").long("host"))
+///     .arg(Arg::new("tag").long("tag").multiple(true))
+///     .arg(Arg::new("verbose").short('v').takes_value(false))
+///     .try_get_matches_from(["serve", "--tag", "a", "--tag", "b", "-v"])
+///     .unwrap();
+///
+/// let config: Config = matches.populate().unwrap();
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.host, None);
+/// assert_eq!(config.tags, ["a", "b"]);
+/// assert!(config.verbose);
+/// ```
+pub trait FromArgMatches: Sized {
+    /// Builds `Self` from `matches`, failing on a bad argument
+    fn from_arg_matches(matches: &ArgMatches) -> CommandResult<Self>;
+}
+
+/// Collects conversion errors so [`FromArgMatches`] can report them all
+///
+/// [`check`](Self::check) unwraps a getter's result, recording the error and
+/// standing in `T::default()` for the value; [`finish`](Self::finish) then
+/// hands back the struct only if nothing failed. Several errors come back as
+/// one [`CommandError::Multiple`], rendered one `error:` line each.
+///
+/// ```
+/// use zfish::command::{App, Arg, ArgErrors, ArgMatches, CommandResult, FromArgMatches};
+///
+/// struct Limits {
+///     jobs: u8,
+///     timeout: Option<u32>,
+/// }
+///
+/// impl FromArgMatches for Limits {
+///     fn from_arg_matches(matches: &ArgMatches) -> CommandResult<Self> {
+///         let mut errors = ArgErrors::new();
+///         let limits = Limits {
+///             jobs: errors.check(matches.value_of_t("jobs")),
+///             timeout: errors.check(matches.value_of_t_opt("timeout")),
+///         };
+///         errors.finish(limits)
+///     }
+/// }
+///
+/// let result: CommandResult<Limits> = App::new("run")
+///     .arg(Arg::new("jobs").short('j'))
+///     .arg(Arg::new("timeout").long("timeout"))
+///     .try_parse_into_from(["run", "-j", "many", "--timeout", "soon"]);
+/// assert_eq!(
+///     result.err().unwrap().to_string(),
+///     "error: invalid value 'many' for 'jobs'\nerror: invalid value 'soon' for 'timeout'"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArgErrors {
+    errors: Vec<CommandError>,
+}
+
+impl ArgErrors {
+    /// Creates an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of `result`, or records its error and returns
+    /// `T::default()`
+    pub fn check<T: Default>(&mut self, result: CommandResult<T>) -> T {
+        result.unwrap_or_else(|err| {
+            self.push(err);
+            T::default()
+        })
+    }
+
+    /// Records an error found by other means, e.g. a cross-field check
+    pub fn push(&mut self, error: CommandError) {
+        self.errors.push(error);
     }
 
-    /// Sets the subcommand
-    fn set_subcommand(&mut self, name: String, matches: ArgMatches) {
-        self.subcommand = Some(Box::new((name, matches)));
+    /// Checks whether no error was recorded
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns `value` if no error was recorded, else the error (or
+    /// [`CommandError::Multiple`] for several)
+    pub fn finish<T>(mut self, value: T) -> CommandResult<T> {
+        match self.errors.len() {
+            0 => Ok(value),
+            1 => Err(self.errors.remove(0)),
+            _ => Err(CommandError::Multiple(self.errors)),
+        }
     }
 }
 
@@ -463,11 +1711,145 @@ pub struct Command {
     name: String,
     about: Option<String>,
     long_about: Option<String>,
+    before_help: Option<String>,
+    after_help: Option<String>,
+    usage: Option<String>,
     version: Option<String>,
     args: Vec<Arg>,
-    subcommands: Vec<Command>,
+    /// Shared so that parsing can add inherited globals without copying the tree
+    subcommands: Arc<Vec<Command>>,
     groups: Vec<ArgGroup>,
     aliases: Vec<String>,
+    styles: HelpStyles,
+    settings: Settings,
+    examples: Vec<Example>,
+    error_exit_code: i32,
+    error_formatter: Option<fn(&Command, &CommandError) -> String>,
+    /// Where everything after `--` is stored, see [`Command::trailing_var_arg`]
+    raw_args: Option<String>,
+}
+
+/// A usage example shown under `EXAMPLES:` in help
+#[derive(Debug, Clone)]
+struct Example {
+    line: String,
+    /// Verified by [`crate::testing::verify_examples`]
+    checked: bool,
+}
+
+/// A parse behavior toggle for [`Command::setting`]
+///
+/// Combine several with `|`. Settings apply to the command they are set on
+/// and are inherited by all of its subcommands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Setting {
+    /// Once a positional value fills the positionals before the variadic one
+    /// (`Arg::last`), every remaining argument goes to the variadic verbatim,
+    /// even if it looks like a flag
+    TrailingVarArg,
+    /// Options accept values starting with `-` (e.g. `--offset -5`)
+    AllowHyphenValues,
+    /// Show help instead of parsing when no arguments are given
+    /// (only for commands that define arguments or subcommands)
+    ArgRequiredElseHelp,
+    /// Fail with [`CommandError::MissingSubcommand`] unless a subcommand is given
+    /// (only for commands that have subcommands)
+    SubcommandRequired,
+    /// Prompt for missing required arguments when stdin is a TTY, when
+    /// parsing through [`App`] (see [`Command::interactive_fallback`])
+    InteractiveFallback,
+    /// A unique prefix of a long flag stands for the flag (`--verb` for
+    /// `--verbose`); see [`Command::infer_long_args`]
+    InferLongArgs,
+    /// A unique prefix of a subcommand name or alias runs that subcommand
+    /// (`bu` for `build`); see [`Command::infer_subcommands`]
+    InferSubcommands,
+    /// A bare word that no positional or subcommand takes is an error
+    /// instead of being ignored; see [`Command::strict_positionals`]
+    StrictPositionals,
+}
+
+impl Setting {
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// A set of [`Setting`]s, built with `|`
+/// Example: `Setting::TrailingVarArg | Setting::AllowHyphenValues`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings(u32);
+
+impl Settings {
+    /// The empty set
+    pub fn empty() -> Self {
+        Settings(0)
+    }
+
+    /// Checks whether `setting` is in this set
+    pub fn contains(self, setting: Setting) -> bool {
+        self.0 & setting.bit() != 0
+    }
+
+    /// Removes `setting` from this set
+    fn without(self, setting: Setting) -> Self {
+        Settings(self.0 & !setting.bit())
+    }
+}
+
+impl From<Setting> for Settings {
+    fn from(setting: Setting) -> Self {
+        Settings(setting.bit())
+    }
+}
+
+impl std::ops::BitOr for Setting {
+    type Output = Settings;
+
+    fn bitor(self, rhs: Setting) -> Settings {
+        Settings(self.bit() | rhs.bit())
+    }
+}
+
+impl std::ops::BitOr<Setting> for Settings {
+    type Output = Settings;
+
+    fn bitor(self, rhs: Setting) -> Settings {
+        Settings(self.0 | rhs.bit())
+    }
+}
+
+impl std::ops::BitOr for Settings {
+    type Output = Settings;
+
+    fn bitor(self, rhs: Settings) -> Settings {
+        Settings(self.0 | rhs.0)
+    }
+}
+
+/// Supplies a value for a missing required argument (interactive fallback)
+type Prompter = fn(&Arg) -> io::Result<String>;
+
+/// Settings inherited from a parent command while parsing
+#[derive(Debug, Clone, Default)]
+struct ParseContext {
+    settings: Settings,
+    /// Asks for missing required values; only the App frontend installs one
+    prompter: Option<Prompter>,
+    globals: Vec<Arg>,
+    global_values: Vec<(String, ArgValue, Vec<usize>)>,
+    argv_offset: usize,
+    /// Argument defaults read by [`App::config_file`]
+    config: Option<Arc<ConfigFile>>,
+    /// Subcommand names from the root down, naming this command's config section
+    config_section: Vec<String>,
+    /// Command names from the root down to this command, for error messages
+    command_path: Vec<String>,
+    /// Variables read by [`App::dotenv`], consulted after the real environment
+    dotenv: Option<Arc<HashMap<String, String>>>,
+    /// Some argument was not valid UTF-8 and is escaped (see [`escape_os`])
+    escaped_os: bool,
 }
 
 impl Command {
@@ -477,11 +1859,20 @@ impl Command {
             name: name.into(),
             about: None,
             long_about: None,
+            before_help: None,
+            after_help: None,
+            usage: None,
             version: None,
             args: Vec::new(),
-            subcommands: Vec::new(),
+            subcommands: Arc::default(),
             groups: Vec::new(),
             aliases: Vec::new(),
+            styles: HelpStyles::default(),
+            settings: Settings::empty(),
+            examples: Vec::new(),
+            error_exit_code: 1,
+            error_formatter: None,
+            raw_args: None,
         }
     }
 
@@ -497,6 +1888,28 @@ impl Command {
         self
     }
 
+    /// Sets text printed above everything else in help, e.g. a banner
+    pub fn before_help(mut self, text: impl Into<String>) -> Self {
+        self.before_help = Some(text.into());
+        self
+    }
+
+    /// Sets text printed below everything else in help, e.g. extended
+    /// examples, links or a copyright notice
+    pub fn after_help(mut self, text: impl Into<String>) -> Self {
+        self.after_help = Some(text.into());
+        self
+    }
+
+    /// Replaces the synthesized usage line in help and error output
+    ///
+    /// Some argument mixes are clearer written by hand.
+    /// Example: `Command::new("cp").override_usage("cp [OPTIONS] <SRC>... <DEST>")`
+    pub fn override_usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = Some(usage.into());
+        self
+    }
+
     /// Sets the version for this command
     pub fn version(mut self, version: impl Into<String>) -> Self {
         self.version = Some(version.into());
@@ -517,13 +1930,13 @@ impl Command {
 
     /// Adds a subcommand to this command
     pub fn subcommand(mut self, subcommand: Command) -> Self {
-        self.subcommands.push(subcommand);
+        Arc::make_mut(&mut self.subcommands).push(subcommand);
         self
     }
 
     /// Adds multiple subcommands to this command
     pub fn subcommands(mut self, subcommands: &[Command]) -> Self {
-        self.subcommands.extend_from_slice(subcommands);
+        Arc::make_mut(&mut self.subcommands).extend_from_slice(subcommands);
         self
     }
 
@@ -548,253 +1961,1188 @@ impl Command {
         self
     }
 
-    /// Gets the name of this command
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    /// Finds an argument by name, short, or long flag
-    fn find_arg(&self, identifier: &str) -> Option<&Arg> {
-        self.args.iter().find(|arg| {
-            arg.name == identifier
-                || arg.short.map(|c| format!("{}", c)) == Some(identifier.to_string())
-                || arg.long.as_deref() == Some(identifier)
-        })
+    /// Adds a usage example, shown under `EXAMPLES:` in help
+    /// Example: `Command::new("build").example("myapp build --release")`
+    pub fn example(mut self, line: impl Into<String>) -> Self {
+        self.examples.push(Example {
+            line: line.into(),
+            checked: false,
+        });
+        self
     }
 
-    /// Finds a subcommand by name or alias
-    fn find_subcommand(&self, name: &str) -> Option<&Command> {
-        self.subcommands
-            .iter()
-            .find(|cmd| cmd.name == name || cmd.aliases.contains(&name.to_string()))
+    /// Adds a usage example that [`verify_examples`](crate::testing::verify_examples)
+    /// parses against the command tree
+    ///
+    /// Write the full command line, starting with the program name, even on
+    /// subcommands (`"myapp build --release"`).
+    pub fn example_checked(mut self, line: impl Into<String>) -> Self {
+        self.examples.push(Example {
+            line: line.into(),
+            checked: true,
+        });
+        self
     }
 
-    /// Generates help text for this command
-    pub fn generate_help(&self) -> String {
-        let mut help = String::new();
-
-        self.generate_header(&mut help);
-        self.generate_usage(&mut help);
-        self.generate_args_section(&mut help);
-        self.generate_options_section(&mut help);
-        self.generate_subcommands_section(&mut help);
-
-        help
+    /// Every checked example in this command and its subcommands, with the
+    /// path of the command it belongs to (`"myapp build"`)
+    pub(crate) fn checked_examples(&self) -> Vec<(String, &str)> {
+        let mut found = Vec::new();
+        self.collect_checked_examples(&self.name, &mut found);
+        found
     }
 
-    /// Generate header section (about and version)
-    fn generate_header(&self, help: &mut String) {
-        if let Some(ref about) = self.about {
-            help.push_str(&format!("{}\n", about));
+    fn collect_checked_examples<'a>(&'a self, path: &str, found: &mut Vec<(String, &'a str)>) {
+        for example in self.examples.iter().filter(|e| e.checked) {
+            found.push((path.to_string(), example.line.as_str()));
         }
-
-        if let Some(ref version) = self.version {
-            help.push_str(&format!("\nVersion: {}\n", version));
+        for subcmd in self.subcommands.iter() {
+            subcmd.collect_checked_examples(&format!("{} {}", path, subcmd.name), found);
         }
     }
 
-    /// Generate usage line
-    fn generate_usage(&self, help: &mut String) {
-        help.push_str(&format!("\nUSAGE:\n    {}", self.name));
-
-        let mut positional_args: Vec<&Arg> =
-            self.args.iter().filter(|a| a.index.is_some()).collect();
-        positional_args.sort_by_key(|a| a.index.unwrap());
+    /// Writes a completion script for `shell` covering this command, its
+    /// flags, `possible_values` and the whole subcommand tree
+    ///
+    /// See the [`shell`](crate::shell) module for what the scripts complete.
+    pub fn generate_completions(&self, shell: Shell) -> String {
+        crate::shell::generate(&self.completion_spec(&[]), shell)
+    }
 
-        if self.args.iter().any(|a| a.index.is_none()) {
-            help.push_str(" [OPTIONS]");
-        }
+    /// Writes the [`generate_completions`](Self::generate_completions) script
+    /// to `out`, e.g. from a `completions <shell>` subcommand
+    pub fn print_completions(&self, shell: Shell, out: &mut impl io::Write) -> io::Result<()> {
+        out.write_all(self.generate_completions(shell).as_bytes())?;
+        out.flush()
+    }
 
-        for arg in &positional_args {
-            self.append_positional_usage(arg, help);
+    /// The command tree as completion scripts see it; `inherited` are the
+    /// global arguments of the parent commands
+    fn completion_spec(&self, inherited: &[&Arg]) -> CommandSpec {
+        let option = |arg: &Arg| OptionSpec {
+            name: arg.name.clone(),
+            short: arg.short,
+            long: arg.long.clone(),
+            help: arg.help.clone(),
+            takes_value: arg.takes_value,
+            multiple: arg.multiple,
+            possible_values: arg.possible_values.clone().unwrap_or_default(),
+            value_hint: arg.value_hint,
+        };
+        let builtin = |short: char, long: &str, help: &str| OptionSpec {
+            name: long.to_string(),
+            short: Some(short),
+            long: Some(long.to_string()),
+            help: Some(help.to_string()),
+            ..OptionSpec::default()
+        };
+
+        let mut options = vec![builtin('h', "help", "Print help")];
+        if self.version.is_some() {
+            options.push(builtin('V', "version", "Print version"));
         }
+        let flagged = |arg: &&Arg| arg.short.is_some() || arg.long.is_some();
+        options.extend(inherited.iter().copied().filter(flagged).map(option));
+        options.extend(self.args.iter().filter(flagged).map(option));
 
-        if !self.subcommands.is_empty() {
-            help.push_str(" <COMMAND>");
+        let mut positionals: Vec<&Arg> = self
+            .args
+            .iter()
+            .filter(|arg| arg.index.is_some() && !flagged(arg))
+            .collect();
+        positionals.sort_by_key(|arg| arg.index);
+
+        let mut globals = inherited.to_vec();
+        globals.extend(self.args.iter().filter(|arg| arg.global));
+        CommandSpec {
+            name: self.name.clone(),
+            about: self.about.clone(),
+            aliases: self.aliases.clone(),
+            options,
+            positionals: positionals
+                .into_iter()
+                .map(|arg| PositionalSpec {
+                    name: arg.name.clone(),
+                    multiple: arg.multiple || arg.last,
+                    possible_values: arg.possible_values.clone().unwrap_or_default(),
+                    value_hint: arg.value_hint,
+                })
+                .collect(),
+            subcommands: self
+                .subcommands
+                .iter()
+                .map(|sub| sub.completion_spec(&globals))
+                .collect(),
         }
-
-        help.push('\n');
     }
 
-    /// Append single positional arg to usage line
-    fn append_positional_usage(&self, arg: &Arg, help: &mut String) {
-        if arg.last {
-            help.push_str(&format!(" [{}]...", arg.name.to_uppercase()));
-        } else if arg.required {
-            help.push_str(&format!(" <{}>", arg.name.to_uppercase()));
-        } else {
-            help.push_str(&format!(" [{}]", arg.name.to_uppercase()));
+    /// Checks the definition for mistakes, panicking with every problem found
+    ///
+    /// Reports duplicate names, short or long flags (including ones that clash
+    /// with inherited globals or with `-h`/`-V`), two positionals at the same
+    /// index, and `requires`, `conflicts_with` or group members that name no
+    /// argument. Subcommands are checked too. Debug builds run it at the start
+    /// of every parse; call it from a test as well so a misconfigured CLI
+    /// fails there rather than in a release build that misparses.
+    #[track_caller]
+    pub fn debug_assert(&self) {
+        let mut problems = Vec::new();
+        self.collect_definition_problems(&self.name, &[], &mut problems);
+        if !problems.is_empty() {
+            panic!(
+                "{} problem(s) in the command definition:\n  {}",
+                problems.len(),
+                problems.join("\n  ")
+            );
         }
     }
 
-    /// Generate ARGS section for positional arguments
-    fn generate_args_section(&self, help: &mut String) {
-        let mut positional_args: Vec<&Arg> =
-            self.args.iter().filter(|a| a.index.is_some()).collect();
-        positional_args.sort_by_key(|a| a.index.unwrap());
+    fn collect_definition_problems(
+        &self,
+        path: &str,
+        inherited: &[&Arg],
+        problems: &mut Vec<String>,
+    ) {
+        // Own args first, so every pair below involves at least one of them
+        let args: Vec<&Arg> = self
+            .args
+            .iter()
+            .chain(
+                inherited
+                    .iter()
+                    .copied()
+                    .filter(|g| !self.args.iter().any(|a| a.name == g.name)),
+            )
+            .collect();
+
+        for (i, arg) in args.iter().enumerate().take(self.args.len()) {
+            for other in &args[i + 1..] {
+                let clash = if arg.name == other.name {
+                    Some("name".to_string())
+                } else if let Some(c) = arg.short.filter(|c| other.short == Some(*c)) {
+                    Some(format!("-{}", c))
+                } else if let Some(l) = arg.long.as_ref().filter(|l| other.long.as_ref() == Some(l))
+                {
+                    Some(format!("--{}", l))
+                } else {
+                    arg.index
+                        .filter(|n| other.index == Some(*n))
+                        .map(|n| format!("position {}", n))
+                };
+                if let Some(clash) = clash {
+                    problems.push(format!(
+                        "{}: '{}' and '{}' both use {}",
+                        path, arg.name, other.name, clash
+                    ));
+                }
+            }
 
-        if positional_args.is_empty() {
-            return;
-        }
+            let mut reserved = vec![
+                ("-h", arg.short == Some('h')),
+                ("--help", arg.long.as_deref() == Some("help")),
+            ];
+            if self.version.is_some() {
+                reserved.push(("-V", arg.short == Some('V')));
+                reserved.push(("--version", arg.long.as_deref() == Some("version")));
+            }
+            for (flag, _) in reserved.into_iter().filter(|(_, used)| *used) {
+                problems.push(format!(
+                    "{}: '{}' uses {}, which is taken by the built-in flag",
+                    path, arg.name, flag
+                ));
+            }
 
-        help.push_str("\nARGS:\n");
-        for arg in &positional_args {
-            self.format_arg_line(arg, help);
+            let references = arg
+                .requires
+                .iter()
+                .map(|name| ("requires", name))
+                .chain(
+                    arg.conflicts_with
+                        .iter()
+                        .map(|name| ("conflicts_with", name)),
+                )
+                .chain(
+                    arg.required_unless
+                        .iter()
+                        .map(|name| ("required_unless_present", name)),
+                )
+                .chain(
+                    arg.required_if
+                        .iter()
+                        .map(|(name, _)| ("required_if_eq", name)),
+                )
+                .chain(
+                    arg.default_value_ifs
+                        .iter()
+                        .map(|(name, _, _)| ("default_value_if", name)),
+                );
+            for (relation, name) in references {
+                if !args.iter().any(|a| &a.name == name) {
+                    problems.push(format!(
+                        "{}: '{}' {} unknown argument '{}'",
+                        path, arg.name, relation, name
+                    ));
+                }
+            }
         }
-    }
-
-    /// Format a single positional argument line
-    fn format_arg_line(&self, arg: &Arg, help: &mut String) {
-        let mut arg_line = format!("    <{}>", arg.name.to_uppercase());
 
-        while arg_line.len() < 30 {
-            arg_line.push(' ');
+        if let Some(ref name) = self.raw_args
+            && args.iter().any(|a| &a.name == name)
+        {
+            problems.push(format!(
+                "{}: '{}' is both an argument and the trailing_var_arg name",
+                path, name
+            ));
         }
 
-        if let Some(ref help_text) = arg.help {
-            arg_line.push_str(help_text);
+        for group in &self.groups {
+            for member in &group.args {
+                if !args.iter().any(|a| &a.name == member) {
+                    problems.push(format!(
+                        "{}: group '{}' has unknown member '{}'",
+                        path, group.name, member
+                    ));
+                }
+            }
+            for other in &group.conflicts_with {
+                if !args.iter().any(|a| &a.name == other)
+                    && !self.groups.iter().any(|g| &g.name == other)
+                {
+                    problems.push(format!(
+                        "{}: group '{}' conflicts_with unknown argument or group '{}'",
+                        path, group.name, other
+                    ));
+                }
+            }
         }
 
-        if arg.required {
-            arg_line.push_str(" [required]");
+        let globals: Vec<&Arg> = args.iter().copied().filter(|a| a.global).collect();
+        for subcmd in self.subcommands.iter() {
+            subcmd.collect_definition_problems(
+                &format!("{} {}", path, subcmd.name),
+                &globals,
+                problems,
+            );
         }
-
-        help.push_str(&format!("{}\n", arg_line));
     }
 
-    /// Generate OPTIONS section for flags and options
-    fn generate_options_section(&self, help: &mut String) {
-        let option_args: Vec<&Arg> = self.args.iter().filter(|a| a.index.is_none()).collect();
-
-        if option_args.is_empty() {
-            return;
-        }
-
-        help.push_str("\nOPTIONS:\n");
-        for arg in &option_args {
-            self.format_option_line(arg, help);
-        }
+    /// Gets the name of this command
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
-    /// Format a single option/flag line
-    fn format_option_line(&self, arg: &Arg, help: &mut String) {
-        let mut arg_line = String::from("    ");
+    /// Finds an argument by name, short, or long flag
+    fn find_arg(&self, identifier: &str) -> Option<&Arg> {
+        self.args.iter().find(|arg| {
+            arg.name == identifier
+                || arg.short.is_some_and(|c| identifier.chars().eq([c]))
+                || arg.long.as_deref() == Some(identifier)
+                || (arg.ignore_case
+                    && arg
+                        .long
+                        .as_deref()
+                        .is_some_and(|long| long.eq_ignore_ascii_case(identifier)))
+        })
+    }
 
-        if let Some(short) = arg.short {
-            arg_line.push_str(&format!("-{}", short));
-            if arg.long.is_some() {
-                arg_line.push_str(", ");
-            }
-        }
+    /// Finds a subcommand by name or alias
+    fn find_subcommand(&self, name: &str) -> Option<&Command> {
+        self.subcommands
+            .iter()
+            .find(|cmd| cmd.name == name || cmd.aliases.iter().any(|a| a == name))
+    }
 
-        if let Some(ref long) = arg.long {
-            arg_line.push_str(&format!("--{}", long));
+    /// Resolves the name of a `--long` flag, expanding unique prefixes under
+    /// [`Setting::InferLongArgs`]
+    ///
+    /// A prefix of the builtin `--help` or `--version` requests help or version.
+    fn find_long_arg(&self, flag_name: &str, ctx: &ParseContext) -> CommandResult<&Arg> {
+        if let Some(arg) = self.find_arg(flag_name) {
+            return Ok(arg);
         }
-
-        if arg.takes_value {
-            arg_line.push_str(&format!(" <{}>", arg.name.to_uppercase()));
+        if !ctx.settings.contains(Setting::InferLongArgs) || flag_name.is_empty() {
+            return Err(self.unknown_long_flag(flag_name));
         }
 
-        while arg_line.len() < 30 {
-            arg_line.push(' ');
+        let builtins = std::iter::once("help").chain(self.version.as_ref().map(|_| "version"));
+        let candidates: Vec<(&str, Option<&Arg>)> = self
+            .args
+            .iter()
+            .filter_map(|a| a.long.as_deref().map(|long| (long, Some(a))))
+            .chain(builtins.map(|long| (long, None)))
+            .filter(|(long, _)| long.starts_with(flag_name))
+            .collect();
+        match candidates[..] {
+            [] => Err(self.unknown_long_flag(flag_name)),
+            [(_, Some(arg))] => Ok(arg),
+            [("help", None)] => Err(CommandError::HelpRequested),
+            [(_, None)] => Err(CommandError::VersionRequested),
+            _ => Err(CommandError::AmbiguousArgument(
+                flag_name.to_string(),
+                candidates
+                    .iter()
+                    .map(|(long, _)| format!("--{}", long))
+                    .collect(),
+            )),
         }
-
-        self.append_option_metadata(arg, &mut arg_line);
-        help.push_str(&format!("{}\n", arg_line));
     }
 
-    /// Append help text, required flag, and default value to option line
-    fn append_option_metadata(&self, arg: &Arg, arg_line: &mut String) {
-        if let Some(ref help_text) = arg.help {
-            arg_line.push_str(help_text);
-        }
-
-        if arg.required {
-            arg_line.push_str(" [required]");
+    /// Finds the subcommand a unique prefix of a name or alias stands for,
+    /// under [`Setting::InferSubcommands`]
+    fn infer_subcommand(
+        &self,
+        prefix: &str,
+        ctx: &ParseContext,
+    ) -> CommandResult<Option<&Command>> {
+        if !ctx.settings.contains(Setting::InferSubcommands) {
+            return Ok(None);
         }
-
-        if let Some(ref default) = arg.default_value {
-            arg_line.push_str(&format!(" [default: {}]", default));
+        let candidates: Vec<&Command> = self
+            .subcommands
+            .iter()
+            .filter(|cmd| {
+                std::iter::once(&cmd.name)
+                    .chain(&cmd.aliases)
+                    .any(|name| name.starts_with(prefix))
+            })
+            .collect();
+        match candidates[..] {
+            [] => Ok(None),
+            [cmd] => Ok(Some(cmd)),
+            // Let positionals take the word, as with a misspelling
+            _ if self.args.iter().any(|a| a.index.is_some()) => Ok(None),
+            _ => Err(CommandError::AmbiguousSubcommand(
+                prefix.to_string(),
+                candidates.iter().map(|cmd| cmd.name.clone()).collect(),
+            )),
         }
     }
 
-    /// Generate COMMANDS section for subcommands
-    fn generate_subcommands_section(&self, help: &mut String) {
-        if self.subcommands.is_empty() {
-            return;
-        }
+    /// Finds the nested subcommand at `path`, a list of names below this command
+    fn find_path(&self, path: &[String]) -> Option<&Command> {
+        path.iter().try_fold(self, |cmd, name| {
+            cmd.subcommands.iter().find(|sub| sub.name == *name)
+        })
+    }
 
-        help.push_str("\nCOMMANDS:\n");
-        for subcmd in &self.subcommands {
-            self.format_subcommand_line(subcmd, help);
+    /// The error for an unknown `--long` flag, with the closest long flag
+    /// (including the builtin `--help` and `--version`) as a suggestion
+    fn unknown_long_flag(&self, flag_name: &str) -> CommandError {
+        let builtins = std::iter::once("help").chain(self.version.as_ref().map(|_| "version"));
+        let longs = self.args.iter().filter_map(|a| a.long.as_deref());
+        match did_you_mean(flag_name, longs.chain(builtins)) {
+            Some(long) => {
+                CommandError::MisspelledArgument(flag_name.to_string(), format!("--{}", long))
+            }
+            None => CommandError::UnknownArgument(flag_name.to_string()),
         }
+    }
 
-        help.push_str("\nRun '<COMMAND> --help' for more information on a specific command.\n");
+    /// The error for a word that names none of our subcommands but is close
+    /// to one of their names or aliases
+    fn misspelled_subcommand(&self, name: &str) -> Option<CommandError> {
+        let names = self
+            .subcommands
+            .iter()
+            .flat_map(|cmd| std::iter::once(&cmd.name).chain(&cmd.aliases))
+            .map(String::as_str);
+        did_you_mean(name, names)
+            .map(|known| CommandError::MisspelledSubcommand(name.to_string(), known.to_string()))
     }
 
-    /// Format a single subcommand line
-    fn format_subcommand_line(&self, subcmd: &Command, help: &mut String) {
-        let mut cmd_line = format!("    {}", subcmd.name);
+    /// Sets the styles used for help and error output
+    pub fn styles(mut self, styles: HelpStyles) -> Self {
+        self.styles = styles;
+        self
+    }
 
-        if !subcmd.aliases.is_empty() {
-            cmd_line.push_str(&format!(" ({})", subcmd.aliases.join(", ")));
-        }
+    /// Sets the exit status used when parsing fails (default `1`)
+    ///
+    /// Help and version requests still exit with `0`. Many GNU tools use `2`
+    /// for usage errors.
+    pub fn error_exit_code(mut self, code: i32) -> Self {
+        self.error_exit_code = code;
+        self
+    }
 
-        while cmd_line.len() < 30 {
-            cmd_line.push(' ');
-        }
+    /// Replaces how parse errors are rendered (help and version are unaffected)
+    ///
+    /// The formatter gets the command that was run and the error, and returns
+    /// the full text to print, trailing newline included.
+    /// [`CommandError::message`] and [`render_usage`](Command::render_usage)
+    /// are the building blocks of the default rendering.
+    /// Example: `cmd.error_formatter(|cmd, err| format!("{}: {}\n", cmd.name(), err.message()))`
+    pub fn error_formatter(mut self, formatter: fn(&Command, &CommandError) -> String) -> Self {
+        self.error_formatter = Some(formatter);
+        self
+    }
+
+    /// Prompts for missing required arguments instead of failing, when stdin is a TTY
+    ///
+    /// Arguments with possible values are offered as a numbered selection.
+    /// Applies to this command and all of its subcommands. Has no effect
+    /// without the `interactive` feature.
+    pub fn interactive_fallback(mut self, enabled: bool) -> Self {
+        self.settings = if enabled {
+            self.settings | Setting::InteractiveFallback
+        } else {
+            self.settings.without(Setting::InteractiveFallback)
+        };
+        self
+    }
+
+    /// Fails with [`CommandError::MissingSubcommand`] when no subcommand is
+    /// given, instead of returning matches without one
+    ///
+    /// The error is shown with the help of the command that needs the
+    /// subcommand. Applies to this command and all of its subcommands (those
+    /// without subcommands of their own are unaffected).
+    pub fn subcommand_required(mut self, required: bool) -> Self {
+        self.settings = if required {
+            self.settings | Setting::SubcommandRequired
+        } else {
+            self.settings.without(Setting::SubcommandRequired)
+        };
+        self
+    }
+
+    /// Accepts unambiguous abbreviations of long flags, so `--verb` means
+    /// `--verbose`
+    ///
+    /// An exact flag name always wins; a prefix shared by several flags fails
+    /// with [`CommandError::AmbiguousArgument`]. Applies to this command and
+    /// all of its subcommands.
+    pub fn infer_long_args(mut self, enabled: bool) -> Self {
+        self.settings = if enabled {
+            self.settings | Setting::InferLongArgs
+        } else {
+            self.settings.without(Setting::InferLongArgs)
+        };
+        self
+    }
+
+    /// Accepts unambiguous abbreviations of subcommand names and aliases, so
+    /// `bu` runs `build`
+    ///
+    /// The matches record the subcommand's full name. A prefix shared by
+    /// several subcommands fails with [`CommandError::AmbiguousSubcommand`]
+    /// (unless the command takes positionals, which then get the word).
+    /// Applies to this command and all of its subcommands.
+    pub fn infer_subcommands(mut self, enabled: bool) -> Self {
+        self.settings = if enabled {
+            self.settings | Setting::InferSubcommands
+        } else {
+            self.settings.without(Setting::InferSubcommands)
+        };
+        self
+    }
+
+    /// Stores everything after `--` verbatim under `name`, for wrappers that
+    /// pass the rest of the command line on to another program
+    ///
+    /// `myapp exec -- cargo test --release` then gives
+    /// `values_of("args") == ["cargo", "test", "--release"]` with no flag
+    /// parsing after `--`; a bare `--` stores an empty list. `name` must not
+    /// be a declared argument. Unlike [`Setting::TrailingVarArg`], this needs
+    /// the `--` but no variadic positional.
+    /// Example: `Command::new("exec").trailing_var_arg("args")`
+    pub fn trailing_var_arg(mut self, name: impl Into<String>) -> Self {
+        self.raw_args = Some(name.into());
+        self
+    }
+
+    /// Rejects bare words that no declared positional or subcommand takes
+    ///
+    /// By default extra words are ignored. With this, `git gti` fails with
+    /// [`CommandError::UnknownSubcommand`] when the command only has
+    /// subcommands, and any other leftover word with
+    /// [`CommandError::UnexpectedArgument`]. Applies to this command and all
+    /// of its subcommands.
+    pub fn strict_positionals(mut self, strict: bool) -> Self {
+        self.settings = if strict {
+            self.settings | Setting::StrictPositionals
+        } else {
+            self.settings.without(Setting::StrictPositionals)
+        };
+        self
+    }
+
+    /// Turns on one or more parse behaviors for this command and its subcommands
+    /// Example: `Command::new("run").setting(Setting::TrailingVarArg | Setting::AllowHyphenValues)`
+    pub fn setting(mut self, settings: impl Into<Settings>) -> Self {
+        self.settings = self.settings | settings.into();
+        self
+    }
+
+    /// Checks whether a setting was turned on for this command itself
+    pub fn is_set(&self, setting: Setting) -> bool {
+        self.settings.contains(setting)
+    }
+
+    /// Generates help text for this command
+    pub fn generate_help(&self) -> String {
+        let mut help = String::with_capacity(self.help_capacity());
+        self.write_help_sections(&mut help)
+            .expect("writing to a String cannot fail");
+        help
+    }
+
+    /// Renders help text into `buf`, appending to whatever it already holds
+    ///
+    /// Reusing one buffer avoids a fresh allocation per render, e.g. when
+    /// writing help for many subcommands into a single page.
+    /// Example: `cmd.help_bytes(&mut buf); io::stdout().write_all(&buf)?;`
+    pub fn help_bytes(&self, buf: &mut Vec<u8>) {
+        buf.reserve(self.help_capacity());
+        self.write_help_sections(&mut ByteSink(buf))
+            .expect("writing to a Vec cannot fail");
+    }
+
+    /// Writes help text to `out`, e.g. stdout, stderr or a pager
+    ///
+    /// Example: `cmd.write_help(&mut io::stderr())?;`
+    pub fn write_help(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut buf = Vec::new();
+        self.help_bytes(&mut buf);
+        out.write_all(&buf)?;
+        out.flush()
+    }
+
+    /// Renders a Markdown reference page for this command and its subcommands
+    ///
+    /// Every command gets a heading (one level deeper per subcommand) with
+    /// its description, usage line, argument and option tables, examples and
+    /// links to its subcommands' sections. Generating a README's CLI
+    /// reference from the builder keeps it from drifting.
+    /// Example: `std::fs::write("docs/cli.md", cmd.to_markdown())?;`
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        self.write_markdown(&self.name, 1, &mut out);
+        // Sections end in a blank line; the page ends in a single newline
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    /// Append this command's Markdown section and those of its subcommands
+    fn write_markdown(&self, path: &str, depth: usize, out: &mut String) {
+        out.push_str(&format!("{} {}\n\n", "#".repeat(depth.min(6)), path));
+        if let Some(about) = self.long_about.as_ref().or(self.about.as_ref()) {
+            out.push_str(&format!("{}\n\n", about));
+        }
+
+        let usage = match self.usage {
+            Some(ref usage) => usage.clone(),
+            // The synthesized line starts with our own name, not the full path
+            None => format!("{}{}", path, &self.usage_line()[self.name.len()..]),
+        };
+        out.push_str(&format!("**Usage:** `{}`\n\n", usage));
+
+        let mut positional_args: Vec<&Arg> =
+            self.args.iter().filter(|a| a.index.is_some()).collect();
+        positional_args.sort_by_key(|a| a.index.unwrap());
+        if !positional_args.is_empty() {
+            let mut table = Table::new(vec!["Argument", "Description"]);
+            for arg in positional_args {
+                let mut name = String::new();
+                self.append_positional_usage(arg, &mut name)
+                    .expect("writing to a String cannot fail");
+                table.add_row(vec![
+                    &format!("`{}`", name.trim_start()),
+                    &markdown_description(arg),
+                ]);
+            }
+            out.push_str(&format!("**Arguments:**\n\n{}\n", table.to_markdown()));
+        }
+
+        for (heading, args) in self.option_sections() {
+            let mut table = Table::new(vec!["Option", "Description"]);
+            for arg in args {
+                let mut flags: Vec<String> = Vec::new();
+                flags.extend(arg.short.map(|c| format!("`-{}`", c)));
+                flags.extend(arg.long.as_ref().map(|long| match arg.takes_value {
+                    true => format!("`--{} <{}>`", long, arg.name.to_uppercase()),
+                    false => format!("`--{}`", long),
+                }));
+                table.add_row(vec![&flags.join(", "), &markdown_description(arg)]);
+            }
+            let heading = match heading {
+                "OPTIONS" => "Options",
+                custom => custom,
+            };
+            out.push_str(&format!("**{}:**\n\n{}\n", heading, table.to_markdown()));
+        }
+
+        if !self.subcommands.is_empty() {
+            out.push_str("**Commands:**\n\n");
+            for subcmd in self.subcommands.iter() {
+                let sub_path = format!("{} {}", path, subcmd.name);
+                out.push_str(&format!(
+                    "- [`{}`](#{})",
+                    subcmd.name,
+                    markdown_anchor(&sub_path)
+                ));
+                if let Some(ref about) = subcmd.about {
+                    out.push_str(&format!(": {}", about));
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+
+        if !self.examples.is_empty() {
+            out.push_str("**Examples:**\n\n```sh\n");
+            for example in &self.examples {
+                out.push_str(&format!("{}\n", example.line));
+            }
+            out.push_str("```\n\n");
+        }
+
+        for subcmd in self.subcommands.iter() {
+            subcmd.write_markdown(&format!("{} {}", path, subcmd.name), depth + 1, out);
+        }
+    }
+
+    /// Rough size of the rendered help, so the buffer is sized up front
+    fn help_capacity(&self) -> usize {
+        // A styled line is ~60 bytes of layout plus its help text
+        let text: usize = self
+            .args
+            .iter()
+            .map(|a| 64 + a.help.as_ref().map_or(0, String::len))
+            .sum();
+        let commands: usize = self
+            .subcommands
+            .iter()
+            .map(|c| 48 + c.about.as_ref().map_or(0, String::len))
+            .sum();
+        let prose = [&self.before_help, &self.after_help]
+            .into_iter()
+            .flatten()
+            .map(String::len)
+            .sum::<usize>();
+        256 + text + commands + prose
+    }
+
+    /// Write every help section, sorting the positional arguments once
+    fn write_help_sections(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let mut positional_args: Vec<&Arg> =
+            self.args.iter().filter(|a| a.index.is_some()).collect();
+        positional_args.sort_by_key(|a| a.index.unwrap());
+
+        self.generate_header(out)?;
+        self.generate_usage(out, &positional_args)?;
+        self.generate_args_section(out, &positional_args)?;
+        self.generate_options_section(out)?;
+        self.generate_subcommands_section(out)?;
+        self.generate_examples_section(out)?;
+
+        if let Some(ref after) = self.after_help {
+            write!(out, "\n{}\n", after)?;
+        }
+        Ok(())
+    }
+
+    /// Generate header section (about and version)
+    fn generate_header(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some(ref before) = self.before_help {
+            writeln!(out, "{}", before)?;
+            if self.about.is_some() {
+                out.write_char('\n')?;
+            }
+        }
+
+        if let Some(ref about) = self.about {
+            writeln!(out, "{}", about)?;
+        }
+
+        if let Some(ref version) = self.version {
+            write!(out, "\nVersion: {}\n", version)?;
+        }
+        Ok(())
+    }
+
+    /// Generate usage line
+    fn generate_usage(&self, out: &mut dyn fmt::Write, positional_args: &[&Arg]) -> fmt::Result {
+        write!(out, "\n{}\n    ", self.styles.paint_heading("USAGE:"))?;
+        self.write_usage_line(out, positional_args)?;
+        out.write_char('\n')
+    }
+
+    /// Build the synthesized usage line (e.g. `myapp [OPTIONS] <FILE>`)
+    fn usage_line(&self) -> String {
+        let mut positional_args: Vec<&Arg> =
+            self.args.iter().filter(|a| a.index.is_some()).collect();
+        positional_args.sort_by_key(|a| a.index.unwrap());
+
+        let mut usage = String::new();
+        self.write_usage_line(&mut usage, &positional_args)
+            .expect("writing to a String cannot fail");
+        usage
+    }
+
+    /// Write the usage line for already-sorted positional arguments
+    fn write_usage_line(&self, out: &mut dyn fmt::Write, positional_args: &[&Arg]) -> fmt::Result {
+        if let Some(ref usage) = self.usage {
+            return out.write_str(usage);
+        }
+        out.write_str(&self.name)?;
+
+        if self.args.iter().any(|a| a.index.is_none()) {
+            out.write_str(" [OPTIONS]")?;
+        }
+
+        for arg in positional_args {
+            self.append_positional_usage(arg, out)?;
+        }
+
+        if !self.subcommands.is_empty() {
+            out.write_str(" <COMMAND>")?;
+        }
+        if let Some(ref name) = self.raw_args {
+            write!(out, " [-- <{}>...]", name.to_uppercase())?;
+        }
+        Ok(())
+    }
+
+    /// Creates an error tied to this command, formatted like the parser's own errors
+    ///
+    /// Use this from handlers for argument-semantic problems discovered after
+    /// parsing (e.g. "--start must be before --end").
+    ///
+    /// ```
+    /// use zfish::command::{Command, ErrorKind};
+    ///
+    /// let cmd = Command::new("report");
+    /// let err = cmd.error(ErrorKind::InvalidValue, "--start must be before --end");
+    /// assert!(err.rendered().contains("--start must be before --end"));
+    /// assert!(err.rendered().contains("report"));
+    /// ```
+    pub fn error(&self, kind: ErrorKind, message: impl Into<String>) -> FormattedError {
+        self.format_error(CommandError::Custom(kind, message.into()))
+    }
+
+    /// Renders any [`CommandError`] with this command's usage line and help hint
+    ///
+    /// [`CommandError::HelpRequested`] renders as the help text and
+    /// [`CommandError::VersionRequested`] as the version line. An error from a
+    /// subcommand ([`CommandError::InSubcommand`]) is rendered with that
+    /// subcommand's usage and help, in this command's styles.
+    pub fn format_error(&self, error: CommandError) -> FormattedError {
+        if let CommandError::InSubcommand(ref path, ref inner) = error
+            && let Some(cmd) = self.error_command(path)
+        {
+            let formatted = cmd.format_error((**inner).clone());
+            return FormattedError { error, ..formatted };
+        }
+        let rendered = match error {
+            CommandError::HelpRequested => format!("{}\n", self.generate_help()),
+            CommandError::VersionRequested => format!("{}\n", self.render_version()),
+            _ => match self.error_formatter {
+                Some(formatter) => formatter(self, &error),
+                None => match error {
+                    // Show what the missing subcommand could be
+                    CommandError::MissingSubcommand(ref path) => format!(
+                        "{} {}\n\n{}\n",
+                        self.styles.paint_error("error:"),
+                        error.message(),
+                        self.find_path(path.get(1..).unwrap_or_default())
+                            .unwrap_or(self)
+                            .generate_help()
+                    ),
+                    _ => self.render_error_message(&error),
+                },
+            },
+        };
+        FormattedError {
+            error,
+            rendered,
+            error_code: self.error_exit_code,
+        }
+    }
+
+    /// The subcommand at `path` (from the root) named by its full path, with
+    /// this command's error presentation
+    fn error_command(&self, path: &[String]) -> Option<Command> {
+        let mut cmd = self.find_path(path.get(1..)?)?.clone();
+        cmd.name = path.join(" ");
+        cmd.styles = self.styles;
+        cmd.error_formatter = self.error_formatter;
+        cmd.error_exit_code = self.error_exit_code;
+        Some(cmd)
+    }
+
+    /// Renders the `--version` line (e.g. `myapp 1.2.0`)
+    pub fn render_version(&self) -> String {
+        match self.version {
+            Some(ref version) => format!("{} {}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Renders the usage line shown under `USAGE:` (e.g. `myapp [OPTIONS] <FILE>`)
+    pub fn render_usage(&self) -> String {
+        self.usage_line()
+    }
+
+    /// Render an error with the usage line and help hint
+    fn render_error_message(&self, error: &CommandError) -> String {
+        let label = self.styles.paint_error("error:");
+        let messages: Vec<String> = error
+            .messages()
+            .iter()
+            .map(|message| format!("{} {}", label, message))
+            .collect();
+        format!(
+            "{}\n\n{}\n    {}\n\nFor more information try --help\n",
+            messages.join("\n"),
+            self.styles.paint_heading("USAGE:"),
+            self.usage_line()
+        )
+    }
+
+    /// Append single positional arg to usage line
+    fn append_positional_usage(&self, arg: &Arg, out: &mut dyn fmt::Write) -> fmt::Result {
+        let name = arg.name.to_uppercase();
+        if arg.last {
+            write!(out, " [{}]...", name)
+        } else if arg.required {
+            write!(out, " <{}>", name)
+        } else {
+            write!(out, " [{}]", name)
+        }
+    }
+
+    /// Generate ARGS section for positional arguments
+    fn generate_args_section(
+        &self,
+        out: &mut dyn fmt::Write,
+        positional_args: &[&Arg],
+    ) -> fmt::Result {
+        if positional_args.is_empty() {
+            return Ok(());
+        }
+
+        write!(out, "\n{}\n", self.styles.paint_heading("ARGS:"))?;
+        for arg in positional_args {
+            self.format_arg_line(arg, out)?;
+        }
+        Ok(())
+    }
+
+    /// Format a single positional argument line
+    fn format_arg_line(&self, arg: &Arg, out: &mut dyn fmt::Write) -> fmt::Result {
+        let placeholder = format!("<{}>", arg.name.to_uppercase());
+        let plain_len = 4 + placeholder.len();
+
+        write!(out, "    {}", self.styles.paint_placeholder(&placeholder))?;
+        write_padding(out, plain_len)?;
+
+        if let Some(ref help_text) = arg.help {
+            out.write_str(help_text)?;
+        }
+
+        if arg.required {
+            out.write_str(&self.styles.paint_value(" [required]"))?;
+        }
+
+        if let Some(ref range) = arg.value_range {
+            out.write_str(
+                &self
+                    .styles
+                    .paint_value(&format!(" [{}]", describe_range(range))),
+            )?;
+        }
+
+        out.write_char('\n')
+    }
+
+    /// Flags and options by help section: `OPTIONS` first, then each
+    /// [`Arg::help_heading`] in the order it first appears
+    fn option_sections(&self) -> Vec<(&str, Vec<&Arg>)> {
+        let mut sections: Vec<(&str, Vec<&Arg>)> = Vec::new();
+        for arg in self.args.iter().filter(|a| a.index.is_none()) {
+            let heading = arg.help_heading.as_deref().unwrap_or("OPTIONS");
+            match sections.iter_mut().find(|(title, _)| *title == heading) {
+                Some((_, args)) => args.push(arg),
+                None => sections.push((heading, vec![arg])),
+            }
+        }
+        if let Some(plain) = sections.iter().position(|(title, _)| *title == "OPTIONS") {
+            let section = sections.remove(plain);
+            sections.insert(0, section);
+        }
+        sections
+    }
+
+    /// Generate the OPTIONS section and any custom option sections
+    fn generate_options_section(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        for (heading, args) in self.option_sections() {
+            write!(
+                out,
+                "\n{}\n",
+                self.styles.paint_heading(&format!("{}:", heading))
+            )?;
+            for arg in args {
+                self.format_option_line(arg, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Format a single option/flag line
+    fn format_option_line(&self, arg: &Arg, out: &mut dyn fmt::Write) -> fmt::Result {
+        // Padding is computed from the unstyled width so colors never shift columns
+        out.write_str("    ")?;
+        let mut plain_len = 4;
+
+        if let Some(short) = arg.short {
+            let flag = format!("-{}", short);
+            plain_len += flag.len();
+            out.write_str(&self.styles.paint_flag(&flag))?;
+            if arg.long.is_some() {
+                out.write_str(", ")?;
+                plain_len += 2;
+            }
+        }
+
+        if let Some(ref long) = arg.long {
+            let flag = format!("--{}", long);
+            plain_len += flag.len();
+            out.write_str(&self.styles.paint_flag(&flag))?;
+        }
+
+        if arg.takes_value {
+            let placeholder = format!("<{}>", arg.name.to_uppercase());
+            plain_len += 1 + placeholder.len();
+            out.write_char(' ')?;
+            out.write_str(&self.styles.paint_placeholder(&placeholder))?;
+        }
+
+        write_padding(out, plain_len)?;
+
+        self.append_option_metadata(arg, out)?;
+        out.write_char('\n')
+    }
+
+    /// Append help text, required flag, and default value to option line
+    fn append_option_metadata(&self, arg: &Arg, out: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some(ref help_text) = arg.help {
+            out.write_str(help_text)?;
+        }
+
+        if arg.required {
+            out.write_str(&self.styles.paint_value(" [required]"))?;
+        }
+
+        if let Some(ref default) = arg.default_value {
+            out.write_str(&self.styles.paint_value(&format!(" [default: {}]", default)))?;
+        }
+
+        if let Some(ref range) = arg.value_range {
+            out.write_str(
+                &self
+                    .styles
+                    .paint_value(&format!(" [{}]", describe_range(range))),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Generate COMMANDS section for subcommands
+    fn generate_subcommands_section(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if self.subcommands.is_empty() {
+            return Ok(());
+        }
+
+        write!(out, "\n{}\n", self.styles.paint_heading("COMMANDS:"))?;
+        for subcmd in self.subcommands.iter() {
+            self.format_subcommand_line(subcmd, out)?;
+        }
+
+        out.write_str("\nRun '<COMMAND> --help' for more information on a specific command.\n")
+    }
+
+    /// Generate the examples section
+    fn generate_examples_section(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if self.examples.is_empty() {
+            return Ok(());
+        }
+
+        write!(out, "\n{}\n", self.styles.paint_heading("EXAMPLES:"))?;
+        for example in &self.examples {
+            writeln!(out, "    {}", example.line)?;
+        }
+        Ok(())
+    }
+
+    /// Format a single subcommand line
+    fn format_subcommand_line(&self, subcmd: &Command, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "    {}", self.styles.paint_flag(&subcmd.name))?;
+        let mut plain_len = 4 + subcmd.name.len();
+
+        if !subcmd.aliases.is_empty() {
+            out.write_str(" (")?;
+            for (i, alias) in subcmd.aliases.iter().enumerate() {
+                if i > 0 {
+                    out.write_str(", ")?;
+                }
+                out.write_str(alias)?;
+                plain_len += alias.len() + if i > 0 { 2 } else { 0 };
+            }
+            out.write_char(')')?;
+            plain_len += 3;
+        }
+
+        write_padding(out, plain_len)?;
 
         if let Some(ref about) = subcmd.about {
-            cmd_line.push_str(about);
+            out.write_str(about)?;
         }
 
-        help.push_str(&format!("{}\n", cmd_line));
+        out.write_char('\n')
     }
 
     /// Helper: Process a value with delimiter support
     fn process_value(&self, arg: &Arg, value: &str, matches: &mut ArgMatches) -> CommandResult<()> {
+        if !arg.multiple && !arg.overrides_self && matches.local_occurrences(&arg.name) > 1 {
+            return Err(CommandError::DuplicateArgument(arg.name.clone()));
+        }
+        let value = &*arg.normalized(value);
         if let Some(delimiter) = arg.value_delimiter {
             // Split by delimiter
-            let values: Vec<String> = value
+            let values = value
                 .split(delimiter)
-                .map(|s| s.trim().to_string())
-                .collect();
-            // Validate each value
-            for v in &values {
-                arg.validate(v)
-                    .map_err(|err| CommandError::ValidationError(arg.name.clone(), err))?;
-            }
+                .map(|s| {
+                    arg.validate(s.trim())
+                        .map_err(|err| CommandError::ValidationError(arg.name.clone(), err))
+                })
+                .collect::<CommandResult<Vec<String>>>()?;
             matches.insert(arg.name.clone(), ArgValue::Multiple(values));
         } else if arg.multiple {
             // Accumulate multiple values
             let current = matches
+                .level_mut()
                 .args
                 .entry(arg.name.clone())
                 .or_insert(ArgValue::Multiple(Vec::new()));
             if let ArgValue::Multiple(vec) = current {
-                arg.validate(value)
+                let value = arg
+                    .validate(value)
                     .map_err(|err| CommandError::ValidationError(arg.name.clone(), err))?;
-                vec.push(value.to_string());
+                vec.push(value);
             }
         } else {
             // Single value
-            arg.validate(value)
+            let value = arg
+                .validate(value)
                 .map_err(|err| CommandError::ValidationError(arg.name.clone(), err))?;
-            matches.insert(arg.name.clone(), ArgValue::Single(value.to_string()));
+            matches.insert(arg.name.clone(), ArgValue::Single(value));
         }
         Ok(())
     }
 
-    /// Parses command-line arguments
-    fn parse_args(&self, args: &[String]) -> CommandResult<ArgMatches> {
+    /// Parses arguments without printing, prompting or exiting
+    ///
+    /// The first item is the program name, as in `std::env::args_os()`. Help and
+    /// version requests come back as [`CommandError::HelpRequested`] and
+    /// [`CommandError::VersionRequested`]; render any error for display with
+    /// [`format_error`](Self::format_error). [`Setting::InteractiveFallback`]
+    /// is ignored here — prompting is done by the [`App`] frontend.
+    ///
+    /// Two things still reach outside `args`: arguments with an
+    /// [`Arg::env`] fallback read the process environment, and debug builds
+    /// run [`debug_assert`](Self::debug_assert) first, which panics if the
+    /// command definition itself is inconsistent.
+    pub fn try_parse_from<I, T>(&self, args: I) -> CommandResult<ArgMatches>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        #[cfg(debug_assertions)]
+        self.debug_assert();
+        let (args, escaped_os) = escape_args(args.into_iter().skip(1));
+        let ctx = ParseContext {
+            argv_offset: 1,
+            escaped_os,
+            ..ParseContext::default()
+        };
+        self.parse_args_with(&args, &ctx)
+    }
+
+    /// Parses command-line arguments with settings inherited from a parent
+    fn parse_args_with(&self, args: &[String], parent: &ParseContext) -> CommandResult<ArgMatches> {
         let mut matches = ArgMatches::new(&self.name);
-        let mut positional_values: Vec<String> = Vec::new();
+        self.parse_level(args, parent, &mut matches)?;
+        Ok(matches)
+    }
 
-        self.parse_command_line(args, &mut matches, &mut positional_values)?;
-        self.process_positional_args(&positional_values, &mut matches);
-        self.validate_matches(&mut matches)?;
+    /// Parses this command's part of argv into the current level of `matches`
+    ///
+    /// Subcommands are parsed into further levels of the same matches, so the
+    /// whole path shares one allocation.
+    fn parse_level(
+        &self,
+        args: &[String],
+        parent: &ParseContext,
+        matches: &mut ArgMatches,
+    ) -> CommandResult<()> {
+        // Parent globals become ordinary args of this command for the duration of the parse
+        let inherited: Vec<&Arg> = parent
+            .globals
+            .iter()
+            .filter(|g| !self.args.iter().any(|a| a.name == g.name))
+            .collect();
+        if !inherited.is_empty() {
+            let mut cmd = self.clone();
+            cmd.args.extend(inherited.into_iter().cloned());
+            return cmd.parse_level(args, parent, matches);
+        }
 
-        Ok(matches)
+        let ctx = ParseContext {
+            settings: parent.settings | self.settings,
+            prompter: parent.prompter,
+            config: parent.config.clone(),
+            config_section: parent.config_section.clone(),
+            command_path: parent
+                .command_path
+                .iter()
+                .chain([&self.name])
+                .cloned()
+                .collect(),
+            dotenv: parent.dotenv.clone(),
+            escaped_os: parent.escaped_os,
+            ..ParseContext::default()
+        };
+        let has_children = !self.args.is_empty() || !self.subcommands.is_empty();
+        if args.is_empty() && has_children && ctx.settings.contains(Setting::ArgRequiredElseHelp) {
+            return Err(CommandError::HelpRequested);
+        }
+        let level = matches.level_mut();
+        level.argv_offset = parent.argv_offset;
+        for (name, value, indices) in &parent.global_values {
+            level.args.insert(name.clone(), value.clone());
+            if !indices.is_empty() {
+                level.indices.insert(name.clone(), indices.clone());
+            }
+        }
+        let mut positional_values: Vec<(usize, String)> = Vec::new();
+
+        self.parse_command_line(args, matches, &mut positional_values, &ctx)?;
+        if ctx.settings.contains(Setting::StrictPositionals) {
+            self.check_unexpected_positionals(&positional_values)?;
+        }
+        if ctx.settings.contains(Setting::SubcommandRequired)
+            && !self.subcommands.is_empty()
+            && matches.subcommand_name().is_none()
+        {
+            return Err(CommandError::MissingSubcommand(ctx.command_path.clone()));
+        }
+        self.process_positional_args(&positional_values, matches);
+        if ctx.escaped_os {
+            self.restore_os_values(matches)?;
+        }
+        self.validate_matches(matches, &ctx)
     }
 
     /// Parse command line arguments (flags, options, subcommands)
@@ -802,37 +3150,130 @@ impl Command {
         &self,
         args: &[String],
         matches: &mut ArgMatches,
-        positional_values: &mut Vec<String>,
+        positional_values: &mut Vec<(usize, String)>,
+        ctx: &ParseContext,
     ) -> CommandResult<()> {
         let mut i = 0;
 
         while i < args.len() {
             let arg = &args[i];
 
+            // Everything after `--` is kept for the trailing var arg as it is
+            if arg == "--"
+                && let Some(ref name) = self.raw_args
+            {
+                for j in i + 1..args.len() {
+                    matches.record_occurrence(name, j);
+                }
+                let rest = args[i + 1..].to_vec();
+                matches.insert(name.clone(), ArgValue::Multiple(rest));
+                break;
+            }
+
             // Check for special flags
             if self.handle_special_flags(arg)? {
                 return Ok(());
             }
 
+            // A lone `-` is a positional value when a positional accepts stdin
+            if arg == "-"
+                && self
+                    .args
+                    .iter()
+                    .any(|a| a.index.is_some() && a.allow_stdin_dash)
+            {
+                positional_values.push((i, arg.clone()));
+                i += 1;
+                continue;
+            }
+
             // Check for subcommand
             if !arg.starts_with('-') {
-                if let Some(subcmd) = self.find_subcommand(arg) {
+                let found = match self.find_subcommand(arg) {
+                    Some(subcmd) => Some((subcmd, arg.clone())),
+                    None => self
+                        .infer_subcommand(arg, ctx)?
+                        .map(|subcmd| (subcmd, subcmd.name.clone())),
+                };
+                if let Some((subcmd, name)) = found {
                     let sub_args = &args[i + 1..];
-                    let sub_matches = subcmd.parse_args(sub_args)?;
-                    matches.set_subcommand(arg.clone(), sub_matches);
+                    let mut child = self.child_context(ctx, matches);
+                    child.argv_offset = matches.level().argv_offset + i + 1;
+                    child.config_section.push(subcmd.name.clone());
+                    matches.enter_subcommand(name, subcmd);
+                    subcmd
+                        .parse_level(sub_args, &child, matches)
+                        .map_err(|err| {
+                            err.in_subcommand(|| {
+                                let mut path = ctx.command_path.clone();
+                                path.push(subcmd.name.clone());
+                                path
+                            })
+                        })?;
+                    matches.leave_subcommand(&child.globals);
                     return Ok(());
                 }
-                positional_values.push(arg.clone());
+                // Without positionals, a bare word close to a subcommand is a typo
+                if !self.args.iter().any(|a| a.index.is_some())
+                    && let Some(err) = self.misspelled_subcommand(arg)
+                {
+                    return Err(err);
+                }
+                positional_values.push((i, arg.clone()));
                 i += 1;
+                if self.in_trailing_var_arg(positional_values.len(), ctx) {
+                    positional_values.extend((i..args.len()).map(|j| (j, args[j].clone())));
+                    break;
+                }
                 continue;
             }
 
-            i += self.parse_flag_or_option(arg, args, i, matches)?;
+            i += self.parse_flag_or_option(arg, args, i, matches, ctx)?;
         }
 
         Ok(())
     }
 
+    /// Whether `count` positional values have filled every positional before a
+    /// `TrailingVarArg` variadic
+    fn in_trailing_var_arg(&self, count: usize, ctx: &ParseContext) -> bool {
+        if !ctx.settings.contains(Setting::TrailingVarArg) || !self.args.iter().any(|a| a.last) {
+            return false;
+        }
+        let fixed = self
+            .args
+            .iter()
+            .filter(|a| a.index.is_some() && !a.last)
+            .count();
+        count >= fixed
+    }
+
+    /// Build the context handed to a subcommand: our globals and their values so far
+    fn child_context(&self, ctx: &ParseContext, matches: &ArgMatches) -> ParseContext {
+        let globals: Vec<Arg> = self.args.iter().filter(|a| a.global).cloned().collect();
+        let global_values = globals
+            .iter()
+            .filter_map(|g| {
+                matches.level().args.get(&g.name).map(|v| {
+                    let indices = matches.indices_of(&g.name).unwrap_or_default().to_vec();
+                    (g.name.clone(), v.clone(), indices)
+                })
+            })
+            .collect();
+        ParseContext {
+            settings: ctx.settings,
+            prompter: ctx.prompter,
+            globals,
+            global_values,
+            argv_offset: 0,
+            config: ctx.config.clone(),
+            config_section: ctx.config_section.clone(),
+            command_path: ctx.command_path.clone(),
+            dotenv: ctx.dotenv.clone(),
+            escaped_os: ctx.escaped_os,
+        }
+    }
+
     /// Handle special flags like --help and --version
     fn handle_special_flags(&self, arg: &str) -> CommandResult<bool> {
         if arg == "--help" || arg == "-h" {
@@ -851,14 +3292,15 @@ impl Command {
         args: &[String],
         index: usize,
         matches: &mut ArgMatches,
+        ctx: &ParseContext,
     ) -> CommandResult<usize> {
         if arg.starts_with("--") && arg.contains('=') {
-            self.parse_long_flag_with_equals(arg, matches)?;
+            self.parse_long_flag_with_equals(arg, index, matches, ctx)?;
             Ok(1)
         } else if arg.starts_with("--") {
-            self.parse_long_flag(arg, args, index, matches)
+            self.parse_long_flag(arg, args, index, matches, ctx)
         } else if arg.starts_with('-') && arg.len() > 1 {
-            self.parse_short_flags(arg, args, index, matches)
+            self.parse_short_flags(arg, args, index, matches, ctx)
         } else {
             Ok(1)
         }
@@ -868,18 +3310,17 @@ impl Command {
     fn parse_long_flag_with_equals(
         &self,
         arg: &str,
+        index: usize,
         matches: &mut ArgMatches,
+        ctx: &ParseContext,
     ) -> CommandResult<()> {
         let parts: Vec<&str> = arg.splitn(2, '=').collect();
         let flag_name = parts[0].trim_start_matches("--");
         let value = parts[1];
 
-        if let Some(found_arg) = self.find_arg(flag_name) {
-            self.process_value(found_arg, value, matches)?;
-        } else {
-            return Err(CommandError::UnknownArgument(flag_name.to_string()));
-        }
-        Ok(())
+        let found_arg = self.find_long_arg(flag_name, ctx)?;
+        matches.record_occurrence(&found_arg.name, index);
+        self.process_value(found_arg, value, matches)
     }
 
     /// Parse long flag (--flag value or --flag)
@@ -889,23 +3330,25 @@ impl Command {
         args: &[String],
         index: usize,
         matches: &mut ArgMatches,
+        ctx: &ParseContext,
     ) -> CommandResult<usize> {
         let flag_name = arg.trim_start_matches("--");
-        let found_arg = self
-            .find_arg(flag_name)
-            .ok_or_else(|| CommandError::UnknownArgument(flag_name.to_string()))?;
+        let found_arg = self.find_long_arg(flag_name, ctx)?;
 
         if found_arg.takes_value {
-            if index + 1 < args.len() && !args[index + 1].starts_with('-') {
+            if index + 1 < args.len() && found_arg.accepts_value(&args[index + 1], ctx) {
+                matches.record_occurrence(&found_arg.name, index + 1);
                 self.process_value(found_arg, &args[index + 1], matches)?;
                 Ok(2) // Consumed current + next
             } else if let Some(ref default) = found_arg.default_value {
+                matches.record_occurrence(&found_arg.name, index);
                 matches.insert(found_arg.name.clone(), ArgValue::Single(default.clone()));
                 Ok(1)
             } else {
                 Ok(1)
             }
         } else {
+            matches.record_occurrence(&found_arg.name, index);
             matches.insert(found_arg.name.clone(), ArgValue::Flag(true));
             Ok(1)
         }
@@ -918,6 +3361,7 @@ impl Command {
         args: &[String],
         index: usize,
         matches: &mut ArgMatches,
+        ctx: &ParseContext,
     ) -> CommandResult<usize> {
         let flags = arg.trim_start_matches('-');
         let mut consumed = 1;
@@ -931,13 +3375,16 @@ impl Command {
 
             if found_arg.takes_value && idx == flags.len() - 1 {
                 // Last flag can take value from next arg
-                if index + 1 < args.len() && !args[index + 1].starts_with('-') {
+                if index + 1 < args.len() && found_arg.accepts_value(&args[index + 1], ctx) {
+                    matches.record_occurrence(&found_arg.name, index + 1);
                     self.process_value(found_arg, &args[index + 1], matches)?;
                     consumed = 2;
                 } else if let Some(ref default) = found_arg.default_value {
+                    matches.record_occurrence(&found_arg.name, index);
                     matches.insert(found_arg.name.clone(), ArgValue::Single(default.clone()));
                 }
             } else {
+                matches.record_occurrence(&found_arg.name, index);
                 matches.insert(found_arg.name.clone(), ArgValue::Flag(true));
             }
         }
@@ -946,37 +3393,101 @@ impl Command {
     }
 
     /// Process positional arguments
-    fn process_positional_args(&self, positional_values: &[String], matches: &mut ArgMatches) {
+    fn process_positional_args(
+        &self,
+        positional_values: &[(usize, String)],
+        matches: &mut ArgMatches,
+    ) {
         let mut positional_args: Vec<&Arg> =
             self.args.iter().filter(|a| a.index.is_some()).collect();
         positional_args.sort_by_key(|a| a.index.unwrap());
 
         for (idx, arg) in positional_args.iter().enumerate() {
             if arg.last {
-                let remaining: Vec<String> = positional_values.iter().skip(idx).cloned().collect();
+                let remaining = positional_values.get(idx..).unwrap_or_default();
                 if !remaining.is_empty() {
-                    matches.insert(arg.name.clone(), ArgValue::Multiple(remaining));
+                    for (index, _) in remaining {
+                        matches.record_occurrence(&arg.name, *index);
+                    }
+                    let values = remaining
+                        .iter()
+                        .map(|(_, v)| arg.normalized(v).into_owned())
+                        .collect();
+                    matches.insert(arg.name.clone(), ArgValue::Multiple(values));
                 }
-            } else if idx < positional_values.len() {
-                matches.insert(
-                    arg.name.clone(),
-                    ArgValue::Single(positional_values[idx].clone()),
-                );
+            } else if let Some((index, value)) = positional_values.get(idx) {
+                matches.record_occurrence(&arg.name, *index);
+                let value = arg.normalized(value).into_owned();
+                matches.insert(arg.name.clone(), ArgValue::Single(value));
+            }
+        }
+    }
+
+    /// Fail on the first positional value that no declared positional takes
+    fn check_unexpected_positionals(
+        &self,
+        positional_values: &[(usize, String)],
+    ) -> CommandResult<()> {
+        let positionals: Vec<&Arg> = self.args.iter().filter(|a| a.index.is_some()).collect();
+        if positionals.iter().any(|a| a.last) {
+            return Ok(());
+        }
+        match positional_values.get(positionals.len()) {
+            Some((_, word)) if positionals.is_empty() && !self.subcommands.is_empty() => {
+                Err(CommandError::UnknownSubcommand(word.clone()))
+            }
+            Some((_, word)) => Err(CommandError::UnexpectedArgument(word.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Turn escaped non-UTF-8 values back into the OS strings they came from
+    ///
+    /// Values after `--` (see [`Command::trailing_var_arg`]) always may be
+    /// invalid UTF-8; they are passed on verbatim.
+    fn restore_os_values(&self, matches: &mut ArgMatches) -> CommandResult<()> {
+        let raw = self.raw_args.iter().map(|name| (name, true));
+        for (name, allow_invalid_utf8) in self
+            .args
+            .iter()
+            .map(|arg| (&arg.name, arg.allow_invalid_utf8))
+            .chain(raw)
+        {
+            let values: &[String] = match matches.level().args.get(name) {
+                Some(ArgValue::Multiple(values)) => values,
+                Some(ArgValue::Single(value)) => std::slice::from_ref(value),
+                _ => continue,
+            };
+            if !values.iter().any(|value| value.chars().any(is_os_escape)) {
+                continue;
+            }
+            if !allow_invalid_utf8 {
+                return Err(CommandError::InvalidUtf8(name.clone()));
             }
+            let restored = values.iter().map(|value| unescape_os(value)).collect();
+            matches.insert(name.clone(), ArgValue::Os(restored));
         }
+        Ok(())
     }
 
     /// Validate matches: check required args, apply defaults, check dependencies
-    fn validate_matches(&self, matches: &mut ArgMatches) -> CommandResult<()> {
+    fn validate_matches(&self, matches: &mut ArgMatches, ctx: &ParseContext) -> CommandResult<()> {
+        self.apply_defaults_and_env(matches, ctx)?;
+        if let Some(mut ask) = ctx.prompter
+            && ctx.settings.contains(Setting::InteractiveFallback)
+        {
+            self.prompt_missing_args(matches, &mut ask)?;
+        }
         self.check_required_args(matches)?;
-        self.apply_defaults_and_env(matches)?;
+        self.parse_typed_values(matches)?;
+        self.check_conditional_requirements(matches)?;
         self.check_dependencies(matches)?;
         self.check_conflicts(matches)?;
         self.validate_groups(matches)?;
         Ok(())
     }
 
-    /// Check for required arguments
+    /// Check for required arguments, after env, config and defaults are applied
     fn check_required_args(&self, matches: &ArgMatches) -> CommandResult<()> {
         for arg in &self.args {
             if arg.required && !matches.is_present(&arg.name) {
@@ -986,93 +3497,572 @@ impl Command {
         Ok(())
     }
 
-    /// Apply default values and environment variables
-    fn apply_defaults_and_env(&self, matches: &mut ArgMatches) -> CommandResult<()> {
-        for arg in &self.args {
-            if !matches.is_present(&arg.name) {
-                if let Some(ref env_var) = arg.env
-                    && let Ok(value) = std::env::var(env_var)
-                {
-                    matches.insert(arg.name.clone(), ArgValue::Single(value));
-                    continue;
-                }
-                if let Some(ref default) = arg.default_value {
-                    matches.insert(arg.name.clone(), ArgValue::Single(default.clone()));
-                }
+    /// Fill missing required arguments with values supplied by `ask`
+    ///
+    /// Runs after env vars, the config file and defaults are applied, so only
+    /// values no other source supplied are asked for.
+    /// Values go through the same validation as command-line values. If `ask`
+    /// fails (e.g. the user gives up), the argument is reported as missing.
+    fn prompt_missing_args(
+        &self,
+        matches: &mut ArgMatches,
+        ask: &mut dyn FnMut(&Arg) -> io::Result<String>,
+    ) -> CommandResult<()> {
+        for arg in &self.args {
+            if arg.required && !matches.is_present(&arg.name) {
+                let value =
+                    ask(arg).map_err(|_| CommandError::MissingArgument(arg.name.clone()))?;
+                self.process_value(arg, &value, matches)?;
+                matches
+                    .level_mut()
+                    .sources
+                    .insert(arg.name.clone(), ValueSource::Prompt);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs each argument's value parser over its final values
+    fn parse_typed_values(&self, matches: &mut ArgMatches) -> CommandResult<()> {
+        for arg in &self.args {
+            let Some(ref parser) = arg.value_parser else {
+                continue;
+            };
+            let values: &[String] = match matches.level().args.get(&arg.name) {
+                Some(ArgValue::Multiple(values)) => values,
+                Some(ArgValue::Single(value)) => std::slice::from_ref(value),
+                _ => continue,
+            };
+            let typed = values
+                .iter()
+                .map(|value| {
+                    (parser.0)(value)
+                        .map_err(|err| CommandError::ValidationError(arg.name.clone(), err))
+                })
+                .collect::<CommandResult<Vec<TypedValue>>>()?;
+            matches.level_mut().typed.insert(arg.name.clone(), typed);
+        }
+        Ok(())
+    }
+
+    /// Apply default values, environment variables and config-file values
+    ///
+    /// Environment values come first (the real environment, then `.env`
+    /// variables from [`App::dotenv`]), then the config file and plain defaults,
+    /// so conditional defaults ([`Arg::default_value_if`]) see every other
+    /// resolved value. Environment and config values are validated like
+    /// command-line values.
+    fn apply_defaults_and_env(
+        &self,
+        matches: &mut ArgMatches,
+        ctx: &ParseContext,
+    ) -> CommandResult<()> {
+        for arg in &self.args {
+            if !matches.is_present(&arg.name)
+                && let Some(ref env_var) = arg.env
+                && let Some(value) = std::env::var(env_var).ok().or_else(|| {
+                    ctx.dotenv
+                        .as_ref()
+                        .and_then(|vars| vars.get(env_var).cloned())
+                })
+            {
+                self.process_value(arg, &value, matches)?;
+                matches
+                    .level_mut()
+                    .sources
+                    .insert(arg.name.clone(), ValueSource::EnvVar);
+            }
+        }
+        if let Some(ref config) = ctx.config {
+            self.apply_config(config, &ctx.config_section, matches)?;
+        }
+        for arg in self.args.iter().filter(|a| a.default_value_ifs.is_empty()) {
+            if !matches.is_present(&arg.name)
+                && let Some(ref default) = arg.default_value
+            {
+                matches.insert_from(
+                    ValueSource::Default,
+                    arg.name.clone(),
+                    ArgValue::Single(default.clone()),
+                );
+            }
+        }
+        for arg in self.args.iter().filter(|a| !a.default_value_ifs.is_empty()) {
+            if matches.is_present(&arg.name) {
+                continue;
+            }
+            let conditional = arg
+                .default_value_ifs
+                .iter()
+                .find(|(other, value, _)| match value {
+                    Some(value) => matches.value_of(other) == Some(value.as_str()),
+                    None => matches.is_present(other),
+                })
+                .map(|(_, _, default)| default);
+            if let Some(default) = conditional.or(arg.default_value.as_ref()) {
+                matches.insert_from(
+                    ValueSource::Default,
+                    arg.name.clone(),
+                    ArgValue::Single(default.clone()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill missing arguments from the config file section of this command
+    ///
+    /// Keys are argument names or long flags. Global arguments may also be set
+    /// in the section of any parent command, the nearest one winning.
+    fn apply_config(
+        &self,
+        config: &ConfigFile,
+        section: &[String],
+        matches: &mut ArgMatches,
+    ) -> CommandResult<()> {
+        for arg in &self.args {
+            if matches.is_present(&arg.name) {
+                continue;
+            }
+            let depths = if arg.global {
+                0..=section.len()
+            } else {
+                section.len()..=section.len()
+            };
+            let found = depths.rev().find_map(|depth| {
+                std::iter::once(&arg.name)
+                    .chain(arg.long.as_ref())
+                    .find_map(|key| {
+                        let mut path = section[..depth].to_vec();
+                        path.push(key.clone());
+                        config.get_all(&path.join("."))
+                    })
+            });
+            let Some(values) = found else {
+                continue;
+            };
+
+            if !arg.takes_value {
+                match values {
+                    [value] if value == "true" => {
+                        matches.insert_from(
+                            ValueSource::ConfigFile,
+                            arg.name.clone(),
+                            ArgValue::Flag(true),
+                        );
+                    }
+                    [value] if value == "false" => {}
+                    _ => {
+                        return Err(CommandError::ValidationError(
+                            arg.name.clone(),
+                            "expected true or false in the config file".to_string(),
+                        ));
+                    }
+                }
+                continue;
+            }
+            if values.len() != 1 && !arg.multiple {
+                return Err(CommandError::ValidationError(
+                    arg.name.clone(),
+                    "takes a single value, not a list, in the config file".to_string(),
+                ));
+            }
+            if values.is_empty() {
+                matches.insert(arg.name.clone(), ArgValue::Multiple(Vec::new()));
+            }
+            for value in values {
+                self.process_value(arg, value, matches)?;
+            }
+            matches
+                .level_mut()
+                .sources
+                .insert(arg.name.clone(), ValueSource::ConfigFile);
+        }
+        Ok(())
+    }
+
+    /// Check `required_unless_present` and `required_if_eq`, after defaults
+    /// are applied so conditions can see them
+    fn check_conditional_requirements(&self, matches: &ArgMatches) -> CommandResult<()> {
+        for arg in &self.args {
+            if matches.is_present(&arg.name) {
+                continue;
+            }
+            let unless_missing = !arg.required_unless.is_empty()
+                && !arg
+                    .required_unless
+                    .iter()
+                    .any(|other| matches.is_present(other));
+            let condition_holds = arg
+                .required_if
+                .iter()
+                .any(|(other, value)| matches.value_of(other) == Some(value.as_str()));
+            if unless_missing || condition_holds {
+                return Err(CommandError::MissingArgument(arg.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check argument dependencies
+    fn check_dependencies(&self, matches: &ArgMatches) -> CommandResult<()> {
+        for arg in &self.args {
+            if matches.is_present(&arg.name) {
+                for required in &arg.requires {
+                    if !matches.is_present(required) {
+                        return Err(CommandError::MissingDependency(
+                            arg.name.clone(),
+                            required.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check argument conflicts
+    fn check_conflicts(&self, matches: &ArgMatches) -> CommandResult<()> {
+        for arg in &self.args {
+            if matches.is_present(&arg.name) {
+                for conflict in &arg.conflicts_with {
+                    if matches.is_present(conflict) {
+                        return Err(CommandError::ArgumentConflict(
+                            arg.name.clone(),
+                            conflict.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate argument groups
+    fn validate_groups(&self, matches: &ArgMatches) -> CommandResult<()> {
+        for group in &self.groups {
+            let present: Vec<&String> = group
+                .args
+                .iter()
+                .filter(|a| matches.is_present(a))
+                .collect();
+            let Some(&first) = present.first() else {
+                if group.required {
+                    return Err(CommandError::MissingArgument(format!(
+                        "{} (one of: {})",
+                        group.name,
+                        group.args.join(", ")
+                    )));
+                }
+                continue;
+            };
+
+            if group.requires_all {
+                if let Some(missing) = group.args.iter().find(|a| !matches.is_present(a)) {
+                    return Err(CommandError::MissingDependency(
+                        first.clone(),
+                        missing.clone(),
+                    ));
+                }
+            } else if !group.multiple && present.len() > 1 {
+                // Groups are mutually exclusive by default
+                return Err(CommandError::ArgumentConflict(
+                    first.clone(),
+                    present[1].clone(),
+                ));
+            }
+
+            for other in &group.conflicts_with {
+                // `other` names a group or, failing that, a single argument
+                let members = match self.groups.iter().find(|g| &g.name == other) {
+                    Some(other_group) => other_group.args.as_slice(),
+                    None => std::slice::from_ref(other),
+                };
+                if let Some(conflict) = members.iter().find(|a| matches.is_present(a)) {
+                    return Err(CommandError::ArgumentConflict(
+                        first.clone(),
+                        conflict.clone(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Pad a help line from its plain width out to the description column
+fn write_padding(out: &mut dyn fmt::Write, plain_len: usize) -> fmt::Result {
+    write!(out, "{:1$}", "", 30usize.saturating_sub(plain_len))
+}
+
+/// An argument's help text and notes, for the Markdown reference
+fn markdown_description(arg: &Arg) -> String {
+    let mut text = arg.help.clone().unwrap_or_default();
+    let mut note = |note: String| {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(&note);
+    };
+    if arg.required {
+        note("[required]".to_string());
+    }
+    if let Some(ref default) = arg.default_value {
+        note(format!("[default: `{}`]", default));
+    }
+    if let Some(ref possible) = arg.possible_values
+        && !possible.is_empty()
+    {
+        let values: Vec<String> = possible.iter().map(|v| format!("`{}`", v)).collect();
+        note(format!("[possible values: {}]", values.join(", ")));
+    }
+    if let Some(ref range) = arg.value_range {
+        note(format!("[{}]", describe_range(range)));
+    }
+    if let Some(ref env) = arg.env {
+        note(format!("[env: `{}`]", env));
+    }
+    text
+}
+
+/// The link target GitHub gives a Markdown heading (`myapp remote add` →
+/// `myapp-remote-add`)
+fn markdown_anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The name a program was run as: the file name of argv\[0\] without any
+/// executable suffix (`/usr/bin/init` and `init.exe` are both `init`)
+fn program_name(argv0: &str) -> String {
+    let name = std::path::Path::new(argv0)
+        .file_name()
+        .map_or(argv0.into(), |name| name.to_string_lossy());
+    let suffix = std::env::consts::EXE_SUFFIX;
+    match name.strip_suffix(suffix) {
+        Some(stem) if !suffix.is_empty() => stem.to_string(),
+        _ => name.into_owned(),
+    }
+}
+
+/// Converts argv to strings for the parser, escaping what is not valid UTF-8
+///
+/// Returns whether anything was escaped, so valid arguments that happen to
+/// contain the escape characters are never mistaken for escapes.
+fn escape_args<T: Into<OsString>>(args: impl Iterator<Item = T>) -> (Vec<String>, bool) {
+    let mut escaped = false;
+    let args = args
+        .map(|arg| {
+            let arg = arg.into();
+            arg.into_string().unwrap_or_else(|arg| {
+                escaped = true;
+                escape_os(&arg)
+            })
+        })
+        .collect();
+    (args, escaped)
+}
+
+// Non-UTF-8 arguments are parsed as strings whose bad parts are mapped onto
+// the end of the last private use plane: each byte 0x80-0xFF on Unix, and
+// each unpaired surrogate on Windows. Any piece of such a string (an option
+// value after `=`, a delimited item) converts back exactly with `unescape_os`.
+#[cfg(unix)]
+const OS_ESCAPES: std::ops::RangeInclusive<u32> = 0x10FF80..=0x10FFFF;
+#[cfg(windows)]
+const OS_ESCAPES: std::ops::RangeInclusive<u32> = 0x10F800..=0x10FFFF;
+// Elsewhere arguments are converted lossily, and nothing is an escape
+#[cfg(not(any(unix, windows)))]
+const OS_ESCAPES: std::ops::RangeInclusive<u32> = 0x110000..=0x110000;
+
+fn is_os_escape(c: char) -> bool {
+    OS_ESCAPES.contains(&u32::from(c))
+}
+
+/// `arg` as a string, its invalid parts escaped (lossy where the platform
+/// has no byte access)
+fn escape_os(arg: &OsStr) -> String {
+    #[cfg(any(unix, windows))]
+    let first = *OS_ESCAPES.start();
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let mut out = String::new();
+        for chunk in arg.as_bytes().utf8_chunks() {
+            out.push_str(chunk.valid());
+            out.extend(
+                chunk
+                    .invalid()
+                    .iter()
+                    .filter_map(|&byte| char::from_u32(first + u32::from(byte) - 0x80)),
+            );
+        }
+        out
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        char::decode_utf16(arg.encode_wide())
+            .filter_map(|unit| match unit {
+                Ok(c) => Some(c),
+                Err(err) => char::from_u32(first + u32::from(err.unpaired_surrogate()) - 0xD800),
+            })
+            .collect()
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        arg.to_string_lossy().into_owned()
+    }
+}
+
+/// Reverses [`escape_os`] for the whole or any part of an escaped argument
+fn unescape_os(value: &str) -> OsString {
+    #[cfg(any(unix, windows))]
+    let first = *OS_ESCAPES.start();
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        let mut bytes = Vec::with_capacity(value.len());
+        for c in value.chars() {
+            match u32::from(c) {
+                code if OS_ESCAPES.contains(&code) => bytes.push((code - first + 0x80) as u8),
+                _ => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
             }
         }
-        Ok(())
+        OsString::from_vec(bytes)
     }
-
-    /// Check argument dependencies
-    fn check_dependencies(&self, matches: &ArgMatches) -> CommandResult<()> {
-        for arg in &self.args {
-            if matches.is_present(&arg.name) {
-                for required in &arg.requires {
-                    if !matches.is_present(required) {
-                        return Err(CommandError::MissingDependency(
-                            arg.name.clone(),
-                            required.clone(),
-                        ));
-                    }
-                }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+        let mut units = Vec::with_capacity(value.len());
+        for c in value.chars() {
+            match u32::from(c) {
+                code if OS_ESCAPES.contains(&code) => units.push((code - first + 0xD800) as u16),
+                _ => units.extend_from_slice(c.encode_utf16(&mut [0; 2])),
             }
         }
-        Ok(())
+        OsString::from_wide(&units)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        OsString::from(value)
     }
+}
 
-    /// Check argument conflicts
-    fn check_conflicts(&self, matches: &ArgMatches) -> CommandResult<()> {
-        for arg in &self.args {
-            if matches.is_present(&arg.name) {
-                for conflict in &arg.conflicts_with {
-                    if matches.is_present(conflict) {
-                        return Err(CommandError::ArgumentConflict(
-                            arg.name.clone(),
-                            conflict.clone(),
-                        ));
-                    }
-                }
-            }
-        }
+/// Help or error text as it should be written to a stream: styled only on a
+/// terminal
+fn for_stream(rendered: &str, terminal: bool) -> Cow<'_, str> {
+    if terminal {
+        Cow::Borrowed(rendered)
+    } else {
+        Cow::Owned(strip_ansi(rendered))
+    }
+}
+
+/// Suggestions further than this many edits from the typo are left out
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// The candidate closest to `typo` by [`levenshtein`] distance, if it is
+/// close enough to be a likely typo (ties go to the earlier candidate)
+fn did_you_mean<'a>(typo: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let len = typo.chars().count();
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(typo, candidate), candidate))
+        // A distance equal to the length would replace the whole word
+        .filter(|&(distance, _)| distance <= MAX_SUGGESTION_DISTANCE && distance < len)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Lets the help writers append straight into a byte buffer
+struct ByteSink<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for ByteSink<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
         Ok(())
     }
+}
 
-    /// Validate argument groups
-    fn validate_groups(&self, matches: &ArgMatches) -> CommandResult<()> {
-        for group in &self.groups {
-            let present_count = group.args.iter().filter(|a| matches.is_present(a)).count();
+/// The prompter `App` installs: ask on the terminal when stdin is a TTY
+fn terminal_prompter() -> Option<Prompter> {
+    #[cfg(feature = "interactive")]
+    if io::stdin().is_terminal() {
+        return Some(ask_for_value);
+    }
+    None
+}
 
-            if group.required && present_count == 0 {
-                return Err(CommandError::MissingArgument(format!(
-                    "{} (one of: {})",
-                    group.name,
-                    group.args.join(", ")
-                )));
-            }
+/// Ask the user for a missing argument's value on the terminal
+#[cfg(feature = "interactive")]
+fn ask_for_value(arg: &Arg) -> io::Result<String> {
+    let label = match arg.help {
+        Some(ref help) => format!("{} ({})", arg.name, help),
+        None => arg.name.clone(),
+    };
 
-            // Groups are mutually exclusive by default
-            if present_count > 1 {
-                let present: Vec<&String> = group
-                    .args
-                    .iter()
-                    .filter(|a| matches.is_present(a))
-                    .collect();
-                return Err(CommandError::ArgumentConflict(
-                    present[0].clone(),
-                    present[1].clone(),
-                ));
-            }
-        }
+    if let Some(ref possible) = arg.possible_values
+        && !possible.is_empty()
+    {
+        let options: Vec<&str> = possible.iter().map(String::as_str).collect();
+        let idx = Prompt::select(&format!("{}:", label), &options)?;
+        return Ok(possible[idx].clone());
+    }
 
-        Ok(())
+    let prompt = format!("{}:", label);
+    let value = match arg.value_hint {
+        ValueHint::AnyPath | ValueHint::FilePath => {
+            LineEditor::new(Prompt::theme().format_question(&prompt))
+                .completer(PathCompleter::new())
+                .read_line()?
+                .unwrap_or_default()
+        }
+        ValueHint::DirPath => LineEditor::new(Prompt::theme().format_question(&prompt))
+            .completer(PathCompleter::directories())
+            .read_line()?
+            .unwrap_or_default(),
+        _ => Prompt::input(&prompt)?,
+    };
+    if value.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no value given",
+        ));
     }
+    Ok(value)
 }
 
 /// Represents the main application
 #[derive(Debug, Clone)]
 pub struct App {
     command: Command,
+    sets_verbosity: bool,
+    sets_assume_yes: bool,
+    multicall: bool,
+    summary: Option<Callback<SummaryFn>>,
+    config_file: Option<PathBuf>,
+    dotenv: Option<PathBuf>,
+}
+
+impl AsRef<Command> for App {
+    fn as_ref(&self) -> &Command {
+        &self.command
+    }
+}
+
+impl AsRef<Command> for Command {
+    fn as_ref(&self) -> &Command {
+        self
+    }
 }
 
 impl App {
@@ -1080,6 +4070,12 @@ impl App {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             command: Command::new(name),
+            sets_verbosity: false,
+            sets_assume_yes: false,
+            multicall: false,
+            summary: None,
+            config_file: None,
+            dotenv: None,
         }
     }
 
@@ -1107,56 +4103,467 @@ impl App {
         self
     }
 
-    /// Parses command-line arguments from `std::env::args()`
-    pub fn get_matches(self) -> ArgMatches {
-        self.get_matches_from(std::env::args())
+    /// Reads defaults for missing arguments from a config file when parsing
+    ///
+    /// See [`config`](crate::config) for the format. Top-level keys are the
+    /// app's arguments and `[sub]` sections its subcommands' (`[remote.add]`
+    /// for nested ones). Values rank below environment variables and above
+    /// `default_value`, and are checked like command-line values. A leading
+    /// `~` stands for the home directory; a missing file is ignored, and one
+    /// that cannot be read or parsed fails with [`CommandError::InvalidConfig`].
+    /// Example: `App::new("myapp").config_file("~/.myapp/config")`
+    pub fn config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_file = Some(path.into());
+        self
     }
 
-    /// Parses command-line arguments from an iterator
-    pub fn get_matches_from<I, T>(self, args: I) -> ArgMatches
+    /// Reads `./.env` when parsing, for [`Arg::env`] fallbacks
+    ///
+    /// The file's variables are consulted after the real environment, which
+    /// is left unchanged. A missing file is ignored, and a malformed one fails
+    /// with [`CommandError::InvalidConfig`]. See [`env`](crate::env) for the
+    /// format.
+    /// Example: `App::new("myapp").dotenv(true)`
+    pub fn dotenv(mut self, dotenv: bool) -> Self {
+        self.dotenv = dotenv.then(|| PathBuf::from(".env"));
+        self
+    }
+
+    /// Like [`dotenv`](Self::dotenv), reading the file at `path` instead of `./.env`
+    /// Example: `App::new("myapp").dotenv_file("/etc/myapp/.env")`
+    pub fn dotenv_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dotenv = Some(path.into());
+        self
+    }
+
+    /// Registers global `-q/--quiet` and counted `-v/--verbose` flags that conflict
+    ///
+    /// Read the result with [`ArgMatches::verbosity`]. A successful parse also
+    /// stores it in the shared [`verbosity`](crate::verbosity) setting, which
+    /// default loggers and progress bars follow.
+    /// Example: `App::new("build").verbosity_args()` accepts `-q`, `-v`, `-vv`, ...
+    pub fn verbosity_args(mut self) -> Self {
+        self.command = self
+            .command
+            .arg(
+                Arg::new(QUIET_ARG)
+                    .short('q')
+                    .long(QUIET_ARG)
+                    .action(ArgAction::SetTrue)
+                    .global(true)
+                    .conflicts_with(VERBOSE_ARG)
+                    .about("Only print errors"),
+            )
+            .arg(
+                Arg::new(VERBOSE_ARG)
+                    .short('v')
+                    .long(VERBOSE_ARG)
+                    .action(ArgAction::Count)
+                    .global(true)
+                    .conflicts_with(QUIET_ARG)
+                    .about("More output per occurrence (-v, -vv)"),
+            );
+        self.sets_verbosity = true;
+        self
+    }
+
+    /// Registers a global `--dry-run` flag, read back with [`ArgMatches::is_dry_run`]
+    /// Example: `App::new("deploy").dry_run_flag()`
+    pub fn dry_run_flag(mut self) -> Self {
+        self.command = self.command.arg(
+            Arg::new(DRY_RUN_ARG)
+                .long(DRY_RUN_ARG)
+                .takes_value(false)
+                .global(true)
+                .about("Show what would be done without making changes"),
+        );
+        self
+    }
+
+    /// Registers a global `-y`/`--yes` flag, read back with [`ArgMatches::assume_yes`]
+    ///
+    /// Parsing also records it for [`fsutil::write_checked`](crate::fsutil::write_checked),
+    /// which then overwrites files without asking.
+    /// Example: `App::new("scaffold").yes_flag()`
+    pub fn yes_flag(mut self) -> Self {
+        self.command = self.command.arg(
+            Arg::new(YES_ARG)
+                .short('y')
+                .long(YES_ARG)
+                .takes_value(false)
+                .global(true)
+                .about("Answer yes to every confirmation"),
+        );
+        self.sets_assume_yes = true;
+        self
+    }
+
+    /// Registers a global `--format` option limited to `formats`; the first is the default
+    ///
+    /// Each name must parse as an [`OutputFormat`]. Read the choice back with
+    /// [`ArgMatches::output_format`] and hand it to [`Table::render_as`](crate::table::Table::render_as).
+    /// Example: `App::new("ls").output_format_arg(&["table", "json", "csv"])`
+    pub fn output_format_arg(mut self, formats: &[&str]) -> Self {
+        let mut arg = Arg::new(FORMAT_ARG)
+            .long(FORMAT_ARG)
+            .possible_values(formats)
+            .global(true)
+            .about("Output format");
+        if let Some(default) = formats.first() {
+            arg = arg.default_value(*default);
+        }
+        self.command = self.command.arg(arg);
+        self
+    }
+
+    /// Turns on parse behaviors for the whole application (see [`Command::setting`])
+    pub fn setting(mut self, settings: impl Into<Settings>) -> Self {
+        self.command = self.command.setting(settings);
+        self
+    }
+
+    /// Prompts for missing required arguments when stdin is a TTY
+    /// Example: `App::new("deploy").interactive_fallback(true)`
+    pub fn interactive_fallback(mut self, enabled: bool) -> Self {
+        self.command = self.command.interactive_fallback(enabled);
+        self
+    }
+
+    /// Fails when no subcommand is given, showing the help
+    /// Example: `App::new("git").subcommand_required(true)`
+    pub fn subcommand_required(mut self, required: bool) -> Self {
+        self.command = self.command.subcommand_required(required);
+        self
+    }
+
+    /// Accepts unambiguous abbreviations of long flags (`--verb` for `--verbose`)
+    /// Example: `App::new("app").infer_long_args(true)`
+    pub fn infer_long_args(mut self, enabled: bool) -> Self {
+        self.command = self.command.infer_long_args(enabled);
+        self
+    }
+
+    /// Accepts unambiguous abbreviations of subcommands (`bu` for `build`)
+    /// Example: `App::new("app").infer_subcommands(true)`
+    pub fn infer_subcommands(mut self, enabled: bool) -> Self {
+        self.command = self.command.infer_subcommands(enabled);
+        self
+    }
+
+    /// Stores everything after `--` verbatim under `name` (see
+    /// [`Command::trailing_var_arg`])
+    /// Example: `App::new("run").trailing_var_arg("args")`
+    pub fn trailing_var_arg(mut self, name: impl Into<String>) -> Self {
+        self.command = self.command.trailing_var_arg(name);
+        self
+    }
+
+    /// Adds an argument group to the top-level command
+    /// Example: `App::new("login").group(ArgGroup::new("auth").args(&["user", "password"]))`
+    pub fn group(mut self, group: ArgGroup) -> Self {
+        self.command = self.command.group(group);
+        self
+    }
+
+    /// Fails on bare words that no positional or subcommand takes
+    /// Example: `App::new("git").strict_positionals(true)`
+    pub fn strict_positionals(mut self, strict: bool) -> Self {
+        self.command = self.command.strict_positionals(strict);
+        self
+    }
+
+    /// Sets the styles used for help and error output
+    /// Example: `App::new("app").styles(HelpStyles::colored())`
+    pub fn styles(mut self, styles: HelpStyles) -> Self {
+        self.command = self.command.styles(styles);
+        self
+    }
+
+    /// Sets the exit status used when parsing fails (see [`Command::error_exit_code`])
+    pub fn error_exit_code(mut self, code: i32) -> Self {
+        self.command = self.command.error_exit_code(code);
+        self
+    }
+
+    /// Replaces how parse errors are rendered (see [`Command::error_formatter`])
+    pub fn error_formatter(mut self, formatter: fn(&Command, &CommandError) -> String) -> Self {
+        self.command = self.command.error_formatter(formatter);
+        self
+    }
+
+    /// Adds a usage example, shown under `EXAMPLES:` in help
+    pub fn example(mut self, line: impl Into<String>) -> Self {
+        self.command = self.command.example(line);
+        self
+    }
+
+    /// Replaces the synthesized usage line in help and error output
+    pub fn override_usage(mut self, usage: impl Into<String>) -> Self {
+        self.command = self.command.override_usage(usage);
+        self
+    }
+
+    /// Sets text printed above everything else in help
+    pub fn before_help(mut self, text: impl Into<String>) -> Self {
+        self.command = self.command.before_help(text);
+        self
+    }
+
+    /// Sets text printed below everything else in help
+    pub fn after_help(mut self, text: impl Into<String>) -> Self {
+        self.command = self.command.after_help(text);
+        self
+    }
+
+    /// Adds a usage example checked by [`verify_examples`](crate::testing::verify_examples)
+    pub fn example_checked(mut self, line: impl Into<String>) -> Self {
+        self.command = self.command.example_checked(line);
+        self
+    }
+
+    /// Picks the subcommand from the name the program was run as, busybox-style
+    ///
+    /// With `multicall(true)`, a binary linked as `init` parses `init -v` as
+    /// if it were `<app> init -v`; the program name may be any subcommand
+    /// name or alias. Run under the app's own name it parses as usual, and
+    /// under any other name parsing fails with
+    /// [`CommandError::UnknownSubcommand`].
+    /// Example: `App::new("box").multicall(true).subcommand(Command::new("init"))`
+    pub fn multicall(mut self, enabled: bool) -> Self {
+        self.multicall = enabled;
+        self
+    }
+
+    /// Sets a hook that [`run`](Self::run) calls with a [`Report`] once `main` returns
+    ///
+    /// Pass [`Report::print`] for the standard footer box, or a closure that
+    /// captures its own state. The hook only runs after `main` has executed:
+    /// when parsing fails or help/version is shown, `main` never runs and
+    /// neither does the hook.
+    /// Example: `App::new("sync").summary(|report| eprintln!("{}", report))`
+    pub fn summary<F>(mut self, hook: F) -> Self
     where
-        I: IntoIterator<Item = T>,
-        T: Into<String>,
+        F: Fn(&Report) + Send + Sync + 'static,
     {
-        let args: Vec<String> = args.into_iter().map(|a| a.into()).collect();
-        let args_slice = if args.len() > 1 { &args[1..] } else { &[] };
+        self.summary = Some(Callback(Arc::new(hook)));
+        self
+    }
+
+    /// Parses `std::env::args_os()`, runs `main` and exits the process
+    ///
+    /// Parse errors exit like [`get_matches`](Self::get_matches). When `main`
+    /// returns an error it is logged with [`log::error`](crate::log::error)
+    /// and the exit status is the [`error_exit_code`](Command::error_exit_code).
+    /// The [`summary`](Self::summary) hook runs after `main` returns, before
+    /// exiting.
+    pub fn run<E: fmt::Display>(self, main: impl FnOnce(ArgMatches) -> Result<(), E>) -> ! {
+        std::process::exit(self.run_from(std::env::args_os(), main))
+    }
 
-        match self.command.parse_args(args_slice) {
+    /// Like [`run`](Self::run) with explicit arguments, returning the exit
+    /// status instead of exiting
+    pub fn run_from<I, T, E>(self, args: I, main: impl FnOnce(ArgMatches) -> Result<(), E>) -> i32
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+        E: fmt::Display,
+    {
+        let start = Instant::now();
+        let matches = match self.parse(args) {
             Ok(matches) => matches,
-            Err(CommandError::HelpRequested) => {
-                println!("{}", self.command.generate_help());
-                std::process::exit(0);
-            }
-            Err(CommandError::VersionRequested) => {
-                if let Some(version) = self.command.version {
-                    println!("{} {}", self.command.name, version);
-                } else {
-                    println!("{}", self.command.name);
-                }
-                std::process::exit(0);
+            Err(e) => {
+                let error = self.command.format_error(e);
+                error.print();
+                return error.exit_code();
             }
+        };
+
+        let code = match main(matches) {
+            Ok(()) => 0,
             Err(e) => {
-                eprintln!("{}", e);
-                eprintln!("\nFor more information try --help");
-                std::process::exit(1);
+                crate::log::error(&e.to_string());
+                self.command.error_exit_code
             }
+        };
+        if let Some(ref hook) = self.summary {
+            (hook.0)(&Report::since(start).exit_code(code));
+        }
+        code
+    }
+
+    /// Parses command-line arguments from `std::env::args_os()`
+    pub fn get_matches(self) -> ArgMatches {
+        self.get_matches_from(std::env::args_os())
+    }
+
+    /// Parses command-line arguments from an iterator
+    pub fn get_matches_from<I, T>(self, args: I) -> ArgMatches
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        match self.parse(args) {
+            Ok(matches) => matches,
+            Err(e) => self.command.format_error(e).exit(),
+        }
+    }
+
+    /// Parses `std::env::args_os()` straight into `T`, exiting on any error
+    ///
+    /// Conversion errors are printed like parse errors.
+    /// Example: `let config: Config = App::new("serve").arg(port).parse_into();`
+    pub fn parse_into<T: FromArgMatches>(self) -> T {
+        self.parse_into_from(std::env::args_os())
+    }
+
+    /// Parses arguments from an iterator straight into `T`, exiting on any error
+    pub fn parse_into_from<T, I, S>(self, args: I) -> T
+    where
+        T: FromArgMatches,
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        match self.parse(args).and_then(|matches| matches.populate()) {
+            Ok(value) => value,
+            Err(e) => self.command.format_error(e).exit(),
         }
     }
 
+    /// Parses arguments from an iterator into `T`, returning parse and
+    /// conversion errors instead of exiting
+    pub fn try_parse_into_from<T, I, S>(self, args: I) -> CommandResult<T>
+    where
+        T: FromArgMatches,
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.parse(args)?.populate()
+    }
+
+    /// Writes help text to `out` (see [`Command::write_help`])
+    pub fn write_help(&self, out: &mut impl io::Write) -> io::Result<()> {
+        self.command.write_help(out)
+    }
+
+    /// Renders a Markdown reference page (see [`Command::to_markdown`])
+    pub fn to_markdown(&self) -> String {
+        self.command.to_markdown()
+    }
+
+    /// Checks the definition for mistakes, panicking if any are found (see
+    /// [`Command::debug_assert`])
+    #[track_caller]
+    pub fn debug_assert(&self) {
+        self.command.debug_assert()
+    }
+
+    /// Writes a completion script for `shell` (see [`Command::generate_completions`])
+    pub fn generate_completions(&self, shell: Shell) -> String {
+        self.command.generate_completions(shell)
+    }
+
+    /// Writes a completion script for `shell` to `out` (see
+    /// [`Command::print_completions`])
+    pub fn print_completions(&self, shell: Shell, out: &mut impl io::Write) -> io::Result<()> {
+        self.command.print_completions(shell, out)
+    }
+
+    /// Creates an error tied to this application (see [`Command::error`])
+    pub fn error(&self, kind: ErrorKind, message: impl Into<String>) -> FormattedError {
+        self.command.error(kind, message)
+    }
+
     /// Tries to parse arguments and returns a Result instead of exiting
     pub fn try_get_matches(self) -> CommandResult<ArgMatches> {
-        self.try_get_matches_from(std::env::args())
+        self.try_get_matches_from(std::env::args_os())
     }
 
     /// Tries to parse arguments from an iterator
     pub fn try_get_matches_from<I, T>(self, args: I) -> CommandResult<ArgMatches>
     where
         I: IntoIterator<Item = T>,
-        T: Into<String>,
+        T: Into<OsString>,
+    {
+        self.parse(args)
+    }
+
+    /// Run the parsing core with the terminal frontend's prompter and verbosity
+    fn parse<I, T>(&self, args: I) -> CommandResult<ArgMatches>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
     {
-        let args: Vec<String> = args.into_iter().map(|a| a.into()).collect();
-        let args_slice = if args.len() > 1 { &args[1..] } else { &[] };
-        self.command.parse_args(args_slice)
+        #[cfg(debug_assertions)]
+        self.command.debug_assert();
+        let mut args = args.into_iter().map(Into::into);
+        let program = args
+            .next()
+            .map(|program| program.to_string_lossy().into_owned());
+        let (mut args, escaped_os) = escape_args(args);
+
+        let dotenv = match self.dotenv {
+            Some(ref path) => match crate::env::read_dotenv(path) {
+                Ok(vars) => Some(Arc::new(vars)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+                Err(err) => {
+                    return Err(CommandError::InvalidConfig(
+                        path.display().to_string(),
+                        err.to_string(),
+                    ));
+                }
+            },
+            None => None,
+        };
+        let config = match self.config_file {
+            Some(ref path) => ConfigFile::load(path)
+                .map_err(|err| CommandError::InvalidConfig(path.display().to_string(), err))?
+                .map(Arc::new),
+            None => None,
+        };
+        let matches = match program.as_deref().map(program_name) {
+            Some(applet) if self.multicall && applet != self.command.name => {
+                if self.command.find_subcommand(&applet).is_none() {
+                    return Err(self
+                        .command
+                        .misspelled_subcommand(&applet)
+                        .unwrap_or(CommandError::UnknownSubcommand(applet)));
+                }
+                // The program name is the subcommand token, at argv index 0
+                args.insert(0, applet);
+                let ctx = ParseContext {
+                    prompter: terminal_prompter(),
+                    config,
+                    dotenv,
+                    escaped_os,
+                    ..ParseContext::default()
+                };
+                self.command.parse_args_with(&args, &ctx)
+            }
+            _ => {
+                let ctx = ParseContext {
+                    argv_offset: 1,
+                    prompter: terminal_prompter(),
+                    config,
+                    dotenv,
+                    escaped_os,
+                    ..ParseContext::default()
+                };
+                self.command.parse_args_with(&args, &ctx)
+            }
+        };
+        matches.map(|matches| self.publish_settings(matches))
+    }
+
+    /// Stores the parsed verbosity and `--yes` in their shared settings, for
+    /// the flags this app registered
+    fn publish_settings(&self, matches: ArgMatches) -> ArgMatches {
+        if self.sets_verbosity {
+            crate::verbosity::set(matches.verbosity());
+        }
+        if self.sets_assume_yes {
+            crate::fsutil::set_assume_yes(matches.assume_yes());
+        }
+        matches
     }
 }
 
@@ -1164,6 +4571,34 @@ impl App {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_for_stream_strips_colors_off_terminal() {
+        let rendered = "\x1b[1;93mUSAGE:\x1b[0m\n    app\n";
+        assert_eq!(for_stream(rendered, true), rendered);
+        assert_eq!(for_stream(rendered, false), "USAGE:\n    app\n");
+    }
+
+    #[test]
+    fn test_program_name() {
+        assert_eq!(program_name("/usr/bin/init"), "init");
+        assert_eq!(program_name("init"), "init");
+        assert_eq!(program_name("./mkfs.ext4"), "mkfs.ext4");
+        let exe = format!("shutdown{}", std::env::consts::EXE_SUFFIX);
+        assert_eq!(program_name(&exe), "shutdown");
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let flags = ["verbose", "version", "output"];
+        assert_eq!(did_you_mean("verbos", flags), Some("verbose"));
+        assert_eq!(did_you_mean("outptu", flags), Some("output"));
+        assert_eq!(did_you_mean("versoin", flags), Some("version"));
+        assert_eq!(did_you_mean("colour", flags), None);
+        // Two edits would rewrite a two-letter word entirely
+        assert_eq!(did_you_mean("xy", ["up"]), None);
+        assert_eq!(did_you_mean("verbose", std::iter::empty()), None);
+    }
+
     #[test]
     fn test_arg_creation() {
         let arg = Arg::new("test")
@@ -1211,6 +4646,40 @@ mod tests {
         assert_eq!(sub.value_of("message"), Some("Initial commit"));
     }
 
+    #[test]
+    fn test_subcommand_levels_share_one_path() {
+        let app = App::new("cloud")
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .global(true)
+                    .takes_value(false),
+            )
+            .subcommand(
+                Command::new("compute").subcommand(
+                    Command::new("instances")
+                        .alias("vm")
+                        .subcommand(Command::new("create").arg(Arg::new("name").index(0))),
+                ),
+            );
+
+        let matches = app
+            .try_get_matches_from(["cloud", "compute", "vm", "create", "web-1", "-v"])
+            .unwrap();
+        assert_eq!(matches.path.len(), 4);
+        let compute = matches.subcommand_matches("compute").unwrap();
+        let (matched, instances) = compute.subcommand().unwrap();
+        assert_eq!(matched, "vm");
+        assert_eq!(instances.command_name(), "instances");
+        let create = instances.subcommand_matches("create").unwrap();
+        assert!(Arc::ptr_eq(&create.path, &matches.path));
+        assert_eq!(create.value_of("name"), Some("web-1"));
+        assert!(create.subcommand().is_none());
+        // A global given at the bottom is visible at every level above it
+        assert!(matches.is_present("verbose") && instances.is_present("verbose"));
+        assert!(!matches.is_present("name"));
+    }
+
     #[test]
     fn test_help_generation() {
         let cmd = Command::new("test")
@@ -1228,4 +4697,87 @@ mod tests {
         assert!(help.contains("--verbose"));
         assert!(help.contains("Verbose output"));
     }
+
+    #[test]
+    fn test_prompt_missing_args() {
+        let cmd = Command::new("deploy")
+            .arg(Arg::new("target").index(0).required(true))
+            .arg(
+                Arg::new("env")
+                    .long("env")
+                    .required(true)
+                    .possible_values(&["staging", "prod"]),
+            );
+
+        let mut asked = Vec::new();
+        let mut matches = ArgMatches::new("deploy");
+        cmd.prompt_missing_args(&mut matches, &mut |arg| {
+            asked.push(arg.name().to_string());
+            Ok(if arg.name() == "env" { "prod" } else { "web-1" }.to_string())
+        })
+        .unwrap();
+
+        assert_eq!(asked, vec!["target", "env"]);
+        assert_eq!(matches.value_of("target"), Some("web-1"));
+        assert_eq!(matches.value_of("env"), Some("prod"));
+        assert_eq!(matches.value_source("env"), Some(ValueSource::Prompt));
+    }
+
+    #[test]
+    fn test_prompt_missing_args_validates_and_gives_up() {
+        let cmd = Command::new("deploy").arg(
+            Arg::new("env")
+                .long("env")
+                .required(true)
+                .possible_values(&["staging", "prod"]),
+        );
+
+        let mut matches = ArgMatches::new("deploy");
+        let result = cmd.prompt_missing_args(&mut matches, &mut |_| Ok("qa".to_string()));
+        assert!(matches!(result, Err(CommandError::ValidationError(name, _)) if name == "env"));
+
+        let mut matches = ArgMatches::new("deploy");
+        let result = cmd.prompt_missing_args(&mut matches, &mut |_| {
+            Err(io::Error::new(io::ErrorKind::UnexpectedEof, "closed"))
+        });
+        assert_eq!(
+            result,
+            Err(CommandError::MissingArgument("env".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_prompt_skips_args_with_env_or_default() {
+        fn ask(arg: &Arg) -> io::Result<String> {
+            Ok(format!("prompted-{}", arg.name()))
+        }
+        let cmd = Command::new("deploy")
+            .interactive_fallback(true)
+            .arg(
+                Arg::new("token")
+                    .long("token")
+                    .required(true)
+                    .env("ZFISH_PROMPT_TEST_TOKEN"),
+            )
+            .arg(
+                Arg::new("region")
+                    .long("region")
+                    .required(true)
+                    .default_value("eu"),
+            )
+            .arg(Arg::new("user").long("user").required(true));
+        let dotenv = HashMap::from([("ZFISH_PROMPT_TEST_TOKEN".to_string(), "s3cret".to_string())]);
+        let ctx = ParseContext {
+            prompter: Some(ask),
+            dotenv: Some(Arc::new(dotenv)),
+            ..ParseContext::default()
+        };
+
+        let matches = cmd.parse_args_with(&[], &ctx).unwrap();
+        assert_eq!(matches.value_of("token"), Some("s3cret"));
+        assert_eq!(matches.value_source("token"), Some(ValueSource::EnvVar));
+        assert_eq!(matches.value_of("region"), Some("eu"));
+        assert_eq!(matches.value_of("user"), Some("prompted-user"));
+        assert_eq!(matches.value_source("user"), Some(ValueSource::Prompt));
+    }
 }
@@ -0,0 +1,126 @@
+//! # Tab Completion
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — completion.rs                                         ║
+//! ║  Completion sources for the interactive line editor           ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! A [`Completer`] turns the word under the cursor into candidate
+//! replacements. [`PathCompleter`] lists matching filesystem entries using
+//! `std::fs` only; the [`LineEditor`](crate::line_editor::LineEditor) calls it
+//! on Tab.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use zfish::completion::{Completer, PathCompleter};
+//!
+//! // Directories first, each with a trailing `/`
+//! for candidate in PathCompleter::new().complete("src/") {
+//!     println!("{}", candidate);
+//! }
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+/// A source of completions for the word under the cursor
+pub trait Completer {
+    /// Returns full replacements for `word`, in display order
+    fn complete(&self, word: &str) -> Vec<String>;
+}
+
+impl<F> Completer for F
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    fn complete(&self, word: &str) -> Vec<String> {
+        self(word)
+    }
+}
+
+/// Completes filesystem paths, directories first with a trailing `/`
+///
+/// Hidden entries are only offered when the typed name starts with `.`.
+#[derive(Debug, Clone, Default)]
+pub struct PathCompleter {
+    dirs_only: bool,
+}
+
+impl PathCompleter {
+    /// Creates a completer offering both files and directories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a completer offering directories only
+    pub fn directories() -> Self {
+        Self { dirs_only: true }
+    }
+}
+
+impl Completer for PathCompleter {
+    fn complete(&self, word: &str) -> Vec<String> {
+        // Split "src/co" into the directory to list and the name prefix
+        let (dir_part, prefix) = match word.rfind('/') {
+            Some(idx) => word.split_at(idx + 1),
+            None => ("", word),
+        };
+        let dir = if dir_part.is_empty() {
+            Path::new(".")
+        } else {
+            Path::new(dir_part)
+        };
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut matches: Vec<(bool, String)> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.'))
+                {
+                    return None;
+                }
+                // Follow symlinks so a link to a directory completes like one
+                let is_dir = fs::metadata(entry.path()).is_ok_and(|m| m.is_dir());
+                if self.dirs_only && !is_dir {
+                    return None;
+                }
+                Some((is_dir, name))
+            })
+            .collect();
+
+        matches.sort_by(|(a_dir, a), (b_dir, b)| b_dir.cmp(a_dir).then_with(|| a.cmp(b)));
+
+        matches
+            .into_iter()
+            .map(|(is_dir, name)| {
+                let slash = if is_dir { "/" } else { "" };
+                format!("{}{}{}", dir_part, name, slash)
+            })
+            .collect()
+    }
+}
+
+/// Longest prefix shared by every candidate (on character boundaries)
+pub(crate) fn common_prefix(candidates: &[String]) -> &str {
+    let Some(first) = candidates.first() else {
+        return "";
+    };
+    let mut end = first.len();
+    for candidate in &candidates[1..] {
+        end = first
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(end.min(candidate.len()), |((idx, _), _)| idx.min(end));
+    }
+    &first[..end]
+}
@@ -0,0 +1,312 @@
+//! # Config Files
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — config.rs                                             ║
+//! ║  A small INI/TOML reader for argument defaults                ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`App::config_file`](crate::command::App::config_file) reads a file in the
+//! subset of TOML (and INI) below and uses it for arguments missing from the
+//! command line. Values rank below environment variables and above
+//! `default_value`, and [`ArgMatches::value_source`](crate::command::ArgMatches::value_source)
+//! reports them as `ConfigFile`.
+//!
+//! ```toml
+//! # Keys are argument names or long flags
+//! color = "always"
+//! verbose = true
+//! include = ["src", "tests"]
+//!
+//! ; A section holds the arguments of a subcommand
+//! [remote.add]
+//! name = origin
+//! ```
+//!
+//! Values are `"double-quoted"` (with `\"`, `\\`, `\n`, `\t` and `\r`
+//! escapes), `'literal'`, bare words, or single-line `[...]` arrays of those.
+//! Lines starting with `#` or `;` are comments, as is anything after ` #`
+//! outside quotes. A key given twice keeps its last value.
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::config::ConfigFile;
+//!
+//! let config = ConfigFile::parse("jobs = 4\n[build]\nprofile = 'release'\n").unwrap();
+//! assert_eq!(config.get("jobs"), Some("4"));
+//! assert_eq!(config.get("build.profile"), Some("release"));
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The values of a parsed config file, by dotted key (`section.key`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    values: HashMap<String, Vec<String>>,
+}
+
+impl ConfigFile {
+    /// Parses config text, failing with the line number of the first error
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut values = HashMap::new();
+        let mut section = String::new();
+
+        for (number, line) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let error = |message: &str| format!("line {}: {}", number + 1, message);
+
+            if let Some(header) = line.strip_prefix('[') {
+                if header.starts_with('[') {
+                    return Err(error("arrays of tables are not supported"));
+                }
+                let name = strip_comment(header)
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("expected ']' after the section name"))?
+                    .trim();
+                if !is_key(name) {
+                    return Err(error(&format!("invalid section name '{}'", name)));
+                }
+                section = format!("{}.", name);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected 'key = value'"))?;
+            let key = key.trim();
+            if !is_key(key) {
+                return Err(error(&format!("invalid key '{}'", key)));
+            }
+            let parsed = parse_value(value.trim()).map_err(|message| error(&message))?;
+            values.insert(format!("{}{}", section, key), parsed);
+        }
+
+        Ok(ConfigFile { values })
+    }
+
+    /// Reads and parses the file at `path`, `Ok(None)` if it does not exist
+    ///
+    /// A leading `~` stands for the home directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        match std::fs::read_to_string(expand_home(path.as_ref())) {
+            Ok(text) => Self::parse(&text).map(Some),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    /// Gets the value of `key` (`name` or `section.name`), the first one for
+    /// an array
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key)?.first().map(String::as_str)
+    }
+
+    /// Gets every value of `key`; a plain value is a list of one
+    pub fn get_all(&self, key: &str) -> Option<&[String]> {
+        self.values.get(key).map(Vec::as_slice)
+    }
+
+    /// Checks whether the file sets no keys at all
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl FromStr for ConfigFile {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// Replaces a leading `~` with the home directory (`HOME`, or `USERPROFILE`
+/// on Windows); other paths are returned as they are
+pub(crate) fn expand_home(path: &Path) -> PathBuf {
+    let Ok(rest) = path.strip_prefix("~") else {
+        return path.to_path_buf();
+    };
+    let home = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    match std::env::var_os(home).filter(|value| !value.is_empty()) {
+        Some(home) => PathBuf::from(home).join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Bare keys and section names: letters, digits, `_`, `-` and `.`
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with('.')
+        && !key.ends_with('.')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// `text` up to a ` #` comment
+fn strip_comment(text: &str) -> &str {
+    let end = text
+        .char_indices()
+        .find(|&(i, c)| c == '#' && (i == 0 || text[..i].ends_with(char::is_whitespace)))
+        .map_or(text.len(), |(i, _)| i);
+    text[..end].trim_end()
+}
+
+/// Parses everything after `=`
+fn parse_value(text: &str) -> Result<Vec<String>, String> {
+    let Some(inner) = text.strip_prefix('[') else {
+        return parse_single(text).map(|value| vec![value]);
+    };
+
+    let mut values = Vec::new();
+    let mut rest = inner.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return match strip_comment(after).trim_start() {
+                "" => Ok(values),
+                extra => Err(format!("unexpected '{}' after the array", extra)),
+            };
+        }
+        if rest.is_empty() {
+            return Err("expected ']' to close the array".to_string());
+        }
+        let (value, after) = parse_scalar(rest, true)?;
+        values.push(value);
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.is_empty() && !rest.starts_with(']') {
+            return Err("expected ',' or ']' in the array".to_string());
+        }
+    }
+}
+
+/// Parses a whole quoted or bare value, allowing only a comment after it
+pub(crate) fn parse_single(text: &str) -> Result<String, String> {
+    let (value, rest) = parse_scalar(text, false)?;
+    match strip_comment(rest).trim_start() {
+        "" => Ok(value),
+        extra => Err(format!("unexpected '{}' after the value", extra)),
+    }
+}
+
+/// Parses one quoted or bare value, returning it and the text after it
+fn parse_scalar(text: &str, in_array: bool) -> Result<(String, &str), String> {
+    if let Some(inner) = text.strip_prefix('\'') {
+        let end = inner
+            .find('\'')
+            .ok_or("unterminated string: missing closing \"'\"")?;
+        return Ok((inner[..end].to_string(), &inner[end + 1..]));
+    }
+
+    if let Some(inner) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = inner.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((value, &inner[i + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, escaped @ ('"' | '\\'))) => value.push(escaped),
+                    Some((_, other)) => return Err(format!("unknown escape '\\{}'", other)),
+                    None => break,
+                },
+                _ => value.push(c),
+            }
+        }
+        return Err("unterminated string: missing closing '\"'".to_string());
+    }
+
+    // A bare word runs to a comment, or to `,`/`]` inside an array
+    let bare = strip_comment(text);
+    let end = if in_array {
+        bare.find([',', ']']).unwrap_or(bare.len())
+    } else {
+        bare.len()
+    };
+    let value = bare[..end].trim();
+    if in_array && value.is_empty() {
+        return Err("expected a value in the array".to_string());
+    }
+    Ok((value.to_string(), &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        let config = ConfigFile::parse(
+            "# settings\n\
+             color = \"al\\\"ways\"   # inline comment\n\
+             name = 'C:\\path'\n\
+             jobs = 4\n\
+             empty =\n\
+             url = http://host/#anchor\n\
+             tags = [\"a\", 'b', c ]\n\
+             \n\
+             ; ini comment\n\
+             [remote.add]\n\
+             name = origin\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.get("color"), Some("al\"ways"));
+        assert_eq!(config.get("name"), Some("C:\\path"));
+        assert_eq!(config.get("jobs"), Some("4"));
+        assert_eq!(config.get("empty"), Some(""));
+        assert_eq!(config.get("url"), Some("http://host/#anchor"));
+        assert_eq!(config.get_all("tags").unwrap(), ["a", "b", "c"]);
+        assert_eq!(config.get("tags"), Some("a"));
+        assert_eq!(config.get("remote.add.name"), Some("origin"));
+        assert_eq!(config.get("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let error = |text: &str| ConfigFile::parse(text).unwrap_err();
+        assert_eq!(error("a = 1\nnot a pair"), "line 2: expected 'key = value'");
+        assert_eq!(
+            error("[build"),
+            "line 1: expected ']' after the section name"
+        );
+        assert_eq!(
+            error("[[bin]]"),
+            "line 1: arrays of tables are not supported"
+        );
+        assert_eq!(error("a b = 1"), "line 1: invalid key 'a b'");
+        assert_eq!(
+            error("a = \"open"),
+            "line 1: unterminated string: missing closing '\"'"
+        );
+        assert_eq!(
+            error("a = [1, 2"),
+            "line 1: expected ']' to close the array"
+        );
+        assert_eq!(error("a = 'x' y"), "line 1: unexpected 'y' after the value");
+    }
+
+    #[test]
+    fn test_expand_home_leaves_other_paths() {
+        assert_eq!(
+            expand_home(Path::new("/etc/app.toml")),
+            PathBuf::from("/etc/app.toml")
+        );
+        assert_eq!(
+            expand_home(Path::new("~app/config")),
+            PathBuf::from("~app/config")
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! # Dotenv Files
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — env.rs                                                ║
+//! ║  Reads .env files for environment variable fallbacks          ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`read_dotenv`] reads `KEY=VALUE` lines into a map.
+//! [`App::dotenv`](crate::command::App::dotenv) reads `./.env` that way and
+//! consults it for [`Arg::env`](crate::command::Arg::env) fallbacks after the
+//! real environment, so the environment always wins over the file and is
+//! never modified. [`load_dotenv`] copies a file into the process environment
+//! instead, for code that reads variables directly.
+//!
+//! ```sh
+//! # Comments and blank lines are skipped
+//! export APP_TOKEN="s3cr\"et"   # `export` is optional
+//! APP_CONFIG='~/.myapp/config'  # single quotes are literal
+//! APP_JOBS=4
+//! ```
+//!
+//! ## Examples
+//!
+//! ```
+//! let vars = zfish::env::parse_dotenv("export NAME='zfish'\nJOBS = 4 # comment\n").unwrap();
+//! assert_eq!(vars, [("NAME".to_string(), "zfish".to_string()), ("JOBS".to_string(), "4".to_string())]);
+//! ```
+
+use crate::config::parse_single;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Parses `.env` text into `(key, value)` pairs in file order
+///
+/// Fails with the line number of the first malformed line.
+pub fn parse_dotenv(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    for (number, line) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map_or(line, str::trim_start);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected 'KEY=VALUE'"))?;
+        let key = key.trim_end();
+        if !is_name(key) {
+            return Err(error(&format!("invalid variable name '{}'", key)));
+        }
+        let value = parse_single(value.trim()).map_err(|message| error(&message))?;
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+/// Reads the `.env` file at `path` into a map, the last assignment winning
+///
+/// A malformed file fails with `InvalidData`.
+pub fn read_dotenv(path: impl AsRef<Path>) -> io::Result<HashMap<String, String>> {
+    let text = std::fs::read_to_string(path)?;
+    let vars = parse_dotenv(&text)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    Ok(vars.into_iter().collect())
+}
+
+/// Loads the `.env` file at `path` into the process environment
+///
+/// Variables that are already set keep their value. Returns how many were
+/// set. A malformed file fails with `InvalidData` before anything is set.
+///
+/// # Safety
+///
+/// Same as [`std::env::set_var`]: no other thread may read or write the
+/// environment while this runs. Prefer [`read_dotenv`], which leaves the
+/// environment alone.
+#[allow(unsafe_code)]
+pub unsafe fn load_dotenv(path: impl AsRef<Path>) -> io::Result<usize> {
+    let text = std::fs::read_to_string(path)?;
+    let vars = parse_dotenv(&text)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+
+    let mut set = 0;
+    for (key, value) in vars {
+        if std::env::var_os(&key).is_none() {
+            // SAFETY: upheld by the caller
+            unsafe { crate::os::set_env_var(&key, &value) };
+            set += 1;
+        }
+    }
+    Ok(set)
+}
+
+/// Variable names: letters, digits, `_` and `.`, not starting with a digit
+fn is_name(key: &str) -> bool {
+    key.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# database\n\
+             export DB_URL=\"postgres://localhost/app\"\n\
+             DB_PASSWORD='p#ss \\n word'\n\
+             \n\
+             JOBS = 4   # inline comment\n\
+             EMPTY=\n\
+             exported=1\n",
+        )
+        .unwrap();
+        let pairs: Vec<(&str, &str)> = vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(
+            pairs,
+            [
+                ("DB_URL", "postgres://localhost/app"),
+                ("DB_PASSWORD", "p#ss \\n word"),
+                ("JOBS", "4"),
+                ("EMPTY", ""),
+                ("exported", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_errors_name_the_line() {
+        let error = |text: &str| parse_dotenv(text).unwrap_err();
+        assert_eq!(error("A=1\nB"), "line 2: expected 'KEY=VALUE'");
+        assert_eq!(error("1A=x"), "line 1: invalid variable name '1A'");
+        assert_eq!(error("MY VAR=x"), "line 1: invalid variable name 'MY VAR'");
+        assert_eq!(
+            error("A=\"open"),
+            "line 1: unterminated string: missing closing '\"'"
+        );
+    }
+}
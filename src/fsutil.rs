@@ -0,0 +1,130 @@
+//! # File Writing Helpers
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — fsutil.rs                                             ║
+//! ║  Ask before overwriting, honouring --yes                      ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Generators call [`write_checked`] instead of `fs::write`. New files are
+//! written straight away; an existing file is only replaced after the user
+//! confirms, or when `--yes` was given
+//! ([`App::yes_flag`](crate::command::App::yes_flag) records it here). When
+//! nobody can be asked (stdin is not a terminal, or the `interactive` feature
+//! is off) the file is left alone.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use zfish::fsutil::{self, WriteOutcome};
+//!
+//! match fsutil::write_checked("Cargo.toml", "[package]\n").unwrap() {
+//!     WriteOutcome::Skipped => println!("kept existing Cargo.toml"),
+//!     outcome => println!("Cargo.toml: {}", outcome),
+//! }
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether confirmations are answered "yes" without asking
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.store(assume_yes, Ordering::Relaxed);
+}
+
+/// Checks whether confirmations are answered "yes" without asking
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// What [`write_checked`] did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriteOutcome {
+    /// The file did not exist and was written
+    Created,
+    /// The file existed and was replaced
+    Overwritten,
+    /// The file already had exactly these contents
+    Unchanged,
+    /// The file existed and was kept (declined, or nobody could be asked)
+    Skipped,
+}
+
+impl WriteOutcome {
+    /// Checks whether the file now holds the new contents
+    pub fn is_written(self) -> bool {
+        !matches!(self, WriteOutcome::Skipped)
+    }
+}
+
+impl fmt::Display for WriteOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WriteOutcome::Created => "created",
+            WriteOutcome::Overwritten => "overwritten",
+            WriteOutcome::Unchanged => "unchanged",
+            WriteOutcome::Skipped => "skipped",
+        })
+    }
+}
+
+/// Writes `contents` to `path`, asking before replacing an existing file
+pub fn write_checked(
+    path: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> io::Result<WriteOutcome> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+
+    // `create_new` fails instead of truncating a file that appeared meanwhile
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+    {
+        Ok(mut file) => {
+            file.write_all(contents)?;
+            return Ok(WriteOutcome::Created);
+        }
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(err),
+    }
+
+    let existing = fs::read(path)?;
+    if existing == contents {
+        return Ok(WriteOutcome::Unchanged);
+    }
+
+    if assume_yes() || confirm_overwrite(path)? {
+        fs::write(path, contents)?;
+        Ok(WriteOutcome::Overwritten)
+    } else {
+        Ok(WriteOutcome::Skipped)
+    }
+}
+
+/// Ask whether to replace `path`; `false` when nobody can answer
+#[cfg(feature = "interactive")]
+fn confirm_overwrite(path: &Path) -> io::Result<bool> {
+    use std::io::IsTerminal;
+
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    let question = format!("Overwrite {}?", path.display());
+    crate::prompt::Prompt::confirm(&question, false)
+}
+
+#[cfg(not(feature = "interactive"))]
+fn confirm_overwrite(_path: &Path) -> io::Result<bool> {
+    Ok(false)
+}
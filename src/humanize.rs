@@ -0,0 +1,106 @@
+//! # Human-Friendly Formatting
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — humanize.rs                                           ║
+//! ║  Durations and byte sizes for humans, both directions         ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! The formatting counterpart to [`value_parser`](crate::value_parser). Values
+//! parsed from `--timeout 90s` can be echoed back as `1m 30s` in confirmations
+//! and logs, and the formatted text parses back to the same value.
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use zfish::humanize;
+//!
+//! let timeout = humanize::parse_duration("90s").unwrap();
+//! assert_eq!(humanize::format_duration(timeout), "1m 30s");
+//! assert_eq!(humanize::parse_duration("1m 30s").unwrap(), timeout);
+//!
+//! assert_eq!(humanize::format_byte_size(512 * 1024 * 1024), "512 MiB");
+//! assert_eq!(humanize::parse_byte_size("512 MiB").unwrap(), 512 * 1024 * 1024);
+//! ```
+
+use std::time::Duration;
+
+/// Binary units used when formatting byte sizes, largest first
+const BYTE_UNITS: &[(u64, &str)] = &[
+    (1 << 50, "PiB"),
+    (1 << 40, "TiB"),
+    (1 << 30, "GiB"),
+    (1 << 20, "MiB"),
+    (1 << 10, "KiB"),
+];
+
+/// Parses a human duration such as `1h30m` or `1m 30s`
+///
+/// Same grammar as [`value_parser::duration`](crate::value_parser::duration).
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    crate::value_parser::duration(value)
+}
+
+/// Formats a duration as space-separated components, e.g. `1h 30m` or `2s 500ms`
+///
+/// Every non-zero component down to nanoseconds is included, so the output
+/// always parses back to exactly the same duration. A zero duration is `0s`.
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+
+    let components = [
+        (total_secs / 86_400, "d"),
+        ((total_secs % 86_400) / 3600, "h"),
+        ((total_secs % 3600) / 60, "m"),
+        (total_secs % 60, "s"),
+        (u64::from(nanos / 1_000_000), "ms"),
+        (u64::from((nanos / 1_000) % 1_000), "us"),
+        (u64::from(nanos % 1_000), "ns"),
+    ];
+
+    let parts: Vec<String> = components
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Parses a byte size such as `512MiB` or `1.5 GB`
+///
+/// Same grammar as [`value_parser::byte_size`](crate::value_parser::byte_size).
+pub fn parse_byte_size(value: &str) -> Result<u64, String> {
+    crate::value_parser::byte_size(value)
+}
+
+/// Formats a byte count with binary units, e.g. `512 MiB`, `1.5 GiB`, `42 B`
+///
+/// Exact multiples of a unit are printed without decimals and parse back to
+/// the same value; other sizes are rounded to at most two decimals.
+pub fn format_byte_size(bytes: u64) -> String {
+    for (i, &(unit_size, unit)) in BYTE_UNITS.iter().enumerate() {
+        if bytes >= unit_size {
+            if bytes.is_multiple_of(unit_size) {
+                return format!("{} {}", bytes / unit_size, unit);
+            }
+            let value = format!("{:.2}", bytes as f64 / unit_size as f64);
+            // Rounding up to 1024 of a unit is 1 of the next one
+            if value == "1024.00" && i > 0 {
+                return format!("1 {}", BYTE_UNITS[i - 1].1);
+            }
+            let value = value.trim_end_matches('0').trim_end_matches('.');
+            return format!("{} {}", value, unit);
+        }
+    }
+    format!("{} B", bytes)
+}
@@ -0,0 +1,126 @@
+//! # Input Sources
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — io.rs                                                 ║
+//! ║  The `-` means stdin convention for filter-style tools        ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! A `<FILE>` argument declared with
+//! [`Arg::allow_stdin_dash`](crate::command::Arg::allow_stdin_dash) accepts
+//! `-`. Parse it as a [`FileOrStdin`] and [`open`](FileOrStdin::open) it to get
+//! an [`InputSource`] that reads from the file or from locked stdin.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use std::io::BufRead;
+//! use zfish::command::{App, Arg};
+//! use zfish::io::FileOrStdin;
+//!
+//! let matches = App::new("wc")
+//!     .arg(Arg::new("file").index(0).allow_stdin_dash(true).default_value("-"))
+//!     .get_matches();
+//!
+//! let input: FileOrStdin = matches.value_of_t("file").unwrap();
+//! let lines = input.open().unwrap().lines().count();
+//! println!("{} {}", lines, input);
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, StdinLock};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A path argument where `-` stands for standard input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileOrStdin {
+    /// Read from standard input (the argument was `-`)
+    Stdin,
+    /// Read from this file
+    Path(PathBuf),
+}
+
+impl FileOrStdin {
+    /// Checks whether this refers to standard input
+    pub fn is_stdin(&self) -> bool {
+        matches!(self, FileOrStdin::Stdin)
+    }
+
+    /// Returns the file path, or `None` for stdin
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            FileOrStdin::Stdin => None,
+            FileOrStdin::Path(path) => Some(path),
+        }
+    }
+
+    /// Opens the file, or locks stdin, for buffered reading
+    pub fn open(&self) -> io::Result<InputSource> {
+        match self {
+            FileOrStdin::Stdin => Ok(InputSource::Stdin(io::stdin().lock())),
+            FileOrStdin::Path(path) => {
+                File::open(path).map(|f| InputSource::File(BufReader::new(f)))
+            }
+        }
+    }
+}
+
+impl FromStr for FileOrStdin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("path must not be empty".to_string()),
+            "-" => Ok(FileOrStdin::Stdin),
+            path => Ok(FileOrStdin::Path(PathBuf::from(path))),
+        }
+    }
+}
+
+impl fmt::Display for FileOrStdin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileOrStdin::Stdin => f.write_str("-"),
+            FileOrStdin::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// An open input: either a buffered file or locked stdin
+#[derive(Debug)]
+pub enum InputSource {
+    /// Locked standard input
+    Stdin(StdinLock<'static>),
+    /// A buffered file
+    File(BufReader<File>),
+}
+
+impl Read for InputSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            InputSource::Stdin(stdin) => stdin.read(buf),
+            InputSource::File(file) => file.read(buf),
+        }
+    }
+}
+
+impl BufRead for InputSource {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            InputSource::Stdin(stdin) => stdin.fill_buf(),
+            InputSource::File(file) => file.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match self {
+            InputSource::Stdin(stdin) => stdin.consume(amount),
+            InputSource::File(file) => file.consume(amount),
+        }
+    }
+}
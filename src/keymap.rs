@@ -0,0 +1,158 @@
+//! # Key Bindings
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — keymap.rs                                             ║
+//! ║  Map key presses to widget actions, app-wide                  ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Interactive widgets ask a [`Keymap`] what a [`Key`] means instead of
+//! matching keys themselves, so bindings can be changed in one place.
+//! [`Keymap::install`] replaces the process-wide keymap; a
+//! [`LineEditor`](crate::line_editor::LineEditor) can also be given its own.
+//!
+//! The line editor (and the skippable prompts built on it) uses `Accept`,
+//! `Cancel`, `Up` and `Down`. Printable characters are always typed as text
+//! there, so letter bindings such as vi's `j`/`k` only affect widgets without
+//! a text field.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use zfish::keymap::{Action, Keymap};
+//! use zfish::Key;
+//!
+//! // Ctrl+G gives up on a question, like in Emacs
+//! Keymap::install(Keymap::default().bind(Key::Ctrl('g'), Action::Cancel));
+//! ```
+
+use crate::term::Key;
+use std::sync::RwLock;
+
+/// `None` until [`Keymap::install`] is called, meaning [`Keymap::default`]
+static KEYMAP: RwLock<Option<Keymap>> = RwLock::new(None);
+
+/// Something a widget can do in response to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Action {
+    /// Submit the current answer
+    Accept,
+    /// Abandon the question (where skipping is allowed)
+    Cancel,
+    /// Move up, or to the previous history entry
+    Up,
+    /// Move down, or to the next history entry
+    Down,
+    /// Flip the highlighted choice on or off
+    Toggle,
+}
+
+/// Key bindings for interactive widgets
+///
+/// The default binds Enter to accept, Esc to cancel, Up/Ctrl+P and
+/// Down/Ctrl+N to move, and Space to toggle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+            .bind(Key::Enter, Action::Accept)
+            .bind(Key::Esc, Action::Cancel)
+            .bind(Key::Up, Action::Up)
+            .bind(Key::Ctrl('p'), Action::Up)
+            .bind(Key::Down, Action::Down)
+            .bind(Key::Ctrl('n'), Action::Down)
+            .bind(Key::Char(' '), Action::Toggle)
+    }
+}
+
+impl Keymap {
+    /// Creates a keymap with no bindings
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// The default bindings plus vi-style `j`/`k` movement and `q` to cancel
+    pub fn vi() -> Self {
+        Self::default()
+            .bind(Key::Char('k'), Action::Up)
+            .bind(Key::Char('j'), Action::Down)
+            .bind(Key::Char('q'), Action::Cancel)
+    }
+
+    /// Binds `key` to `action`, replacing any earlier binding of that key
+    pub fn bind(mut self, key: Key, action: Action) -> Self {
+        self = self.unbind(key);
+        self.bindings.push((key, action));
+        self
+    }
+
+    /// Removes the binding of `key`, if any
+    pub fn unbind(mut self, key: Key) -> Self {
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self
+    }
+
+    /// Looks up what `key` does
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+
+    /// Every key bound to `action`, in binding order
+    pub fn keys_for(&self, action: Action) -> Vec<Key> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    /// Makes `keymap` the process-wide keymap used by all widgets
+    pub fn install(keymap: Keymap) {
+        *KEYMAP.write().unwrap_or_else(|e| e.into_inner()) = Some(keymap);
+    }
+
+    /// The process-wide keymap
+    pub fn current() -> Keymap {
+        KEYMAP
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_replaces_and_unbind_removes() {
+        let keymap = Keymap::default()
+            .bind(Key::Esc, Action::Accept)
+            .unbind(Key::Char(' '));
+        assert_eq!(keymap.action(Key::Esc), Some(Action::Accept));
+        assert_eq!(keymap.action(Key::Char(' ')), None);
+        assert_eq!(keymap.keys_for(Action::Accept), [Key::Enter, Key::Esc]);
+        assert_eq!(keymap.keys_for(Action::Cancel), []);
+
+        let vi = Keymap::vi();
+        assert_eq!(vi.action(Key::Char('j')), Some(Action::Down));
+        assert_eq!(
+            vi.keys_for(Action::Up),
+            [Key::Up, Key::Ctrl('p'), Key::Char('k')]
+        );
+    }
+}
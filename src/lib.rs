@@ -25,6 +25,24 @@
 //! - **Interactive Prompts**: Text input, password entry, confirmation dialogs
 //! - **Logging**: Leveled output with timestamp support
 //! - **Terminal Control**: Size detection, cursor movement, screen clearing
+//! - **Value Parsers**: Paths, URLs, durations (`1h30m`), byte sizes (`512MiB`) and ranges, stored typed in the matches
+//! - **Humanize**: Format durations and sizes back for humans (`1m 30s`, `512 MiB`)
+//! - **Line Editor**: Raw-mode input with editing keys and Tab path completion
+//! - **Stdin Dash**: `-` means stdin for `<FILE>` args, opened via `io::InputSource`
+//! - **Verbosity**: `-q`/`-v` flags that quiet logs and progress bars app-wide
+//! - **Platforms**: Non-panicking fallbacks everywhere, reported by `Platform::capabilities()`
+//! - **`cli!` Macro**: Derive-like declarations that expand to the builders, no proc-macros
+//! - **Fuzzy Matching**: Levenshtein and Jaro-Winkler in `text::similarity`
+//! - **Standard Directories**: Per-app config, data and cache dirs in `paths` (XDG, AppData)
+//! - **Config Files**: `App::config_file` reads argument defaults from an INI/TOML-subset file
+//! - **Dotenv**: `env::read_dotenv` and `App::dotenv` read `.env` files for `Arg::env` fallbacks
+//! - **Safe Writes**: `fsutil::write_checked` asks before overwriting, or honours `--yes`
+//! - **Shell Completions**: Bash, zsh, fish and PowerShell scripts generated from the command tree
+//! - **Markdown Reference**: `Command::to_markdown` renders a CLI docs page from the builder
+//! - **Plain Mode**: `ZFISH_PLAIN=1` forces ASCII, no color and no animation in every module
+//! - **Run Summary**: `App::run` with a "Finished with 2 warnings in 3.2s" footer
+//! - **Task Runner**: Checklists of steps with spinners, parallel jobs and logs kept for failures
+//! - **Checked Examples**: Help examples parsed in tests by `testing::verify_examples`
 //!
 //! ## Quick Start
 //!
@@ -39,10 +57,18 @@
 //!
 //! ## Feature Flags
 //!
-//! - `colour` (default): Enable ANSI color support
-//! - `raw`: Raw terminal mode for interactive apps
-//! - `progress`: Progress bars and spinners (requires `raw`)
-//! - `interactive`: Interactive prompts (requires `raw`)
+//! All features are on by default (`full`). Parse-only users can build with
+//! `default-features = false` to drop the terminal I/O code:
+//!
+//! - `colour`: ANSI color output (without it, styled text prints plain)
+//! - `raw`: Raw terminal mode and key input (`Terminal::raw_mode`, `Key`)
+//! - `progress`: The `progress` module (requires `raw`)
+//! - `interactive`: The `prompt`, `line_editor`, `keymap` and `completion` modules and
+//!   `Command::interactive_fallback` prompting (requires `raw`)
+//! - `full`: All of the above
+//!
+//! Using a gated item without its feature fails to compile, and rustc
+//! points at the feature to enable.
 //!
 //! ## Project Status
 //!
@@ -80,26 +106,65 @@
 
 pub mod args;
 pub mod command;
+#[cfg(feature = "interactive")]
+pub mod completion;
+pub mod config;
+pub mod env;
+pub mod fsutil;
+pub mod humanize;
+pub mod io;
+#[cfg(feature = "interactive")]
+pub mod keymap;
+#[cfg(feature = "interactive")]
+pub mod line_editor;
 pub mod log;
+mod macros;
+pub mod paths;
+pub mod plain;
+pub mod platform;
+#[cfg(feature = "progress")]
 pub mod progress;
+#[cfg(feature = "interactive")]
 pub mod prompt;
+pub mod shell;
 pub mod style;
+pub mod summary;
 pub mod table;
+#[cfg(feature = "progress")]
+pub mod tasks;
 pub mod term;
+pub mod testing;
+pub mod text;
 pub mod unicode;
+mod unicode_tables;
+pub mod value_parser;
+pub mod verbosity;
 
 // Platform-specific code (unsafe allowed here)
 pub(crate) mod os;
 
 // Re-export main components for easier access
 pub use args::Args;
-pub use command::{App, Arg, ArgMatches, Command, CommandError, CommandResult};
+pub use command::{
+    App, Arg, ArgErrors, ArgMatches, Command, CommandError, CommandResult, ErrorKind,
+    FromArgMatches, HelpStyles, Setting, Settings, ValueHint, ValueSource,
+};
+#[cfg(feature = "interactive")]
+pub use keymap::Keymap;
 pub use log::{Level, Logger};
-pub use progress::{ProgressBar, ProgressStyle};
-pub use prompt::Prompt;
-pub use style::{Color, Style};
-pub use table::{Alignment, BoxStyle, Table, draw_box, draw_separator};
-pub use term::Terminal;
+pub use platform::{Capabilities, Platform};
+#[cfg(feature = "progress")]
+pub use progress::{DrawTarget, ProgressBar, ProgressStyle, TimedProgress, TimedProgressHandle};
+#[cfg(feature = "interactive")]
+pub use prompt::{Prompt, PromptTheme};
+pub use style::{Color, Style, TextStyle};
+pub use table::{
+    Alignment, BoxStyle, LiveTable, OutputFormat, Table, TableBuilder, TableError, TableLayout,
+    TextBox, draw_box, draw_separator, join_horizontal, join_vertical,
+};
+#[cfg(feature = "raw")]
+pub use term::Key;
+pub use term::{Frame, Terminal};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
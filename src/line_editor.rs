@@ -0,0 +1,737 @@
+//! # Line Editor
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — line_editor.rs                                        ║
+//! ║  Raw-mode line input with cursor movement and Tab completion  ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`LineEditor`] reads one line in raw mode with the usual shell editing
+//! keys (arrows, Home/End, Ctrl+A/E/U/K/W) and Tab completion from any
+//! [`Completer`]. Ctrl+Z suspends the program as usual on Unix, with the
+//! terminal restored while it is stopped and the line redrawn after `fg`.
+//! When stdin is not a terminal, or the platform has no raw
+//! mode (WASI), it falls back to a plain buffered read, so the same code works
+//! in pipes, tests and under wasmtime.
+//!
+//! Give the editor a [`History`] for shell-style recall: Up/Down step through
+//! earlier lines and Ctrl+R searches them. A history loaded from a file is
+//! saved back after every accepted line, so a REPL keeps it across runs.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use zfish::completion::PathCompleter;
+//! use zfish::line_editor::LineEditor;
+//!
+//! let mut editor = LineEditor::new("config file: ").completer(PathCompleter::new());
+//! if let Some(path) = editor.read_line().unwrap() {
+//!     println!("Using {}", path);
+//! }
+//! ```
+//!
+//! ```no_run
+//! use zfish::line_editor::{History, LineEditor};
+//!
+//! let path = zfish::paths::data_dir("myapp").unwrap().join("history");
+//! let history = History::load(path).unwrap().max_len(500);
+//! let mut editor = LineEditor::new("> ").history(history);
+//! while let Some(line) = editor.read_line().unwrap() {
+//!     println!("you said {}", line);
+//! }
+//! ```
+
+use crate::completion::{Completer, common_prefix};
+use crate::keymap::{Action, Keymap};
+use crate::term::{Key, Terminal};
+use crate::unicode::display_width;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// Default number of lines a [`History`] keeps
+const DEFAULT_HISTORY_LEN: usize = 1000;
+
+/// Previously entered lines, oldest first, optionally backed by a file
+///
+/// Blank lines are not recorded and entering a line again moves it to the
+/// end instead of storing a duplicate.
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: Vec<String>,
+    max_len: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    /// Creates an in-memory history
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            max_len: DEFAULT_HISTORY_LEN,
+            path: None,
+        }
+    }
+
+    /// Loads a history file (one line per entry), remembering the path for
+    /// [`save`](Self::save)
+    ///
+    /// A missing file gives an empty history; it is created on the first save.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut history = Self {
+            path: Some(path.to_path_buf()),
+            ..Self::new()
+        };
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.lines().for_each(|line| history.push(line)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        Ok(history)
+    }
+
+    /// Sets how many lines are kept; the oldest are dropped first
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self.trim();
+        self
+    }
+
+    /// Records a line as the newest entry
+    pub fn push(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if line.trim().is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| *entry != line);
+        self.entries.push(line);
+        self.trim();
+    }
+
+    /// The recorded lines, oldest first
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// The file this history was loaded from, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Writes the history back to the file it was loaded from
+    ///
+    /// Does nothing for an in-memory history.
+    pub fn save(&self) -> io::Result<()> {
+        match &self.path {
+            Some(path) => self.save_to(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the history to `path`, one line per entry
+    ///
+    /// Missing parent directories are created.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = self.entries.join("\n");
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        fs::write(path, contents)
+    }
+
+    fn trim(&mut self) {
+        let excess = self.entries.len().saturating_sub(self.max_len);
+        self.entries.drain(..excess);
+    }
+}
+
+/// Reads a line of input with editing keys and Tab completion
+pub struct LineEditor {
+    prompt: String,
+    completer: Option<Box<dyn Completer>>,
+    cancel_on_esc: bool,
+    history: Option<History>,
+    keymap: Option<Keymap>,
+}
+
+impl std::fmt::Debug for LineEditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineEditor")
+            .field("prompt", &self.prompt)
+            .field("completer", &self.completer.is_some())
+            .field("cancel_on_esc", &self.cancel_on_esc)
+            .field("history", &self.history)
+            .field("keymap", &self.keymap)
+            .finish()
+    }
+}
+
+impl LineEditor {
+    /// Creates an editor that shows `prompt` before the input
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            completer: None,
+            cancel_on_esc: false,
+            history: None,
+            keymap: None,
+        }
+    }
+
+    /// Sets the source of Tab completions
+    pub fn completer(mut self, completer: impl Completer + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Makes Esc (or whatever key the keymap binds to [`Action::Cancel`])
+    /// abandon the line, so that [`read_line`](Self::read_line) returns `Ok(None)`
+    pub fn cancel_on_esc(mut self, enabled: bool) -> Self {
+        self.cancel_on_esc = enabled;
+        self
+    }
+
+    /// Enables Up/Down recall and Ctrl+R search over `history`
+    ///
+    /// Accepted lines are added to it, and saved when it was
+    /// [loaded](History::load) from a file.
+    pub fn history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Uses `keymap` instead of the process-wide [`Keymap::current`]
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    /// Reads one line; `Ok(None)` means end of input (Ctrl+D on an empty line)
+    /// or, with [`cancel_on_esc`](Self::cancel_on_esc), that Esc was pressed
+    ///
+    /// Ctrl+C returns an [`io::ErrorKind::Interrupted`] error.
+    pub fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = self.read_line_inner()?;
+        if let (Some(line), Some(history)) = (&line, &mut self.history) {
+            history.push(line.as_str());
+            // An unwritable history file must not get in the way of input
+            let _ = history.save();
+        }
+        Ok(line)
+    }
+
+    fn read_line_inner(&mut self) -> io::Result<Option<String>> {
+        if !io::stdin().is_terminal() || crate::plain::is_plain() {
+            return self.read_line_cooked();
+        }
+
+        // Targets without raw mode (e.g. wasm32-wasi) still get plain line input
+        let mut raw = match Terminal::raw_mode() {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::Unsupported => {
+                return self.read_line_cooked();
+            }
+            Err(err) => return Err(err),
+        };
+        let mut state = EditState {
+            cancel_on_esc: self.cancel_on_esc,
+            history: self
+                .history
+                .as_ref()
+                .map(|history| history.entries.clone())
+                .unwrap_or_default(),
+            keymap: self.keymap.clone().unwrap_or_else(Keymap::current),
+            ..EditState::default()
+        };
+        let mut stdout = io::stdout();
+        self.render(&mut stdout, &state)?;
+
+        loop {
+            let key = Terminal::read_key()?;
+            match state.apply(key, self.completer.as_deref()) {
+                Outcome::Continue => {}
+                Outcome::List(candidates) => {
+                    write!(stdout, "\r\n{}\r\n", candidates.join("  "))?;
+                }
+                Outcome::Submit => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(Some(state.buffer));
+                }
+                Outcome::Eof | Outcome::Cancel => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(None);
+                }
+                Outcome::Interrupt => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+                }
+                Outcome::Suspend => {
+                    // Leave the shell prompt on a fresh line, and redraw below it on `fg`
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    raw.suspend()?;
+                }
+            }
+            self.render(&mut stdout, &state)?;
+        }
+    }
+
+    /// Plain prompt-and-read_line for non-interactive stdin
+    fn read_line_cooked(&self) -> io::Result<Option<String>> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{}", self.prompt)?;
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+    }
+
+    /// Redraw the prompt line and place the cursor
+    fn render(&self, out: &mut impl Write, state: &EditState) -> io::Result<()> {
+        match &state.search {
+            Some(search) => {
+                let label = if search.index.is_none() && !search.query.is_empty() {
+                    "failed reverse-i-search"
+                } else {
+                    "reverse-i-search"
+                };
+                write!(
+                    out,
+                    "\r\x1b[K({})`{}': {}",
+                    label, search.query, state.buffer
+                )?;
+            }
+            None => write!(out, "\r\x1b[K{}{}", self.prompt, state.buffer)?,
+        }
+        let tail = display_width(&state.buffer[state.cursor..]);
+        if tail > 0 {
+            write!(out, "\x1b[{}D", tail)?;
+        }
+        out.flush()
+    }
+}
+
+/// What the editor should do after a key press
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Continue,
+    List(Vec<String>),
+    Submit,
+    Eof,
+    Cancel,
+    Interrupt,
+    Suspend,
+}
+
+/// The text being edited and the cursor's byte offset into it
+#[derive(Debug, Default)]
+struct EditState {
+    buffer: String,
+    cursor: usize,
+    cancel_on_esc: bool,
+    keymap: Keymap,
+    history: Vec<String>,
+    /// History entry shown by Up/Down, and the unfinished line it replaced
+    recalled: Option<usize>,
+    draft: String,
+    search: Option<Search>,
+}
+
+/// An active Ctrl+R reverse search
+#[derive(Debug)]
+struct Search {
+    query: String,
+    /// History entry currently matched (`None` when nothing matches)
+    index: Option<usize>,
+    /// The line before the search started, restored on Esc
+    original: String,
+}
+
+impl EditState {
+    fn apply(&mut self, key: Key, completer: Option<&dyn Completer>) -> Outcome {
+        if self.search.is_some() {
+            match self.apply_search(key) {
+                Some(outcome) => return outcome,
+                // Any other key accepts the match and is handled normally
+                None => self.search = None,
+            }
+        }
+
+        // Printable characters are always text, whatever the keymap says
+        let action = match key {
+            Key::Char(_) => None,
+            _ => self.keymap.action(key),
+        };
+        match action {
+            Some(Action::Accept) => return Outcome::Submit,
+            Some(Action::Cancel) if self.cancel_on_esc => return Outcome::Cancel,
+            Some(Action::Up) => {
+                self.recall_older();
+                return Outcome::Continue;
+            }
+            Some(Action::Down) => {
+                self.recall_newer();
+                return Outcome::Continue;
+            }
+            _ => {}
+        }
+
+        match key {
+            Key::Ctrl('c') => return Outcome::Interrupt,
+            Key::Ctrl('z') if crate::os::JOB_CONTROL => return Outcome::Suspend,
+            Key::Ctrl('d') if self.buffer.is_empty() => return Outcome::Eof,
+            Key::Ctrl('d') | Key::Delete => {
+                if let Some(c) = self.buffer[self.cursor..].chars().next() {
+                    self.buffer
+                        .replace_range(self.cursor..self.cursor + c.len_utf8(), "");
+                }
+            }
+            Key::Char(c) => {
+                self.buffer.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            Key::Backspace | Key::Ctrl('h') => {
+                if let Some(c) = self.buffer[..self.cursor].chars().next_back() {
+                    self.cursor -= c.len_utf8();
+                    self.buffer
+                        .replace_range(self.cursor..self.cursor + c.len_utf8(), "");
+                }
+            }
+            Key::Left | Key::Ctrl('b') => {
+                if let Some(c) = self.buffer[..self.cursor].chars().next_back() {
+                    self.cursor -= c.len_utf8();
+                }
+            }
+            Key::Right | Key::Ctrl('f') => {
+                if let Some(c) = self.buffer[self.cursor..].chars().next() {
+                    self.cursor += c.len_utf8();
+                }
+            }
+            Key::Home | Key::Ctrl('a') => self.cursor = 0,
+            Key::End | Key::Ctrl('e') => self.cursor = self.buffer.len(),
+            Key::Ctrl('u') => {
+                self.buffer.replace_range(..self.cursor, "");
+                self.cursor = 0;
+            }
+            Key::Ctrl('k') => self.buffer.truncate(self.cursor),
+            Key::Ctrl('r') => {
+                self.search = Some(Search {
+                    query: String::new(),
+                    index: None,
+                    original: self.buffer.clone(),
+                });
+            }
+            Key::Ctrl('w') => {
+                let before = self.buffer[..self.cursor].trim_end();
+                let start = before.rfind(' ').map_or(0, |idx| idx + 1);
+                self.buffer.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            Key::Tab => {
+                if let Some(completer) = completer {
+                    return self.complete(completer);
+                }
+            }
+            _ => {}
+        }
+        Outcome::Continue
+    }
+
+    /// Step back to the previous history entry, saving the unfinished line
+    fn recall_older(&mut self) {
+        let index = match self.recalled {
+            _ if self.history.is_empty() => return,
+            None => {
+                self.draft = self.buffer.clone();
+                self.history.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.recall(Some(index));
+    }
+
+    /// Step forward through history, ending at the unfinished line
+    fn recall_newer(&mut self) {
+        match self.recalled {
+            Some(index) if index + 1 < self.history.len() => self.recall(Some(index + 1)),
+            Some(_) => self.recall(None),
+            None => {}
+        }
+    }
+
+    /// Show history entry `index`, or the saved draft for `None`
+    fn recall(&mut self, index: Option<usize>) {
+        self.buffer = match index {
+            Some(index) => self.history[index].clone(),
+            None => std::mem::take(&mut self.draft),
+        };
+        self.cursor = self.buffer.len();
+        self.recalled = index;
+    }
+
+    /// Handle a key during reverse search; `None` means the search is over
+    /// and the key should be applied to the accepted line
+    fn apply_search(&mut self, key: Key) -> Option<Outcome> {
+        let search = self.search.as_mut()?;
+        let action = match key {
+            Key::Char(_) => None,
+            _ => self.keymap.action(key),
+        };
+        let newest = self.history.len();
+        let from = match key {
+            Key::Char(c) => {
+                search.query.push(c);
+                search.index.map_or(newest, |index| index + 1)
+            }
+            Key::Backspace | Key::Ctrl('h') => {
+                search.query.pop();
+                newest
+            }
+            Key::Ctrl('r') => search.index.unwrap_or(newest),
+            _ if action == Some(Action::Cancel) || key == Key::Ctrl('g') => {
+                self.buffer = std::mem::take(&mut search.original);
+                self.cursor = self.buffer.len();
+                self.search = None;
+                return Some(Outcome::Continue);
+            }
+            _ if action == Some(Action::Accept) => {
+                self.search = None;
+                return Some(Outcome::Submit);
+            }
+            Key::Ctrl('c') => return Some(Outcome::Interrupt),
+            _ => return None,
+        };
+
+        // Search backwards from just before `from`, newest first
+        let found = if search.query.is_empty() {
+            None
+        } else {
+            self.history[..from]
+                .iter()
+                .rposition(|entry| entry.contains(&search.query))
+        };
+        search.index = found.or(if key == Key::Ctrl('r') {
+            search.index
+        } else {
+            None
+        });
+        if let Some(index) = found {
+            self.buffer = self.history[index].clone();
+            self.cursor = self.buffer.find(&search.query).unwrap_or(0);
+        }
+        Some(Outcome::Continue)
+    }
+
+    /// Complete the word before the cursor, or list candidates if ambiguous
+    fn complete(&mut self, completer: &dyn Completer) -> Outcome {
+        let start = self.buffer[..self.cursor]
+            .rfind(char::is_whitespace)
+            .map_or(0, |idx| idx + 1);
+        let word = &self.buffer[start..self.cursor];
+        let candidates = completer.complete(word);
+
+        let replacement = match candidates.len() {
+            0 => return Outcome::Continue,
+            1 => candidates[0].clone(),
+            _ => {
+                let prefix = common_prefix(&candidates);
+                if prefix.len() <= word.len() {
+                    return Outcome::List(candidates);
+                }
+                prefix.to_string()
+            }
+        };
+
+        self.buffer.replace_range(start..self.cursor, &replacement);
+        self.cursor = start + replacement.len();
+        Outcome::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_keys(state: &mut EditState, text: &str) {
+        for c in text.chars() {
+            state.apply(Key::Char(c), None);
+        }
+    }
+
+    #[test]
+    fn test_editing_keys() {
+        let mut state = EditState::default();
+        type_keys(&mut state, "hllo");
+        state.apply(Key::Home, None);
+        state.apply(Key::Right, None);
+        state.apply(Key::Char('e'), None);
+        assert_eq!(state.buffer, "hello");
+        assert_eq!(state.cursor, 2);
+
+        state.apply(Key::End, None);
+        state.apply(Key::Backspace, None);
+        assert_eq!(state.buffer, "hell");
+
+        state.apply(Key::Home, None);
+        state.apply(Key::Delete, None);
+        assert_eq!(state.buffer, "ell");
+        assert_eq!(state.apply(Key::Enter, None), Outcome::Submit);
+    }
+
+    #[test]
+    fn test_kill_keys_and_unicode() {
+        let mut state = EditState::default();
+        type_keys(&mut state, "copy café now");
+        state.apply(Key::Ctrl('w'), None);
+        assert_eq!(state.buffer, "copy café ");
+        state.apply(Key::Left, None);
+        state.apply(Key::Left, None);
+        state.apply(Key::Ctrl('k'), None);
+        assert_eq!(state.buffer, "copy caf");
+        state.apply(Key::Ctrl('u'), None);
+        assert_eq!(state.buffer, "");
+        assert_eq!(state.apply(Key::Ctrl('d'), None), Outcome::Eof);
+        assert_eq!(state.apply(Key::Ctrl('c'), None), Outcome::Interrupt);
+        let suspend = if crate::os::JOB_CONTROL {
+            Outcome::Suspend
+        } else {
+            Outcome::Continue
+        };
+        assert_eq!(state.apply(Key::Ctrl('z'), None), suspend);
+    }
+
+    #[test]
+    fn test_esc_cancels_only_when_enabled() {
+        let mut state = EditState::default();
+        type_keys(&mut state, "abc");
+        assert_eq!(state.apply(Key::Esc, None), Outcome::Continue);
+        assert_eq!(state.buffer, "abc");
+
+        state.cancel_on_esc = true;
+        assert_eq!(state.apply(Key::Esc, None), Outcome::Cancel);
+    }
+
+    fn with_history(entries: &[&str]) -> EditState {
+        EditState {
+            history: entries.iter().map(|e| e.to_string()).collect(),
+            ..EditState::default()
+        }
+    }
+
+    #[test]
+    fn test_history_recall_keeps_draft() {
+        let mut state = with_history(&["cargo build", "cargo test"]);
+        type_keys(&mut state, "git");
+        state.apply(Key::Up, None);
+        assert_eq!(state.buffer, "cargo test");
+        state.apply(Key::Up, None);
+        state.apply(Key::Up, None);
+        assert_eq!(state.buffer, "cargo build");
+        assert_eq!(state.cursor, state.buffer.len());
+
+        state.apply(Key::Down, None);
+        assert_eq!(state.buffer, "cargo test");
+        state.apply(Key::Down, None);
+        assert_eq!(state.buffer, "git");
+        assert_eq!(state.apply(Key::Down, None), Outcome::Continue);
+        assert_eq!(state.buffer, "git");
+    }
+
+    #[test]
+    fn test_reverse_search() {
+        let mut state = with_history(&["make docs", "cargo build", "make test", "ls"]);
+        type_keys(&mut state, "draft");
+        state.apply(Key::Ctrl('r'), None);
+        type_keys(&mut state, "make");
+        assert_eq!(state.buffer, "make test");
+        state.apply(Key::Ctrl('r'), None);
+        assert_eq!(state.buffer, "make docs");
+
+        type_keys(&mut state, "x");
+        assert_eq!(state.search.as_ref().unwrap().index, None);
+        state.apply(Key::Backspace, None);
+        assert_eq!(state.buffer, "make test");
+
+        // Esc restores the line from before the search
+        state.apply(Key::Esc, None);
+        assert_eq!(state.buffer, "draft");
+        assert!(state.search.is_none());
+
+        // Other keys accept the match and then apply
+        state.apply(Key::Ctrl('r'), None);
+        type_keys(&mut state, "carg");
+        state.apply(Key::End, None);
+        type_keys(&mut state, "!");
+        assert_eq!(state.buffer, "cargo build!");
+        state.apply(Key::Ctrl('r'), None);
+        type_keys(&mut state, "ls");
+        assert_eq!(state.apply(Key::Enter, None), Outcome::Submit);
+        assert_eq!(state.buffer, "ls");
+    }
+
+    #[test]
+    fn test_custom_keymap() {
+        let mut state = EditState {
+            keymap: Keymap::vi()
+                .unbind(Key::Enter)
+                .bind(Key::Ctrl('j'), Action::Accept)
+                .bind(Key::PageUp, Action::Up),
+            ..with_history(&["older"])
+        };
+        // Letters stay text even when bound
+        type_keys(&mut state, "jk");
+        assert_eq!(state.buffer, "jk");
+        assert_eq!(state.apply(Key::Enter, None), Outcome::Continue);
+
+        state.apply(Key::PageUp, None);
+        assert_eq!(state.buffer, "older");
+        assert_eq!(state.apply(Key::Ctrl('j'), None), Outcome::Submit);
+    }
+
+    #[test]
+    fn test_tab_completion() {
+        let completer = |word: &str| -> Vec<String> {
+            ["src/", "src/lib.rs", "Cargo.toml", "Cargo.lock"]
+                .iter()
+                .filter(|c| c.starts_with(word) && **c != word)
+                .map(|c| c.to_string())
+                .collect()
+        };
+
+        let mut state = EditState::default();
+        type_keys(&mut state, "open Car");
+        assert_eq!(state.apply(Key::Tab, Some(&completer)), Outcome::Continue);
+        assert_eq!(state.buffer, "open Cargo.");
+
+        let listed = state.apply(Key::Tab, Some(&completer));
+        assert_eq!(
+            listed,
+            Outcome::List(vec!["Cargo.toml".to_string(), "Cargo.lock".to_string()])
+        );
+
+        type_keys(&mut state, "t");
+        state.apply(Key::Tab, Some(&completer));
+        assert_eq!(state.buffer, "open Cargo.toml");
+        assert_eq!(state.cursor, state.buffer.len());
+    }
+}
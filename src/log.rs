@@ -1,8 +1,84 @@
 //! Logging utilities for CLI applications.
+//!
+//! Pass a [`Logger`] around, or install one app-wide with [`init`] and log
+//! from anywhere through [`logger`] or the [`error`]/[`warn`]/[`info`]/[`debug`]
+//! shortcuts. Loggers are `Send + Sync`, so the global one can be used from
+//! any thread.
 
 use crate::style::Color;
+use std::fmt;
 use std::io::{self, Write};
-use std::time::{SystemTime, UNIX_EPOCH}; // Removed unused Style import
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH}; // Removed unused Style import
+
+/// The app-wide logger, see [`init`]
+static GLOBAL: OnceLock<Logger> = OnceLock::new();
+
+/// Warnings and errors logged by any logger, shown or not
+static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+static ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+/// The first [`MAX_ERROR_MESSAGES`] error messages, for [`error_messages`]
+static ERROR_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// How many error messages [`error_messages`] keeps
+pub const MAX_ERROR_MESSAGES: usize = 10;
+
+/// Install `logger` as the app-wide logger returned by [`logger`].
+///
+/// Only the first call wins: returns `false` (and drops `logger`) if a logger
+/// was already installed or the global logger was used before `init`. Being
+/// static, the global logger is never dropped, so pending "repeated" and
+/// "suppressed" notices are only printed when the next message arrives.
+pub fn init(logger: Logger) -> bool {
+    GLOBAL.set(logger).is_ok()
+}
+
+/// The app-wide logger; a default [`Logger`] until [`init`] is called.
+pub fn logger() -> &'static Logger {
+    GLOBAL.get_or_init(Logger::new)
+}
+
+/// Log an error message with the app-wide logger.
+pub fn error(message: &str) {
+    logger().error(message);
+}
+
+/// Log a warning message with the app-wide logger.
+pub fn warn(message: &str) {
+    logger().warn(message);
+}
+
+/// Log an info message with the app-wide logger.
+pub fn info(message: &str) {
+    logger().info(message);
+}
+
+/// Log a debug message with the app-wide logger.
+pub fn debug(message: &str) {
+    logger().debug(message);
+}
+
+/// How many warnings have been logged so far, by any logger.
+///
+/// Messages hidden by the level filter or a rate limit still count.
+pub fn warning_count() -> usize {
+    WARNINGS.load(Ordering::Relaxed)
+}
+
+/// How many errors have been logged so far, by any logger.
+pub fn error_count() -> usize {
+    ERRORS.load(Ordering::Relaxed)
+}
+
+/// The first errors logged, oldest first (at most [`MAX_ERROR_MESSAGES`]).
+pub fn error_messages() -> Vec<String> {
+    ERROR_MESSAGES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
 
 /// Log levels for different types of messages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -17,10 +93,55 @@ pub enum Level {
     Debug,
 }
 
+impl Level {
+    /// Map a `-q`/`-v` verbosity count to a level.
+    ///
+    /// Negative (quiet) keeps only errors, `0` is the default `Info`, and any
+    /// positive count enables `Debug`.
+    pub fn from_verbosity(verbosity: i8) -> Self {
+        match verbosity {
+            i8::MIN..=-1 => Level::Error,
+            0 => Level::Info,
+            1..=i8::MAX => Level::Debug,
+        }
+    }
+}
+
 /// A simple logger for CLI applications.
 #[derive(Debug)]
 pub struct Logger {
-    level: Level,
+    level: Option<Level>,
+    dry_run: bool,
+    /// Scope names from outermost to innermost, see [`scoped`](Self::scoped)
+    scopes: Vec<String>,
+    limits: Mutex<RateLimit>,
+    /// Destination other than stderr, shared with scoped children
+    writer: Option<SharedWriter>,
+    highlights: Vec<HighlightRule>,
+}
+
+/// A fragment to colour in messages, see [`Logger::highlight_rule`]
+#[derive(Debug, Clone)]
+struct HighlightRule {
+    pattern: String,
+    color: Color,
+}
+
+impl HighlightRule {
+    /// `*` and `?` make the pattern a glob matched against whole words
+    fn is_glob(&self) -> bool {
+        self.pattern.contains(['*', '?'])
+    }
+}
+
+/// A writer behind a lock, so lines from different threads never interleave
+#[derive(Clone)]
+struct SharedWriter(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl fmt::Debug for SharedWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedWriter")
+    }
 }
 
 impl Default for Logger {
@@ -31,16 +152,107 @@ impl Default for Logger {
 
 impl Logger {
     /// Create a new logger with the default level (Info).
+    ///
+    /// Until [`level`](Self::level) is called, the logger follows the shared
+    /// [`verbosity`](crate::verbosity) setting when one has been set.
     pub fn new() -> Self {
-        Logger { level: Level::Info }
+        Logger {
+            level: None,
+            dry_run: false,
+            scopes: Vec::new(),
+            limits: Mutex::new(RateLimit::default()),
+            writer: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    /// Create a logger whose level follows a `-q`/`-v` verbosity count.
+    ///
+    /// Typically `Logger::from_verbosity(matches.verbosity())`.
+    pub fn from_verbosity(verbosity: i8) -> Self {
+        Self::new().level(Level::from_verbosity(verbosity))
     }
 
     /// Set the maximum log level.
     pub fn level(mut self, level: Level) -> Self {
-        self.level = level;
+        self.level = Some(level);
+        self
+    }
+
+    /// Write lines to `writer` instead of stderr.
+    ///
+    /// Each line is written and flushed while holding a lock, so loggers
+    /// writing to it from several threads never mix their lines.
+    pub fn writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.writer = Some(SharedWriter(Arc::new(Mutex::new(Box::new(writer)))));
+        self
+    }
+
+    /// Paint the parts of messages that match `pattern` in `color`.
+    ///
+    /// A plain pattern matches wherever it occurs (`"error:"`). A pattern with
+    /// `*` (any run of characters) or `?` (one character) is a glob matched
+    /// against whole whitespace-separated words, e.g. `"*.rs"` for file names
+    /// or `"*ms"` for durations. When rules overlap, the one added first wins.
+    pub fn highlight_rule(mut self, pattern: impl Into<String>, color: Color) -> Self {
+        let pattern = pattern.into();
+        if !pattern.is_empty() {
+            self.highlights.push(HighlightRule { pattern, color });
+        }
+        self
+    }
+
+    /// Tag every message with `[dry-run]`.
+    ///
+    /// Typically wired to `matches.is_dry_run()` so that output from a dry run
+    /// can't be mistaken for real changes.
+    pub fn dry_run_prefix(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Collapse identical consecutive messages arriving within `window`.
+    ///
+    /// Instead of repeating them, the logger prints
+    /// "last message repeated N times" once a different message arrives, the
+    /// window runs out, or the logger is dropped.
+    pub fn dedup_window(self, window: Duration) -> Self {
+        self.lock_limits().dedup_window = Some(window);
+        self
+    }
+
+    /// Print at most `limit` messages per second, dropping the rest.
+    ///
+    /// The number of dropped messages is reported when the next second starts.
+    /// Protects the terminal from runaway logging loops.
+    pub fn max_per_second(self, limit: u32) -> Self {
+        self.lock_limits().max_per_second = Some(limit);
         self
     }
 
+    /// Create a child logger that prefixes its lines with `name`.
+    ///
+    /// Scopes nest, so `logger.scoped("deploy").scoped("build")` prints
+    /// `deploy ▸ build ▸ compiling`. The child keeps the level, dry-run tag and
+    /// limits of its parent.
+    pub fn scoped(&self, name: impl Into<String>) -> Logger {
+        let mut scopes = self.scopes.clone();
+        scopes.push(name.into());
+        let limits = self.lock_limits();
+        Logger {
+            level: self.level,
+            dry_run: self.dry_run,
+            scopes,
+            limits: Mutex::new(RateLimit {
+                dedup_window: limits.dedup_window,
+                max_per_second: limits.max_per_second,
+                ..RateLimit::default()
+            }),
+            writer: self.writer.clone(),
+            highlights: self.highlights.clone(),
+        }
+    }
+
     /// Log an error message.
     pub fn error(&self, message: &str) {
         self.log(Level::Error, message);
@@ -61,12 +273,52 @@ impl Logger {
         self.log(Level::Debug, message);
     }
 
+    /// The explicit level, else the shared verbosity, else Info.
+    fn effective_level(&self) -> Level {
+        self.level
+            .or_else(|| crate::verbosity::get().map(Level::from_verbosity))
+            .unwrap_or(Level::Info)
+    }
+
+    fn lock_limits(&self) -> std::sync::MutexGuard<'_, RateLimit> {
+        self.limits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Log a message with the given level.
     fn log(&self, level: Level, message: &str) {
-        if level > self.level {
+        self.tally(level, message);
+        if level > self.effective_level() {
             return;
         }
 
+        let (notices, admitted) = self.lock_limits().check(level, message, Instant::now());
+        for (notice_level, notice) in notices {
+            self.write(notice_level, &notice);
+        }
+        if admitted {
+            self.write(level, message);
+        }
+    }
+
+    /// Count warnings and errors for [`warning_count`] and [`error_count`].
+    fn tally(&self, level: Level, message: &str) {
+        match level {
+            Level::Warn => {
+                WARNINGS.fetch_add(1, Ordering::Relaxed);
+            }
+            Level::Error => {
+                ERRORS.fetch_add(1, Ordering::Relaxed);
+                let mut messages = ERROR_MESSAGES.lock().unwrap_or_else(|e| e.into_inner());
+                if messages.len() < MAX_ERROR_MESSAGES {
+                    messages.push(scope_prefix(&self.scopes) + message);
+                }
+            }
+            Level::Info | Level::Debug => {}
+        }
+    }
+
+    /// Format and write one line to the writer, or stderr.
+    fn write(&self, level: Level, message: &str) {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
@@ -80,7 +332,25 @@ impl Logger {
             Level::Debug => Color::BrightBlack.paint("DEBUG"),
         };
 
-        let output = format!("[{}] {} {}\n", timestamp, level_str, message);
+        let message = scope_prefix(&self.scopes) + &highlight(message, &self.highlights);
+        let output = if self.dry_run {
+            format!(
+                "[{}] {} {} {}\n",
+                timestamp,
+                level_str,
+                Color::Yellow.paint("[dry-run]"),
+                message
+            )
+        } else {
+            format!("[{}] {} {}\n", timestamp, level_str, message)
+        };
+
+        if let Some(SharedWriter(writer)) = &self.writer {
+            let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writer.write_all(output.as_bytes());
+            let _ = writer.flush();
+            return;
+        }
 
         // Write to stderr
         let stderr = io::stderr();
@@ -89,3 +359,299 @@ impl Logger {
         let _ = handle.flush();
     }
 }
+
+impl Drop for Logger {
+    fn drop(&mut self) {
+        let notices = self.lock_limits().flush();
+        for (level, notice) in notices {
+            self.write(level, &notice);
+        }
+    }
+}
+
+/// `"deploy ▸ build ▸ "` for the scopes `["deploy", "build"]` (`>` in
+/// [plain mode](crate::plain))
+fn scope_prefix(scopes: &[String]) -> String {
+    let separator = if crate::plain::is_plain() { '>' } else { '▸' };
+    scopes
+        .iter()
+        .map(|scope| format!("{} {} ", scope, separator))
+        .collect()
+}
+
+/// `message` with the fragments matched by `rules` painted
+fn highlight(message: &str, rules: &[HighlightRule]) -> String {
+    let spans = highlight_spans(message, rules);
+    if spans.is_empty() {
+        return message.to_string();
+    }
+
+    let mut output = String::with_capacity(message.len());
+    let mut end = 0;
+    for (start, stop, color) in spans {
+        output.push_str(&message[end..start]);
+        output.push_str(&color.paint(&message[start..stop]).to_string());
+        end = stop;
+    }
+    output.push_str(&message[end..]);
+    output
+}
+
+/// Byte ranges of `message` to colour, sorted and never overlapping
+fn highlight_spans(message: &str, rules: &[HighlightRule]) -> Vec<(usize, usize, Color)> {
+    let mut spans: Vec<(usize, usize, Color)> = Vec::new();
+    for rule in rules {
+        let matches: Vec<(usize, usize)> = if rule.is_glob() {
+            let pattern: Vec<char> = rule.pattern.chars().collect();
+            words(message)
+                .filter(|&(start, stop)| {
+                    let word: Vec<char> = message[start..stop].chars().collect();
+                    glob_match(&pattern, &word)
+                })
+                .collect()
+        } else {
+            message
+                .match_indices(rule.pattern.as_str())
+                .map(|(start, text)| (start, start + text.len()))
+                .collect()
+        };
+        for (start, stop) in matches {
+            if spans.iter().all(|&(s, e, _)| stop <= s || start >= e) {
+                spans.push((start, stop, rule.color));
+            }
+        }
+    }
+    spans.sort_by_key(|&(start, _, _)| start);
+    spans
+}
+
+/// Byte ranges of the whitespace-separated words in `text`
+fn words(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    text.split_whitespace().map(move |word| {
+        let start = word.as_ptr() as usize - text.as_ptr() as usize;
+        (start, start + word.len())
+    })
+}
+
+/// Match `text` against a glob where `*` is any run and `?` any one character
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Duplicate suppression and throttling state for a [`Logger`].
+#[derive(Debug, Default)]
+struct RateLimit {
+    dedup_window: Option<Duration>,
+    max_per_second: Option<u32>,
+    /// Last printed message and when it was first printed
+    last: Option<(Level, String, Instant)>,
+    repeats: u32,
+    second_start: Option<Instant>,
+    printed_this_second: u32,
+    dropped: u32,
+}
+
+impl RateLimit {
+    /// Decide whether a message is printed; returns notices to print before it.
+    fn check(&mut self, level: Level, message: &str, now: Instant) -> (Vec<(Level, String)>, bool) {
+        let mut notices = Vec::new();
+
+        if let Some(window) = self.dedup_window {
+            if let Some((last_level, last_message, since)) = &self.last
+                && *last_level == level
+                && last_message == message
+                && now.duration_since(*since) < window
+            {
+                self.repeats += 1;
+                return (notices, false);
+            }
+            notices.extend(self.take_repeats());
+        }
+
+        if let Some(limit) = self.max_per_second {
+            let new_second = self
+                .second_start
+                .is_none_or(|start| now.duration_since(start) >= Duration::from_secs(1));
+            if new_second {
+                notices.extend(self.take_dropped());
+                self.second_start = Some(now);
+                self.printed_this_second = 0;
+            }
+            if self.printed_this_second >= limit {
+                self.dropped += 1;
+                return (notices, false);
+            }
+            self.printed_this_second += 1;
+        }
+
+        if self.dedup_window.is_some() {
+            self.last = Some((level, message.to_string(), now));
+        }
+        (notices, true)
+    }
+
+    /// Notices still pending (e.g. when the logger goes away).
+    fn flush(&mut self) -> Vec<(Level, String)> {
+        self.take_repeats()
+            .into_iter()
+            .chain(self.take_dropped())
+            .collect()
+    }
+
+    fn take_repeats(&mut self) -> Option<(Level, String)> {
+        let repeats = std::mem::take(&mut self.repeats);
+        let (level, _, _) = self.last.as_ref()?;
+        (repeats > 0).then(|| {
+            let times = if repeats == 1 { "time" } else { "times" };
+            (
+                *level,
+                format!("last message repeated {} {}", repeats, times),
+            )
+        })
+    }
+
+    fn take_dropped(&mut self) -> Option<(Level, String)> {
+        let dropped = std::mem::take(&mut self.dropped);
+        (dropped > 0).then(|| {
+            let messages = if dropped == 1 { "message" } else { "messages" };
+            (
+                Level::Warn,
+                format!("{} {} suppressed by rate limit", dropped, messages),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(notices: Vec<(Level, String)>) -> Vec<String> {
+        notices.into_iter().map(|(_, text)| text).collect()
+    }
+
+    #[test]
+    fn test_scope_prefix() {
+        assert_eq!(scope_prefix(&[]), "");
+
+        let logger = Logger::new().scoped("deploy").scoped("build");
+        assert_eq!(scope_prefix(&logger.scopes), "deploy ▸ build ▸ ");
+    }
+
+    #[test]
+    fn test_highlight_spans() {
+        let logger = Logger::new()
+            .highlight_rule("*.rs", Color::Cyan)
+            .highlight_rule("*ms", Color::Yellow)
+            .highlight_rule("main", Color::Red)
+            .highlight_rule("error", Color::Red);
+        let message = "error compiling src/main.rs in 120ms (error again)";
+        let spans: Vec<&str> = highlight_spans(message, &logger.highlights)
+            .into_iter()
+            .map(|(start, stop, _)| &message[start..stop])
+            .collect();
+        // "main" is inside the earlier "*.rs" match, so it is not painted again
+        assert_eq!(spans, ["error", "src/main.rs", "120ms", "error"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let glob = |pattern: &str, text: &str| {
+            let pattern: Vec<char> = pattern.chars().collect();
+            let text: Vec<char> = text.chars().collect();
+            glob_match(&pattern, &text)
+        };
+        assert!(glob("*.rs", "lib.rs"));
+        assert!(glob("a*b*c", "axxbyyc"));
+        assert!(glob("v?.?", "v1.2"));
+        assert!(glob("*", ""));
+        assert!(!glob("*.rs", "lib.rs.bak"));
+        assert!(!glob("v?", "v12"));
+    }
+
+    #[test]
+    fn test_dedup_collapses_repeats() {
+        let start = Instant::now();
+        let mut limits = RateLimit {
+            dedup_window: Some(Duration::from_secs(5)),
+            ..RateLimit::default()
+        };
+
+        assert_eq!(limits.check(Level::Info, "retrying", start), (vec![], true));
+        for i in 1..=3 {
+            let at = start + Duration::from_millis(i * 100);
+            assert_eq!(limits.check(Level::Info, "retrying", at), (vec![], false));
+        }
+
+        let (notices, admitted) = limits.check(Level::Info, "done", start + Duration::from_secs(1));
+        assert!(admitted);
+        assert_eq!(texts(notices), ["last message repeated 3 times"]);
+
+        // Outside the window the same message is printed again
+        let later = start + Duration::from_secs(10);
+        assert!(limits.check(Level::Info, "done", later).1);
+        assert!(limits.flush().is_empty());
+    }
+
+    #[test]
+    fn test_throttle_drops_and_reports() {
+        let start = Instant::now();
+        let mut limits = RateLimit {
+            max_per_second: Some(2),
+            ..RateLimit::default()
+        };
+
+        let admitted: Vec<bool> = (0..5)
+            .map(|i| limits.check(Level::Warn, &format!("tick {}", i), start).1)
+            .collect();
+        assert_eq!(admitted, [true, true, false, false, false]);
+
+        let (notices, admitted) = limits.check(Level::Warn, "next", start + Duration::from_secs(1));
+        assert!(admitted);
+        assert_eq!(
+            notices,
+            [(
+                Level::Warn,
+                "3 messages suppressed by rate limit".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_flush_reports_pending_repeats() {
+        let start = Instant::now();
+        let mut limits = RateLimit {
+            dedup_window: Some(Duration::from_secs(1)),
+            ..RateLimit::default()
+        };
+        limits.check(Level::Error, "disk full", start);
+        limits.check(Level::Error, "disk full", start);
+        assert_eq!(
+            limits.flush(),
+            [(Level::Error, "last message repeated 1 time".to_string())]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! # Declarative CLI Macro
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — macros.rs                                             ║
+//! ║  `cli!`: a terse DSL over the App/Command/Arg builders        ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+
+/// Builds an [`App`](crate::command::App) from a terse description
+///
+/// Derive-style ergonomics without a proc-macro dependency: the macro expands
+/// to the same builder calls you would write by hand.
+///
+/// - `key: value` calls the builder method of that name (`version: "1.0"`
+///   becomes `.version("1.0")`), so every builder is available.
+/// - On arguments, `help` and `default` are short for `about` and
+///   `default_value`; a bare `flag` means the argument takes no value, and any
+///   other bare word sets that option to `true` (`required`, `multiple`, ...).
+/// - `args { ... }` and `subcommands { ... }` take names (identifiers or
+///   string literals) each followed by a braced body.
+///
+/// # Examples
+///
+/// ```
+/// let app = zfish::cli! {
+///     "myapp" {
+///         version: "1.0.0",
+///         about: "Builds things",
+///         args {
+///             verbose { short: 'v', long: "verbose", flag, help: "Print more" }
+///             "out-dir" { short: 'o', long: "out-dir", default: "dist" }
+///         }
+///         subcommands {
+///             build {
+///                 about: "Compile the project",
+///                 args {
+///                     release { long: "release", flag }
+///                 }
+///             }
+///         }
+///     }
+/// };
+///
+/// let matches = app.try_get_matches_from(["myapp", "-v", "build", "--release"]).unwrap();
+/// assert!(matches.is_present("verbose"));
+/// assert_eq!(matches.value_of("out-dir"), Some("dist"));
+/// assert!(matches.subcommand_matches("build").unwrap().is_present("release"));
+/// ```
+#[macro_export]
+macro_rules! cli {
+    ($name:tt { $($body:tt)* }) => {
+        $crate::cli!(@cmd $crate::command::App::new($crate::cli!(@name $name)); $($body)*)
+    };
+
+    // ── command body ────────────────────────────────────────────────────
+    (@cmd $cmd:expr;) => { $cmd };
+    (@cmd $cmd:expr; args { $($args:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::cli!(@cmd $crate::cli!(@args $cmd; $($args)*); $($($rest)*)?)
+    };
+    (@cmd $cmd:expr; args { $($args:tt)* } $($rest:tt)+) => {
+        $crate::cli!(@cmd $crate::cli!(@args $cmd; $($args)*); $($rest)+)
+    };
+    (@cmd $cmd:expr; subcommands { $($sub:tt { $($sub_body:tt)* })* } $(, $($rest:tt)*)?) => {
+        $crate::cli!(@cmd $cmd $(.subcommand($crate::cli!(
+            @cmd $crate::command::Command::new($crate::cli!(@name $sub)); $($sub_body)*
+        )))*; $($($rest)*)?)
+    };
+    (@cmd $cmd:expr; subcommands { $($sub:tt { $($sub_body:tt)* })* } $($rest:tt)+) => {
+        $crate::cli!(@cmd $cmd; subcommands { $($sub { $($sub_body)* })* }, $($rest)+)
+    };
+    (@cmd $cmd:expr; $key:ident : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::cli!(@cmd $cmd.$key($value); $($($rest)*)?)
+    };
+
+    // ── arguments ───────────────────────────────────────────────────────
+    (@args $cmd:expr;) => { $cmd };
+    (@args $cmd:expr; $name:tt { $($props:tt)* } $($rest:tt)*) => {
+        $crate::cli!(@args $cmd.arg($crate::cli!(
+            @arg $crate::command::Arg::new($crate::cli!(@name $name)); $($props)*
+        )); $($rest)*)
+    };
+
+    (@arg $arg:expr;) => { $arg };
+    (@arg $arg:expr; help : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::cli!(@arg $arg.about($value); $($($rest)*)?)
+    };
+    (@arg $arg:expr; default : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::cli!(@arg $arg.default_value($value); $($($rest)*)?)
+    };
+    (@arg $arg:expr; $key:ident : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::cli!(@arg $arg.$key($value); $($($rest)*)?)
+    };
+    (@arg $arg:expr; flag $(, $($rest:tt)*)?) => {
+        $crate::cli!(@arg $arg.takes_value(false); $($($rest)*)?)
+    };
+    (@arg $arg:expr; $key:ident $(, $($rest:tt)*)?) => {
+        $crate::cli!(@arg $arg.$key(true); $($($rest)*)?)
+    };
+
+    // ── names ───────────────────────────────────────────────────────────
+    (@name $name:ident) => { stringify!($name) };
+    (@name $name:literal) => { $name };
+}
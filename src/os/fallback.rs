@@ -0,0 +1,82 @@
+//! # Fallback Platform Support Module
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — os/fallback.rs                                        ║
+//! ║  Portable stand-ins for targets without terminal control      ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Used on wasm32-wasi, Redox and any other target without a known terminal
+//! API. Nothing here panics or needs FFI; everything goes through plain
+//! stdin/stdout and the environment the host passes in (e.g.
+//! `wasmtime --env COLUMNS=120`):
+//!
+//! - passwords are read as a plain line, **with echo**
+//! - the size comes from `COLUMNS`/`LINES`, or is unknown
+//! - raw mode fails with [`io::ErrorKind::Unsupported`], which the line
+//!   editor treats as "fall back to line input"
+
+use std::io;
+
+/// Nothing to save: raw mode is never entered
+pub type TerminalMode = ();
+
+/// Passwords are echoed; there is no way to hide them here
+pub const HIDDEN_INPUT: bool = false;
+/// Raw mode is unavailable
+pub const RAW_MODE: bool = false;
+/// The size only comes from the environment
+pub const SIZE_QUERY: bool = false;
+/// There is no job control to stop the process with
+pub const JOB_CONTROL: bool = false;
+
+/// Read a password from plain stdin (echo cannot be turned off)
+pub fn read_password() -> io::Result<String> {
+    let mut password = String::new();
+    io::stdin().read_line(&mut password)?;
+    let len = password.trim_end_matches(['\r', '\n']).len();
+    password.truncate(len);
+    Ok(password)
+}
+
+/// Terminal size from the `COLUMNS` and `LINES` environment variables
+pub fn get_terminal_size() -> Option<(u16, u16)> {
+    let dimension = |name: &str| {
+        std::env::var(name)
+            .ok()?
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|&n| n > 0)
+    };
+    Some((dimension("COLUMNS")?, dimension("LINES").unwrap_or(24)))
+}
+
+/// Raw mode is not available on this target
+pub fn enable_raw_mode() -> io::Result<TerminalMode> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "raw mode is not supported on this platform",
+    ))
+}
+
+/// Nothing to restore, since raw mode is never entered
+pub fn restore_terminal(_mode: &TerminalMode) -> io::Result<()> {
+    Ok(())
+}
+
+/// Nothing to do without job control
+pub fn suspend() -> io::Result<()> {
+    Ok(())
+}
+
+/// Stdin cannot be polled on this target
+pub fn wait_for_input(_timeout: std::time::Duration) -> io::Result<bool> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "polling input is not supported on this platform",
+    ))
+}
@@ -14,53 +14,153 @@
 
 // Allow unsafe code in this module and submodules
 #![allow(unsafe_code)]
+// Backends implement every touchpoint; the `raw`/`interactive` features
+// decide which of them the rest of the crate calls
+#![cfg_attr(not(all(feature = "raw", feature = "interactive")), allow(dead_code))]
+
+// Exactly one backend is compiled and re-exported as `imp`. build.rs sets
+// `zfish_termios` for the Unix targets unix.rs has a termios layout for;
+// every other target (wasm, Redox, ...) gets the FFI-free fallback.
 
 #[cfg(windows)]
 pub mod windows;
+#[cfg(windows)]
+use windows as imp;
 
-#[cfg(unix)]
+#[cfg(zfish_termios)]
 pub mod unix;
+#[cfg(zfish_termios)]
+use unix as imp;
+
+#[cfg(not(any(windows, zfish_termios)))]
+pub mod fallback;
+#[cfg(not(any(windows, zfish_termios)))]
+use fallback as imp;
 
-/// Read a password with echo disabled (platform-specific implementation)
+/// Whether passwords are read with echo turned off on this platform
+pub const HIDDEN_INPUT: bool = imp::HIDDEN_INPUT;
+/// Whether raw mode is available on this platform
+pub const RAW_MODE: bool = imp::RAW_MODE;
+/// Whether the terminal size can be queried (rather than read from `COLUMNS`/`LINES`)
+pub const SIZE_QUERY: bool = imp::SIZE_QUERY;
+/// Whether the process can be suspended and resumed by the shell
+pub const JOB_CONTROL: bool = imp::JOB_CONTROL;
+
+/// Read a password with echo disabled where the platform allows it
+#[cfg(feature = "interactive")]
 pub fn read_password() -> std::io::Result<String> {
-    #[cfg(windows)]
-    {
-        windows::read_password()
-    }
+    imp::read_password()
+}
+
+/// Get terminal size (width, height), or `None` if it cannot be determined
+pub fn get_terminal_size() -> Option<(u16, u16)> {
+    imp::get_terminal_size()
+}
+
+/// Terminal state saved when entering raw mode
+#[cfg(feature = "raw")]
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalState {
+    inner: imp::TerminalMode,
+}
+
+/// Put the terminal into raw mode, returning the state to restore afterwards
+///
+/// Fails with `ErrorKind::Unsupported` where raw mode is unavailable.
+#[cfg(feature = "raw")]
+pub fn enable_raw_mode() -> std::io::Result<TerminalState> {
+    imp::enable_raw_mode().map(|inner| TerminalState { inner })
+}
+
+/// Restore a terminal state saved by [`enable_raw_mode`]
+#[cfg(feature = "raw")]
+pub fn restore_terminal(state: &TerminalState) -> std::io::Result<()> {
+    imp::restore_terminal(&state.inner)
+}
 
-    #[cfg(unix)]
+/// Wait up to `timeout` for stdin to become readable, `false` on timeout
+///
+/// May return `false` early when interrupted by a signal.
+#[cfg(feature = "raw")]
+pub fn wait_for_input(timeout: std::time::Duration) -> std::io::Result<bool> {
+    imp::wait_for_input(timeout)
+}
+
+/// Stop the process until the shell resumes it; does nothing without job control
+#[cfg(feature = "raw")]
+pub fn suspend() -> std::io::Result<()> {
+    imp::suspend()
+}
+
+/// Set an environment variable for the whole process
+///
+/// # Safety
+///
+/// Same as [`std::env::set_var`]: no other thread may read or write the
+/// environment at the same time.
+pub unsafe fn set_env_var(key: &str, value: &str) {
+    // SAFETY: upheld by the caller
+    unsafe { std::env::set_var(key, value) }
+}
+
+/// How styled text reaches the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorBackend {
+    /// ANSI escape sequences (Unix terminals, Windows 10+ consoles)
+    Ansi,
+    /// `SetConsoleTextAttribute` calls, for Windows consoles without VT processing
+    LegacyConsole,
+}
+
+/// Detect the color backend once per process
+///
+/// On Windows this also enables VT processing on the console when available.
+pub fn color_backend() -> ColorBackend {
+    static BACKEND: std::sync::OnceLock<ColorBackend> = std::sync::OnceLock::new();
+
+    *BACKEND.get_or_init(|| {
+        #[cfg(windows)]
+        {
+            if windows::enable_virtual_terminal() || windows::console_attributes().is_none() {
+                ColorBackend::Ansi
+            } else {
+                ColorBackend::LegacyConsole
+            }
+        }
+
+        #[cfg(not(windows))]
+        {
+            ColorBackend::Ansi
+        }
+    })
+}
+
+/// Current console text attributes, or `None` where there is no Windows console
+pub fn console_attributes() -> Option<u16> {
+    #[cfg(windows)]
     {
-        unix::read_password()
+        windows::console_attributes()
     }
 
-    #[cfg(not(any(windows, unix)))]
+    #[cfg(not(windows))]
     {
-        // Fallback for other platforms
-        use std::io::{self, Write};
-        print!("Password: ");
-        io::stdout().flush()?;
-        let mut password = String::new();
-        io::stdin().read_line(&mut password)?;
-        password.pop(); // Remove newline
-        Ok(password)
+        None
     }
 }
 
-/// Get terminal size (width, height) - platform-specific implementation
-pub fn get_terminal_size() -> Option<(u16, u16)> {
+/// Set console text attributes (see [`ColorBackend::LegacyConsole`])
+pub fn set_console_attributes(attributes: u16) -> std::io::Result<()> {
     #[cfg(windows)]
     {
-        windows::get_terminal_size()
-    }
-
-    #[cfg(unix)]
-    {
-        unix::get_terminal_size()
+        windows::set_console_attributes(attributes)
     }
 
-    #[cfg(not(any(windows, unix)))]
+    #[cfg(not(windows))]
     {
-        // Fallback for other platforms
-        Some((80, 24))
+        let _ = attributes;
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "console attributes are only available on Windows",
+        ))
     }
 }
@@ -11,99 +11,194 @@
 //!
 //! This module contains Unix/Linux-specific unsafe code for terminal operations.
 //! All unsafe blocks must have SAFETY comments explaining why they are sound.
+//!
+//! `struct termios` and the ioctl numbers differ between kernels, so the
+//! layouts live in a per-family `sys` module, picked by the `zfish_termios`
+//! cfg from build.rs: Linux/Android on the generic ABI and the BSDs
+//! (including macOS and iOS). Other Unix targets (Redox, Solaris, Linux on
+//! MIPS/PowerPC/SPARC, ...) use `os/fallback.rs`.
 
+use std::ffi::{c_int, c_short, c_ulong};
 use std::io;
 use std::os::unix::io::AsRawFd;
+use std::time::Duration;
 
-/// Read a password with echo disabled on Unix/Linux
-pub fn read_password() -> io::Result<String> {
-    // Define the termios structs and constants using raw FFI
+/// Linux and Android on architectures using the generic termios ABI
+/// (bionic reads only the kernel-sized prefix of this struct)
+#[cfg(zfish_termios = "linux")]
+mod sys {
+    use std::ffi::c_ulong;
+
+    /// Terminal attributes as laid out by Linux `struct termios`
     #[repr(C)]
-    struct Termios {
-        c_iflag: u32,
-        c_oflag: u32,
-        c_cflag: u32,
-        c_lflag: u32,
-        c_line: u8,
-        c_cc: [u8; 32],
-        c_ispeed: u32,
-        c_ospeed: u32,
+    #[derive(Debug, Clone, Copy)]
+    pub struct Termios {
+        pub c_iflag: u32,
+        pub c_oflag: u32,
+        pub c_cflag: u32,
+        pub c_lflag: u32,
+        pub c_line: u8,
+        pub c_cc: [u8; 32],
+        pub c_ispeed: u32,
+        pub c_ospeed: u32,
     }
 
-    const ECHO: u32 = 0x00000008;
-    const ECHONL: u32 = 0x00000040;
-    const TCSANOW: i32 = 0;
+    pub type TcFlag = u32;
+
+    pub const ISIG: TcFlag = 0o000001;
+    pub const ICANON: TcFlag = 0o000002;
+    pub const ECHO: TcFlag = 0o000010;
+    pub const ECHONL: TcFlag = 0o000100;
+    pub const IEXTEN: TcFlag = 0o100000;
+    pub const ICRNL: TcFlag = 0o000400;
+    pub const IXON: TcFlag = 0o002000;
+    pub const VTIME: usize = 5;
+    pub const VMIN: usize = 6;
+    pub const TIOCGWINSZ: c_ulong = 0x5413;
+    pub const SIGTSTP: std::ffi::c_int = 20;
+    pub type Nfds = c_ulong;
+}
+
+/// macOS, iOS and the BSDs, which share the 4.4BSD termios layout
+#[cfg(zfish_termios = "bsd")]
+mod sys {
+    use std::ffi::c_ulong;
+
+    /// `tcflag_t` is `unsigned long` on Apple platforms and `unsigned int` elsewhere
+    #[cfg(target_vendor = "apple")]
+    pub type TcFlag = c_ulong;
+    #[cfg(not(target_vendor = "apple"))]
+    pub type TcFlag = u32;
 
-    unsafe extern "C" {
-        fn tcgetattr(fd: i32, termios: *mut Termios) -> i32;
-        fn tcsetattr(fd: i32, optional_actions: i32, termios: *const Termios) -> i32;
+    /// Terminal attributes as laid out by BSD `struct termios`
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Termios {
+        pub c_iflag: TcFlag,
+        pub c_oflag: TcFlag,
+        pub c_cflag: TcFlag,
+        pub c_lflag: TcFlag,
+        pub c_cc: [u8; 20],
+        pub c_ispeed: TcFlag,
+        pub c_ospeed: TcFlag,
     }
 
-    let stdin_fd = io::stdin().as_raw_fd();
-    let mut termios = std::mem::MaybeUninit::<Termios>::uninit();
+    pub const ECHO: TcFlag = 0x0000_0008;
+    pub const ECHONL: TcFlag = 0x0000_0010;
+    pub const ISIG: TcFlag = 0x0000_0080;
+    pub const ICANON: TcFlag = 0x0000_0100;
+    pub const IEXTEN: TcFlag = 0x0000_0400;
+    pub const ICRNL: TcFlag = 0x0000_0100;
+    pub const IXON: TcFlag = 0x0000_0200;
+    pub const VMIN: usize = 16;
+    pub const VTIME: usize = 17;
+    pub const TIOCGWINSZ: c_ulong = 0x4008_7468;
+    pub const SIGTSTP: std::ffi::c_int = 18;
+    pub type Nfds = std::ffi::c_uint;
+}
 
-    // SAFETY: tcgetattr is called with a valid file descriptor and a properly
-    // allocated termios struct. The FFI call is checked for errors.
-    unsafe {
-        // Get the current terminal attributes
-        if tcgetattr(stdin_fd, termios.as_mut_ptr()) != 0 {
-            return Err(io::Error::last_os_error());
-        }
+use sys::*;
+
+/// Saved terminal state for [`restore_terminal`]
+pub type TerminalMode = Termios;
 
-        let mut termios = termios.assume_init();
-        let original_lflag = termios.c_lflag;
+/// Passwords are read with echo turned off
+pub const HIDDEN_INPUT: bool = true;
+/// Stdin can be switched to raw mode
+pub const RAW_MODE: bool = true;
+/// The window size can be queried from the terminal
+pub const SIZE_QUERY: bool = true;
+/// Shells can stop and resume the process (Ctrl+Z, `fg`)
+pub const JOB_CONTROL: bool = true;
 
-        // Disable echo
-        termios.c_lflag &= !(ECHO | ECHONL);
+const TCSANOW: c_int = 0;
+const POLLIN: c_short = 0x0001;
 
-        // Set the new terminal attributes
-        if tcsetattr(stdin_fd, TCSANOW, &termios) != 0 {
+/// `struct pollfd`, the same on every supported Unix
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: c_short,
+    revents: c_short,
+}
+
+unsafe extern "C" {
+    fn tcgetattr(fd: c_int, termios: *mut Termios) -> c_int;
+    fn tcsetattr(fd: c_int, optional_actions: c_int, termios: *const Termios) -> c_int;
+    // Variadic in C; declaring it so keeps the call correct on Apple arm64,
+    // where variadic arguments are passed on the stack
+    fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    fn kill(pid: c_int, sig: c_int) -> c_int;
+    fn poll(fds: *mut PollFd, nfds: Nfds, timeout: c_int) -> c_int;
+}
+
+/// Read the current attributes of `fd`
+///
+/// Starts from all zeroes: bionic only writes the kernel-sized prefix of
+/// the Linux layout, leaving the tail of `c_cc` and the speeds untouched.
+fn get_attributes(fd: c_int) -> io::Result<Termios> {
+    let mut termios = std::mem::MaybeUninit::<Termios>::zeroed();
+
+    // SAFETY: tcgetattr is given a valid pointer to a Termios, which is all
+    // integers and so valid when zeroed, whatever part of it the libc fills in.
+    unsafe {
+        if tcgetattr(fd, termios.as_mut_ptr()) != 0 {
             return Err(io::Error::last_os_error());
         }
+        Ok(termios.assume_init())
+    }
+}
 
-        // Ensure we restore the terminal attributes even if reading fails
-        struct TermiosResetter {
-            fd: i32,
-            original_lflag: u32,
-            termios: Termios,
+/// Apply `termios` to `fd` immediately
+fn set_attributes(fd: c_int, termios: &Termios) -> io::Result<()> {
+    // SAFETY: the termios struct is fully initialised (it came from tcgetattr)
+    unsafe {
+        if tcsetattr(fd, TCSANOW, termios) != 0 {
+            return Err(io::Error::last_os_error());
         }
+    }
+    Ok(())
+}
 
-        impl Drop for TermiosResetter {
-            fn drop(&mut self) {
-                // SAFETY: Restoring original termios settings
-                unsafe extern "C" {
-                    fn tcsetattr(fd: i32, optional_actions: i32, termios: *const Termios) -> i32;
-                }
-
-                self.termios.c_lflag = self.original_lflag;
-                unsafe {
-                    // Restore original settings
-                    const TCSANOW: i32 = 0;
-                    tcsetattr(self.fd, TCSANOW, &self.termios);
-                }
-            }
+/// Read a password with echo disabled on Unix/Linux
+pub fn read_password() -> io::Result<String> {
+    let stdin_fd = io::stdin().as_raw_fd();
+    let original = get_attributes(stdin_fd)?;
+
+    let mut silent = original;
+    silent.c_lflag &= !(ECHO | ECHONL);
+    set_attributes(stdin_fd, &silent)?;
+
+    // Ensure we restore the terminal attributes even if reading fails
+    struct TermiosResetter {
+        fd: c_int,
+        original: Termios,
+    }
+
+    impl Drop for TermiosResetter {
+        fn drop(&mut self) {
+            let _ = set_attributes(self.fd, &self.original);
         }
+    }
 
-        let _resetter = TermiosResetter {
-            fd: stdin_fd,
-            original_lflag,
-            termios,
-        };
+    let _resetter = TermiosResetter {
+        fd: stdin_fd,
+        original,
+    };
 
-        // Read the password from stdin
-        let mut password = String::new();
-        io::stdin().read_line(&mut password)?;
+    // Read the password from stdin
+    let mut password = String::new();
+    io::stdin().read_line(&mut password)?;
 
-        // Remove trailing newline if present
-        if password.ends_with('\n') {
+    // Remove trailing newline if present
+    if password.ends_with('\n') {
+        password.pop();
+        if password.ends_with('\r') {
             password.pop();
-            if password.ends_with('\r') {
-                password.pop();
-            }
         }
-
-        Ok(password)
     }
+
+    Ok(password)
 }
 
 /// Get terminal size on Unix/Linux using ioctl
@@ -116,12 +211,6 @@ pub fn get_terminal_size() -> Option<(u16, u16)> {
         ws_ypixel: u16,
     }
 
-    const TIOCGWINSZ: u64 = 0x5413;
-
-    unsafe extern "C" {
-        fn ioctl(fd: i32, request: u64, argp: *mut Winsize) -> i32;
-    }
-
     // SAFETY: ioctl is called with a valid file descriptor (stdout),
     // a proper request code for getting window size, and a properly
     // allocated Winsize struct. The FFI call is checked for errors.
@@ -129,14 +218,79 @@ pub fn get_terminal_size() -> Option<(u16, u16)> {
         let mut ws: Winsize = std::mem::zeroed();
         let stdout_fd = io::stdout().as_raw_fd();
 
-        if ioctl(stdout_fd, TIOCGWINSZ, &mut ws) == 0 {
+        if ioctl(stdout_fd, TIOCGWINSZ, &mut ws as *mut Winsize) == 0 {
             // Success - return (width, height)
             if ws.ws_col > 0 && ws.ws_row > 0 {
                 return Some((ws.ws_col, ws.ws_row));
             }
         }
 
-        // Fall back to default if ioctl fails
+        // Not a terminal (or a zero-sized one): let callers pick a default
         None
     }
 }
+
+/// Switch stdin to raw mode and return the previous attributes for restoring
+///
+/// Input is delivered byte by byte without echo, line buffering, signal keys
+/// (Ctrl+C arrives as a byte) or CR-to-NL translation. Output processing is
+/// left on so `\n` still starts a new line.
+pub fn enable_raw_mode() -> io::Result<Termios> {
+    let stdin_fd = io::stdin().as_raw_fd();
+    let original = get_attributes(stdin_fd)?;
+
+    let mut raw = original;
+    raw.c_lflag &= !(ISIG | ICANON | ECHO | IEXTEN);
+    raw.c_iflag &= !(ICRNL | IXON);
+    raw.c_cc[VMIN] = 1;
+    raw.c_cc[VTIME] = 0;
+
+    set_attributes(stdin_fd, &raw)?;
+    Ok(original)
+}
+
+/// Restore terminal attributes saved by [`enable_raw_mode`]
+pub fn restore_terminal(original: &Termios) -> io::Result<()> {
+    set_attributes(io::stdin().as_raw_fd(), original)
+}
+
+/// Stop the process group with SIGTSTP, as the tty does for Ctrl+Z outside
+/// raw mode; returns once the shell resumes it with SIGCONT
+///
+/// No handler is installed for SIGTSTP or SIGCONT, so a stop sent from
+/// outside the process does not go through here (see `RawMode::suspend`).
+pub fn suspend() -> io::Result<()> {
+    // SAFETY: kill takes no pointers; pid 0 addresses our own process group
+    if unsafe { kill(0, SIGTSTP) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Wait until stdin is readable or `timeout` passes; `false` on timeout or EINTR
+pub fn wait_for_input(timeout: Duration) -> io::Result<bool> {
+    // Round up, so a sub-millisecond timeout still waits rather than spinning
+    let millis = timeout
+        .as_nanos()
+        .div_ceil(1_000_000)
+        .min(c_int::MAX as u128) as c_int;
+    let mut fd = PollFd {
+        fd: io::stdin().as_raw_fd(),
+        events: POLLIN,
+        revents: 0,
+    };
+
+    // SAFETY: poll is given exactly one valid, initialised pollfd
+    match unsafe { poll(&mut fd, 1, millis) } {
+        -1 => {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                Ok(false)
+            } else {
+                Err(err)
+            }
+        }
+        0 => Ok(false),
+        _ => Ok(true),
+    }
+}
@@ -15,6 +15,18 @@
 use std::io;
 use std::ptr;
 
+/// Saved console input mode for [`restore_terminal`]
+pub type TerminalMode = u32;
+
+/// Passwords are read with echo turned off
+pub const HIDDEN_INPUT: bool = true;
+/// The console input can be switched to raw mode
+pub const RAW_MODE: bool = true;
+/// The window size can be queried from the console
+pub const SIZE_QUERY: bool = true;
+/// Consoles have no job control; Ctrl+Z is an ordinary key
+pub const JOB_CONTROL: bool = false;
+
 /// Read a password with echo disabled on Windows
 pub fn read_password() -> io::Result<String> {
     // Windows API constants
@@ -169,3 +181,270 @@ pub fn get_terminal_size() -> Option<(u16, u16)> {
         Some((width, height))
     }
 }
+
+/// Switch the console input to raw mode and return the previous mode for restoring
+///
+/// Disables line input, echo and Ctrl+C processing, and enables virtual
+/// terminal input so arrow keys arrive as the same escape sequences as on Unix.
+pub fn enable_raw_mode() -> io::Result<u32> {
+    const STD_INPUT_HANDLE: u32 = 0xFFFFFFF6;
+    const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+    const ENABLE_LINE_INPUT: u32 = 0x0002;
+    const ENABLE_ECHO_INPUT: u32 = 0x0004;
+    const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x0200;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(nStdHandle: u32) -> *mut core::ffi::c_void;
+        fn GetConsoleMode(hConsoleHandle: *mut core::ffi::c_void, lpMode: *mut u32) -> i32;
+        fn SetConsoleMode(hConsoleHandle: *mut core::ffi::c_void, dwMode: u32) -> i32;
+    }
+
+    // SAFETY: All Windows API calls are checked for errors and the console
+    // handle is valid for the process lifetime.
+    unsafe {
+        let handle = GetStdHandle(STD_INPUT_HANDLE);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut original_mode: u32 = 0;
+        if GetConsoleMode(handle, &mut original_mode) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let raw_mode = (original_mode
+            & !(ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT))
+            | ENABLE_VIRTUAL_TERMINAL_INPUT;
+        if SetConsoleMode(handle, raw_mode) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(original_mode)
+    }
+}
+
+/// Restore a console input mode saved by [`enable_raw_mode`]
+pub fn restore_terminal(original_mode: &u32) -> io::Result<()> {
+    const STD_INPUT_HANDLE: u32 = 0xFFFFFFF6;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(nStdHandle: u32) -> *mut core::ffi::c_void;
+        fn SetConsoleMode(hConsoleHandle: *mut core::ffi::c_void, dwMode: u32) -> i32;
+    }
+
+    // SAFETY: the handle is checked and the mode was read from this console
+    unsafe {
+        let handle = GetStdHandle(STD_INPUT_HANDLE);
+        if handle.is_null() || SetConsoleMode(handle, *original_mode) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Screen buffer info for the stdout console, or `None` if stdout is not a console
+fn stdout_screen_info() -> Option<(*mut core::ffi::c_void, u16)> {
+    const STD_OUTPUT_HANDLE: u32 = 0xFFFFFFF5;
+
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    #[repr(C)]
+    struct COORD {
+        x: i16,
+        y: i16,
+    }
+
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    #[repr(C)]
+    struct SMALL_RECT {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    #[repr(C)]
+    struct CONSOLE_SCREEN_BUFFER_INFO {
+        dw_size: COORD,
+        dw_cursor_position: COORD,
+        w_attributes: u16,
+        sr_window: SMALL_RECT,
+        dw_maximum_window_size: COORD,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(nStdHandle: u32) -> *mut core::ffi::c_void;
+        fn GetConsoleScreenBufferInfo(
+            hConsoleOutput: *mut core::ffi::c_void,
+            lpConsoleScreenBufferInfo: *mut CONSOLE_SCREEN_BUFFER_INFO,
+        ) -> i32;
+    }
+
+    // SAFETY: the handle is checked before use and the info struct is a
+    // zeroed #[repr(C)] value of the layout the API expects.
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        if handle.is_null() {
+            return None;
+        }
+
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+        if GetConsoleScreenBufferInfo(handle, &mut info) == 0 {
+            return None;
+        }
+        Some((handle, info.w_attributes))
+    }
+}
+
+/// Try to turn on ANSI escape processing for the stdout console
+///
+/// Fails on consoles older than Windows 10 and when stdout is not a console.
+pub fn enable_virtual_terminal() -> bool {
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetConsoleMode(hConsoleHandle: *mut core::ffi::c_void, lpMode: *mut u32) -> i32;
+        fn SetConsoleMode(hConsoleHandle: *mut core::ffi::c_void, dwMode: u32) -> i32;
+    }
+
+    let Some((handle, _)) = stdout_screen_info() else {
+        return false;
+    };
+
+    // SAFETY: the handle was just validated by GetConsoleScreenBufferInfo
+    unsafe {
+        let mut mode: u32 = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return false;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+/// Current text attributes of the stdout console
+pub fn console_attributes() -> Option<u16> {
+    stdout_screen_info().map(|(_, attributes)| attributes)
+}
+
+/// Set the text attributes used for subsequent stdout console output
+pub fn set_console_attributes(attributes: u16) -> io::Result<()> {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn SetConsoleTextAttribute(hConsoleOutput: *mut core::ffi::c_void, wAttributes: u16)
+        -> i32;
+    }
+
+    let Some((handle, _)) = stdout_screen_info() else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "stdout is not a console",
+        ));
+    };
+
+    // SAFETY: the handle was just validated by GetConsoleScreenBufferInfo
+    unsafe {
+        if SetConsoleTextAttribute(handle, attributes) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Nothing to do without job control
+pub fn suspend() -> io::Result<()> {
+    Ok(())
+}
+
+/// Wait until a key press is pending on the console or `timeout` passes
+///
+/// The console handle is also signalled for mouse, focus, resize and key-up
+/// events, which never reach the byte reader; those are discarded so that a
+/// following read does not block.
+pub fn wait_for_input(timeout: std::time::Duration) -> io::Result<bool> {
+    const STD_INPUT_HANDLE: u32 = 0xFFFFFFF6;
+    const WAIT_OBJECT_0: u32 = 0;
+    const WAIT_TIMEOUT: u32 = 0x102;
+    const KEY_EVENT: u16 = 0x0001;
+
+    /// `INPUT_RECORD` with the union read as a `KEY_EVENT_RECORD`
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct InputRecord {
+        event_type: u16,
+        key_down: i32,
+        repeat_count: u16,
+        virtual_key_code: u16,
+        virtual_scan_code: u16,
+        unicode_char: u16,
+        control_key_state: u32,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(nStdHandle: u32) -> *mut core::ffi::c_void;
+        fn WaitForSingleObject(hHandle: *mut core::ffi::c_void, dwMilliseconds: u32) -> u32;
+        fn PeekConsoleInputW(
+            hConsoleInput: *mut core::ffi::c_void,
+            lpBuffer: *mut InputRecord,
+            nLength: u32,
+            lpNumberOfEventsRead: *mut u32,
+        ) -> i32;
+        fn ReadConsoleInputW(
+            hConsoleInput: *mut core::ffi::c_void,
+            lpBuffer: *mut InputRecord,
+            nLength: u32,
+            lpNumberOfEventsRead: *mut u32,
+        ) -> i32;
+    }
+
+    let deadline = std::time::Instant::now() + timeout;
+
+    // SAFETY: the handle is checked, and the record buffer holds exactly the
+    // one INPUT_RECORD (20 bytes, like the C layout) that each call asks for.
+    unsafe {
+        let handle = GetStdHandle(STD_INPUT_HANDLE);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            // INFINITE is u32::MAX, so stay just below it
+            let millis = remaining
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .min(u32::MAX as u128 - 1) as u32;
+            match WaitForSingleObject(handle, millis) {
+                WAIT_OBJECT_0 => {}
+                WAIT_TIMEOUT => return Ok(false),
+                _ => return Err(io::Error::last_os_error()),
+            }
+
+            let mut record = std::mem::MaybeUninit::<InputRecord>::uninit();
+            let mut count: u32 = 0;
+            if PeekConsoleInputW(handle, record.as_mut_ptr(), 1, &mut count) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if count == 1 {
+                let record = record.assume_init();
+                if record.event_type == KEY_EVENT
+                    && record.key_down != 0
+                    && record.unicode_char != 0
+                {
+                    return Ok(true);
+                }
+                let mut discarded = std::mem::MaybeUninit::<InputRecord>::uninit();
+                if ReadConsoleInputW(handle, discarded.as_mut_ptr(), 1, &mut count) == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            if std::time::Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+    }
+}
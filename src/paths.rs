@@ -0,0 +1,150 @@
+//! # Standard Directories
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — paths.rs                                              ║
+//! ║  Where an app keeps its config, cache and data               ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Each function returns the per-app directory (the base joined with
+//! `app_name`) without creating it:
+//!
+//! | Function       | Unix and macOS                                  | Windows                   |
+//! |----------------|-------------------------------------------------|---------------------------|
+//! | [`config_dir`] | `$XDG_CONFIG_HOME`, else `~/.config`            | `%APPDATA%`               |
+//! | [`data_dir`]   | `$XDG_DATA_HOME`, else `~/.local/share`         | `%APPDATA%`               |
+//! | [`cache_dir`]  | `$XDG_CACHE_HOME`, else `~/.cache`              | `%LOCALAPPDATA%`          |
+//!
+//! As the XDG spec requires, relative `XDG_*` values are ignored. On Windows,
+//! a missing `APPDATA`/`LOCALAPPDATA` falls back to `%USERPROFILE%\AppData\...`.
+//! `None` means no home directory could be found.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! let config = zfish::paths::config_dir("myapp").unwrap().join("config.toml");
+//! let history = zfish::paths::data_dir("myapp").map(|dir| dir.join("history"));
+//! # let _ = (config, history);
+//! ```
+
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// The kind of directory being resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Config,
+    Data,
+    Cache,
+}
+
+/// Directory for the app's configuration files
+pub fn config_dir(app_name: &str) -> Option<PathBuf> {
+    from_env(Kind::Config, app_name)
+}
+
+/// Directory for data the app creates and keeps, such as history
+pub fn data_dir(app_name: &str) -> Option<PathBuf> {
+    from_env(Kind::Data, app_name)
+}
+
+/// Directory for files the app can recreate, safe for the user to delete
+pub fn cache_dir(app_name: &str) -> Option<PathBuf> {
+    from_env(Kind::Cache, app_name)
+}
+
+/// Resolve `kind` from the process environment
+fn from_env(kind: Kind, app_name: &str) -> Option<PathBuf> {
+    resolve(kind, app_name, cfg!(windows), &|name| env::var_os(name))
+}
+
+/// Resolve a directory with `var` standing in for the environment
+fn resolve(
+    kind: Kind,
+    app_name: &str,
+    windows: bool,
+    var: &dyn Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    let set = |name: &str| {
+        var(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = if windows {
+        let (name, fallback) = match kind {
+            Kind::Config | Kind::Data => ("APPDATA", "Roaming"),
+            Kind::Cache => ("LOCALAPPDATA", "Local"),
+        };
+        set(name).or_else(|| Some(set("USERPROFILE")?.join("AppData").join(fallback)))
+    } else {
+        let (name, fallback) = match kind {
+            Kind::Config => ("XDG_CONFIG_HOME", ".config"),
+            Kind::Data => ("XDG_DATA_HOME", ".local/share"),
+            Kind::Cache => ("XDG_CACHE_HOME", ".cache"),
+        };
+        set(name)
+            .filter(|path| path.is_absolute())
+            .or_else(|| Some(set("HOME")?.join(fallback)))
+    }?;
+    Some(base.join(app_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    #[test]
+    fn test_resolve_unix() {
+        let vars = [("HOME", "/home/me"), ("XDG_CACHE_HOME", "/tmp/cache")];
+        let dir = |kind| resolve(kind, "app", false, &env(&vars));
+        assert_eq!(
+            dir(Kind::Config),
+            Some(PathBuf::from("/home/me/.config/app"))
+        );
+        assert_eq!(
+            dir(Kind::Data),
+            Some(PathBuf::from("/home/me/.local/share/app"))
+        );
+        assert_eq!(dir(Kind::Cache), Some(PathBuf::from("/tmp/cache/app")));
+
+        // Relative XDG values are ignored, empty HOME counts as unset
+        let vars = [("XDG_CONFIG_HOME", "rel"), ("HOME", "")];
+        assert_eq!(resolve(Kind::Config, "app", false, &env(&vars)), None);
+    }
+
+    #[test]
+    fn test_resolve_windows() {
+        let vars = [
+            ("APPDATA", r"C:\Users\me\AppData\Roaming"),
+            ("USERPROFILE", r"C:\Users\me"),
+        ];
+        let dir = |kind| resolve(kind, "app", true, &env(&vars));
+        assert_eq!(
+            dir(Kind::Config),
+            Some(PathBuf::from(r"C:\Users\me\AppData\Roaming").join("app"))
+        );
+        assert_eq!(
+            dir(Kind::Cache),
+            Some(
+                PathBuf::from(r"C:\Users\me")
+                    .join("AppData")
+                    .join("Local")
+                    .join("app")
+            )
+        );
+        assert_eq!(resolve(Kind::Data, "app", true, &env(&[])), None);
+    }
+}
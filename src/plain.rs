@@ -0,0 +1,67 @@
+//! # Plain Mode
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — plain.rs                                              ║
+//! ║  ZFISH_PLAIN=1: ASCII only, no color, nothing animated        ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Setting `ZFISH_PLAIN` (to anything but empty or `0`) asks any zfish-based
+//! tool to write output that exotic terminals and log collectors handle
+//! predictably. No escape sequence is written at all:
+//!
+//! | Module                 | In plain mode                                           |
+//! |------------------------|---------------------------------------------------------|
+//! | `style`                | no color or text attributes                             |
+//! | `table`                | every [`BoxStyle`](crate::table::BoxStyle) drawn as `Ascii` |
+//! | `progress`, `tasks`    | one line when work finishes instead of redrawing         |
+//! | `term`                 | [`Frame`](crate::term::Frame) prints its last frame once; no notifications |
+//! | `prompt`, `line_editor`| ASCII symbols, plain line input, no answer summaries    |
+//!
+//! Apps can also switch it on themselves, e.g. from a `--plain` flag, with
+//! [`set`].
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::plain;
+//!
+//! plain::set(true);
+//! assert!(plain::is_plain());
+//! plain::reset();
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable that turns plain mode on
+pub const ENV_VAR: &str = "ZFISH_PLAIN";
+
+/// Marker for "follow the environment"
+const UNSET: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+
+static PLAIN: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Turns plain mode on or off for the rest of the process, whatever
+/// `ZFISH_PLAIN` says
+pub fn set(enabled: bool) {
+    PLAIN.store(if enabled { ON } else { OFF }, Ordering::Relaxed);
+}
+
+/// Goes back to following `ZFISH_PLAIN`
+pub fn reset() {
+    PLAIN.store(UNSET, Ordering::Relaxed);
+}
+
+/// Checks whether plain mode is in effect
+pub fn is_plain() -> bool {
+    match PLAIN.load(Ordering::Relaxed) {
+        ON => true,
+        OFF => false,
+        _ => std::env::var_os(ENV_VAR).is_some_and(|value| !value.is_empty() && value != "0"),
+    }
+}
@@ -0,0 +1,122 @@
+//! # Platform Capabilities
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — platform.rs                                           ║
+//! ║  What the terminal layer can do on the current target         ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Every platform touchpoint degrades instead of panicking:
+//!
+//! | Touchpoint          | Windows / Linux / Android / macOS / iOS / BSD | Other targets (wasm, Redox, ...)     |
+//! |---------------------|-----------------------------------------------|--------------------------------------|
+//! | `Prompt::password`  | echo turned off                               | plain line read, **echoed**          |
+//! | `Terminal::size`    | queried from the terminal                     | `COLUMNS`/`LINES`, else `None`       |
+//! | `Terminal::raw_mode`| supported                                     | `ErrorKind::Unsupported`             |
+//! | `LineEditor`        | raw-mode editing                              | plain line input                     |
+//!
+//! Without a TTY (pipes, CI, services) the size is `None`, raw mode fails
+//! and the line editor reads plain lines on every platform.
+//! [`Platform::capabilities`] reports all of this at runtime, e.g. to show
+//! a warning before asking for a secret.
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::platform::Platform;
+//!
+//! let caps = Platform::capabilities();
+//! if !caps.hidden_password_input {
+//!     eprintln!("warning: your password will be visible as you type");
+//! }
+//! println!("{}", caps);
+//! ```
+
+use crate::os::{self, ColorBackend};
+use crate::style::StyledString;
+use std::fmt;
+use std::io::{self, IsTerminal};
+
+/// Information about the platform zfish is running on
+#[derive(Debug, Clone, Copy)]
+pub struct Platform;
+
+impl Platform {
+    /// The operating system name, as in `std::env::consts::OS` (e.g. `"linux"`)
+    pub fn name() -> &'static str {
+        std::env::consts::OS
+    }
+
+    /// Reports which terminal features work here, right now
+    pub fn capabilities() -> Capabilities {
+        Capabilities {
+            hidden_password_input: os::HIDDEN_INPUT,
+            raw_mode: cfg!(feature = "raw") && os::RAW_MODE,
+            size_query: os::SIZE_QUERY,
+            stdin_is_terminal: io::stdin().is_terminal(),
+            stdout_is_terminal: io::stdout().is_terminal(),
+            colors: StyledString::supports_colors(),
+            legacy_console: os::color_backend() == ColorBackend::LegacyConsole,
+            plain: crate::plain::is_plain(),
+        }
+    }
+}
+
+/// A snapshot of the platform's terminal capabilities
+///
+/// The first three fields are fixed per target; the rest depend on how the
+/// process was started. Displays as a `name: yes/no` report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// `Prompt::password` hides what is typed
+    pub hidden_password_input: bool,
+    /// `Terminal::raw_mode` is available (when stdin is a terminal and zfish
+    /// was built with the `raw` feature)
+    pub raw_mode: bool,
+    /// `Terminal::size` asks the terminal rather than reading `COLUMNS`/`LINES`
+    pub size_query: bool,
+    /// Standard input is a terminal
+    pub stdin_is_terminal: bool,
+    /// Standard output is a terminal
+    pub stdout_is_terminal: bool,
+    /// Styled text is rendered in color
+    pub colors: bool,
+    /// Colors go through Windows console attributes rather than ANSI escapes
+    pub legacy_console: bool,
+    /// [Plain mode](crate::plain) is on (`ZFISH_PLAIN`)
+    pub plain: bool,
+}
+
+impl Capabilities {
+    /// Checks whether raw-mode key input can be used right now
+    pub fn interactive(&self) -> bool {
+        self.raw_mode && self.stdin_is_terminal
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        writeln!(f, "platform: {}", Platform::name())?;
+        writeln!(
+            f,
+            "hidden password input: {}",
+            yes_no(self.hidden_password_input)
+        )?;
+        writeln!(f, "raw mode: {}", yes_no(self.raw_mode))?;
+        writeln!(f, "size query: {}", yes_no(self.size_query))?;
+        writeln!(f, "stdin is a terminal: {}", yes_no(self.stdin_is_terminal))?;
+        writeln!(
+            f,
+            "stdout is a terminal: {}",
+            yes_no(self.stdout_is_terminal)
+        )?;
+        writeln!(f, "colors: {}", yes_no(self.colors))?;
+        writeln!(f, "legacy console: {}", yes_no(self.legacy_console))?;
+        write!(f, "plain mode: {}", yes_no(self.plain))
+    }
+}
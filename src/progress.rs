@@ -1,7 +1,20 @@
 //! Progress bar and spinner utilities for CLI applications.
+//!
+//! Bars draw to stderr, so `mytool > out.txt` captures only the real output.
+//! Use [`ProgressBar::draw_target`] with [`DrawTarget::Stdout`] to get the
+//! old behaviour back.
 
-use std::io::{self, Write};
-use std::time::Instant;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often a [`TimedProgress`] bar redraws
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// OSC 9;4 sequence that removes the taskbar/tab progress indicator
+const TITLE_PROGRESS_CLEAR: &str = "\x1b]9;4;0;0\x07";
 
 /// Progress bar style (visual appearance)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -17,6 +30,45 @@ pub enum ProgressStyle {
     Spinner,
 }
 
+/// Where a [`ProgressBar`] draws itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DrawTarget {
+    /// Standard error (default), which keeps piped stdout clean
+    #[default]
+    Stderr,
+    /// Standard output, as before zfish drew bars to stderr
+    Stdout,
+}
+
+impl DrawTarget {
+    /// Write `text` and flush, ignoring errors (a bar must never fail the app)
+    pub(crate) fn write(self, text: &str) {
+        let _ = match self {
+            DrawTarget::Stderr => {
+                let mut handle = io::stderr().lock();
+                handle
+                    .write_all(text.as_bytes())
+                    .and_then(|_| handle.flush())
+            }
+            DrawTarget::Stdout => {
+                let mut handle = io::stdout().lock();
+                handle
+                    .write_all(text.as_bytes())
+                    .and_then(|_| handle.flush())
+            }
+        };
+    }
+
+    /// Whether the target is a terminal, so drawing in place works
+    pub(crate) fn is_terminal(self) -> bool {
+        match self {
+            DrawTarget::Stderr => io::stderr().is_terminal(),
+            DrawTarget::Stdout => io::stdout().is_terminal(),
+        }
+    }
+}
+
 /// A progress bar for displaying progress of operations.
 #[derive(Debug)]
 pub struct ProgressBar {
@@ -28,6 +80,17 @@ pub struct ProgressBar {
     pub style: ProgressStyle,
     start_time: Instant,
     spinner_frame: usize,
+    /// Terminal width at the last render, to notice resizes
+    last_term_width: Option<u16>,
+    /// Set for bars driven by wall-clock time, whose steps are milliseconds
+    duration: Option<Duration>,
+    /// Mirror the percentage into the terminal's progress indicator
+    title_progress: bool,
+    /// Percentage last sent with OSC 9;4, `None` when nothing is shown
+    title_percent: Option<u8>,
+    /// Desktop notification title sent when the bar finishes
+    finish_notification: Option<String>,
+    target: DrawTarget,
 }
 
 impl ProgressBar {
@@ -40,6 +103,26 @@ impl ProgressBar {
             style: ProgressStyle::default(),
             start_time: Instant::now(),
             spinner_frame: 0,
+            last_term_width: None,
+            duration: None,
+            title_progress: false,
+            title_percent: None,
+            finish_notification: None,
+            target: DrawTarget::default(),
+        }
+    }
+
+    /// Create a bar that fills over `duration` of wall-clock time.
+    ///
+    /// For "waiting 30s for the service to start" flows: call
+    /// [`start`](TimedProgress::start) and the bar ticks on its own.
+    pub fn for_duration(duration: Duration) -> TimedProgress {
+        let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let mut bar = ProgressBar::new(millis.max(1));
+        bar.duration = Some(duration);
+        TimedProgress {
+            bar,
+            on_complete: None,
         }
     }
 
@@ -55,6 +138,33 @@ impl ProgressBar {
         self
     }
 
+    /// Choose where the bar is drawn (stderr unless set).
+    pub fn draw_target(mut self, target: DrawTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Also show the percentage in the terminal's tab or taskbar button.
+    ///
+    /// Uses the OSC 9;4 progress sequence understood by Windows Terminal,
+    /// ConEmu and others (ignored elsewhere). The indicator is removed when
+    /// the bar finishes, is abandoned or is dropped.
+    pub fn set_title_progress(&mut self, enabled: bool) {
+        if !enabled {
+            self.clear_title_progress();
+        }
+        self.title_progress = enabled;
+    }
+
+    /// Raise a desktop notification titled `message` when the bar finishes.
+    ///
+    /// Lets users who switched away know the work is done; see
+    /// [`term::notify`](crate::term::notify). Abandoning the bar sends nothing.
+    pub fn notify_on_finish(mut self, message: impl Into<String>) -> Self {
+        self.finish_notification = Some(message.into());
+        self
+    }
+
     /// Update the progress bar to the given position.
     pub fn set(&mut self, position: u64) {
         self.current = position;
@@ -67,23 +177,102 @@ impl ProgressBar {
         self.render();
     }
 
+    /// Get the current position.
+    pub fn position(&self) -> u64 {
+        self.current
+    }
+
     /// Finish the progress bar with an optional message.
     pub fn finish(&mut self, message: &str) {
         self.current = self.total;
-        self.render();
+        if crate::verbosity::is_quiet() {
+            return;
+        }
+        self.render_last();
+
+        self.clear_title_progress();
 
         // Move to the next line and display completion message
-        println!("\n{}", message);
+        self.write_raw(&format!("\n{}\n", message));
+        self.send_finish_notification(message);
+    }
+
+    /// Finish the progress bar and erase it, leaving the cursor at the start of the line.
+    ///
+    /// Useful for clean logs: nothing of the bar remains once the work is done.
+    pub fn finish_and_clear(&mut self) {
+        self.current = self.total;
+        self.clear_title_progress();
+        // In plain mode nothing was drawn, so there is nothing to erase
+        if !crate::plain::is_plain() {
+            self.write_raw("\r\x1b[2K");
+        }
+        self.send_finish_notification("");
+    }
+
+    /// Finish the progress bar where it is, leaving it visible, and move to the next line.
+    pub fn finish_at_current_pos(&mut self) {
+        self.render_last();
+        self.clear_title_progress();
+        self.write_raw("\n");
+        self.send_finish_notification("");
+    }
+
+    /// Stop the progress bar where it is, leaving it visible, and print a message below it.
+    ///
+    /// Use this when the work failed or was cancelled, so the bar does not claim 100%.
+    pub fn abandon_with_message(&mut self, message: &str) {
+        self.render_last();
+        self.clear_title_progress();
+        self.write_raw(&format!("\n{}\n", message));
+    }
+
+    /// Remove the OSC 9;4 indicator if one is showing.
+    fn clear_title_progress(&mut self) {
+        if self.title_percent.take().is_some() {
+            self.write_raw(TITLE_PROGRESS_CLEAR);
+        }
+    }
+
+    /// Send the [`notify_on_finish`](Self::notify_on_finish) notification once.
+    fn send_finish_notification(&mut self, body: &str) {
+        if let Some(title) = self.finish_notification.take()
+            && !crate::verbosity::is_quiet()
+        {
+            let _ = crate::term::notify(&title, body);
+        }
     }
 
-    /// Render the progress bar to stdout.
+    /// Write raw text to the draw target (suppressed in quiet mode).
+    fn write_raw(&self, text: &str) {
+        if crate::verbosity::is_quiet() {
+            return;
+        }
+        self.target.write(text);
+    }
+
+    /// Render the progress bar to the draw target (suppressed in quiet mode).
     fn render(&mut self) {
+        self.render_frame(false);
+    }
+
+    /// Render the bar one last time, just before its line is ended.
+    fn render_last(&mut self) {
+        self.render_frame(true);
+    }
+
+    /// In [plain mode](crate::plain) only the last frame is written, as a
+    /// line of its own without `\r` or escape sequences.
+    fn render_frame(&mut self, last: bool) {
+        let plain = crate::plain::is_plain();
+        if crate::verbosity::is_quiet() || (plain && !last) {
+            return;
+        }
+
         // Cap current at total to prevent overflow
         let current = self.current.min(self.total);
-
-        let percent = (current as f64 / self.total as f64) * 100.0;
-        let filled_width = (self.width as f64 * (current as f64 / self.total as f64)) as u16;
-        let empty_width = self.width - filled_width;
+        let ratio = current as f64 / self.total as f64;
+        let percent = ratio * 100.0;
 
         // Calculate elapsed time and estimate remaining time
         let elapsed = self.start_time.elapsed();
@@ -99,32 +288,64 @@ impl ProgressBar {
             0.0
         };
 
+        let stats = match self.duration {
+            Some(duration) => format!(
+                " {:.1}% {:.1}s/{:.1}s",
+                percent,
+                current as f64 / 1000.0,
+                duration.as_secs_f64()
+            ),
+            None => format!(
+                " {:.1}% ({}/{}) {:.1}/s ETA: {:.1}s",
+                percent, current, self.total, items_per_sec, remaining_secs
+            ),
+        };
+
+        // Size the bar for the terminal as it is now, so a resize between
+        // ticks (SIGWINCH) is picked up on the next render
+        let term_width = crate::term::Terminal::size().map(|(width, _)| width);
+        let bar_width = bar_width(self.width, term_width, stats.len());
+        let filled_width = ((f64::from(bar_width) * ratio) as u16).min(bar_width);
+        let empty_width = bar_width - filled_width;
+
         // Build the progress bar based on style
-        let bar = self.build_bar(filled_width, empty_width);
+        let bar = self.build_bar(filled_width, empty_width, bar_width);
+
+        // After a resize the old line may be longer than the new one: clear it
+        let clear =
+            if !plain && self.last_term_width.is_some() && self.last_term_width != term_width {
+                "\x1b[2K"
+            } else {
+                ""
+            };
+        self.last_term_width = term_width;
+
+        let carriage_return = if plain { "" } else { "\r" };
+        let mut output = format!("{}{}[{}]{}", carriage_return, clear, bar, stats);
 
-        // Format the progress bar
-        let mut output = format!(
-            "\r[{}] {:.1}% ({}/{}) {:.1}/s ETA: {:.1}s",
-            bar, percent, current, self.total, items_per_sec, remaining_secs
-        );
+        // Only send the indicator when the whole percentage changes
+        let title = if self.title_progress && !plain && self.title_percent != Some(percent as u8) {
+            self.title_percent = Some(percent as u8);
+            format!("\x1b]9;4;1;{}\x07", percent as u8)
+        } else {
+            String::new()
+        };
 
-        // Truncate if too long for terminal
-        if let Some((width, _)) = crate::term::Terminal::size() {
-            let max_len = width as usize;
+        // Truncate if still too long (very narrow terminals)
+        if let Some(width) = term_width {
+            let max_len = clear.len() + usize::from(width);
             if output.len() > max_len {
                 output.truncate(max_len);
             }
         }
 
         // Print the progress bar (without newline)
-        let stdout = io::stdout();
-        let mut handle = stdout.lock();
-        let _ = handle.write_all(output.as_bytes());
-        let _ = handle.flush();
+        output.push_str(&title);
+        self.write_raw(&output);
     }
 
     /// Build the progress bar string based on the selected style
-    fn build_bar(&mut self, filled_width: u16, empty_width: u16) -> String {
+    fn build_bar(&mut self, filled_width: u16, empty_width: u16, bar_width: u16) -> String {
         match self.style {
             ProgressStyle::Classic => {
                 // [==========          ]
@@ -137,9 +358,9 @@ impl ProgressBar {
             ProgressStyle::Arrow => {
                 // [=========>          ]
                 if filled_width == 0 {
-                    " ".repeat(self.width as usize)
-                } else if filled_width >= self.width {
-                    "=".repeat(self.width as usize)
+                    " ".repeat(bar_width as usize)
+                } else if filled_width >= bar_width {
+                    "=".repeat(bar_width as usize)
                 } else {
                     format!(
                         "{}>{}",
@@ -162,8 +383,8 @@ impl ProgressBar {
                 self.spinner_frame = (self.spinner_frame + 1) % SPINNER_CHARS.len();
                 let spinner_char = SPINNER_CHARS[self.spinner_frame];
 
-                let mut bar = String::with_capacity(self.width as usize);
-                for i in 0..self.width {
+                let mut bar = String::with_capacity(bar_width as usize);
+                for i in 0..bar_width {
                     if i < filled_width {
                         bar.push(spinner_char);
                     } else {
@@ -175,3 +396,164 @@ impl ProgressBar {
         }
     }
 }
+
+/// A progress bar that fills over a fixed duration, see [`ProgressBar::for_duration`]
+pub struct TimedProgress {
+    bar: ProgressBar,
+    on_complete: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl std::fmt::Debug for TimedProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimedProgress")
+            .field("bar", &self.bar)
+            .field("on_complete", &self.on_complete.is_some())
+            .finish()
+    }
+}
+
+impl TimedProgress {
+    /// Set the width of the progress bar.
+    pub fn width(mut self, width: u16) -> Self {
+        self.bar.width = width;
+        self
+    }
+
+    /// Set the style of the progress bar.
+    pub fn with_style(mut self, style: ProgressStyle) -> Self {
+        self.bar.style = style;
+        self
+    }
+
+    /// Choose where the bar is drawn (stderr unless set).
+    pub fn draw_target(mut self, target: DrawTarget) -> Self {
+        self.bar.target = target;
+        self
+    }
+
+    /// Run `callback` once the full duration has passed (not when cancelled).
+    ///
+    /// The callback runs on the ticker thread.
+    pub fn on_complete(mut self, callback: impl FnOnce() + Send + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Start ticking on a background thread.
+    pub fn start(self) -> TimedProgressHandle {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancelled);
+        let TimedProgress {
+            mut bar,
+            on_complete,
+        } = self;
+
+        let thread = thread::spawn(move || {
+            let started = Instant::now();
+            loop {
+                if flag.load(Ordering::Relaxed) {
+                    bar.finish_at_current_pos();
+                    return false;
+                }
+                let elapsed = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed >= bar.total {
+                    bar.set(bar.total);
+                    bar.finish_at_current_pos();
+                    if let Some(callback) = on_complete {
+                        callback();
+                    }
+                    return true;
+                }
+                bar.set(elapsed);
+                thread::sleep(TICK_INTERVAL.min(Duration::from_millis(bar.total - elapsed)));
+            }
+        });
+
+        TimedProgressHandle {
+            cancelled,
+            thread: Some(thread),
+        }
+    }
+}
+
+/// A running [`TimedProgress`] bar
+///
+/// Dropping the handle cancels the bar.
+#[derive(Debug)]
+pub struct TimedProgressHandle {
+    cancelled: Arc<AtomicBool>,
+    thread: Option<JoinHandle<bool>>,
+}
+
+impl TimedProgressHandle {
+    /// Check whether the bar has stopped (completed or cancelled).
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// Stop the bar where it is; returns `true` if it had already completed.
+    pub fn cancel(mut self) -> bool {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.join()
+    }
+
+    /// Block until the full duration has passed; returns `true` when it completed.
+    pub fn wait(mut self) -> bool {
+        self.join()
+    }
+
+    fn join(&mut self) -> bool {
+        self.thread
+            .take()
+            .is_some_and(|thread| thread.join().unwrap_or(false))
+    }
+}
+
+impl Drop for TimedProgressHandle {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.join();
+    }
+}
+
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        self.clear_title_progress();
+    }
+}
+
+/// Width of the bar itself: the configured width, shrunk so that
+/// `\r[` + bar + `]` + stats fits on one line of the terminal
+fn bar_width(configured: u16, term_width: Option<u16>, stats_len: usize) -> u16 {
+    let Some(term_width) = term_width else {
+        return configured;
+    };
+    // Leave the last column free, where terminals would wrap
+    let available = usize::from(term_width).saturating_sub(stats_len + 3);
+    configured.min(u16::try_from(available).unwrap_or(u16::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_width_follows_terminal() {
+        let stats = " 50.0% (5/10) 1.0/s ETA: 5.0s".len();
+        assert_eq!(bar_width(40, None, stats), 40);
+        assert_eq!(bar_width(40, Some(200), stats), 40);
+        assert_eq!(bar_width(40, Some(50), stats), 50 - stats as u16 - 3);
+        assert_eq!(bar_width(40, Some(10), stats), 0);
+    }
+
+    #[test]
+    fn test_narrow_bar_renders_without_overflow() {
+        let mut pb = ProgressBar::new(10).with_style(ProgressStyle::Arrow);
+        for bar_width in [0, 1, 3] {
+            for filled in 0..=bar_width {
+                let bar = pb.build_bar(filled, bar_width - filled, bar_width);
+                assert_eq!(bar.chars().count(), usize::from(bar_width));
+            }
+        }
+    }
+}
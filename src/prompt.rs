@@ -10,55 +10,311 @@
 //! ```
 //!
 //! This module provides utilities for interactive user input in CLI applications.
+//!
+//! Every prompt is drawn with the process-wide [`PromptTheme`]; call
+//! [`Prompt::set_theme`] once at startup to rebrand them all.
+//!
+//! ```no_run
+//! use zfish::Color;
+//! use zfish::prompt::{Prompt, PromptTheme};
+//!
+//! Prompt::set_theme(PromptTheme {
+//!     prefix: "›",
+//!     highlight: Some(Color::Magenta),
+//!     ..PromptTheme::default()
+//! });
+//! let name = Prompt::input("Project name").unwrap();
+//! ```
+//!
+//! For options that need explaining, [`Prompt::question`] adds a short hint
+//! and a longer help text that is shown when the user answers `?`.
+//!
+//! ```no_run
+//! use zfish::prompt::Prompt;
+//!
+//! let tls = Prompt::question("Enable TLS?")
+//!     .hint("press ? for details")
+//!     .long_help("Serves HTTPS on port 443.\nNeeds a certificate in ./certs.")
+//!     .confirm(true)
+//!     .unwrap();
+//! ```
+//!
+//! [`Prompt::confirm_opt`], [`Prompt::input_opt`] and [`Prompt::select_opt`]
+//! let the user skip a question with Esc, returning `None`. zfish has no
+//! wizard or multi-step flow of its own, so deciding which question comes
+//! next after a skip is left to the caller:
+//!
+//! ```no_run
+//! use zfish::prompt::Prompt;
+//!
+//! let tls = Prompt::confirm_opt("Enable TLS?", false).unwrap();
+//! if tls == Some(true) {
+//!     let _cert = Prompt::input_opt("Certificate path").unwrap();
+//! }
+//! ```
 
-use std::io::{self, Write};
+use crate::line_editor::LineEditor;
+use crate::os::{self, ColorBackend};
+use crate::style::{Color, Style};
+use std::io::{self, IsTerminal, Write};
+use std::sync::RwLock;
+
+static THEME: RwLock<PromptTheme> = RwLock::new(PromptTheme::DEFAULT);
+
+/// Symbols and colors shared by all prompts
+#[derive(Debug, Clone, Copy)]
+pub struct PromptTheme {
+    /// Shown before every question (`?`)
+    pub prefix: &'static str,
+    /// Marks an answered question (`✔`)
+    pub success: &'static str,
+    /// Marks a rejected answer (`✖`)
+    pub error: &'static str,
+    /// Color of the prefix and of option numbers; `None` keeps them plain
+    pub highlight: Option<Color>,
+    /// Color of the success and error symbols, also dimming answer summaries;
+    /// `None` keeps them plain
+    pub status_colors: Option<(Color, Color)>,
+    /// Replace each answered question with a one-line summary
+    /// (only on terminals where the cursor can be moved back)
+    pub summary: bool,
+}
+
+impl PromptTheme {
+    const DEFAULT: Self = Self {
+        prefix: "?",
+        success: "✔",
+        error: "✖",
+        highlight: Some(Color::Cyan),
+        status_colors: Some((Color::Green, Color::Red)),
+        summary: true,
+    };
+
+    /// The default symbols without any colors
+    pub fn plain() -> Self {
+        Self {
+            highlight: None,
+            status_colors: None,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// ASCII symbols, no colors and no summaries, used in
+    /// [plain mode](crate::plain) whatever theme is set
+    pub fn ascii() -> Self {
+        Self {
+            success: "+",
+            error: "x",
+            summary: false,
+            ..Self::plain()
+        }
+    }
+
+    /// `? Project name ` — the question line, ready for input
+    pub fn format_question(&self, prompt: &str) -> String {
+        format!(
+            "{} {} ",
+            paint(self.highlight, self.prefix),
+            prompt.trim_end()
+        )
+    }
+
+    /// `✖ message` — shown when an answer is rejected
+    pub fn format_error(&self, message: &str) -> String {
+        let color = self.status_colors.map(|(_, error)| error);
+        format!("{} {}", paint(color, self.error), message)
+    }
+
+    /// `✔ Project name · my-app` — a record of an answered question
+    pub fn format_answer(&self, prompt: &str, answer: &str) -> String {
+        let text = format!("{} · {}", prompt.trim_end(), answer);
+        match self.status_colors {
+            Some((success, _)) => {
+                format!("{} {}", success.paint(self.success), Style::Dim.apply(text))
+            }
+            None => format!("{} {}", self.success, text),
+        }
+    }
+
+    /// The long help of a [`Question`], indented below it
+    fn format_help(&self, text: &str) -> String {
+        text.lines()
+            .map(|line| {
+                let line = format!("  {}", line);
+                match self.status_colors {
+                    Some(_) => format!("{}\n", Style::Dim.apply(line)),
+                    None => format!("{}\n", line),
+                }
+            })
+            .collect()
+    }
+
+    fn format_hint(&self, hint: &str) -> String {
+        let hint = format!("({})", hint);
+        match self.status_colors {
+            Some(_) => Style::Dim.apply(hint).to_string(),
+            None => hint,
+        }
+    }
+
+    fn format_option(&self, number: usize, option: &str) -> String {
+        format!(
+            "  {} {}",
+            paint(self.highlight, &format!("{})", number)),
+            option
+        )
+    }
+}
+
+impl Default for PromptTheme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Replace the last `lines` lines of output with the answer summary
+fn write_summary(out: &mut impl Write, lines: usize, prompt: &str, answer: &str) -> io::Result<()> {
+    let theme = Prompt::theme();
+    if !theme.summary || !can_rewrite_lines() {
+        return Ok(());
+    }
+    write!(
+        out,
+        "\x1b[{}A\r\x1b[J{}\n",
+        lines,
+        theme.format_answer(prompt, answer)
+    )?;
+    out.flush()
+}
+
+/// Interactive session on a terminal with raw mode and ANSI cursor movement
+fn can_rewrite_lines() -> bool {
+    os::RAW_MODE
+        && os::color_backend() == ColorBackend::Ansi
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+}
+
+fn paint(color: Option<Color>, text: &str) -> String {
+    match color {
+        Some(color) => color.paint(text).to_string(),
+        None => text.to_string(),
+    }
+}
 
 /// Utilities for prompting user input
 #[derive(Debug)]
 pub struct Prompt;
 
 impl Prompt {
+    /// Sets the theme used by every prompt from now on
+    pub fn set_theme(theme: PromptTheme) {
+        *THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+    }
+
+    /// Gets the current prompt theme ([`PromptTheme::ascii`] in
+    /// [plain mode](crate::plain))
+    pub fn theme() -> PromptTheme {
+        if crate::plain::is_plain() {
+            return PromptTheme::ascii();
+        }
+        *THEME.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Starts a [`Question`] that can carry a hint and long help text
+    pub fn question(prompt: impl Into<String>) -> Question {
+        Question {
+            prompt: prompt.into(),
+            hint: None,
+            long_help: None,
+        }
+    }
+
     /// Prompt for a yes/no confirmation
     pub fn confirm(prompt: &str, default: bool) -> io::Result<bool> {
+        Self::question(prompt).confirm(default)
+    }
+
+    /// Like [`confirm`](Self::confirm), but Esc or end of input skips the question
+    pub fn confirm_opt(prompt: &str, default: bool) -> io::Result<Option<bool>> {
+        Self::question(prompt).confirm_opt(default)
+    }
+
+    fn confirm_inner(q: &Question, default: bool, optional: bool) -> io::Result<Option<bool>> {
         let yes_no = if default { "[Y/n]" } else { "[y/N]" };
-        let full_prompt = format!("{} {} ", prompt, yes_no);
+        let full_prompt = q.format(&Self::theme(), &format!(" {}", yes_no));
+        let prompt = q.prompt.as_str();
 
         let stdout = io::stdout();
         let mut handle = stdout.lock();
-        handle.write_all(full_prompt.as_bytes())?;
-        handle.flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let mut lines = 1;
+        let input = loop {
+            let Some(input) = read_answer(&mut handle, &full_prompt, optional)? else {
+                write_summary(&mut handle, lines, prompt, SKIPPED)?;
+                return Ok(None);
+            };
+            if !q.show_help_for(&mut handle, &input, &mut lines)? {
+                break input;
+            }
+            // The question is asked again below the help
+            lines += 1;
+        };
 
-        let input = input.trim().to_lowercase();
-
-        Ok(match input.as_str() {
+        let answer = match input.trim().to_lowercase().as_str() {
             "y" | "yes" => true,
             "n" | "no" => false,
             "" => default,
             _ => false,
-        })
+        };
+        write_summary(
+            &mut handle,
+            lines,
+            prompt,
+            if answer { "yes" } else { "no" },
+        )?;
+        Ok(Some(answer))
     }
 
     /// Prompt for a line of text input
     pub fn input(prompt: &str) -> io::Result<String> {
-        let full_prompt = format!("{} ", prompt);
+        Self::question(prompt).input()
+    }
+
+    /// Like [`input`](Self::input), but returns `None` when the question is
+    /// skipped with Esc, an empty answer or end of input
+    pub fn input_opt(prompt: &str) -> io::Result<Option<String>> {
+        Self::question(prompt).input_opt()
+    }
+
+    fn input_inner(q: &Question, optional: bool) -> io::Result<Option<String>> {
+        let full_prompt = q.format(&Self::theme(), "");
 
         let stdout = io::stdout();
         let mut handle = stdout.lock();
-        handle.write_all(full_prompt.as_bytes())?;
-        handle.flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let mut lines = 1;
+        let input = loop {
+            let answer = read_answer(&mut handle, &full_prompt, optional)?;
+            match answer {
+                Some(input) if q.show_help_for(&mut handle, &input, &mut lines)? => lines += 1,
+                answer => break answer,
+            }
+        };
+        let input = input
+            .map(|input| input.trim().to_string())
+            .filter(|input| !optional || !input.is_empty());
 
-        Ok(input.trim().to_string())
+        write_summary(
+            &mut handle,
+            lines,
+            &q.prompt,
+            input.as_deref().unwrap_or(SKIPPED),
+        )?;
+        Ok(input)
     }
 
     /// Prompt for a password with hidden input
     pub fn password(prompt: &str) -> io::Result<String> {
-        let full_prompt = format!("{} ", prompt);
+        let full_prompt = Self::theme().format_question(prompt);
 
         let stdout = io::stdout();
         let mut handle = stdout.lock();
@@ -69,13 +325,241 @@ impl Prompt {
         let password = crate::os::read_password()?;
 
         // Print a newline since we don't echo during password input
-        println!();
+        writeln!(handle)?;
 
+        write_summary(&mut handle, 1, prompt, "********")?;
         Ok(password)
     }
 
+    /// Prompt the user to pick one of `options` from a numbered list
+    ///
+    /// Accepts either the number or the option text; re-asks on anything else.
+    /// Returns the index of the chosen option.
+    pub fn select(prompt: &str, options: &[&str]) -> io::Result<usize> {
+        Self::question(prompt).select(options)
+    }
+
+    /// Like [`select`](Self::select), but Esc or end of input skips the question
+    pub fn select_opt(prompt: &str, options: &[&str]) -> io::Result<Option<usize>> {
+        Self::question(prompt).select_opt(options)
+    }
+
+    fn select_inner(q: &Question, options: &[&str], optional: bool) -> io::Result<Option<usize>> {
+        if options.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "select requires at least one option",
+            ));
+        }
+
+        let theme = Self::theme();
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+        let prompt = q.prompt.as_str();
+        writeln!(handle, "{}", q.format(&theme, "").trim_end())?;
+        for (idx, option) in options.iter().enumerate() {
+            writeln!(handle, "{}", theme.format_option(idx + 1, option))?;
+        }
+
+        // Lines written so far, all replaced by the summary at the end
+        let mut lines = 1 + options.len();
+        let mut first_try = true;
+        loop {
+            if !first_try {
+                lines += 1;
+                writeln!(
+                    handle,
+                    "{}",
+                    theme.format_error(&format!(
+                        "Enter a number from 1 to {} or an option name",
+                        options.len()
+                    ))
+                )?;
+            }
+            first_try = false;
+
+            let question = format!("Select [1-{}]: ", options.len());
+            let answer = read_answer(&mut handle, &question, optional)?;
+            lines += 1;
+            let Some(input) = answer else {
+                if optional {
+                    write_summary(&mut handle, lines, prompt, SKIPPED)?;
+                }
+                return Ok(None);
+            };
+            if q.show_help_for(&mut handle, &input, &mut lines)? {
+                first_try = true;
+                continue;
+            }
+
+            let input = input.trim();
+            let choice = match input.parse::<usize>() {
+                Ok(n) if (1..=options.len()).contains(&n) => Some(n - 1),
+                _ => options.iter().position(|o| *o == input),
+            };
+            if let Some(idx) = choice {
+                write_summary(&mut handle, lines, prompt, options[idx])?;
+                return Ok(Some(idx));
+            }
+        }
+    }
+
     /// Alias for `input` - prompt for text input
     pub fn text(prompt: &str) -> io::Result<String> {
         Self::input(prompt)
     }
 }
+
+/// A question with a short hint and on-demand help, from [`Prompt::question`]
+///
+/// The hint is shown after the question. Answering `?` prints the long help
+/// below it and asks again, so the explanation is there for users who need
+/// it without cluttering the prompt for everyone else.
+#[derive(Debug, Clone)]
+pub struct Question {
+    prompt: String,
+    hint: Option<String>,
+    long_help: Option<String>,
+}
+
+impl Question {
+    /// Sets the hint shown after the question, e.g. `"press ? for details"`
+    ///
+    /// With long help but no hint, `"? for help"` is shown.
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Sets the explanation shown when the user answers `?`
+    pub fn long_help(mut self, text: impl Into<String>) -> Self {
+        self.long_help = Some(text.into());
+        self
+    }
+
+    /// Asks for a line of text, see [`Prompt::input`]
+    pub fn input(&self) -> io::Result<String> {
+        Ok(Prompt::input_inner(self, false)?.unwrap_or_default())
+    }
+
+    /// Asks for optional text, see [`Prompt::input_opt`]
+    pub fn input_opt(&self) -> io::Result<Option<String>> {
+        Prompt::input_inner(self, true)
+    }
+
+    /// Asks yes or no, see [`Prompt::confirm`]
+    pub fn confirm(&self, default: bool) -> io::Result<bool> {
+        Ok(Prompt::confirm_inner(self, default, false)?.unwrap_or(default))
+    }
+
+    /// Asks yes or no, allowing a skip, see [`Prompt::confirm_opt`]
+    pub fn confirm_opt(&self, default: bool) -> io::Result<Option<bool>> {
+        Prompt::confirm_inner(self, default, true)
+    }
+
+    /// Asks for one of `options`, see [`Prompt::select`]
+    pub fn select(&self, options: &[&str]) -> io::Result<usize> {
+        Prompt::select_inner(self, options, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "no selection made"))
+    }
+
+    /// Asks for one of `options`, allowing a skip, see [`Prompt::select_opt`]
+    pub fn select_opt(&self, options: &[&str]) -> io::Result<Option<usize>> {
+        Prompt::select_inner(self, options, true)
+    }
+
+    /// The question line: prompt, `suffix` (e.g. `[Y/n]`), then the hint
+    fn format(&self, theme: &PromptTheme, suffix: &str) -> String {
+        let hint = match (&self.hint, &self.long_help) {
+            (Some(hint), _) => format!(" {}", theme.format_hint(hint)),
+            (None, Some(_)) => format!(" {}", theme.format_hint("? for help")),
+            (None, None) => String::new(),
+        };
+        theme.format_question(&format!("{}{}{}", self.prompt.trim_end(), suffix, hint))
+    }
+
+    /// Print the long help if `input` asks for it, adding its lines to `lines`
+    fn show_help_for(
+        &self,
+        out: &mut impl Write,
+        input: &str,
+        lines: &mut usize,
+    ) -> io::Result<bool> {
+        let Some(text) = self.long_help.as_deref().filter(|_| input.trim() == "?") else {
+            return Ok(false);
+        };
+        let panel = Prompt::theme().format_help(text);
+        out.write_all(panel.as_bytes())?;
+        out.flush()?;
+        *lines += panel.lines().count();
+        Ok(true)
+    }
+}
+
+/// Summary answer for a skipped question
+const SKIPPED: &str = "skipped";
+
+/// Show `question` and read one line; `None` at end of input
+///
+/// Optional questions read through the line editor so that Esc can skip them.
+fn read_answer(out: &mut impl Write, question: &str, optional: bool) -> io::Result<Option<String>> {
+    if optional {
+        return LineEditor::new(question).cancel_on_esc(true).read_line();
+    }
+
+    out.write_all(question.as_bytes())?;
+    out.flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_theme_formatting() {
+        let theme = PromptTheme::plain();
+        assert_eq!(theme.format_question("Project name? "), "? Project name? ");
+        assert_eq!(
+            theme.format_answer("Project name", "my-app"),
+            "✔ Project name · my-app"
+        );
+        assert_eq!(theme.format_error("try again"), "✖ try again");
+        assert_eq!(theme.format_option(2, "green"), "  2) green");
+    }
+
+    #[test]
+    fn test_question_hint_and_help() {
+        let theme = PromptTheme::plain();
+        let question = Prompt::question("Enable TLS?").long_help("Serves HTTPS.\nNeeds a cert.");
+        assert_eq!(
+            question.format(&theme, " [Y/n]"),
+            "? Enable TLS? [Y/n] (? for help) "
+        );
+        let question = question.hint("press ? for details");
+        assert_eq!(
+            question.format(&theme, ""),
+            "? Enable TLS? (press ? for details) "
+        );
+        assert_eq!(
+            theme.format_help("Serves HTTPS.\nNeeds a cert."),
+            "  Serves HTTPS.\n  Needs a cert.\n"
+        );
+        assert_eq!(Prompt::question("Name").format(&theme, ""), "? Name ");
+    }
+
+    #[test]
+    fn test_custom_symbols() {
+        let theme = PromptTheme {
+            prefix: ">",
+            success: "ok",
+            ..PromptTheme::plain()
+        };
+        assert_eq!(theme.format_question("Name"), "> Name ");
+        assert_eq!(theme.format_answer("Name", "x"), "ok Name · x");
+    }
+}
@@ -0,0 +1,804 @@
+//! # Shell Completions
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — shell.rs                                              ║
+//! ║  Completion scripts generated from the command tree           ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`Command::generate_completions`](crate::command::Command::generate_completions)
+//! writes a completion script for a [`Shell`] from the same builder
+//! definition the parser uses, so completions never drift from the CLI.
+//! Scripts complete subcommands (and their aliases), flags, and the
+//! `possible_values` of options and positionals; arguments with a path
+//! [`ValueHint`] complete file or directory names, and `Hostname` and
+//! `CommandName` hints complete known hosts and commands (PowerShell has no
+//! host list, so it offers nothing for those). Zsh, fish and PowerShell
+//! also show each flag's and subcommand's help as its description.
+//!
+//! | Shell        | Install the script as                                         |
+//! |--------------|---------------------------------------------------------------|
+//! | `bash`       | `~/.local/share/bash-completion/completions/<name>`           |
+//! | `zsh`        | `_<name>` in a directory on `$fpath`                          |
+//! | `fish`       | `~/.config/fish/completions/<name>.fish`                      |
+//! | `powershell` | dot-sourced from `$PROFILE`                                   |
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::command::{App, Arg, Command};
+//! use zfish::shell::Shell;
+//!
+//! let app = App::new("myapp")
+//!     .arg(Arg::new("color").long("color").possible_values(&["auto", "never"]))
+//!     .subcommand(Command::new("build").alias("b"));
+//!
+//! let script = app.generate_completions(Shell::Bash);
+//! assert!(script.contains("complete -F _myapp"));
+//! ```
+//!
+//! A `completions <shell>` subcommand can print the script for whichever
+//! shell the user names:
+//!
+//! ```no_run
+//! use zfish::command::{App, Arg, Command};
+//! use zfish::shell::Shell;
+//!
+//! let app = || {
+//!     App::new("myapp").subcommand(
+//!         Command::new("completions").arg(
+//!             Arg::new("shell")
+//!                 .index(0)
+//!                 .required(true)
+//!                 .possible_values(&["bash", "zsh", "fish", "powershell"]),
+//!         ),
+//!     )
+//! };
+//!
+//! let matches = app().get_matches();
+//! if let Some(("completions", sub)) = matches.subcommand() {
+//!     let shell: Shell = sub.value_of("shell").unwrap().parse().unwrap();
+//!     app().print_completions(shell, &mut std::io::stdout()).unwrap();
+//! }
+//! ```
+
+use crate::command::ValueHint;
+use std::fmt::{self, Write};
+
+/// A shell that completion scripts can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Shell {
+    /// GNU Bash (with or without the bash-completion package)
+    Bash,
+    /// Z shell, through its `_arguments` completion system
+    Zsh,
+    /// The fish shell
+    Fish,
+    /// PowerShell 5.1 and PowerShell 7+ (`pwsh`)
+    PowerShell,
+}
+
+impl Shell {
+    /// Every supported shell
+    pub const ALL: &'static [Shell] = &[Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell];
+
+    /// The shell's name, as accepted by [`FromStr`](std::str::FromStr)
+    /// (which also takes `pwsh` for PowerShell)
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::PowerShell => "powershell",
+        }
+    }
+}
+
+impl std::str::FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("pwsh") {
+            return Ok(Shell::PowerShell);
+        }
+        Shell::ALL
+            .iter()
+            .copied()
+            .find(|shell| shell.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Shell::ALL.iter().map(|shell| shell.name()).collect();
+                format!("unknown shell '{}', expected {}", s, names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A command as completion scripts see it, built by
+/// `Command::completion_spec`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommandSpec {
+    pub(crate) name: String,
+    pub(crate) about: Option<String>,
+    pub(crate) aliases: Vec<String>,
+    /// Flags and options, including inherited globals and `-h`/`-V`
+    pub(crate) options: Vec<OptionSpec>,
+    /// Positional arguments, in index order
+    pub(crate) positionals: Vec<PositionalSpec>,
+    pub(crate) subcommands: Vec<CommandSpec>,
+}
+
+/// A flag or option of a [`CommandSpec`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OptionSpec {
+    pub(crate) name: String,
+    pub(crate) short: Option<char>,
+    pub(crate) long: Option<String>,
+    pub(crate) help: Option<String>,
+    pub(crate) takes_value: bool,
+    pub(crate) multiple: bool,
+    pub(crate) possible_values: Vec<String>,
+    pub(crate) value_hint: ValueHint,
+}
+
+/// A positional argument of a [`CommandSpec`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PositionalSpec {
+    pub(crate) name: String,
+    /// Takes every remaining value (`last` or `multiple`)
+    pub(crate) multiple: bool,
+    pub(crate) possible_values: Vec<String>,
+    pub(crate) value_hint: ValueHint,
+}
+
+impl OptionSpec {
+    /// `-o` and `--output`, whichever exist
+    fn flags(&self) -> Vec<String> {
+        let short = self.short.map(|c| format!("-{}", c));
+        let long = self.long.as_ref().map(|l| format!("--{}", l));
+        short.into_iter().chain(long).collect()
+    }
+}
+
+/// Writes the completion script for `spec` in `shell`'s language
+pub(crate) fn generate(spec: &CommandSpec, shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(spec),
+        Shell::Zsh => zsh(spec),
+        Shell::Fish => fish(spec),
+        Shell::PowerShell => powershell(spec),
+    }
+}
+
+/// The first line of a help text, for one-line descriptions
+fn summary(help: Option<&String>) -> Option<&str> {
+    help.and_then(|help| help.lines().next())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+}
+
+/// A bash completion function, `_<name>`, registered with `complete -F`
+///
+/// The function walks the words before the cursor to find the subcommand
+/// being completed (skipping option values), then offers flags when the word
+/// starts with `-`, and subcommands and positional values otherwise. An empty
+/// result falls back to bash's filename completion. Fixed words go through a
+/// `_<name>_words` helper rather than `compgen -W`, which would split values
+/// containing spaces and strip their quotes.
+fn bash(spec: &CommandSpec) -> String {
+    let function = format!("_{}", identifier(&spec.name));
+    let mut out = String::new();
+    let _ = writeln!(out, "{}() {{", function);
+    out.push_str("    local cur prev cmd i\n");
+    out.push_str("    COMPREPLY=()\n");
+    out.push_str("    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    out.push_str("    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    let _ = writeln!(out, "    cmd=\"{}\"", identifier(&spec.name));
+    out.push('\n');
+    out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+    out.push_str("        case \"${cmd},${COMP_WORDS[i]}\" in\n");
+    bash_walk_cases(spec, &identifier(&spec.name), &mut out);
+    out.push_str("        esac\n");
+    out.push_str("    done\n\n");
+    out.push_str("    case \"${cmd}\" in\n");
+    bash_command_cases(spec, &function, &identifier(&spec.name), &mut out);
+    out.push_str("    esac\n");
+    out.push_str("}\n\n");
+    // Offers each argument that starts with `cur`, escaped as `printf %q` does
+    let _ = writeln!(out, "{}_words() {{", function);
+    out.push_str("    local word\n");
+    out.push_str("    for word in \"$@\"; do\n");
+    out.push_str("        if [[ \"${word}\" == \"${cur}\"* ]]; then\n");
+    out.push_str("            COMPREPLY+=(\"$(printf '%q' \"${word}\")\")\n");
+    out.push_str("        fi\n");
+    out.push_str("    done\n");
+    out.push_str("}\n\n");
+    let _ = writeln!(
+        out,
+        "complete -F {} -o bashdefault -o default {}",
+        function, spec.name
+    );
+    out
+}
+
+/// `case` arms that move `cmd` into a subcommand or skip an option's value
+fn bash_walk_cases(spec: &CommandSpec, path: &str, out: &mut String) {
+    let valued: Vec<String> = spec
+        .options
+        .iter()
+        .filter(|opt| opt.takes_value)
+        .flat_map(OptionSpec::flags)
+        .map(|flag| format!("\"{},{}\"", path, flag))
+        .collect();
+    if !valued.is_empty() {
+        let _ = writeln!(out, "            {}) ((i++)) ;;", valued.join("|"));
+    }
+
+    for sub in &spec.subcommands {
+        let sub_path = format!("{}__{}", path, identifier(&sub.name));
+        let patterns: Vec<String> = std::iter::once(&sub.name)
+            .chain(&sub.aliases)
+            .map(|name| format!("\"{},{}\"", path, name))
+            .collect();
+        let _ = writeln!(
+            out,
+            "            {}) cmd=\"{}\" ;;",
+            patterns.join("|"),
+            sub_path
+        );
+        bash_walk_cases(sub, &sub_path, out);
+    }
+}
+
+/// One `case` arm per command, completing the word under the cursor
+///
+/// `function` is the completion function, whose `_words` helper offers words.
+fn bash_command_cases(spec: &CommandSpec, function: &str, path: &str, out: &mut String) {
+    let flags: Vec<String> = spec.options.iter().flat_map(OptionSpec::flags).collect();
+    let words: Vec<&String> = spec
+        .subcommands
+        .iter()
+        .map(|sub| &sub.name)
+        .chain(spec.positionals.iter().flat_map(|pos| &pos.possible_values))
+        .collect();
+
+    let _ = writeln!(out, "        {})", path);
+    let valued: Vec<&OptionSpec> = spec.options.iter().filter(|opt| opt.takes_value).collect();
+    if !valued.is_empty() {
+        out.push_str("            case \"${prev}\" in\n");
+        for opt in valued {
+            let _ = writeln!(out, "                {})", opt.flags().join("|"));
+            if !opt.possible_values.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "                    {}_words {}",
+                    function,
+                    bash_words(&opt.possible_values)
+                );
+                out.push_str("                    return 0 ;;\n");
+                continue;
+            }
+            let values = match opt.value_hint {
+                ValueHint::AnyPath | ValueHint::FilePath => "-f",
+                ValueHint::DirPath => "-d",
+                ValueHint::Hostname => "-A hostname",
+                ValueHint::CommandName => "-c",
+                _ => {
+                    // Unknown values: leave it to bash's default completion
+                    out.push_str("                    return 0 ;;\n");
+                    continue;
+                }
+            };
+            let _ = writeln!(
+                out,
+                "                    COMPREPLY=($(compgen {} -- \"${{cur}}\"))",
+                values
+            );
+            out.push_str("                    return 0 ;;\n");
+        }
+        out.push_str("            esac\n");
+    }
+    out.push_str("            if [[ \"${cur}\" == -* ]]; then\n");
+    let _ = writeln!(
+        out,
+        "                {}_words {}",
+        function,
+        bash_words(&flags)
+    );
+    if !words.is_empty() {
+        out.push_str("            else\n");
+        let _ = writeln!(
+            out,
+            "                {}_words {}",
+            function,
+            bash_words(&words)
+        );
+    }
+    out.push_str("            fi\n");
+    out.push_str("            return 0 ;;\n");
+
+    for sub in &spec.subcommands {
+        let sub_path = format!("{}__{}", path, identifier(&sub.name));
+        bash_command_cases(sub, function, &sub_path, out);
+    }
+}
+
+/// Space-separated single-quoted words, passed as separate arguments
+fn bash_words<S: AsRef<str>>(words: &[S]) -> String {
+    let quoted: Vec<String> = words
+        .iter()
+        .map(|word| format!("'{}'", word.as_ref().replace('\'', "'\\''")))
+        .collect();
+    quoted.join(" ")
+}
+
+/// A zsh completion function using `_arguments`, one per command
+///
+/// Works both autoloaded from `$fpath` (`#compdef`) and sourced directly.
+fn zsh(spec: &CommandSpec) -> String {
+    let path = identifier(&spec.name);
+    let mut out = format!("#compdef {}\n\n", spec.name);
+    zsh_function(spec, &path, &mut out);
+    let _ = write!(
+        out,
+        "if [ \"$funcstack[1]\" = \"_{path}\" ]; then\n    _{path} \"$@\"\nelse\n    compdef _{path} {name}\nfi\n",
+        path = path,
+        name = spec.name
+    );
+    out
+}
+
+fn zsh_function(spec: &CommandSpec, path: &str, out: &mut String) {
+    let mut specs: Vec<String> = spec.options.iter().flat_map(zsh_option).collect();
+    if spec.subcommands.is_empty() {
+        for pos in &spec.positionals {
+            let repeat = if pos.multiple { "*" } else { "" };
+            let action = zsh_action(&pos.possible_values, pos.value_hint);
+            specs.push(format!("{}:{}:{}", repeat, zsh_escape(&pos.name), action));
+        }
+    } else {
+        specs.push(format!(": :_{}_commands", path));
+        specs.push("*::arg:->args".to_string());
+    }
+
+    let _ = writeln!(out, "_{}() {{", path);
+    out.push_str("    local curcontext=\"$curcontext\" state line\n");
+    out.push_str("    _arguments -C -s \\\n");
+    for spec in &specs {
+        let _ = writeln!(out, "        {} \\", zsh_quote(spec));
+    }
+    out.push_str("        && return 0\n");
+    if !spec.subcommands.is_empty() {
+        out.push_str("\n    case $state in\n        args)\n            case $line[1] in\n");
+        for sub in &spec.subcommands {
+            let names: Vec<&str> = std::iter::once(&sub.name)
+                .chain(&sub.aliases)
+                .map(String::as_str)
+                .collect();
+            let _ = writeln!(
+                out,
+                "                {}) _{}__{} ;;",
+                names.join("|"),
+                path,
+                identifier(&sub.name)
+            );
+        }
+        out.push_str("            esac\n            ;;\n    esac\n");
+    }
+    out.push_str("}\n\n");
+
+    if !spec.subcommands.is_empty() {
+        let _ = writeln!(out, "_{}_commands() {{", path);
+        out.push_str("    local commands\n    commands=(\n");
+        for sub in &spec.subcommands {
+            let entry = match summary(sub.about.as_ref()) {
+                Some(about) => format!("{}:{}", sub.name.replace(':', "\\:"), about),
+                None => sub.name.replace(':', "\\:"),
+            };
+            let _ = writeln!(out, "        {}", zsh_quote(&entry));
+        }
+        out.push_str("    )\n");
+        let _ = writeln!(
+            out,
+            "    _describe -t commands {} commands",
+            zsh_quote(&format!("{} commands", spec.name))
+        );
+        out.push_str("}\n\n");
+        for sub in &spec.subcommands {
+            zsh_function(sub, &format!("{}__{}", path, identifier(&sub.name)), out);
+        }
+    }
+}
+
+/// `_arguments` specs for one flag or option: `-o+[help]:name:action`
+fn zsh_option(opt: &OptionSpec) -> Vec<String> {
+    let flags = opt.flags();
+    let prefix = if opt.multiple {
+        "*".to_string()
+    } else if flags.len() > 1 {
+        format!("({})", flags.join(" "))
+    } else {
+        String::new()
+    };
+    let help = summary(opt.help.as_ref())
+        .map(|help| format!("[{}]", zsh_escape(help)))
+        .unwrap_or_default();
+    let value = if opt.takes_value {
+        format!(
+            ":{}:{}",
+            zsh_escape(&opt.name),
+            zsh_action(&opt.possible_values, opt.value_hint)
+        )
+    } else {
+        String::new()
+    };
+
+    flags
+        .iter()
+        .map(|flag| {
+            let separator = match (opt.takes_value, flag.starts_with("--")) {
+                (false, _) => "",
+                (true, true) => "=",
+                (true, false) => "+",
+            };
+            format!("{}{}{}{}{}", prefix, flag, separator, help, value)
+        })
+        .collect()
+}
+
+/// What zsh completes for a value: the possible values, or by hint
+fn zsh_action(possible_values: &[String], hint: ValueHint) -> String {
+    if !possible_values.is_empty() {
+        let values: Vec<String> = possible_values
+            .iter()
+            .map(|value| {
+                value
+                    .chars()
+                    .flat_map(|c| match c {
+                        '\\' | ' ' | '(' | ')' | ':' => vec!['\\', c],
+                        c => vec![c],
+                    })
+                    .collect()
+            })
+            .collect();
+        return format!("({})", values.join(" "));
+    }
+    match hint {
+        ValueHint::AnyPath | ValueHint::FilePath => "_files".to_string(),
+        ValueHint::DirPath => "_files -/".to_string(),
+        ValueHint::Hostname => "_hosts".to_string(),
+        ValueHint::CommandName => "_command_names -e".to_string(),
+        _ => "_default".to_string(),
+    }
+}
+
+/// Escape text used inside an `_arguments` spec (help, value names)
+fn zsh_escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '\\' | '[' | ']' | ':' | '$' | '`' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
+
+/// `text` as a single-quoted zsh word
+fn zsh_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// `complete -c` rules, conditioned on a helper that finds the command path
+///
+/// `__fish_<name>_path` walks the tokens before the cursor like the bash
+/// script does and prints the path of the subcommand being completed.
+fn fish(spec: &CommandSpec) -> String {
+    let root = identifier(&spec.name);
+    let helper = format!("__fish_{}_path", root);
+    let mut out = format!("function {}\n", helper);
+    out.push_str("    set -l tokens (commandline -opc)\n");
+    let _ = writeln!(out, "    set -l path {}", root);
+    out.push_str("    set -e tokens[1]\n");
+    out.push_str("    while set -q tokens[1]\n");
+    out.push_str("        switch \"$path,$tokens[1]\"\n");
+    fish_walk_cases(spec, &root, &mut out);
+    out.push_str("        end\n");
+    out.push_str("        set -e tokens[1]\n");
+    out.push_str("    end\n");
+    out.push_str("    echo $path\n");
+    out.push_str("end\n\n");
+    fish_rules(spec, &spec.name, &root, &helper, &mut out);
+    out
+}
+
+fn fish_walk_cases(spec: &CommandSpec, path: &str, out: &mut String) {
+    let valued: Vec<String> = spec
+        .options
+        .iter()
+        .filter(|opt| opt.takes_value)
+        .flat_map(OptionSpec::flags)
+        .map(|flag| fish_quote(&format!("{},{}", path, flag)))
+        .collect();
+    if !valued.is_empty() {
+        let _ = writeln!(out, "            case {}", valued.join(" "));
+        out.push_str("                set -e tokens[1]\n");
+    }
+    for sub in &spec.subcommands {
+        let sub_path = format!("{}__{}", path, identifier(&sub.name));
+        let patterns: Vec<String> = std::iter::once(&sub.name)
+            .chain(&sub.aliases)
+            .map(|name| fish_quote(&format!("{},{}", path, name)))
+            .collect();
+        let _ = writeln!(out, "            case {}", patterns.join(" "));
+        let _ = writeln!(out, "                set path {}", sub_path);
+        fish_walk_cases(sub, &sub_path, out);
+    }
+}
+
+fn fish_rules(spec: &CommandSpec, program: &str, path: &str, helper: &str, out: &mut String) {
+    let rule = format!(
+        "complete -c {} -n {}",
+        program,
+        fish_quote(&format!("test ({}) = {}", helper, path))
+    );
+    let described = |line: String, help: Option<&String>| match summary(help) {
+        Some(help) => format!("{} -d {}", line, fish_quote(help)),
+        None => line,
+    };
+
+    for opt in &spec.options {
+        let mut line = rule.clone();
+        if let Some(short) = opt.short {
+            let _ = write!(line, " -s {}", fish_quote(&short.to_string()));
+        }
+        if let Some(long) = &opt.long {
+            let _ = write!(line, " -l {}", fish_quote(long));
+        }
+        if opt.takes_value {
+            line.push_str(" -r");
+            line.push_str(&fish_values(&opt.possible_values, opt.value_hint));
+        }
+        let _ = writeln!(out, "{}", described(line, opt.help.as_ref()));
+    }
+    for sub in &spec.subcommands {
+        let line = format!("{} -f -a {}", rule, fish_quote(&sub.name));
+        let _ = writeln!(out, "{}", described(line, sub.about.as_ref()));
+    }
+    if spec.subcommands.is_empty() {
+        for pos in &spec.positionals {
+            let values = fish_values(&pos.possible_values, pos.value_hint);
+            if !values.is_empty() {
+                let _ = writeln!(out, "{}{}", rule, values);
+            }
+        }
+    }
+
+    for sub in &spec.subcommands {
+        let sub_path = format!("{}__{}", path, identifier(&sub.name));
+        fish_rules(sub, program, &sub_path, helper, out);
+    }
+}
+
+/// The flags that make fish offer a value's possible values, or paths
+fn fish_values(possible_values: &[String], hint: ValueHint) -> String {
+    if !possible_values.is_empty() {
+        let values: Vec<String> = possible_values.iter().map(|v| fish_escape(v)).collect();
+        return format!(" -f -a {}", fish_quote(&values.join(" ")));
+    }
+    match hint {
+        ValueHint::AnyPath | ValueHint::FilePath => " -F".to_string(),
+        ValueHint::DirPath => " -f -a '(__fish_complete_directories)'".to_string(),
+        ValueHint::Hostname => " -f -a '(__fish_print_hostnames)'".to_string(),
+        ValueHint::CommandName => " -f -a '(__fish_complete_command)'".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Escape a word inside a fish `-a` list (split on spaces by fish)
+fn fish_escape(word: &str) -> String {
+    word.chars()
+        .flat_map(|c| match c {
+            '\\' | ' ' | '(' | ')' | '$' | '"' | '\'' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
+
+/// `text` as a single-quoted fish word
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// A `Register-ArgumentCompleter -Native` script block
+///
+/// The words before the cursor are walked like in the bash script; an
+/// option value without possible values (paths, free text) returns nothing,
+/// so PowerShell falls back to completing file names.
+fn powershell(spec: &CommandSpec) -> String {
+    let mut out = String::new();
+    out.push_str("using namespace System.Management.Automation\n");
+    out.push_str("using namespace System.Management.Automation.Language\n\n");
+    let _ = writeln!(
+        out,
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{",
+        powershell_quote(&spec.name)
+    );
+    out.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n\n");
+    out.push_str("    # The words before the one being completed\n");
+    out.push_str("    $words = @($commandAst.CommandElements |\n");
+    out.push_str("        Where-Object { $_.Extent.EndOffset -lt $cursorPosition } |\n");
+    out.push_str("        ForEach-Object { $_.ToString() })\n");
+    let _ = writeln!(out, "    $path = {}", powershell_quote(&spec.name));
+    out.push_str("    for ($i = 1; $i -lt $words.Count; $i++) {\n");
+    out.push_str("        switch -CaseSensitive (\"$path,$($words[$i])\") {\n");
+    powershell_walk_cases(spec, &spec.name, &mut out);
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("    $prev = $words[-1]\n\n");
+
+    out.push_str("    $values = switch -CaseSensitive (\"$path,$prev\") {\n");
+    powershell_value_cases(spec, &spec.name, &mut out);
+    out.push_str("    }\n");
+    out.push_str("    if ($values) {\n");
+    out.push_str("        $values | Where-Object { $_ -like \"$wordToComplete*\" } |\n");
+    out.push_str("            ForEach-Object { [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_) }\n");
+    out.push_str("        return\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    $completions = switch -CaseSensitive ($path) {\n");
+    powershell_command_cases(spec, &spec.name, &mut out);
+    out.push_str("    }\n");
+    out.push_str(
+        "    $completions | Where-Object { $_.CompletionText -like \"$wordToComplete*\" } |\n",
+    );
+    out.push_str("        Sort-Object -Property ListItemText\n");
+    out.push_str("}\n");
+    out
+}
+
+fn powershell_walk_cases(spec: &CommandSpec, path: &str, out: &mut String) {
+    for flag in spec
+        .options
+        .iter()
+        .filter(|opt| opt.takes_value)
+        .flat_map(OptionSpec::flags)
+    {
+        let _ = writeln!(
+            out,
+            "            {} {{ $i++; break }}",
+            powershell_quote(&format!("{},{}", path, flag))
+        );
+    }
+    for sub in &spec.subcommands {
+        let sub_path = format!("{};{}", path, sub.name);
+        for name in std::iter::once(&sub.name).chain(&sub.aliases) {
+            let _ = writeln!(
+                out,
+                "            {} {{ $path = {}; break }}",
+                powershell_quote(&format!("{},{}", path, name)),
+                powershell_quote(&sub_path)
+            );
+        }
+        powershell_walk_cases(sub, &sub_path, out);
+    }
+}
+
+/// Arms giving the possible values of the option before the cursor
+fn powershell_value_cases(spec: &CommandSpec, path: &str, out: &mut String) {
+    for opt in spec.options.iter().filter(|opt| opt.takes_value) {
+        let body = if !opt.possible_values.is_empty() {
+            let values: Vec<String> = opt
+                .possible_values
+                .iter()
+                .map(|v| powershell_quote(v))
+                .collect();
+            format!("{}; break", values.join(", "))
+        } else if opt.value_hint == ValueHint::CommandName {
+            "(Get-Command -Name \"$wordToComplete*\").Name | Sort-Object -Unique; break".to_string()
+        } else {
+            "return".to_string()
+        };
+        for flag in opt.flags() {
+            let _ = writeln!(
+                out,
+                "        {} {{ {} }}",
+                powershell_quote(&format!("{},{}", path, flag)),
+                body
+            );
+        }
+    }
+    for sub in &spec.subcommands {
+        powershell_value_cases(sub, &format!("{};{}", path, sub.name), out);
+    }
+}
+
+/// Arms listing the flags, subcommands and positional values of a command
+fn powershell_command_cases(spec: &CommandSpec, path: &str, out: &mut String) {
+    let result = |text: &str, kind: &str, help: Option<&String>| {
+        format!(
+            "            [CompletionResult]::new({text}, {text}, [CompletionResultType]::{kind}, {tip})\n",
+            text = powershell_quote(text),
+            kind = kind,
+            // The tooltip must not be empty
+            tip = powershell_quote(summary(help).unwrap_or(text))
+        )
+    };
+
+    let _ = writeln!(out, "        {} {{", powershell_quote(path));
+    for opt in &spec.options {
+        for flag in opt.flags() {
+            out.push_str(&result(&flag, "ParameterName", opt.help.as_ref()));
+        }
+    }
+    for sub in &spec.subcommands {
+        out.push_str(&result(&sub.name, "ParameterValue", sub.about.as_ref()));
+    }
+    for value in spec.positionals.iter().flat_map(|pos| &pos.possible_values) {
+        out.push_str(&result(value, "ParameterValue", None));
+    }
+    out.push_str("            break\n");
+    out.push_str("        }\n");
+
+    for sub in &spec.subcommands {
+        powershell_command_cases(sub, &format!("{};{}", path, sub.name), out);
+    }
+}
+
+/// `text` as a single-quoted PowerShell string
+fn powershell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// `name` with everything but ASCII letters, digits and `_` replaced by `_`,
+/// for use in shell function names
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_names_round_trip() {
+        for shell in Shell::ALL {
+            assert_eq!(shell.name().parse::<Shell>(), Ok(*shell));
+        }
+        assert_eq!("BASH".parse::<Shell>(), Ok(Shell::Bash));
+        assert_eq!("pwsh".parse::<Shell>(), Ok(Shell::PowerShell));
+        assert!(
+            "tcsh"
+                .parse::<Shell>()
+                .unwrap_err()
+                .contains("expected bash, zsh, fish, powershell")
+        );
+    }
+
+    #[test]
+    fn test_bash_words_escapes() {
+        assert_eq!(bash_words(&["a b", "$c", "it's"]), "'a b' '$c' 'it'\\''s'");
+        assert_eq!(identifier("my-app.v2"), "my_app_v2");
+    }
+
+    #[test]
+    fn test_zsh_and_fish_escapes() {
+        assert_eq!(zsh_escape("a [b]: $c"), "a \\[b\\]\\: \\$c");
+        assert_eq!(zsh_quote("it's"), "'it'\\''s'");
+        assert_eq!(
+            zsh_action(&["a b".to_string(), "c".to_string()], ValueHint::Unknown),
+            "(a\\ b c)"
+        );
+        assert_eq!(zsh_action(&[], ValueHint::DirPath), "_files -/");
+        assert_eq!(fish_quote("it's \\ ok"), "'it\\'s \\\\ ok'");
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+    }
+}
@@ -58,12 +58,35 @@
 //! println!("{}", fancy);
 //! ```
 //!
+//! ### Reusable Styles
+//!
+//! ```
+//! use zfish::{Color, Style, TextStyle};
+//!
+//! const HEADING: TextStyle = Style::new().bold().underline().fg(Color::Cyan);
+//! println!("{}", HEADING.apply("Summary"));
+//! println!("{}", HEADING.apply("Details"));
+//! ```
+//!
+//! ### Pre-styled Input
+//!
+//! ```
+//! use zfish::style::{parse_ansi, strip_ansi, Color};
+//!
+//! let segments = parse_ansi("\x1b[31merror\x1b[0m: disk full");
+//! assert_eq!(segments[0].text, "error");
+//! assert_eq!(segments[0].color, Some(Color::Red));
+//! assert_eq!(strip_ansi("\x1b[1mbold\x1b[22m"), "bold");
+//! ```
+//!
 //! ## Color Detection
 //!
 //! The module automatically detects terminal capabilities:
 //! - Respects `NO_COLOR` environment variable (disables all colors)
 //! - Checks `COLORTERM` for true color support
 //! - Checks `TERM` for basic ANSI support
+//! - On Windows, enables VT processing; consoles older than Windows 10 fall
+//!   back to `SetConsoleTextAttribute` (used by [`StyledString::print`])
 //!
 //! ## Performance
 //!
@@ -71,10 +94,23 @@
 //! - Minimal string formatting overhead
 //! - Fast ANSI code generation
 
+use crate::os::{self, ColorBackend};
+use crate::unicode::{display_width, prefix_within};
 use std::fmt;
+use std::io::{self, Write};
+use std::ops::{Add, AddAssign};
+
+// Windows console attribute bits
+const FOREGROUND_BLUE: u16 = 0x0001;
+const FOREGROUND_GREEN: u16 = 0x0002;
+const FOREGROUND_RED: u16 = 0x0004;
+const FOREGROUND_INTENSITY: u16 = 0x0008;
+const FOREGROUND_MASK: u16 = 0x000F;
+const COMMON_LVB_REVERSE_VIDEO: u16 = 0x4000;
+const COMMON_LVB_UNDERSCORE: u16 = 0x8000;
 
 /// ANSI color codes for terminal output
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Color {
     /// Black (ANSI code 30)
     Black,
@@ -113,7 +149,7 @@ pub enum Color {
 }
 
 /// Text styling options
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Style {
     /// Bold or increased intensity (ANSI code 1)
     Bold,
@@ -132,11 +168,17 @@ pub enum Style {
 }
 
 /// A styled string with color and style attributes
-#[derive(Debug)]
+///
+/// Styled strings can be joined with `+` (with each other or with plain
+/// `&str`), keeping each part's styling, and padded or truncated by
+/// display width.
+#[derive(Debug, Clone)]
 pub struct StyledString {
     text: String,
     color: Option<Color>,
     styles: Vec<Style>,
+    /// Parts joined on with `+`, each with an empty `appended`
+    appended: Vec<StyledString>,
 }
 
 impl Color {
@@ -165,17 +207,220 @@ impl Color {
         }
     }
 
+    /// Nearest of the 16 standard colors, as an ANSI index (0-7 normal, 8-15 bright)
+    fn ansi16_index(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::Custom(n @ 0..=15) => n,
+            Color::Custom(n @ 16..=231) => {
+                // 6x6x6 cube: a channel counts as "on" from level 3 up
+                let n = n - 16;
+                let (r, g, b) = (n / 36, (n / 6) % 6, n % 6);
+                let base = u8::from(r >= 3) | u8::from(g >= 3) << 1 | u8::from(b >= 3) << 2;
+                if r.max(g).max(b) >= 4 && base != 0 {
+                    base + 8
+                } else {
+                    base
+                }
+            }
+            Color::Custom(n) => match n - 232 {
+                0..=5 => 0,
+                6..=13 => 8,
+                14..=19 => 7,
+                _ => 15,
+            },
+        }
+    }
+
+    /// Color for an ANSI index (0-7 normal, 8-15 bright)
+    fn from_ansi16_index(index: u8) -> Self {
+        const COLORS: [Color; 16] = [
+            Color::Black,
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+            Color::White,
+            Color::BrightBlack,
+            Color::BrightRed,
+            Color::BrightGreen,
+            Color::BrightYellow,
+            Color::BrightBlue,
+            Color::BrightMagenta,
+            Color::BrightCyan,
+            Color::BrightWhite,
+        ];
+        COLORS[usize::from(index % 16)]
+    }
+
+    /// Nearest 256-palette color for a 24-bit color, from the 6x6x6 cube
+    ///
+    /// Usable in constants: `const ORANGE: Color = Color::rgb(0xff, 0x88, 0x00);`
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        const fn level(v: u8) -> u8 {
+            ((v as u16 * 5 + 127) / 255) as u8
+        }
+        Color::Custom(16 + 36 * level(r) + 6 * level(g) + level(b))
+    }
+
+    /// The kebab-case name of a standard color (`"bright-red"`), `None` for `Custom`
+    pub const fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Color::Black => "black",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+            Color::White => "white",
+            Color::BrightBlack => "bright-black",
+            Color::BrightRed => "bright-red",
+            Color::BrightGreen => "bright-green",
+            Color::BrightYellow => "bright-yellow",
+            Color::BrightBlue => "bright-blue",
+            Color::BrightMagenta => "bright-magenta",
+            Color::BrightCyan => "bright-cyan",
+            Color::BrightWhite => "bright-white",
+            Color::Custom(_) => return None,
+        })
+    }
+
+    /// Windows console foreground attribute for this color
+    fn console_attribute(self) -> u16 {
+        let index = self.ansi16_index();
+        // ANSI orders the bits red, green, blue; the console uses blue, green, red
+        let mut attribute = 0;
+        if index & 1 != 0 {
+            attribute |= FOREGROUND_RED;
+        }
+        if index & 2 != 0 {
+            attribute |= FOREGROUND_GREEN;
+        }
+        if index & 4 != 0 {
+            attribute |= FOREGROUND_BLUE;
+        }
+        if index >= 8 {
+            attribute |= FOREGROUND_INTENSITY;
+        }
+        attribute
+    }
+
     /// Apply color to a string
     pub fn paint<T: Into<String>>(self, text: T) -> StyledString {
-        StyledString {
-            text: text.into(),
-            color: Some(self),
-            styles: Vec::new(),
+        StyledString::new(text.into(), Some(self), Vec::new())
+    }
+}
+
+/// Parses `"red"`, `"bright-red"` (also `bright_red`, `brightred`), `"gray"`,
+/// a 256-palette index such as `"196"`, or a hex color such as `"#ff8800"`
+/// or `"#f80"` (mapped to the nearest palette entry)
+impl std::str::FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        if let Some(hex) = input.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| format!("invalid hex color '{}'", s));
+        }
+        if input.starts_with(|c: char| c.is_ascii_digit()) {
+            return input
+                .parse()
+                .map(Color::Custom)
+                .map_err(|_| format!("color index '{}' must be between 0 and 255", s));
+        }
+
+        let name: String = input
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let name = name.replace("grey", "gray");
+        let color = match name.as_str() {
+            "gray" => Color::BrightBlack,
+            "lightgray" => Color::White,
+            _ => (0..16)
+                .map(Color::from_ansi16_index)
+                .find(|color| color.name().is_some_and(|n| n.replace('-', "") == name))
+                .ok_or_else(|| format!("unknown color '{}'", s))?,
+        };
+        Ok(color)
+    }
+}
+
+/// `rrggbb` or `rgb` hex digits
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        6 => Some(Color::rgb(
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => {
+            let short = |i: usize| channel(&hex[i..=i]).map(|v| v * 17);
+            Some(Color::rgb(short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+/// Writes the [`name`](Color::name), or the palette index for `Custom`;
+/// the output parses back to the same color
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Custom(n) => write!(f, "{}", n),
+            named => f.write_str(named.name().unwrap_or_default()),
         }
     }
 }
 
 impl Style {
+    /// Start composing a reusable [`TextStyle`]
+    ///
+    /// Example: `Style::new().bold().fg(Color::Cyan)`
+    #[allow(clippy::new_ret_no_self)]
+    pub const fn new() -> TextStyle {
+        TextStyle::new()
+    }
+
+    /// All styles, in ANSI code order
+    const ALL: [Style; 7] = [
+        Style::Bold,
+        Style::Dim,
+        Style::Italic,
+        Style::Underline,
+        Style::Blink,
+        Style::Reverse,
+        Style::Hidden,
+    ];
+
+    /// Bit for this style in a [`TextStyle`]
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
     /// Convert style to its ANSI style code
     fn code(self) -> u8 {
         match self {
@@ -191,23 +436,227 @@ impl Style {
 
     /// Apply style to a string
     pub fn apply<T: Into<String>>(&self, text: T) -> StyledString {
-        StyledString {
-            text: text.into(),
-            color: None,
-            styles: vec![*self],
+        StyledString::new(text.into(), None, vec![*self])
+    }
+}
+
+/// A color and set of styles that can paint any number of strings
+///
+/// Built with [`Style::new`]; it is `Copy` and the builders are `const`, so
+/// a stylesheet can be a handful of constants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    fg: Option<Color>,
+    styles: u8,
+}
+
+impl TextStyle {
+    /// A style that leaves text unchanged
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            styles: 0,
         }
     }
+
+    /// Set the foreground color
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Add a [`Style`]
+    pub const fn with(mut self, style: Style) -> Self {
+        self.styles |= style.bit();
+        self
+    }
+
+    /// Add bold
+    pub const fn bold(self) -> Self {
+        self.with(Style::Bold)
+    }
+
+    /// Add dim
+    pub const fn dim(self) -> Self {
+        self.with(Style::Dim)
+    }
+
+    /// Add italic
+    pub const fn italic(self) -> Self {
+        self.with(Style::Italic)
+    }
+
+    /// Add underline
+    pub const fn underline(self) -> Self {
+        self.with(Style::Underline)
+    }
+
+    /// Add blink
+    pub const fn blink(self) -> Self {
+        self.with(Style::Blink)
+    }
+
+    /// Add reverse video
+    pub const fn reverse(self) -> Self {
+        self.with(Style::Reverse)
+    }
+
+    /// Add hidden
+    pub const fn hidden(self) -> Self {
+        self.with(Style::Hidden)
+    }
+
+    /// Checks whether `style` is part of this style
+    pub const fn has(self, style: Style) -> bool {
+        self.styles & style.bit() != 0
+    }
+
+    /// Paint `text` with this style
+    pub fn apply<T: Into<String>>(&self, text: T) -> StyledString {
+        let styles = Style::ALL
+            .into_iter()
+            .filter(|style| self.has(*style))
+            .collect();
+        StyledString::new(text.into(), self.fg, styles)
+    }
+}
+
+impl From<Style> for TextStyle {
+    fn from(style: Style) -> Self {
+        TextStyle::new().with(style)
+    }
+}
+
+impl From<Color> for TextStyle {
+    fn from(color: Color) -> Self {
+        TextStyle::new().fg(color)
+    }
 }
 
 impl StyledString {
-    /// Add a style to this styled string
+    fn new(text: String, color: Option<Color>, styles: Vec<Style>) -> Self {
+        Self {
+            text,
+            color,
+            styles,
+            appended: Vec::new(),
+        }
+    }
+
+    /// The parts making up this string, each formatted on its own
+    fn parts(&self) -> impl Iterator<Item = &StyledString> {
+        std::iter::once(self).chain(&self.appended)
+    }
+
+    /// Add a style to this styled string (to every part of a joined string)
     pub fn style(mut self, style: Style) -> Self {
         self.styles.push(style);
+        for part in &mut self.appended {
+            part.styles.push(style);
+        }
         self
     }
 
+    /// The text without any styling
+    pub fn plain_text(&self) -> String {
+        self.parts().map(|part| part.text.as_str()).collect()
+    }
+
+    /// Width of the text in terminal cells
+    pub fn len_cells(&self) -> usize {
+        self.parts().map(|part| display_width(&part.text)).sum()
+    }
+
+    /// Pad with spaces on the right to `width` cells (left-aligned)
+    pub fn pad_right(self, width: usize) -> Self {
+        let padding = width.saturating_sub(self.len_cells());
+        self + " ".repeat(padding).as_str()
+    }
+
+    /// Pad with spaces on the left to `width` cells (right-aligned)
+    pub fn pad_left(self, width: usize) -> Self {
+        let padding = width.saturating_sub(self.len_cells());
+        StyledString::new(" ".repeat(padding), None, Vec::new()) + self
+    }
+
+    /// Cut the text to at most `width` cells, keeping each part's styling
+    ///
+    /// A wide character that would straddle the limit is dropped.
+    pub fn truncate(mut self, width: usize) -> Self {
+        let mut remaining = width;
+        let mut appended = std::mem::take(&mut self.appended);
+        let mut kept = 0;
+        for part in std::iter::once(&mut self).chain(appended.iter_mut()) {
+            let full = part.text.len();
+            let end = prefix_within(&part.text, remaining);
+            remaining -= display_width(&part.text[..end]);
+            part.text.truncate(end);
+            if end < full || remaining == 0 {
+                break;
+            }
+            kept += 1;
+        }
+        appended.truncate(kept);
+        self.appended = appended;
+        self
+    }
+
+    /// Prints to stdout, using console attributes on legacy Windows consoles
+    ///
+    /// Unlike `print!("{}", styled)`, this keeps colors on Windows consoles that
+    /// cannot interpret ANSI escape sequences.
+    pub fn print(&self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        let original = match os::color_backend() {
+            ColorBackend::LegacyConsole if StyledString::supports_colors() => {
+                os::console_attributes()
+            }
+            _ => None,
+        };
+        let Some(original) = original else {
+            return write!(stdout, "{}", self);
+        };
+
+        stdout.flush()?;
+        for part in self.parts() {
+            os::set_console_attributes(part.console_attributes(original))?;
+            let written = write!(stdout, "{}", part.text).and_then(|()| stdout.flush());
+            os::set_console_attributes(original)?;
+            written?;
+        }
+        Ok(())
+    }
+
+    /// Like [`print`](Self::print), followed by a newline
+    pub fn println(&self) -> io::Result<()> {
+        self.print()?;
+        writeln!(io::stdout())
+    }
+
+    /// Console attributes for this string, keeping the background of `original`
+    fn console_attributes(&self, original: u16) -> u16 {
+        let mut attributes = match self.color {
+            Some(color) => (original & !FOREGROUND_MASK) | color.console_attribute(),
+            None => original,
+        };
+        for style in &self.styles {
+            attributes |= match style {
+                Style::Bold => FOREGROUND_INTENSITY,
+                Style::Underline => COMMON_LVB_UNDERSCORE,
+                Style::Reverse => COMMON_LVB_REVERSE_VIDEO,
+                _ => 0,
+            };
+        }
+        attributes
+    }
+
     /// Detect if terminal supports colors
-    fn supports_colors() -> bool {
+    pub(crate) fn supports_colors() -> bool {
+        // Built without the `colour` feature: always plain text
+        if !cfg!(feature = "colour") || crate::plain::is_plain() {
+            return false;
+        }
+
         // `NO_COLOR` environment variable should ALWAYS disable colors, even in tests
         // This follows the NO_COLOR standard: https://no-color.org/
         // Check this FIRST before any other logic
@@ -221,17 +670,33 @@ impl StyledString {
             return std::env::var("COLORTERM").is_ok();
         }
 
-        // Standard detection for non-test environments
-        std::env::var("COLORTERM").is_ok() || std::env::var("TERM").is_ok_and(|term| term != "dumb")
+        // Standard detection for non-test environments; Windows consoles rarely
+        // set TERM, so a console on stdout counts as color-capable too
+        std::env::var("COLORTERM").is_ok()
+            || std::env::var("TERM").is_ok_and(|term| term != "dumb")
+            || os::console_attributes().is_some()
     }
 }
 
 impl fmt::Display for StyledString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !StyledString::supports_colors() {
-            return write!(f, "{}", self.text);
+        // Escape sequences would print as garbage on a legacy console
+        let plain =
+            !StyledString::supports_colors() || os::color_backend() == ColorBackend::LegacyConsole;
+        for part in self.parts() {
+            if plain {
+                f.write_str(&part.text)?;
+            } else {
+                part.fmt_part(f)?;
+            }
         }
+        Ok(())
+    }
+}
 
+impl StyledString {
+    /// Write this part's text with its own escape codes
+    fn fmt_part(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Start building the ANSI escape sequence
         let mut codes = Vec::new();
 
@@ -254,3 +719,405 @@ impl fmt::Display for StyledString {
         }
     }
 }
+
+impl Add for StyledString {
+    type Output = StyledString;
+
+    fn add(mut self, rhs: StyledString) -> StyledString {
+        self += rhs;
+        self
+    }
+}
+
+impl Add<&str> for StyledString {
+    type Output = StyledString;
+
+    fn add(mut self, rhs: &str) -> StyledString {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for StyledString {
+    fn add_assign(&mut self, mut rhs: StyledString) {
+        let rest = std::mem::take(&mut rhs.appended);
+        self.appended.push(rhs);
+        self.appended.extend(rest);
+    }
+}
+
+impl AddAssign<&str> for StyledString {
+    fn add_assign(&mut self, rhs: &str) {
+        self.appended
+            .push(StyledString::new(rhs.to_string(), None, Vec::new()));
+    }
+}
+
+/// A run of text with a single color and set of styles, see [`parse_ansi`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The text without escape sequences
+    pub text: String,
+    /// Foreground color, if any
+    pub color: Option<Color>,
+    /// Active styles, in the order they were turned on
+    pub styles: Vec<Style>,
+}
+
+impl Segment {
+    /// Re-create the styled text, e.g. after slicing `text`
+    pub fn to_styled(&self) -> StyledString {
+        StyledString::new(self.text.clone(), self.color, self.styles.clone())
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_styled().fmt(f)
+    }
+}
+
+/// Split pre-styled text into [`Segment`]s
+///
+/// Understands SGR sequences (colors, including 256-color and 24-bit, and
+/// styles); other escape sequences such as cursor movement, OSC hyperlinks
+/// and background colors are dropped.
+pub fn parse_ansi(input: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current = Segment {
+        text: String::new(),
+        color: None,
+        styles: Vec::new(),
+    };
+
+    let mut rest = input;
+    while let Some(esc) = rest.find('\x1b') {
+        current.text.push_str(&rest[..esc]);
+        rest = &rest[esc..];
+        let len = escape_len(rest);
+        if let Some(params) = rest[..len]
+            .strip_prefix("\x1b[")
+            .and_then(|seq| seq.strip_suffix('m'))
+        {
+            let (color, styles) = (current.color, current.styles.clone());
+            let mut next = Segment {
+                text: String::new(),
+                color,
+                styles,
+            };
+            apply_sgr(params, &mut next);
+            if next.color != current.color || next.styles != current.styles {
+                let done = std::mem::replace(&mut current, next);
+                if !done.text.is_empty() {
+                    segments.push(done);
+                }
+            }
+        }
+        rest = &rest[len..];
+    }
+    current.text.push_str(rest);
+    if !current.text.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
+
+/// Remove all escape sequences from `input`
+pub fn strip_ansi(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(esc) = rest.find('\x1b') {
+        out.push_str(&rest[..esc]);
+        rest = &rest[esc + escape_len(&rest[esc..])..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Byte length of the escape sequence at the start of `s` (which starts with ESC)
+fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    match bytes.get(1) {
+        // CSI: parameters and intermediates, then a final byte in 0x40..=0x7e
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7e).contains(b))
+            .map_or(bytes.len(), |end| end + 3),
+        // OSC: ends with BEL or ESC \
+        Some(b']') => {
+            let body = &bytes[2..];
+            let bel = body.iter().position(|&b| b == 0x07).map(|end| end + 3);
+            let st = body
+                .windows(2)
+                .position(|w| w == b"\x1b\\")
+                .map(|end| end + 4);
+            match (bel, st) {
+                (Some(a), Some(b)) => a.min(b),
+                (Some(end), None) | (None, Some(end)) => end,
+                (None, None) => bytes.len(),
+            }
+        }
+        // Two-character escape (ESC + one char), or a lone ESC
+        Some(_) => 1 + s[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
+    }
+}
+
+/// Apply the parameters of an SGR (`ESC [ ... m`) sequence to `segment`
+fn apply_sgr(params: &str, segment: &mut Segment) {
+    let codes: Vec<u16> = params
+        .split([';', ':'])
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+
+    let set = |segment: &mut Segment, style: Style, on: bool| {
+        segment.styles.retain(|s| *s != style);
+        if on {
+            segment.styles.push(style);
+        }
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => {
+                segment.color = None;
+                segment.styles.clear();
+            }
+            1 => set(segment, Style::Bold, true),
+            2 => set(segment, Style::Dim, true),
+            3 => set(segment, Style::Italic, true),
+            4 => set(segment, Style::Underline, true),
+            5 => set(segment, Style::Blink, true),
+            7 => set(segment, Style::Reverse, true),
+            8 => set(segment, Style::Hidden, true),
+            22 => {
+                set(segment, Style::Bold, false);
+                set(segment, Style::Dim, false);
+            }
+            23 => set(segment, Style::Italic, false),
+            24 => set(segment, Style::Underline, false),
+            25 => set(segment, Style::Blink, false),
+            27 => set(segment, Style::Reverse, false),
+            28 => set(segment, Style::Hidden, false),
+            code @ 30..=37 => segment.color = Some(Color::from_ansi16_index((code - 30) as u8)),
+            code @ 90..=97 => segment.color = Some(Color::from_ansi16_index((code - 82) as u8)),
+            39 => segment.color = None,
+            code @ (38 | 48) => {
+                let color = match codes.get(i + 1) {
+                    Some(5) => {
+                        let n = codes.get(i + 2).copied().unwrap_or(0);
+                        i += 2;
+                        Color::Custom(u8::try_from(n).unwrap_or(u8::MAX))
+                    }
+                    Some(2) => {
+                        let channel = |k: usize| codes.get(i + k).copied().unwrap_or(0);
+                        let channel = |k: usize| u8::try_from(channel(k)).unwrap_or(u8::MAX);
+                        let color = Color::rgb(channel(2), channel(3), channel(4));
+                        i += 4;
+                        color
+                    }
+                    _ => break,
+                };
+                // Backgrounds (48) are not tracked
+                if code == 38 {
+                    segment.color = Some(color);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_style_is_reusable() {
+        const HEADING: TextStyle = Style::new().underline().bold().fg(Color::Cyan);
+        for text in ["one", "two"] {
+            let styled = HEADING.apply(text);
+            assert_eq!(styled.text, text);
+            assert_eq!(styled.color, Some(Color::Cyan));
+            assert_eq!(styled.styles, [Style::Bold, Style::Underline]);
+        }
+
+        assert!(HEADING.has(Style::Bold));
+        assert!(!HEADING.has(Style::Dim));
+        assert_eq!(TextStyle::from(Style::Dim), Style::new().dim());
+        assert_eq!(TextStyle::from(Color::Red), Style::new().fg(Color::Red));
+        assert!(Style::new().apply("x").styles.is_empty());
+    }
+
+    #[test]
+    fn test_styled_string_concat_keeps_parts() {
+        let mut line = Color::Blue.paint("INFO").style(Style::Bold) + " ";
+        line += Color::Green.paint("deployed");
+        line += " in 3s";
+        assert_eq!(line.plain_text(), "INFO deployed in 3s");
+        assert_eq!(line.len_cells(), 19);
+
+        let parts: Vec<_> = line.parts().map(|p| (p.text.as_str(), p.color)).collect();
+        assert_eq!(
+            parts,
+            [
+                ("INFO", Some(Color::Blue)),
+                (" ", None),
+                ("deployed", Some(Color::Green)),
+                (" in 3s", None)
+            ]
+        );
+        assert_eq!(line.appended[1].styles, []);
+    }
+
+    #[test]
+    fn test_styled_string_pad_and_truncate() {
+        let cell = Color::Red.paint("日本") + "go";
+        assert_eq!(cell.len_cells(), 6);
+        assert_eq!(cell.clone().pad_right(8).plain_text(), "日本go  ");
+        assert_eq!(cell.clone().pad_left(8).plain_text(), "  日本go");
+        assert_eq!(cell.clone().pad_left(3).plain_text(), "日本go");
+
+        assert_eq!(cell.clone().truncate(5).plain_text(), "日本g");
+        let cut = cell.clone().truncate(3);
+        assert_eq!(cut.plain_text(), "日");
+        assert!(cut.appended.is_empty());
+        assert_eq!(cut.color, Some(Color::Red));
+        assert_eq!(cell.truncate(10).plain_text(), "日本go");
+    }
+
+    #[test]
+    fn test_color_from_str() {
+        assert_eq!("red".parse(), Ok(Color::Red));
+        assert_eq!("Bright-Red".parse(), Ok(Color::BrightRed));
+        assert_eq!("bright_cyan".parse(), Ok(Color::BrightCyan));
+        assert_eq!("brightwhite".parse(), Ok(Color::BrightWhite));
+        assert_eq!("grey".parse(), Ok(Color::BrightBlack));
+        assert_eq!("196".parse(), Ok(Color::Custom(196)));
+        assert_eq!("#ff0000".parse(), Ok(Color::Custom(196)));
+        assert_eq!("#f80".parse(), "#ff8800".parse::<Color>());
+        assert_eq!(Color::rgb(0xff, 0x88, 0x00), Color::Custom(214));
+
+        assert!("256".parse::<Color>().is_err());
+        assert!("#ff00".parse::<Color>().is_err());
+        assert!("#gggggg".parse::<Color>().is_err());
+        assert_eq!(
+            "purple".parse::<Color>(),
+            Err("unknown color 'purple'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_color_display_round_trips() {
+        for color in (0..16)
+            .map(Color::from_ansi16_index)
+            .chain([Color::Custom(42)])
+        {
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+        assert_eq!(Color::BrightMagenta.to_string(), "bright-magenta");
+        assert_eq!(Color::Custom(7).to_string(), "7");
+    }
+
+    #[test]
+    fn test_parse_ansi_segments() {
+        let segments = parse_ansi("plain \x1b[1;31mbold red\x1b[22m red\x1b[0m end");
+        assert_eq!(
+            segments,
+            [
+                Segment {
+                    text: "plain ".into(),
+                    color: None,
+                    styles: vec![],
+                },
+                Segment {
+                    text: "bold red".into(),
+                    color: Some(Color::Red),
+                    styles: vec![Style::Bold],
+                },
+                Segment {
+                    text: " red".into(),
+                    color: Some(Color::Red),
+                    styles: vec![],
+                },
+                Segment {
+                    text: " end".into(),
+                    color: None,
+                    styles: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ansi_extended_colors() {
+        let segments = parse_ansi("\x1b[38;5;196ma\x1b[38;2;0;255;0mb\x1b[92;48;5;4mc\x1b[39md");
+        let colors: Vec<_> = segments.iter().map(|s| s.color).collect();
+        assert_eq!(
+            colors,
+            [
+                Some(Color::Custom(196)),
+                Some(Color::Custom(46)),
+                Some(Color::BrightGreen),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi_drops_all_sequences() {
+        assert_eq!(strip_ansi("\x1b[2K\x1b[1Aup"), "up");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://example.com\x07link\x1b]8;;\x1b\\"),
+            "link"
+        );
+        assert_eq!(strip_ansi("cut\x1b["), "cut");
+        assert_eq!(strip_ansi("a\x1b"), "a");
+        assert_eq!(strip_ansi("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn test_console_attribute_translation() {
+        assert_eq!(Color::Black.console_attribute(), 0);
+        assert_eq!(Color::Red.console_attribute(), FOREGROUND_RED);
+        assert_eq!(Color::Blue.console_attribute(), FOREGROUND_BLUE);
+        assert_eq!(
+            Color::Yellow.console_attribute(),
+            FOREGROUND_RED | FOREGROUND_GREEN
+        );
+        assert_eq!(
+            Color::BrightCyan.console_attribute(),
+            FOREGROUND_GREEN | FOREGROUND_BLUE | FOREGROUND_INTENSITY
+        );
+        assert_eq!(Color::White.console_attribute(), 0x0007);
+    }
+
+    #[test]
+    fn test_custom_colors_map_to_nearest_standard() {
+        assert_eq!(Color::Custom(9).ansi16_index(), 9);
+        assert_eq!(Color::Custom(196).ansi16_index(), 9); // pure red
+        assert_eq!(Color::Custom(46).ansi16_index(), 10); // pure green
+        assert_eq!(Color::Custom(18).ansi16_index(), 0); // very dark blue
+        assert_eq!(Color::Custom(124).ansi16_index(), 1); // dark red
+        assert_eq!(Color::Custom(232).ansi16_index(), 0);
+        assert_eq!(Color::Custom(244).ansi16_index(), 8);
+        assert_eq!(Color::Custom(255).ansi16_index(), 15);
+    }
+
+    #[test]
+    fn test_console_attributes_keep_background() {
+        let original = 0x0010 | 0x0007; // white on blue
+        let styled = Color::Red.paint("x").style(Style::Bold);
+        assert_eq!(
+            styled.console_attributes(original),
+            0x0010 | FOREGROUND_RED | FOREGROUND_INTENSITY
+        );
+        let underlined = Style::Underline.apply("x");
+        assert_eq!(
+            underlined.console_attributes(original),
+            original | COMMON_LVB_UNDERSCORE
+        );
+    }
+}
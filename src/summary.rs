@@ -0,0 +1,172 @@
+//! # Run Summary
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — summary.rs                                            ║
+//! ║  "Finished with 2 warnings in 3.2s" footers                   ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! A [`Report`] collects how long the run took and what was logged through
+//! any [`Logger`](crate::log::Logger): the warning and error counts, plus the
+//! first error messages. [`App::run`](crate::command::App::run) builds one
+//! and passes it to the hook set with
+//! [`App::summary`](crate::command::App::summary) just before exiting.
+//! [`Report::print`] draws the standard footer box.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use zfish::command::App;
+//! use zfish::summary::Report;
+//!
+//! App::new("sync")
+//!     .summary(Report::print)
+//!     .run(|_matches| {
+//!         zfish::log::warn("skipped 2 locked files");
+//!         Ok::<(), String>(())
+//!     });
+//! ```
+
+use crate::style::Color;
+use crate::table::TextBox;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// What happened during a run, for an end-of-run footer
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Report {
+    /// Time since the run started
+    pub elapsed: Duration,
+    /// Warnings logged
+    pub warnings: usize,
+    /// Errors logged
+    pub errors: usize,
+    /// The first error messages, see [`log::error_messages`](crate::log::error_messages)
+    pub error_messages: Vec<String>,
+    /// The status the process exits with
+    pub exit_code: i32,
+}
+
+impl Report {
+    /// A report of everything logged so far, timed from `start`
+    pub fn since(start: Instant) -> Self {
+        Self {
+            elapsed: start.elapsed(),
+            warnings: crate::log::warning_count(),
+            errors: crate::log::error_count(),
+            error_messages: crate::log::error_messages(),
+            exit_code: 0,
+        }
+    }
+
+    /// Sets the exit status the report describes
+    pub fn exit_code(mut self, code: i32) -> Self {
+        self.exit_code = code;
+        self
+    }
+
+    /// Whether the run failed (non-zero exit status)
+    pub fn failed(&self) -> bool {
+        self.exit_code != 0
+    }
+
+    /// The one-line summary, e.g. `Finished with 2 warnings in 3.2s`
+    pub fn headline(&self) -> String {
+        let verb = if self.failed() { "Failed" } else { "Finished" };
+        let counts: Vec<String> = [(self.errors, "error"), (self.warnings, "warning")]
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, noun)| plural(*count, noun))
+            .collect();
+        if counts.is_empty() {
+            format!("{} in {}", verb, format_elapsed(self.elapsed))
+        } else {
+            format!(
+                "{} with {} in {}",
+                verb,
+                counts.join(" and "),
+                format_elapsed(self.elapsed)
+            )
+        }
+    }
+
+    /// The footer box: the headline, then the recorded error messages
+    pub fn render(&self) -> String {
+        let mut lines = vec![self.headline()];
+        lines.extend(self.error_messages.iter().map(|m| format!("error: {}", m)));
+        let unlisted = self.errors.saturating_sub(self.error_messages.len());
+        if unlisted > 0 {
+            lines.push(format!("... and {} more", plural(unlisted, "error")));
+        }
+
+        let color = if self.failed() || self.errors > 0 {
+            Color::Red
+        } else if self.warnings > 0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        TextBox::new(lines.join("\n")).color(color).render()
+    }
+
+    /// Prints the footer box to stderr
+    pub fn print(&self) {
+        eprint!("{}", self.render());
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.headline())
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Tenths of a second under a minute (`3.2s`), whole seconds above (`2m 5s`)
+pub(crate) fn format_elapsed(elapsed: Duration) -> String {
+    if elapsed < Duration::from_secs(60) {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    } else {
+        crate::humanize::format_duration(Duration::from_secs(elapsed.as_secs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(warnings: usize, errors: usize, exit_code: i32) -> Report {
+        Report {
+            elapsed: Duration::from_millis(3_240),
+            warnings,
+            errors,
+            error_messages: Vec::new(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn test_headline() {
+        assert_eq!(report(0, 0, 0).headline(), "Finished in 3.2s");
+        assert_eq!(
+            report(2, 0, 0).headline(),
+            "Finished with 2 warnings in 3.2s"
+        );
+        assert_eq!(
+            report(1, 1, 1).headline(),
+            "Failed with 1 error and 1 warning in 3.2s"
+        );
+        assert_eq!(format_elapsed(Duration::from_millis(125_900)), "2m 5s");
+    }
+}
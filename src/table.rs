@@ -15,6 +15,23 @@
 //! table.print();
 //! ```
 //!
+//! # Tables From Data
+//!
+//! [`Table::from_csv_str`] and [`Table::from_json_array_str`] build a table
+//! straight from file contents, for "pretty-print this data file" tools.
+//!
+//! ```
+//! use zfish::table::Table;
+//!
+//! let table = Table::from_csv_str("name,size\nCargo.toml,1.2K\n", true).unwrap();
+//! assert_eq!(table.to_csv(), "name,size\nCargo.toml,1.2K\n");
+//! ```
+//!
+//! # Live Tables
+//!
+//! [`Table::live`] keeps a table on screen and redraws it in place as cells
+//! change, for `top`-like monitoring commands.
+//!
 //! # Manual Table Drawing
 //!
 //! If the automated `Table` API doesn't fit your specific needs (e.g., complex
@@ -29,8 +46,10 @@
 //! - Unicode-aware manual drawing with proper width calculation
 //! - Hybrid approach mixing automated and manual techniques
 
-use crate::style::{Color, Style};
-use crate::unicode::display_width;
+use crate::style::{Color, Style, strip_ansi};
+use crate::term::Frame;
+use crate::unicode::{display_width, wrap};
+use std::io;
 
 /// Box drawing styles for tables
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,9 +67,15 @@ pub enum BoxStyle {
 }
 
 impl BoxStyle {
-    /// Get the box drawing characters for this style
+    /// Get the box drawing characters for this style (always `Ascii` in
+    /// [plain mode](crate::plain))
     fn chars(&self) -> BoxChars {
-        match self {
+        let style = if crate::plain::is_plain() {
+            &BoxStyle::Ascii
+        } else {
+            self
+        };
+        match style {
             BoxStyle::Single => BoxChars {
                 top_left: '┌',
                 top_right: '┐',
@@ -148,13 +173,65 @@ pub enum Alignment {
 }
 // (Width logic moved to crate::unicode)
 
+/// Errors from building a [`Table`] out of CSV or JSON text, or from bad rows
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TableError {
+    /// The input is not valid CSV or JSON, or has the wrong shape
+    Parse {
+        /// 1-based line of the input where the problem was found
+        line: usize,
+        /// What went wrong
+        message: String,
+    },
+    /// A row does not have one cell per column, see [`Table::try_add_row`]
+    ColumnCountMismatch {
+        /// Number of columns in the table
+        expected: usize,
+        /// Number of cells in the row
+        got: usize,
+    },
+}
+
+impl std::fmt::Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            TableError::ColumnCountMismatch { expected, got } => {
+                write!(f, "expected {} cells in row, got {}", expected, got)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// A table cell with its display width measured once, when it is added
+#[derive(Debug, Clone)]
+struct Cell {
+    text: String,
+    width: usize,
+}
+
+impl Cell {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            width: display_width(text),
+        }
+    }
+}
+
 /// A formatted table with automatic column width calculation and borders
 #[derive(Debug)]
 pub struct Table {
-    headers: Vec<String>,
-    rows: Vec<Vec<String>>,
+    headers: Vec<Cell>,
+    rows: Vec<Vec<Cell>>,
     col_widths: Vec<usize>,
     col_alignments: Vec<Alignment>,
+    /// Columns given an alignment explicitly, which auto-alignment leaves alone
+    pinned_alignments: Vec<bool>,
+    auto_align: bool,
     box_style: BoxStyle,
     indent: usize,
     has_header_separator: bool,
@@ -172,8 +249,8 @@ impl Table {
     /// let table = Table::new(vec!["Name", "Age", "City"]);
     /// ```
     pub fn new(headers: Vec<&str>) -> Self {
-        let col_widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
-        let headers: Vec<String> = headers.into_iter().map(|h| h.to_string()).collect();
+        let headers: Vec<Cell> = headers.into_iter().map(Cell::new).collect();
+        let col_widths: Vec<usize> = headers.iter().map(|h| h.width).collect();
         let col_count = col_widths.len();
 
         Table {
@@ -181,6 +258,8 @@ impl Table {
             rows: Vec::new(),
             col_widths,
             col_alignments: vec![Alignment::Left; col_count],
+            pinned_alignments: vec![false; col_count],
+            auto_align: false,
             box_style: BoxStyle::Single,
             indent: 3,
             has_header_separator: true,
@@ -188,6 +267,126 @@ impl Table {
         }
     }
 
+    /// Starts a [`TableBuilder`] whose rows must have exactly `N` cells
+    ///
+    /// A row with the wrong number of cells is a compile error rather than
+    /// a cell silently left out of the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let table = Table::builder(["Name", "Age", "City"])
+    ///     .add_row(["Alice", "25", "NYC"])
+    ///     .add_row(["Bob", "30", "LA"])
+    ///     .build();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use zfish::table::Table;
+    ///
+    /// let table = Table::builder::<2>(["Name", "Age"]).add_row(["Alice", "25", "NYC"]);
+    /// ```
+    pub fn builder<const N: usize>(headers: [&str; N]) -> TableBuilder<N> {
+        TableBuilder {
+            table: Table::new(headers.to_vec()),
+        }
+    }
+
+    /// Builds a table from CSV text (RFC 4180 quoting, `\n` or `\r\n` line ends)
+    ///
+    /// With `has_headers` the first record names the columns; otherwise they
+    /// are called `Column 1`, `Column 2`, ... Every record must have the same
+    /// number of fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let table = Table::from_csv_str("Bob,\"Portland, OR\"\n", false).unwrap();
+    /// assert_eq!(table.to_csv(), "Column 1,Column 2\nBob,\"Portland, OR\"\n");
+    /// ```
+    pub fn from_csv_str(data: &str, has_headers: bool) -> Result<Self, TableError> {
+        let mut records = parse_csv(data)?.into_iter();
+        let first = records.next();
+        let (headers, first_row) = match first {
+            Some((_, record)) if has_headers => (record, None),
+            Some((line, record)) => {
+                let headers = (1..=record.len())
+                    .map(|n| format!("Column {}", n))
+                    .collect();
+                (headers, Some((line, record)))
+            }
+            None => (Vec::new(), None),
+        };
+
+        let mut table = Table::new(headers.iter().map(String::as_str).collect());
+        for (line, record) in first_row.into_iter().chain(records) {
+            if record.len() != headers.len() {
+                return Err(TableError::Parse {
+                    line,
+                    message: format!("expected {} fields, found {}", headers.len(), record.len()),
+                });
+            }
+            table.add_row(record.iter().map(String::as_str).collect());
+        }
+        Ok(table)
+    }
+
+    /// Builds a table from a JSON array of objects
+    ///
+    /// Columns are the object keys in order of first appearance; a key missing
+    /// from an object leaves an empty cell. Strings are shown as-is, `null` as
+    /// an empty cell, and nested arrays or objects as compact JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let table = Table::from_json_array_str(r#"[{"name": "a", "size": 1}, {"name": "b"}]"#).unwrap();
+    /// assert_eq!(table.to_csv(), "name,size\na,1\nb,\n");
+    /// ```
+    pub fn from_json_array_str(data: &str) -> Result<Self, TableError> {
+        let mut parser = JsonParser { data, pos: 0 };
+        let value = parser.parse_document()?;
+        let JsonValue::Array(items) = value else {
+            return Err(parser.error_at(0, "expected a JSON array of objects"));
+        };
+
+        let mut headers: Vec<String> = Vec::new();
+        let mut objects = Vec::with_capacity(items.len());
+        for (offset, item) in items {
+            let JsonValue::Object(fields) = item else {
+                return Err(parser.error_at(offset, "expected every array element to be an object"));
+            };
+            for (key, _) in &fields {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+            objects.push(fields);
+        }
+
+        let mut table = Table::new(headers.iter().map(String::as_str).collect());
+        for fields in objects {
+            let row: Vec<String> = headers
+                .iter()
+                .map(|header| {
+                    fields
+                        .iter()
+                        .rev()
+                        .find(|(key, _)| key == header)
+                        .map_or_else(String::new, |(_, value)| value.to_cell())
+                })
+                .collect();
+            table.add_row(row.iter().map(String::as_str).collect());
+        }
+        Ok(table)
+    }
+
     /// Adds a row to the table
     ///
     /// # Examples
@@ -199,15 +398,44 @@ impl Table {
     /// table.add_row(vec!["Alice", "25"]);
     /// table.add_row(vec!["Bob", "30"]);
     /// ```
+    ///
+    /// Cells beyond the last column are not shown; use
+    /// [`try_add_row`](Self::try_add_row) to reject such rows instead.
     pub fn add_row(&mut self, row: Vec<&str>) {
         // Update column widths based on display width (not byte length)
+        let row: Vec<Cell> = row.into_iter().map(Cell::new).collect();
         for (i, cell) in row.iter().enumerate() {
             if i < self.col_widths.len() {
-                self.col_widths[i] = self.col_widths[i].max(display_width(cell));
+                self.col_widths[i] = self.col_widths[i].max(cell.width);
             }
         }
-        self.rows
-            .push(row.into_iter().map(|s| s.to_string()).collect());
+        self.rows.push(row);
+    }
+
+    /// Adds a row, failing unless it has exactly one cell per column
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::{Table, TableError};
+    ///
+    /// let mut table = Table::new(vec!["Name", "Age"]);
+    /// assert!(table.try_add_row(vec!["Alice", "25"]).is_ok());
+    /// assert_eq!(
+    ///     table.try_add_row(vec!["Bob", "30", "LA"]),
+    ///     Err(TableError::ColumnCountMismatch { expected: 2, got: 3 })
+    /// );
+    /// ```
+    pub fn try_add_row(&mut self, row: Vec<&str>) -> Result<(), TableError> {
+        let expected = self.headers.len();
+        if row.len() != expected {
+            return Err(TableError::ColumnCountMismatch {
+                expected,
+                got: row.len(),
+            });
+        }
+        self.add_row(row);
+        Ok(())
     }
 
     /// Sets the box drawing style for the table
@@ -254,10 +482,61 @@ impl Table {
     pub fn set_column_alignment(&mut self, col_index: usize, alignment: Alignment) -> &mut Self {
         if col_index < self.col_alignments.len() {
             self.col_alignments[col_index] = alignment;
+            self.pinned_alignments[col_index] = true;
         }
         self
     }
 
+    /// Right-aligns columns whose cells are mostly numbers, when enabled
+    ///
+    /// Numbers may carry a sign, thousands separators, a currency symbol or a
+    /// `%`. Empty cells are not counted, and columns set with
+    /// [`set_column_alignment`](Self::set_column_alignment) keep their alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::{Table, BoxStyle};
+    ///
+    /// let mut table = Table::new(vec!["Item", "Price"]);
+    /// table.set_box_style(BoxStyle::Ascii).set_indent(0).set_auto_align(true);
+    /// table.add_row(vec!["Coffee", "$3.50"]);
+    /// table.add_row(vec!["Bagel", "$12.00"]);
+    /// assert!(table.render().contains("| Coffee |  $3.50 |"));
+    /// ```
+    pub fn set_auto_align(&mut self, enabled: bool) -> &mut Self {
+        self.auto_align = enabled;
+        self
+    }
+
+    /// Alignment of each column once auto-alignment is applied
+    fn effective_alignments(&self) -> Vec<Alignment> {
+        (0..self.col_alignments.len())
+            .map(|col| {
+                if self.auto_align && !self.pinned_alignments[col] && self.is_numeric_column(col) {
+                    Alignment::Right
+                } else {
+                    self.col_alignments[col]
+                }
+            })
+            .collect()
+    }
+
+    /// Checks whether more than half of a column's non-empty cells are numbers
+    fn is_numeric_column(&self, col: usize) -> bool {
+        let (mut numeric, mut filled) = (0, 0);
+        for cell in self.rows.iter().filter_map(|row| row.get(col)) {
+            if cell.width == 0 {
+                continue;
+            }
+            filled += 1;
+            if looks_numeric(&cell.text) {
+                numeric += 1;
+            }
+        }
+        numeric * 2 > filled
+    }
+
     /// Enables or disables the separator line after the header row
     pub fn set_header_separator(&mut self, enabled: bool) -> &mut Self {
         self.has_header_separator = enabled;
@@ -270,38 +549,295 @@ impl Table {
         self
     }
 
-    /// Formats a cell according to the column's alignment
-    fn format_cell(&self, text: &str, width: usize, alignment: Alignment) -> String {
-        let text_width = display_width(text);
-        let padding = width.saturating_sub(text_width);
+    /// Display width of each column's content, without the cell padding
+    pub fn column_widths(&self) -> &[usize] {
+        &self.col_widths
+    }
 
-        match alignment {
-            Alignment::Left => {
-                format!("{}{}", text, " ".repeat(padding))
-            }
-            Alignment::Right => {
-                format!("{}{}", " ".repeat(padding), text)
-            }
-            Alignment::Center => {
-                let left_pad = padding / 2;
-                let right_pad = padding - left_pad;
-                format!("{}{}{}", " ".repeat(left_pad), text, " ".repeat(right_pad))
-            }
+    /// Number of data rows (the header row is not counted)
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Where [`render`](Self::render) puts everything, for drawing around the table
+    ///
+    /// Column widths are measured as cells are added, so this is cheap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Name", "Age"]);
+    /// table.set_indent(2);
+    /// table.add_row(vec!["Alice", "25"]);
+    ///
+    /// let layout = table.layout();
+    /// assert_eq!(layout.column_offsets, [4, 12]);
+    /// let top_border = table.render().lines().next().unwrap().to_string();
+    /// assert_eq!(top_border.chars().count(), layout.width);
+    /// ```
+    pub fn layout(&self) -> TableLayout {
+        let mut column_offsets = Vec::with_capacity(self.col_widths.len());
+        // Indent, left border and the padding space before the first cell
+        let mut offset = self.indent + 2;
+        for width in &self.col_widths {
+            column_offsets.push(offset);
+            offset += width + 3;
+        }
+        let footer = self.has_footer_separator && self.rows.len() >= 2;
+
+        TableLayout {
+            indent: self.indent,
+            width: offset - 1,
+            column_widths: self.col_widths.clone(),
+            column_offsets,
+            alignments: self.effective_alignments(),
+            line_count: 3
+                + usize::from(self.has_header_separator)
+                + self.rows.len()
+                + usize::from(footer),
         }
     }
 
-    /// Prints a horizontal line with the given junction characters
-    fn print_line(&self, left: char, _mid: char, right: char, junction: char) {
+    /// Appends a horizontal line with the given junction characters
+    fn render_line(&self, out: &mut String, left: char, right: char, junction: char) {
         let chars = self.box_style.chars();
-        print!("{}", " ".repeat(self.indent));
-        print!("{}", left);
+        out.push_str(&" ".repeat(self.indent));
+        out.push(left);
         for (i, width) in self.col_widths.iter().enumerate() {
-            print!("{}", chars.horizontal.to_string().repeat(width + 2));
+            out.push_str(&chars.horizontal.to_string().repeat(width + 2));
             if i < self.col_widths.len() - 1 {
-                print!("{}", junction);
+                out.push(junction);
+            }
+        }
+        out.push(right);
+        out.push('\n');
+    }
+
+    /// Appends one row of cells between vertical borders
+    fn render_cells(&self, out: &mut String, cells: &[Cell], alignments: &[Alignment]) {
+        let chars = self.box_style.chars();
+        out.push_str(&" ".repeat(self.indent));
+        out.push(chars.vertical);
+        for (i, (cell, width)) in cells.iter().zip(&self.col_widths).enumerate() {
+            let formatted = pad(&cell.text, cell.width, *width, alignments[i]);
+            out.push_str(&format!(" {} ", formatted));
+            out.push(chars.vertical);
+        }
+        out.push('\n');
+    }
+
+    /// Renders the table with borders to a string, exactly as [`print`](Self::print) shows it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::{Table, BoxStyle};
+    ///
+    /// let mut table = Table::new(vec!["Name"]);
+    /// table.set_box_style(BoxStyle::Ascii).set_indent(0);
+    /// table.add_row(vec!["Alice"]);
+    /// assert_eq!(table.render(), "+-------+\n| Name  |\n+-------+\n| Alice |\n+-------+\n");
+    /// ```
+    pub fn render(&self) -> String {
+        let chars = self.box_style.chars();
+        let alignments = self.effective_alignments();
+        let mut out = String::new();
+
+        // Top border
+        self.render_line(&mut out, chars.top_left, chars.top_right, chars.t_down);
+
+        // Headers
+        self.render_cells(&mut out, &self.headers, &alignments);
+
+        // Header separator
+        if self.has_header_separator {
+            self.render_line(&mut out, chars.t_right, chars.t_left, chars.cross);
+        }
+
+        // Rows
+        for (idx, row) in self.rows.iter().enumerate() {
+            self.render_cells(&mut out, row, &alignments);
+
+            // Footer separator before last row if enabled
+            if self.has_footer_separator && idx + 2 == self.rows.len() {
+                self.render_line(&mut out, chars.t_right, chars.t_left, chars.cross);
             }
         }
-        println!("{}", right);
+
+        // Bottom border
+        self.render_line(&mut out, chars.bottom_left, chars.bottom_right, chars.t_up);
+
+        out
+    }
+
+    /// Renders the rows as a JSON array of objects keyed by header
+    ///
+    /// All values are emitted as JSON strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Name", "Age"]);
+    /// table.add_row(vec!["Alice", "25"]);
+    /// assert_eq!(table.to_json(), "[\n  {\"Name\": \"Alice\", \"Age\": \"25\"}\n]\n");
+    /// ```
+    pub fn to_json(&self) -> String {
+        if self.rows.is_empty() {
+            return "[]\n".to_string();
+        }
+
+        let objects: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let fields: Vec<String> = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(key, value)| {
+                        format!("{}: {}", json_string(&key.text), json_string(&value.text))
+                    })
+                    .collect();
+                format!("  {{{}}}", fields.join(", "))
+            })
+            .collect();
+
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+
+    /// Renders the headers and rows as CSV (RFC 4180 quoting)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Name", "City"]);
+    /// table.add_row(vec!["Bob", "Portland, OR"]);
+    /// assert_eq!(table.to_csv(), "Name,City\nBob,\"Portland, OR\"\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        for line in std::iter::once(&self.headers).chain(&self.rows) {
+            let fields: Vec<String> = line.iter().map(|cell| csv_field(&cell.text)).collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Renders the rows as a YAML sequence of mappings keyed by header
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Name", "Age"]);
+    /// table.add_row(vec!["Alice", "25"]);
+    /// assert_eq!(table.to_yaml(), "- Name: \"Alice\"\n  Age: \"25\"\n");
+    /// ```
+    pub fn to_yaml(&self) -> String {
+        if self.rows.is_empty() {
+            return "[]\n".to_string();
+        }
+
+        let mut out = String::new();
+        for row in &self.rows {
+            for (i, (key, value)) in self.headers.iter().zip(row).enumerate() {
+                let marker = if i == 0 { "- " } else { "  " };
+                out.push_str(&format!(
+                    "{}{}: {}\n",
+                    marker,
+                    yaml_key(&key.text),
+                    json_string(&value.text)
+                ));
+            }
+        }
+        out
+    }
+
+    /// Renders the table as a GitHub-flavored Markdown pipe table
+    ///
+    /// Columns are padded so the source stays readable, and the separator row
+    /// carries each column's alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Flag", "Meaning"]);
+    /// table.add_row(vec!["-v", "a | b"]);
+    /// assert_eq!(
+    ///     table.to_markdown(),
+    ///     "| Flag | Meaning |\n| ---- | ------- |\n| -v   | a \\| b  |\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let lines: Vec<Vec<String>> = std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|line| line.iter().map(|cell| markdown_cell(&cell.text)).collect())
+            .collect();
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|col| {
+                lines
+                    .iter()
+                    .filter_map(|line| line.get(col))
+                    .map(|cell| display_width(cell))
+                    .max()
+                    .unwrap_or(0)
+                    .max(3)
+            })
+            .collect();
+
+        let separator: Vec<String> = widths
+            .iter()
+            .zip(self.effective_alignments())
+            .map(|(width, alignment)| match alignment {
+                Alignment::Left => "-".repeat(*width),
+                Alignment::Right => format!("{}:", "-".repeat(width - 1)),
+                Alignment::Center => format!(":{}:", "-".repeat(width - 2)),
+            })
+            .collect();
+
+        let row = |line: &[String]| {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(col, width)| {
+                    let cell = line.get(col).map_or("", String::as_str);
+                    format!("{}{}", cell, " ".repeat(width - display_width(cell)))
+                })
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+
+        let mut out = row(&lines[0]);
+        out.push_str(&format!("| {} |\n", separator.join(" | ")));
+        for line in &lines[1..] {
+            out.push_str(&row(line));
+        }
+        out
+    }
+
+    /// Renders the table in the given output format
+    pub fn render_as(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => self.render(),
+            OutputFormat::Json => self.to_json(),
+            OutputFormat::Csv => self.to_csv(),
+            OutputFormat::Yaml => self.to_yaml(),
+        }
+    }
+
+    /// Prints the table to stdout in the given output format
+    pub fn print_as(&self, format: OutputFormat) {
+        print!("{}", self.render_as(format));
     }
 
     /// Prints the table to stdout
@@ -316,55 +852,743 @@ impl Table {
     /// table.print();
     /// ```
     pub fn print(&self) {
-        let chars = self.box_style.chars();
+        print!("{}", self.render());
+    }
 
-        // Print top border
-        self.print_line(chars.top_left, chars.t_down, chars.top_right, chars.t_down);
+    /// Draws the table and keeps it on screen for in-place updates
+    ///
+    /// Each change through the returned [`LiveTable`] redraws only the lines
+    /// that differ, using a [`Frame`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use zfish::table::Table;
+    ///
+    /// let mut table = Table::new(vec!["Service", "Status"]);
+    /// table.add_row(vec!["api", "starting"]);
+    /// let mut live = table.live().unwrap();
+    /// live.update_cell(0, 1, "running").unwrap();
+    /// live.push_row(vec!["worker", "starting"]).unwrap();
+    /// live.finish().unwrap();
+    /// ```
+    pub fn live(self) -> io::Result<LiveTable> {
+        let mut live = LiveTable {
+            table: self,
+            frame: Frame::new(),
+        };
+        live.redraw()?;
+        Ok(live)
+    }
+
+    /// Recompute a column's width after a cell in it changed
+    fn refit_column(&mut self, col: usize) {
+        let rows = self.rows.iter().filter_map(|row| row.get(col));
+        self.col_widths[col] = std::iter::once(&self.headers[col])
+            .chain(rows)
+            .map(|cell| cell.width)
+            .max()
+            .unwrap_or(0);
+    }
+}
 
-        // Print headers
-        print!("{}", " ".repeat(self.indent));
-        print!("{}", chars.vertical);
-        for (i, (header, width)) in self.headers.iter().zip(&self.col_widths).enumerate() {
-            let formatted = self.format_cell(header, *width, self.col_alignments[i]);
-            print!(" {} ", formatted);
-            print!("{}", chars.vertical);
+/// The measured geometry of a [`Table`], from [`Table::layout`]
+///
+/// Positions and widths are in terminal columns, counted from the start of
+/// the line (so they include the indent).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TableLayout {
+    /// Spaces before the left border
+    pub indent: usize,
+    /// Width of every rendered line, indent and borders included
+    pub width: usize,
+    /// Content width of each column, without the cell padding
+    pub column_widths: Vec<usize>,
+    /// Column where each column's content starts
+    pub column_offsets: Vec<usize>,
+    /// Alignment each column is rendered with (after auto-alignment)
+    pub alignments: Vec<Alignment>,
+    /// Number of lines [`Table::render`] produces
+    pub line_count: usize,
+}
+
+/// A fluent [`Table`] builder with the column count in its type, from [`Table::builder`]
+#[derive(Debug)]
+pub struct TableBuilder<const N: usize> {
+    table: Table,
+}
+
+impl<const N: usize> TableBuilder<N> {
+    /// Adds a row of exactly `N` cells
+    pub fn add_row(mut self, row: [&str; N]) -> Self {
+        self.table.add_row(row.to_vec());
+        self
+    }
+
+    /// Adds every row from `rows`
+    pub fn rows<'a>(mut self, rows: impl IntoIterator<Item = [&'a str; N]>) -> Self {
+        for row in rows {
+            self.table.add_row(row.to_vec());
         }
-        println!();
+        self
+    }
 
-        // Print header separator
-        if self.has_header_separator {
-            self.print_line(chars.t_right, chars.cross, chars.t_left, chars.cross);
+    /// Finishes building; styling is then set on the [`Table`] as usual
+    pub fn build(self) -> Table {
+        self.table
+    }
+}
+
+impl<const N: usize> From<TableBuilder<N>> for Table {
+    fn from(builder: TableBuilder<N>) -> Self {
+        builder.build()
+    }
+}
+
+/// A [`Table`] kept on screen and redrawn in place, from [`Table::live`]
+///
+/// Dropping it moves the cursor below the table, like [`finish`](Self::finish).
+#[derive(Debug)]
+pub struct LiveTable {
+    table: Table,
+    frame: Frame,
+}
+
+impl LiveTable {
+    /// Replaces one cell and redraws; positions outside the table are ignored
+    pub fn update_cell(&mut self, row: usize, col: usize, value: &str) -> io::Result<()> {
+        let Some(cell) = self.table.rows.get_mut(row).and_then(|r| r.get_mut(col)) else {
+            return Ok(());
+        };
+        *cell = Cell::new(value);
+        if col < self.table.col_widths.len() {
+            self.table.refit_column(col);
         }
+        self.redraw()
+    }
 
-        // Print rows
-        for (idx, row) in self.rows.iter().enumerate() {
-            print!("{}", " ".repeat(self.indent));
-            print!("{}", chars.vertical);
-            for (i, (cell, width)) in row.iter().zip(&self.col_widths).enumerate() {
-                let formatted = self.format_cell(cell, *width, self.col_alignments[i]);
-                print!(" {} ", formatted);
-                print!("{}", chars.vertical);
+    /// Appends a row and redraws
+    pub fn push_row(&mut self, row: Vec<&str>) -> io::Result<()> {
+        self.table.add_row(row);
+        self.redraw()
+    }
+
+    /// The table as currently shown
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Redraws the lines that changed since the last draw
+    pub fn redraw(&mut self) -> io::Result<()> {
+        for line in self.table.render().lines() {
+            self.frame.line(line);
+        }
+        self.frame.flush()
+    }
+
+    /// Stops updating, leaving the cursor below the table, and returns it
+    pub fn finish(mut self) -> io::Result<Table> {
+        self.frame.finish()?;
+        let empty = Table::new(Vec::new());
+        Ok(std::mem::replace(&mut self.table, empty))
+    }
+}
+
+impl Drop for LiveTable {
+    fn drop(&mut self) {
+        let _ = self.frame.finish();
+    }
+}
+
+/// Output formats a [`Table`] can be rendered in
+///
+/// Parses case-insensitively from `table`, `json`, `csv` and `yaml` (or `yml`),
+/// which makes it a natural target for a `--format` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Bordered, aligned table for humans
+    Table,
+    /// JSON array of objects
+    Json,
+    /// Comma-separated values with a header line
+    Csv,
+    /// YAML sequence of mappings
+    Yaml,
+}
+
+impl OutputFormat {
+    /// The canonical lowercase name (`"table"`, `"json"`, ...)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Yaml => "yaml",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            _ => Err(format!(
+                "unknown output format '{}', expected table, json, csv or yaml",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Quotes and escapes a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let value = &strip_ansi(value);
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Splits CSV text into records, each with the line it starts on
+fn parse_csv(data: &str) -> Result<Vec<(usize, Vec<String>)>, TableError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut in_quotes = false;
+    // Whether the current record has any content (blank lines are skipped)
+    let mut started = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                started = true;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                started = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                if started || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                started = false;
+                line += 1;
+                record_line = line;
+            }
+            c => {
+                field.push(c);
+                started = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(TableError::Parse {
+            line: record_line,
+            message: "unterminated quoted field".to_string(),
+        });
+    }
+    if started || !field.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+/// A parsed JSON value; array elements keep their byte offset for errors
+#[derive(Debug)]
+enum JsonValue {
+    Null,
+    Bool(bool),
+    /// The number exactly as written
+    Number(String),
+    String(String),
+    Array(Vec<(usize, JsonValue)>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Text for a table cell
+    fn to_cell(&self) -> String {
+        match self {
+            JsonValue::Null => String::new(),
+            JsonValue::String(s) => s.clone(),
+            other => other.to_compact(),
+        }
+    }
+
+    /// Compact JSON text
+    fn to_compact(&self) -> String {
+        match self {
+            JsonValue::Null => "null".to_string(),
+            JsonValue::Bool(b) => b.to_string(),
+            JsonValue::Number(n) => n.clone(),
+            JsonValue::String(s) => json_string(s),
+            JsonValue::Array(items) => {
+                let items: Vec<String> = items.iter().map(|(_, v)| v.to_compact()).collect();
+                format!("[{}]", items.join(","))
+            }
+            JsonValue::Object(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}:{}", json_string(k), v.to_compact()))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            }
+        }
+    }
+}
+
+/// A small recursive-descent JSON parser (RFC 8259)
+struct JsonParser<'a> {
+    data: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn parse_document(&mut self) -> Result<JsonValue, TableError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos < self.data.len() {
+            return Err(self.error("unexpected data after the JSON value"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: &str) -> TableError {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, offset: usize, message: &str) -> TableError {
+        TableError::Parse {
+            line: self.data[..offset].matches('\n').count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), TableError> {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, TableError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("expected a JSON value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_literal(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, TableError> {
+        if self.data[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a JSON value"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, TableError> {
+        let start = self.pos;
+        let rest = &self.data.as_bytes()[start..];
+        let len = rest
+            .iter()
+            .position(|b| !matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+            .unwrap_or(rest.len());
+        let text = &self.data[start..start + len];
+        if text.parse::<f64>().is_err() || text.starts_with('+') {
+            return Err(self.error("invalid number"));
+        }
+        self.pos += len;
+        Ok(JsonValue::Number(text.to_string()))
+    }
+
+    fn parse_string(&mut self) -> Result<String, TableError> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.data[self.pos..].chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => out.push(self.parse_escape()?),
+                c if (c as u32) < 0x20 => return Err(self.error("control character in string")),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, TableError> {
+        let escaped = self
+            .peek()
+            .ok_or_else(|| self.error("unterminated string"))?;
+        self.pos += 1;
+        Ok(match escaped {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.parse_hex4()?;
+                if (0xD800..0xDC00).contains(&high) && self.data[self.pos..].starts_with("\\u") {
+                    self.pos += 2;
+                    let low = self.parse_hex4()?;
+                    let code =
+                        0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                    char::from_u32(code).unwrap_or('\u{FFFD}')
+                } else {
+                    char::from_u32(high).unwrap_or('\u{FFFD}')
+                }
             }
-            println!();
+            _ => return Err(self.error("invalid escape in string")),
+        })
+    }
 
-            // Print footer separator before last row if enabled
-            if self.has_footer_separator && idx == self.rows.len() - 2 {
-                self.print_line(chars.t_right, chars.cross, chars.t_left, chars.cross);
+    fn parse_hex4(&mut self) -> Result<u32, TableError> {
+        let digits = self
+            .data
+            .get(self.pos..self.pos + 4)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap_or(0xFFFD))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, TableError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            let offset = self.pos;
+            items.push((offset, self.parse_value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
             }
         }
+    }
 
-        // Print bottom border
-        self.print_line(
-            chars.bottom_left,
-            chars.t_up,
-            chars.bottom_right,
-            chars.t_up,
-        );
+    fn parse_object(&mut self) -> Result<JsonValue, TableError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+/// Escapes a cell for a Markdown table, where `|` ends the cell and a line
+/// break ends the row
+fn markdown_cell(value: &str) -> String {
+    strip_ansi(value)
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    let value = &strip_ansi(value);
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Emits a YAML mapping key, quoting it unless it is a plain identifier
+fn yaml_key(key: &str) -> String {
+    let key = &strip_ansi(key);
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ')
+        && !key.starts_with([' ', '-'])
+        && !key.ends_with(' ');
+    if plain {
+        key.to_string()
+    } else {
+        json_string(key)
+    }
+}
+
+/// Text in a box, wrapped to fit the terminal
+///
+/// # Examples
+///
+/// ```
+/// use zfish::table::{Alignment, BoxStyle, TextBox};
+///
+/// let rendered = TextBox::new("Build finished in 3.2s")
+///     .box_style(BoxStyle::Ascii)
+///     .max_width(16)
+///     .alignment(Alignment::Center)
+///     .render();
+/// assert_eq!(
+///     rendered,
+///     "+-------------+\n\
+///      |    Build    |\n\
+///      | finished in |\n\
+///      |    3.2s     |\n\
+///      +-------------+\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextBox {
+    text: String,
+    box_style: BoxStyle,
+    color: Option<Color>,
+    max_width: Option<usize>,
+    padding: usize,
+    alignment: Alignment,
+}
+
+impl TextBox {
+    /// Creates a box around `text`; `\n` starts a new line
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            box_style: BoxStyle::Single,
+            color: None,
+            max_width: None,
+            padding: 1,
+            alignment: Alignment::Left,
+        }
+    }
+
+    /// Sets the border characters
+    pub fn box_style(mut self, style: BoxStyle) -> Self {
+        self.box_style = style;
+        self
+    }
+
+    /// Paints the whole box (bold) in `color`
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Limits the total width, borders included (default: the terminal width)
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Sets the spaces between the border and the text on each side (default: 1)
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Aligns the lines inside the box
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Renders the box, one line per `\n`
+    pub fn render(&self) -> String {
+        let chars = self.box_style.chars();
+        let max_width = self
+            .max_width
+            .or_else(|| crate::term::Terminal::size().map(|(width, _)| usize::from(width)));
+        let lines = match max_width {
+            Some(max) => wrap(&self.text, max.saturating_sub(2 + 2 * self.padding)),
+            None => self.text.lines().map(str::to_string).collect(),
+        };
+        let width = lines
+            .iter()
+            .map(|line| display_width(line))
+            .max()
+            .unwrap_or(0);
+        let horizontal = chars
+            .horizontal
+            .to_string()
+            .repeat(width + 2 * self.padding);
+        let pad = " ".repeat(self.padding);
+
+        let mut rows = Vec::with_capacity(lines.len() + 2);
+        rows.push(format!(
+            "{}{}{}",
+            chars.top_left, horizontal, chars.top_right
+        ));
+        for line in &lines {
+            rows.push(format!(
+                "{}{}{}{}{}",
+                chars.vertical,
+                pad,
+                align(line, width, self.alignment),
+                pad,
+                chars.vertical
+            ));
+        }
+        rows.push(format!(
+            "{}{}{}",
+            chars.bottom_left, horizontal, chars.bottom_right
+        ));
+
+        let mut out = String::new();
+        for row in rows {
+            match self.color {
+                Some(color) => out.push_str(&color.paint(row).style(Style::Bold).to_string()),
+                None => out.push_str(&row),
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prints the box to stdout
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+/// Checks whether a cell reads as a number: `-1,024`, `$3.50`, `(12)`, `45%`
+fn looks_numeric(text: &str) -> bool {
+    let plain = strip_ansi(text);
+    let mut s = plain.trim();
+    if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        s = inner;
+    }
+    s = s.strip_prefix(['-', '+', '−']).unwrap_or(s);
+    s = s.trim_start_matches(['$', '€', '£', '¥', '₹']);
+    s = s.strip_prefix(['-', '+', '−']).unwrap_or(s);
+    s = s
+        .trim_end_matches(['%', '$', '€', '£', '¥', '₹'])
+        .trim_end();
+
+    s.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && s.chars().any(|c| c.is_ascii_digit())
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ',' | '.' | '_'))
+}
+
+/// Pads `text` to `width` cells according to `alignment`
+fn align(text: &str, width: usize, alignment: Alignment) -> String {
+    pad(text, display_width(text), width, alignment)
+}
+
+/// Like [`align`], for text already known to be `text_width` cells wide
+fn pad(text: &str, text_width: usize, width: usize, alignment: Alignment) -> String {
+    let padding = width.saturating_sub(text_width);
+    match alignment {
+        Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
+        Alignment::Right => format!("{}{}", " ".repeat(padding), text),
+        Alignment::Center => {
+            let left_pad = padding / 2;
+            let right_pad = padding - left_pad;
+            format!("{}{}{}", " ".repeat(left_pad), text, " ".repeat(right_pad))
+        }
     }
 }
 
 /// Helper function to draw a simple box around text
 ///
+/// Long text wraps to fit the terminal; see [`TextBox`] for alignment,
+/// padding and width options.
+///
 /// # Examples
 ///
 /// ```
@@ -374,26 +1598,73 @@ impl Table {
 /// draw_box("Hello World", BoxStyle::Double, Color::Green);
 /// ```
 pub fn draw_box(text: &str, style: BoxStyle, color: Color) {
-    let chars = style.chars();
-    let width = display_width(text);
-
-    let top = format!(
-        "{}{}{}",
-        chars.top_left,
-        chars.horizontal.to_string().repeat(width + 2),
-        chars.top_right
-    );
-    let middle = format!("{} {} {}", chars.vertical, text, chars.vertical);
-    let bottom = format!(
-        "{}{}{}",
-        chars.bottom_left,
-        chars.horizontal.to_string().repeat(width + 2),
-        chars.bottom_right
-    );
-
-    println!("{}", color.paint(&top).style(Style::Bold));
-    println!("{}", color.paint(&middle).style(Style::Bold));
-    println!("{}", color.paint(&bottom).style(Style::Bold));
+    TextBox::new(text).box_style(style).color(color).print();
+}
+
+/// Places two rendered blocks side by side, `gap` spaces apart
+///
+/// Lines are padded by display width (colors are fine), and the shorter block
+/// is extended with blank lines, so the result is a rectangle that can be
+/// joined again.
+///
+/// # Examples
+///
+/// ```
+/// use zfish::table::join_horizontal;
+///
+/// assert_eq!(join_horizontal("ab\nc\n", "x\n", 1), "ab x\nc   \n");
+/// ```
+pub fn join_horizontal(a: &str, b: &str, gap: usize) -> String {
+    let (a_lines, a_width) = block(a);
+    let (b_lines, b_width) = block(b);
+    let height = a_lines.len().max(b_lines.len());
+
+    let mut out = String::new();
+    for row in 0..height {
+        let left = a_lines.get(row).copied().unwrap_or("");
+        let right = b_lines.get(row).copied().unwrap_or("");
+        out.push_str(&align(left, a_width, Alignment::Left));
+        out.push_str(&" ".repeat(gap));
+        out.push_str(&align(right, b_width, Alignment::Left));
+        out.push('\n');
+    }
+    out
+}
+
+/// Stacks two rendered blocks, `gap` blank lines apart
+///
+/// Every line is padded to the wider block's width.
+///
+/// # Examples
+///
+/// ```
+/// use zfish::table::join_vertical;
+///
+/// assert_eq!(join_vertical("abc\n", "x\n", 0), "abc\nx  \n");
+/// ```
+pub fn join_vertical(a: &str, b: &str, gap: usize) -> String {
+    let (a_lines, a_width) = block(a);
+    let (b_lines, b_width) = block(b);
+    let width = a_width.max(b_width);
+
+    let gap_lines = std::iter::repeat_n("", gap);
+    let mut out = String::new();
+    for line in a_lines.into_iter().chain(gap_lines).chain(b_lines) {
+        out.push_str(&align(line, width, Alignment::Left));
+        out.push('\n');
+    }
+    out
+}
+
+/// The lines of a rendered block and its width in cells
+fn block(text: &str) -> (Vec<&str>, usize) {
+    let lines: Vec<&str> = text.lines().collect();
+    let width = lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    (lines, width)
 }
 
 /// Helper function to draw a horizontal separator line
@@ -453,24 +1724,33 @@ mod tests {
         assert_eq!(table.col_alignments[1], Alignment::Right);
     }
 
+    #[test]
+    fn test_looks_numeric() {
+        for yes in [
+            "42", "-1,024", "+3.5", "$3.50", "-$12", "€9", "45%", "(12.00)", "1_000", ".5",
+        ] {
+            assert!(looks_numeric(yes), "{}", yes);
+        }
+        for no in ["", "-", "$", "v1.2", "12ms", "N/A", "1.2.3a", "abc", "1 2"] {
+            assert!(!looks_numeric(no), "{}", no);
+        }
+    }
+
     #[test]
     fn test_cell_formatting_left() {
-        let table = Table::new(vec!["Test"]);
-        let result = table.format_cell("Hi", 5, Alignment::Left);
+        let result = align("Hi", 5, Alignment::Left);
         assert_eq!(result, "Hi   ");
     }
 
     #[test]
     fn test_cell_formatting_right() {
-        let table = Table::new(vec!["Test"]);
-        let result = table.format_cell("Hi", 5, Alignment::Right);
+        let result = align("Hi", 5, Alignment::Right);
         assert_eq!(result, "   Hi");
     }
 
     #[test]
     fn test_cell_formatting_center() {
-        let table = Table::new(vec!["Test"]);
-        let result = table.format_cell("Hi", 6, Alignment::Center);
+        let result = align("Hi", 6, Alignment::Center);
         assert_eq!(result, "  Hi  ");
     }
 
@@ -0,0 +1,678 @@
+//! # Task Runner
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — tasks.rs                                              ║
+//! ║  A checklist of steps with spinners and captured logs         ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`TaskRunner`] runs named steps on worker threads while the calling thread
+//! draws a checklist on stderr: a spinner and status message for each running
+//! task, then `✓` or `✗` with the time it took. With
+//! [`jobs`](TaskRunner::jobs) above one, tasks run in parallel and each gets
+//! its own line.
+//!
+//! Every task logs through [`TaskContext::logger`] and writes other output to
+//! [`TaskContext::output`]. Both go into a buffer instead of the terminal,
+//! as does the message of a task that panics. The buffer is printed only
+//! when the task fails, like cargo and yarn keep successful steps quiet.
+//! Long failure output is cut to its last [`FAILURE_LINES`] lines; with `-v`
+//! (see [`verbosity`](crate::verbosity)), every task's output is printed in
+//! full. After a failure, no new tasks start, and the rest are reported as
+//! skipped.
+//!
+//! Without a terminal, one line is printed as each task finishes. Quiet
+//! verbosity hides the checklist but not the failure report.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use std::io::Write;
+//! use zfish::tasks::TaskRunner;
+//!
+//! let result = TaskRunner::new()
+//!     .task("Fetch", |ctx| {
+//!         ctx.set_message("downloading index");
+//!         ctx.logger().info("fetched 120 packages");
+//!         writeln!(ctx.output(), "index: 4.2 MiB").map_err(|e| e.to_string())
+//!     })
+//!     .task("Build", |ctx| {
+//!         ctx.logger().debug("compiling 12 crates");
+//!         Err("linker not found")
+//!     })
+//!     .run();
+//!
+//! if let Err(err) = result {
+//!     eprintln!("{}", err);
+//! }
+//! ```
+
+use crate::log::{Level, Logger};
+use crate::progress::DrawTarget;
+use crate::style::Color;
+use crate::term::Frame;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the checklist redraws while tasks run
+const TICK_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Lines of a failed task's output shown without `-v` (the last ones)
+pub const FAILURE_LINES: usize = 20;
+
+/// Spinner frames for running tasks, as in [`ProgressStyle::Spinner`](crate::progress::ProgressStyle::Spinner)
+const SPINNER_FRAMES: &[char] = &['|', '/', '-', '\\'];
+
+type TaskFn<'a> = Box<dyn FnOnce(&TaskContext) -> Result<(), String> + Send + 'a>;
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Send + Sync + 'static>;
+
+/// How many runners are capturing panics, and the hook they replaced
+static PANIC_HOOK: Mutex<(usize, Option<Arc<PanicHook>>)> = Mutex::new((0, None));
+
+thread_local! {
+    /// The output of the task running on this thread, for the panic hook
+    static PANIC_OUTPUT: RefCell<Option<TaskOutput>> = const { RefCell::new(None) };
+}
+
+/// Runs a list of named tasks and draws their progress as a checklist
+///
+/// Task closures may borrow from the caller: they all finish before
+/// [`run`](Self::run) returns.
+pub struct TaskRunner<'a> {
+    tasks: Vec<(String, TaskFn<'a>)>,
+    jobs: usize,
+    target: DrawTarget,
+}
+
+impl fmt::Debug for TaskRunner<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.tasks.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("TaskRunner")
+            .field("tasks", &names)
+            .field("jobs", &self.jobs)
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+impl Default for TaskRunner<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TaskRunner<'a> {
+    /// Creates a runner with no tasks that runs one task at a time
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            jobs: 1,
+            target: DrawTarget::default(),
+        }
+    }
+
+    /// Adds a task; tasks start in the order they were added
+    ///
+    /// The error is shown with `Display` in the checklist and the final report.
+    pub fn task<E: fmt::Display>(
+        mut self,
+        name: impl Into<String>,
+        run: impl FnOnce(&TaskContext) -> Result<(), E> + Send + 'a,
+    ) -> Self {
+        self.tasks.push((
+            name.into(),
+            Box::new(move |ctx| run(ctx).map_err(|err| err.to_string())),
+        ));
+        self
+    }
+
+    /// Sets how many tasks may run at the same time (at least one)
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Chooses where the checklist is drawn (stderr unless set)
+    pub fn draw_target(mut self, target: DrawTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Runs every task, returning their outcomes in the order they were added
+    ///
+    /// Fails with a [`TaskError`] (which still holds every outcome) if any
+    /// task returned an error or panicked.
+    pub fn run(self) -> Result<Vec<TaskOutcome>, TaskError> {
+        let shared = Arc::new(Shared::new(&self.tasks));
+        let queue: Mutex<VecDeque<(usize, TaskFn<'a>)>> = Mutex::new(
+            self.tasks
+                .into_iter()
+                .map(|(_, run)| run)
+                .enumerate()
+                .collect(),
+        );
+        let workers = self.jobs.min(shared.lock().len());
+        let mut view = View::new(self.target);
+        let capture = PanicCapture::install();
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| scope.spawn(|| work(&queue, &shared)))
+                .collect();
+
+            let mut slots = shared.lock();
+            loop {
+                let done = handles.iter().all(|handle| handle.is_finished());
+                view.draw(&slots, done);
+                if done {
+                    break;
+                }
+                slots = shared
+                    .changed
+                    .wait_timeout(slots, TICK_INTERVAL)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0;
+            }
+        });
+        drop(capture);
+
+        let outcomes: Vec<TaskOutcome> = shared.lock().drain(..).map(Slot::into_outcome).collect();
+        view.finish(&outcomes);
+        if outcomes
+            .iter()
+            .any(|o| matches!(o.status, TaskStatus::Failed(_)))
+        {
+            Err(TaskError { outcomes })
+        } else {
+            Ok(outcomes)
+        }
+    }
+}
+
+/// Handed to each task: its name, its status line and its logger
+#[derive(Debug)]
+pub struct TaskContext {
+    name: String,
+    index: usize,
+    shared: Arc<Shared>,
+    logger: Logger,
+    output: TaskOutput,
+}
+
+impl TaskContext {
+    /// The task's name, as given to [`TaskRunner::task`]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Shows `message` next to the task's spinner
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.shared.lock()[self.index].message = message.into();
+        self.shared.changed.notify_all();
+    }
+
+    /// A logger whose output is kept, and printed only if the task fails
+    ///
+    /// It logs every level, including debug.
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// A writer for the task's other output, kept with its log
+    ///
+    /// Use it in place of `println!`, or hand it to anything taking
+    /// `impl Write`, such as a child process's captured output.
+    pub fn output(&self) -> TaskOutput {
+        self.output.clone()
+    }
+}
+
+/// How a task ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaskStatus {
+    /// The task returned `Ok`
+    Succeeded,
+    /// The task returned this error, or panicked
+    Failed(String),
+    /// The task never started because an earlier one failed
+    Skipped,
+}
+
+/// The result of one task
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TaskOutcome {
+    /// The task's name
+    pub name: String,
+    /// How it ended
+    pub status: TaskStatus,
+    /// How long it ran (zero if skipped)
+    pub elapsed: Duration,
+    /// Everything the task wrote to [`TaskContext::logger`] and
+    /// [`TaskContext::output`], in order
+    pub log: String,
+}
+
+/// At least one task failed; returned by [`TaskRunner::run`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskError {
+    outcomes: Vec<TaskOutcome>,
+}
+
+impl TaskError {
+    /// Every task's outcome, in the order the tasks were added
+    pub fn outcomes(&self) -> &[TaskOutcome] {
+        &self.outcomes
+    }
+
+    /// The tasks that failed
+    pub fn failed(&self) -> impl Iterator<Item = &TaskOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|o| matches!(o.status, TaskStatus::Failed(_)))
+    }
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut failed = self.failed();
+        if let Some(TaskOutcome {
+            name,
+            status: TaskStatus::Failed(message),
+            ..
+        }) = failed.next()
+        {
+            write!(f, "task '{}' failed: {}", name, message)?;
+        }
+        let more = failed.count();
+        if more > 0 {
+            write!(f, " (and {} more)", more)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+/// State shared between the workers and the drawing thread
+#[derive(Debug)]
+struct Shared {
+    slots: Mutex<Vec<Slot>>,
+    /// Signalled whenever a slot changes
+    changed: Condvar,
+}
+
+impl Shared {
+    fn new(tasks: &[(String, TaskFn<'_>)]) -> Self {
+        let slots = tasks
+            .iter()
+            .map(|(name, _)| Slot {
+                name: name.clone(),
+                state: SlotState::Pending,
+                message: String::new(),
+                log: TaskOutput::default(),
+            })
+            .collect();
+        Self {
+            slots: Mutex::new(slots),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Slot>> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, index: usize, state: SlotState) {
+        self.lock()[index].state = state;
+        self.changed.notify_all();
+    }
+
+    fn any_failed(&self) -> bool {
+        self.lock()
+            .iter()
+            .any(|slot| matches!(slot.state, SlotState::Failed(..)))
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    name: String,
+    state: SlotState,
+    message: String,
+    log: TaskOutput,
+}
+
+#[derive(Debug)]
+enum SlotState {
+    Pending,
+    Running(Instant),
+    Succeeded(Duration),
+    Failed(Duration, String),
+}
+
+impl Slot {
+    fn into_outcome(self) -> TaskOutcome {
+        let (status, elapsed) = match self.state {
+            SlotState::Pending => (TaskStatus::Skipped, Duration::ZERO),
+            // Workers always finish their task before the runner returns
+            SlotState::Running(started) => (TaskStatus::Succeeded, started.elapsed()),
+            SlotState::Succeeded(elapsed) => (TaskStatus::Succeeded, elapsed),
+            SlotState::Failed(elapsed, message) => (TaskStatus::Failed(message), elapsed),
+        };
+        TaskOutcome {
+            name: self.name,
+            status,
+            elapsed,
+            log: self.log.text(),
+        }
+    }
+}
+
+/// A worker: take tasks off the queue until it is empty or a task has failed
+fn work<'a>(queue: &Mutex<VecDeque<(usize, TaskFn<'a>)>>, shared: &Arc<Shared>) {
+    loop {
+        if shared.any_failed() {
+            break;
+        }
+        let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        let Some((index, run)) = next else {
+            break;
+        };
+
+        let (name, log) = {
+            let slots = shared.lock();
+            (slots[index].name.clone(), slots[index].log.clone())
+        };
+        let started = Instant::now();
+        shared.update(index, SlotState::Running(started));
+
+        let ctx = TaskContext {
+            name,
+            index,
+            shared: Arc::clone(shared),
+            logger: Logger::new().level(Level::Debug).writer(log.clone()),
+            output: log,
+        };
+        PANIC_OUTPUT.with(|output| *output.borrow_mut() = Some(ctx.output()));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| run(&ctx)))
+            .unwrap_or_else(|payload| Err(panic_message(payload.as_ref())));
+        PANIC_OUTPUT.with(|output| *output.borrow_mut() = None);
+        // Dropping the logger writes any pending "repeated" notices
+        drop(ctx);
+
+        let elapsed = started.elapsed();
+        shared.update(
+            index,
+            match result {
+                Ok(()) => SlotState::Succeeded(elapsed),
+                Err(message) => SlotState::Failed(elapsed, message),
+            },
+        );
+    }
+    shared.changed.notify_all();
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    match detail {
+        Some(detail) => format!("panicked: {}", detail),
+        None => "panicked".to_string(),
+    }
+}
+
+/// Keeps panic messages from tasks off the checklist while runners are alive
+///
+/// The default panic hook prints straight to stderr, over the live
+/// checklist. While any runner holds one of these, a panic on a task thread
+/// is written to that task's output instead; panics elsewhere still go to
+/// the previous hook, which is put back when the last runner finishes.
+struct PanicCapture;
+
+impl PanicCapture {
+    fn install() -> Self {
+        let mut hook = PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        if hook.0 == 0 {
+            let previous = Arc::new(panic::take_hook());
+            hook.1 = Some(Arc::clone(&previous));
+            panic::set_hook(Box::new(move |info| {
+                let captured = PANIC_OUTPUT.with(|output| match *output.borrow() {
+                    Some(ref output) => {
+                        let thread = thread::current();
+                        let name = thread.name().unwrap_or("<unnamed>");
+                        let _ = writeln!(output.clone(), "thread '{}' {}", name, info);
+                        true
+                    }
+                    None => false,
+                });
+                if !captured {
+                    previous(info);
+                }
+            }));
+        }
+        hook.0 += 1;
+        PanicCapture
+    }
+}
+
+impl Drop for PanicCapture {
+    fn drop(&mut self) {
+        let mut hook = PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        hook.0 -= 1;
+        // Swapping hooks while panicking aborts; ours still falls back to the
+        // previous one, so leave it in place then
+        if hook.0 > 0 || thread::panicking() {
+            return;
+        }
+        if let Some(previous) = hook.1.take() {
+            // Dropping our hook releases its reference to the previous one
+            drop(panic::take_hook());
+            match Arc::try_unwrap(previous) {
+                Ok(previous) => panic::set_hook(previous),
+                Err(previous) => panic::set_hook(Box::new(move |info| previous(info))),
+            }
+        }
+    }
+}
+
+/// The buffer a task's log and output are kept in, from [`TaskContext::output`]
+#[derive(Debug, Clone, Default)]
+pub struct TaskOutput(Arc<Mutex<Vec<u8>>>);
+
+impl Write for TaskOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl TaskOutput {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap_or_else(|e| e.into_inner())).into_owned()
+    }
+}
+
+/// Draws the checklist: redrawn in place on a terminal, one line per
+/// finished task otherwise
+#[derive(Debug)]
+struct View {
+    target: DrawTarget,
+    live: bool,
+    quiet: bool,
+    frame: Frame,
+    tick: usize,
+    /// Tasks already reported, when not drawing live
+    printed: Vec<bool>,
+}
+
+impl View {
+    fn new(target: DrawTarget) -> Self {
+        Self {
+            target,
+            live: target.is_terminal() && !crate::plain::is_plain(),
+            quiet: crate::verbosity::is_quiet(),
+            frame: Frame::new(),
+            tick: 0,
+            printed: Vec::new(),
+        }
+    }
+
+    /// Draw the current state; `done` once every worker has stopped, when
+    /// tasks still pending were skipped
+    fn draw(&mut self, slots: &[Slot], done: bool) {
+        if self.quiet {
+            return;
+        }
+        self.tick += 1;
+        if self.live {
+            for slot in slots {
+                self.frame.line(slot_line(slot, self.tick, done));
+            }
+            let mut buf = Vec::new();
+            let _ = self.frame.render_to(&mut buf);
+            if done {
+                let _ = self.frame.finish_to(&mut buf);
+            }
+            self.target.write(&String::from_utf8_lossy(&buf));
+            return;
+        }
+
+        self.printed.resize(slots.len(), false);
+        for (slot, printed) in slots.iter().zip(&mut self.printed) {
+            let finished = match slot.state {
+                SlotState::Succeeded(_) | SlotState::Failed(..) => true,
+                SlotState::Pending => done,
+                SlotState::Running(_) => false,
+            };
+            if finished && !*printed {
+                *printed = true;
+                self.target
+                    .write(&format!("{}\n", slot_line(slot, self.tick, done)));
+            }
+        }
+    }
+
+    /// Report each failure with the output its task captured, and with `-v`
+    /// every other task's output too
+    fn finish(&mut self, outcomes: &[TaskOutcome]) {
+        let verbose = crate::verbosity::is_verbose();
+        for outcome in outcomes {
+            let report = match &outcome.status {
+                TaskStatus::Failed(message) => format!(
+                    "{}\n{}",
+                    Color::Red.paint(format!("{} failed: {}", outcome.name, message)),
+                    indent_output(
+                        &outcome.log,
+                        if verbose { None } else { Some(FAILURE_LINES) }
+                    )
+                ),
+                _ if verbose && !outcome.log.is_empty() => format!(
+                    "{}\n{}",
+                    Color::BrightBlack.paint(format!("{} output:", outcome.name)),
+                    indent_output(&outcome.log, None)
+                ),
+                _ => continue,
+            };
+            self.target.write(&report);
+        }
+    }
+}
+
+/// Indent captured output under its heading, keeping only the last `limit`
+/// lines if given
+fn indent_output(output: &str, limit: Option<usize>) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    let hidden = limit.map_or(0, |limit| lines.len().saturating_sub(limit));
+    let mut text = String::new();
+    if hidden > 0 {
+        let noun = if hidden == 1 { "line" } else { "lines" };
+        text.push_str(&format!(
+            "    {}\n",
+            Color::BrightBlack.paint(format!("... {} earlier {} (-v shows all)", hidden, noun))
+        ));
+    }
+    for line in &lines[hidden..] {
+        text.push_str(&format!("    {}\n", line));
+    }
+    text
+}
+
+/// One checklist row
+fn slot_line(slot: &Slot, tick: usize, done: bool) -> String {
+    let (pending, succeeded, failed) = if crate::plain::is_plain() {
+        (".", "+", "x")
+    } else {
+        ("·", "✓", "✗")
+    };
+    match &slot.state {
+        SlotState::Pending if done => format!(
+            "  {} {}",
+            Color::BrightBlack.paint("-"),
+            Color::BrightBlack.paint(format!("{} (skipped)", slot.name))
+        ),
+        SlotState::Pending => format!("  {} {}", Color::BrightBlack.paint(pending), slot.name),
+        SlotState::Running(started) => {
+            let spinner = SPINNER_FRAMES[tick % SPINNER_FRAMES.len()];
+            let message = if slot.message.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", Color::BrightBlack.paint(slot.message.as_str()))
+            };
+            format!(
+                "  {} {}{} ({})",
+                Color::Cyan.paint(spinner.to_string()),
+                slot.name,
+                message,
+                crate::summary::format_elapsed(started.elapsed())
+            )
+        }
+        SlotState::Succeeded(elapsed) => format!(
+            "  {} {} ({})",
+            Color::Green.paint(succeeded),
+            slot.name,
+            crate::summary::format_elapsed(*elapsed)
+        ),
+        SlotState::Failed(elapsed, message) => format!(
+            "  {} {} ({}) {}",
+            Color::Red.paint(failed),
+            slot.name,
+            crate::summary::format_elapsed(*elapsed),
+            Color::Red.paint(message.as_str())
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indent_output_keeps_last_lines() {
+        let output = "one\ntwo\nthree\n";
+        assert_eq!(indent_output(output, None), "    one\n    two\n    three\n");
+        let cut = indent_output(output, Some(2));
+        assert!(cut.contains("... 1 earlier line (-v shows all)"));
+        assert!(cut.ends_with("    two\n    three\n"));
+        assert_eq!(indent_output(output, Some(3)), indent_output(output, None));
+    }
+}
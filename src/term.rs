@@ -1,14 +1,142 @@
 //! Terminal manipulation utilities.
+//!
+//! Raw mode and key input ([`Key`], [`RawMode`], [`Terminal::raw_mode`],
+//! [`Terminal::read_key`], [`Terminal::poll_key`]) need the `raw` feature.
 
+#[cfg(feature = "raw")]
+use std::collections::VecDeque;
+#[cfg(feature = "raw")]
+use std::io::Read;
 use std::io::{self, Write};
+#[cfg(feature = "raw")]
+use std::sync::Mutex;
+#[cfg(feature = "raw")]
+use std::time::{Duration, Instant};
+
+/// A single key press decoded from terminal input
+#[cfg(feature = "raw")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Key {
+    /// A printable character
+    Char(char),
+    /// A control chord such as Ctrl+C (`Ctrl('c')`)
+    Ctrl(char),
+    /// Enter / Return
+    Enter,
+    /// Tab
+    Tab,
+    /// Shift+Tab
+    BackTab,
+    /// Backspace
+    Backspace,
+    /// Delete (forward delete)
+    Delete,
+    /// Escape
+    Esc,
+    /// Left arrow
+    Left,
+    /// Right arrow
+    Right,
+    /// Up arrow
+    Up,
+    /// Down arrow
+    Down,
+    /// Home
+    Home,
+    /// End
+    End,
+    /// Page Up
+    PageUp,
+    /// Page Down
+    PageDown,
+    /// An escape sequence zfish does not recognise
+    Unknown,
+}
+
+/// Keeps the terminal in raw mode until dropped
+///
+/// Created by [`Terminal::raw_mode`]. Dropping the guard restores the previous
+/// terminal settings, including on early returns and panics.
+#[cfg(feature = "raw")]
+#[derive(Debug)]
+pub struct RawMode {
+    state: crate::os::TerminalState,
+}
+
+#[cfg(feature = "raw")]
+impl RawMode {
+    /// Suspends the process the way Ctrl+Z does in cooked mode, keeping the
+    /// terminal usable while it is stopped
+    ///
+    /// Raw mode delivers Ctrl+Z as [`Key::Ctrl('z')`](Key::Ctrl) instead of
+    /// stopping the process, so widgets call this when they read it. The
+    /// previous settings are restored, the process stops, and raw mode is
+    /// entered again once the shell resumes it (`fg`); redraw afterwards.
+    /// Does nothing where there is no job control (Windows, wasm).
+    ///
+    /// Only this in-band Ctrl+Z is handled. zfish installs no signal
+    /// handlers, so a `SIGTSTP` sent from outside (`kill -TSTP`) stops the
+    /// process with the terminal still raw, and nothing redraws on
+    /// `SIGCONT`.
+    pub fn suspend(&mut self) -> io::Result<()> {
+        if !crate::os::JOB_CONTROL {
+            return Ok(());
+        }
+        crate::os::restore_terminal(&self.state)?;
+        crate::os::suspend()?;
+        self.state = crate::os::enable_raw_mode()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "raw")]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = crate::os::restore_terminal(&self.state);
+    }
+}
+
+/// Bytes and keys read from stdin but not yet handed out by `read_key`
+#[cfg(feature = "raw")]
+struct InputQueue {
+    bytes: Vec<u8>,
+    keys: VecDeque<Key>,
+}
+
+#[cfg(feature = "raw")]
+impl InputQueue {
+    /// Block for the next chunk of stdin and decode whatever keys it completes
+    fn read_more(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; 64];
+        let n = io::stdin().lock().read(&mut buf)?;
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "stdin closed"));
+        }
+
+        self.bytes.extend_from_slice(&buf[..n]);
+        let consumed = decode_keys(&self.bytes, &mut self.keys);
+        self.bytes.drain(..consumed);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "raw")]
+static INPUT: Mutex<InputQueue> = Mutex::new(InputQueue {
+    bytes: Vec::new(),
+    keys: VecDeque::new(),
+});
 
 /// Terminal utilities for cursor manipulation and screen clearing.
 #[derive(Debug)]
 pub struct Terminal;
 
 impl Terminal {
-    /// Clear the entire screen.
+    /// Clear the entire screen (does nothing in [plain mode](crate::plain)).
     pub fn clear_screen() -> io::Result<()> {
+        if crate::plain::is_plain() {
+            return Ok(());
+        }
         let stdout = io::stdout();
         let mut handle = stdout.lock();
         handle.write_all(b"\x1b[2J")?;
@@ -16,7 +144,12 @@ impl Terminal {
     }
 
     /// Move the cursor to the specified position (1-based coordinates).
+    ///
+    /// Does nothing in [plain mode](crate::plain).
     pub fn move_cursor(row: u16, col: u16) -> io::Result<()> {
+        if crate::plain::is_plain() {
+            return Ok(());
+        }
         let stdout = io::stdout();
         let mut handle = stdout.lock();
         write!(handle, "\x1b[{};{}H", row, col)?;
@@ -28,6 +161,67 @@ impl Terminal {
         crate::os::get_terminal_size()
     }
 
+    /// Put the terminal into raw mode for the lifetime of the returned guard.
+    ///
+    /// Fails when stdin is not a terminal, and with [`io::ErrorKind::Unsupported`]
+    /// on platforms without raw mode (e.g. wasm32-wasi).
+    #[cfg(feature = "raw")]
+    pub fn raw_mode() -> io::Result<RawMode> {
+        crate::os::enable_raw_mode().map(|state| RawMode { state })
+    }
+
+    /// Block until the next key press and return it.
+    ///
+    /// The terminal should be in raw mode (see [`raw_mode`](Self::raw_mode)),
+    /// otherwise input only arrives once the user presses Enter.
+    #[cfg(feature = "raw")]
+    pub fn read_key() -> io::Result<Key> {
+        let mut input = INPUT.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(key) = input.keys.pop_front() {
+                return Ok(key);
+            }
+            input.read_more()?;
+        }
+    }
+
+    /// Wait up to `timeout` for a key press, returning `None` if none came.
+    ///
+    /// Lets a spinner keep animating while listening for a cancel key. Like
+    /// [`read_key`](Self::read_key) this expects raw mode. It fails with
+    /// [`io::ErrorKind::Unsupported`] where stdin cannot be polled (e.g.
+    /// wasm32-wasi).
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use zfish::{Key, Terminal};
+    ///
+    /// let _raw = Terminal::raw_mode()?;
+    /// let frames = ['|', '/', '-', '\\'];
+    /// for frame in frames.iter().cycle() {
+    ///     eprint!("\r{} working (press q to abort)", frame);
+    ///     if Terminal::poll_key(Duration::from_millis(100))? == Some(Key::Char('q')) {
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "raw")]
+    pub fn poll_key(timeout: Duration) -> io::Result<Option<Key>> {
+        let deadline = Instant::now() + timeout;
+        let mut input = INPUT.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(key) = input.keys.pop_front() {
+                return Ok(Some(key));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !crate::os::wait_for_input(remaining)? {
+                return Ok(None);
+            }
+            input.read_more()?;
+        }
+    }
+
     /// Print text at the specified position.
     pub fn print_at(row: u16, col: u16, text: &str) -> io::Result<()> {
         Self::move_cursor(row, col)?;
@@ -37,3 +231,374 @@ impl Terminal {
         handle.flush()
     }
 }
+
+/// Ask the terminal to raise a desktop notification.
+///
+/// Sends OSC 9 to iTerm2, ConEmu and kitty, OSC 777 to rxvt-unicode, foot,
+/// WezTerm and Ghostty, and a bell everywhere else, which most terminals turn
+/// into a sound or an urgent-window hint. Nothing is written when stdout is
+/// not a terminal, or in [plain mode](crate::plain).
+///
+/// ```no_run
+/// zfish::term::notify("Build finished", "3 crates compiled in 42s").unwrap();
+/// ```
+pub fn notify(title: &str, body: &str) -> io::Result<()> {
+    use std::io::IsTerminal;
+
+    if !io::stdout().is_terminal() || crate::plain::is_plain() {
+        return Ok(());
+    }
+    let sequence = Notifier::detect(|name| std::env::var(name).ok()).sequence(title, body);
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    handle.write_all(sequence.as_bytes())?;
+    handle.flush()
+}
+
+/// How the running terminal can be asked for a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notifier {
+    /// `OSC 9 ; message BEL`
+    Osc9,
+    /// `OSC 777 ; notify ; title ; body BEL`
+    Osc777,
+    /// A plain BEL
+    Bell,
+}
+
+impl Notifier {
+    /// Guess the terminal from the environment
+    fn detect(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if program == "iTerm.app" || var("ConEmuPID").is_some() || term == "xterm-kitty" {
+            Notifier::Osc9
+        } else if matches!(program.as_str(), "WezTerm" | "ghostty")
+            || term.starts_with("rxvt")
+            || term.starts_with("foot")
+        {
+            Notifier::Osc777
+        } else {
+            Notifier::Bell
+        }
+    }
+
+    fn sequence(self, title: &str, body: &str) -> String {
+        // Control characters would end the sequence early
+        let clean = |text: &str| text.chars().filter(|c| !c.is_control()).collect::<String>();
+        match self {
+            Notifier::Osc9 if body.is_empty() => format!("\x1b]9;{}\x07", clean(title)),
+            Notifier::Osc9 => format!("\x1b]9;{}: {}\x07", clean(title), clean(body)),
+            Notifier::Osc777 => format!(
+                "\x1b]777;notify;{};{}\x07",
+                clean(title).replace(';', ","),
+                clean(body)
+            ),
+            Notifier::Bell => "\x07".to_string(),
+        }
+    }
+}
+
+/// An inline screen region redrawn without flicker
+///
+/// Build each frame with [`line`](Self::line), then [`flush`](Self::flush) it:
+/// only rows that changed since the previous frame are rewritten, and the
+/// whole update goes out in a single write. The region starts at the cursor
+/// and grows downwards; lines should fit the terminal width, since wrapped
+/// lines throw off the row arithmetic.
+///
+/// ```no_run
+/// use zfish::term::Frame;
+///
+/// let mut frame = Frame::new();
+/// for done in 0..=10 {
+///     frame.line(format!("downloading: {}/10", done));
+///     frame.line("uploading: waiting");
+///     frame.flush().unwrap();
+/// }
+/// frame.finish().unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct Frame {
+    /// Lines of the frame being built
+    next: Vec<String>,
+    /// Lines currently on screen
+    shown: Vec<String>,
+    /// Row of the region the cursor is on
+    cursor_row: usize,
+    buf: Vec<u8>,
+}
+
+impl Frame {
+    /// Create an empty frame at the cursor position.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a line to the frame being built.
+    pub fn line(&mut self, text: impl Into<String>) -> &mut Self {
+        self.next.push(text.into());
+        self
+    }
+
+    /// Write the changes since the last frame to stdout.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let stdout = io::stdout();
+        self.render_to(&mut stdout.lock())
+    }
+
+    /// Write the changes since the last frame to `out` in one call.
+    ///
+    /// Rows the new frame no longer uses are blanked, so the region never
+    /// shrinks on screen. In [plain mode](crate::plain) nothing is written
+    /// until [`finish`](Self::finish), which prints the last frame as plain
+    /// lines.
+    pub fn render_to(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let mut next = std::mem::take(&mut self.next);
+        if crate::plain::is_plain() {
+            self.shown = next;
+            return Ok(());
+        }
+        if next.len() < self.shown.len() {
+            next.resize(self.shown.len(), String::new());
+        }
+
+        self.buf.clear();
+        for (row, text) in next.iter().enumerate() {
+            if self.shown.get(row) == Some(text) {
+                continue;
+            }
+            self.move_to(row);
+            self.buf.extend_from_slice(b"\r\x1b[2K");
+            self.buf.extend_from_slice(text.as_bytes());
+        }
+        self.shown = next;
+
+        if !self.buf.is_empty() {
+            out.write_all(&self.buf)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Leave the region: move below it so normal output can continue.
+    pub fn finish(&mut self) -> io::Result<()> {
+        let stdout = io::stdout();
+        self.finish_to(&mut stdout.lock())
+    }
+
+    /// Like [`finish`](Self::finish), writing to `out` instead of stdout.
+    pub fn finish_to(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.shown.is_empty() {
+            return Ok(());
+        }
+        if crate::plain::is_plain() {
+            for line in self.shown.drain(..) {
+                writeln!(out, "{}", line)?;
+            }
+            return out.flush();
+        }
+        self.buf.clear();
+        self.move_to(self.shown.len() - 1);
+        self.buf.extend_from_slice(b"\r\n");
+        self.shown.clear();
+        self.cursor_row = 0;
+
+        out.write_all(&self.buf)?;
+        out.flush()
+    }
+
+    /// Queue cursor movement to `row`, scrolling with newlines past the bottom
+    fn move_to(&mut self, row: usize) {
+        let last_row = self.shown.len().saturating_sub(1);
+        if row < self.cursor_row {
+            write_cursor(&mut self.buf, self.cursor_row - row, 'A');
+        } else if row > self.cursor_row {
+            // Existing rows can be reached with cursor-down; new ones need \n
+            let down = row.min(last_row).saturating_sub(self.cursor_row);
+            if down > 0 {
+                write_cursor(&mut self.buf, down, 'B');
+            }
+            for _ in self.cursor_row + down..row {
+                self.buf.extend_from_slice(b"\r\n");
+            }
+        }
+        self.cursor_row = row;
+    }
+}
+
+fn write_cursor(buf: &mut Vec<u8>, count: usize, direction: char) {
+    buf.extend_from_slice(format!("\x1b[{}{}", count, direction).as_bytes());
+}
+
+/// Decode as many complete keys from `bytes` as possible into `keys`
+///
+/// Returns the number of bytes consumed; an incomplete UTF-8 character at the
+/// end is left for the next read. A lone ESC at the end of a read is taken as
+/// the Escape key, since terminals send whole escape sequences in one write.
+#[cfg(feature = "raw")]
+pub(crate) fn decode_keys(bytes: &[u8], keys: &mut VecDeque<Key>) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        let (key, len) = match bytes[i] {
+            0x1b => decode_escape(&bytes[i..]),
+            b'\r' | b'\n' => (Key::Enter, 1),
+            b'\t' => (Key::Tab, 1),
+            0x7f | 0x08 => (Key::Backspace, 1),
+            b @ 0x01..=0x1a => (Key::Ctrl((b'a' + b - 1) as char), 1),
+            b if b < 0x20 => (Key::Unknown, 1),
+            b => {
+                let len = utf8_len(b);
+                if i + len > bytes.len() {
+                    break;
+                }
+                match std::str::from_utf8(&bytes[i..i + len]) {
+                    Ok(s) => (s.chars().next().map_or(Key::Unknown, Key::Char), len),
+                    Err(_) => (Key::Unknown, 1),
+                }
+            }
+        };
+        keys.push_back(key);
+        i += len;
+    }
+    i
+}
+
+/// Decode an escape sequence starting at `bytes[0] == ESC`
+#[cfg(feature = "raw")]
+fn decode_escape(bytes: &[u8]) -> (Key, usize) {
+    match bytes.get(1) {
+        Some(b'[') => {
+            // CSI: parameters (digits and ';') followed by a final byte
+            let end = bytes[2..]
+                .iter()
+                .position(|b| (0x40..=0x7e).contains(b))
+                .map(|p| p + 2);
+            let Some(end) = end else {
+                return (Key::Unknown, bytes.len());
+            };
+            let params = &bytes[2..end];
+            let key = match (bytes[end], params) {
+                (b'A', _) => Key::Up,
+                (b'B', _) => Key::Down,
+                (b'C', _) => Key::Right,
+                (b'D', _) => Key::Left,
+                (b'H', _) => Key::Home,
+                (b'F', _) => Key::End,
+                (b'Z', _) => Key::BackTab,
+                (b'~', b"1" | b"7") => Key::Home,
+                (b'~', b"4" | b"8") => Key::End,
+                (b'~', b"3") => Key::Delete,
+                (b'~', b"5") => Key::PageUp,
+                (b'~', b"6") => Key::PageDown,
+                _ => Key::Unknown,
+            };
+            (key, end + 1)
+        }
+        Some(b'O') => {
+            let key = match bytes.get(2) {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                _ => Key::Unknown,
+            };
+            (key, bytes.len().min(3))
+        }
+        _ => (Key::Esc, 1),
+    }
+}
+
+/// Length of a UTF-8 sequence from its first byte
+#[cfg(feature = "raw")]
+fn utf8_len(first: u8) -> usize {
+    match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    }
+}
+
+#[cfg(all(test, feature = "raw"))]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Vec<Key> {
+        let mut keys = VecDeque::new();
+        decode_keys(bytes, &mut keys);
+        keys.into_iter().collect()
+    }
+
+    #[test]
+    fn test_decode_plain_and_control() {
+        assert_eq!(
+            decode(b"a\r\t\x7f\x03"),
+            vec![
+                Key::Char('a'),
+                Key::Enter,
+                Key::Tab,
+                Key::Backspace,
+                Key::Ctrl('c')
+            ]
+        );
+        assert_eq!(decode("é".as_bytes()), vec![Key::Char('é')]);
+    }
+
+    #[test]
+    fn test_decode_escape_sequences() {
+        assert_eq!(
+            decode(b"\x1b[A\x1b[D\x1bOC\x1b[3~\x1b[Z\x1b[1;5C"),
+            vec![
+                Key::Up,
+                Key::Left,
+                Key::Right,
+                Key::Delete,
+                Key::BackTab,
+                Key::Right
+            ]
+        );
+        assert_eq!(decode(b"\x1b"), vec![Key::Esc]);
+        assert_eq!(decode(b"\x1bx"), vec![Key::Esc, Key::Char('x')]);
+    }
+
+    #[test]
+    fn test_notifier_detect_and_sequence() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            Notifier::detect(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Notifier::Osc9
+        );
+        assert_eq!(
+            Notifier::detect(env(&[("TERM", "rxvt-unicode-256color")])),
+            Notifier::Osc777
+        );
+        assert_eq!(Notifier::detect(env(&[("TERM", "xterm")])), Notifier::Bell);
+
+        assert_eq!(
+            Notifier::Osc9.sequence("Done", "all good"),
+            "\x1b]9;Done: all good\x07"
+        );
+        assert_eq!(
+            Notifier::Osc777.sequence("a;b\x07", "c;d"),
+            "\x1b]777;notify;a,b;c;d\x07"
+        );
+        assert_eq!(Notifier::Bell.sequence("x", "y"), "\x07");
+    }
+
+    #[test]
+    fn test_decode_keeps_partial_utf8() {
+        let bytes = "→".as_bytes();
+        let mut keys = VecDeque::new();
+        assert_eq!(decode_keys(&bytes[..2], &mut keys), 0);
+        assert!(keys.is_empty());
+    }
+}
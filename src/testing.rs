@@ -0,0 +1,176 @@
+//! # Testing Helpers
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — testing.rs                                            ║
+//! ║  Keep help examples in sync with the command tree             ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! Examples added with [`Command::example_checked`] are shown in help like any
+//! other example. [`verify_examples`] splits each one into words (shell-style
+//! quotes and backslashes) and parses it against the real command tree, so a
+//! renamed flag breaks a test instead of silently rotting in `--help`.
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::command::{App, Arg, Command};
+//!
+//! let app = App::new("myapp").subcommand(
+//!     Command::new("build")
+//!         .arg(Arg::new("release").long("release"))
+//!         .example_checked("myapp build --release"),
+//! );
+//!
+//! zfish::testing::assert_examples(&app);
+//! ```
+
+use crate::command::{Command, CommandError, ErrorKind};
+use std::fmt;
+
+/// A checked example that did not parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExampleFailure {
+    /// Path of the command that declared the example (`"myapp build"`)
+    pub command: String,
+    /// The example as written
+    pub example: String,
+    /// Why it was rejected
+    pub error: CommandError,
+}
+
+impl fmt::Display for ExampleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "example `{}` (in '{}'): {}",
+            self.example, self.command, self.error
+        )
+    }
+}
+
+impl std::error::Error for ExampleFailure {}
+
+/// Parses every checked example of `cmd` and its subcommands
+///
+/// Examples are parsed from the root, so they must start with the program
+/// name. An example that asks for `--help` or `--version` counts as valid.
+pub fn verify_examples(cmd: &impl AsRef<Command>) -> Result<(), Vec<ExampleFailure>> {
+    let root = cmd.as_ref();
+    let failures: Vec<ExampleFailure> = root
+        .checked_examples()
+        .into_iter()
+        .filter_map(|(command, example)| {
+            let error = match split_words(example) {
+                Ok(words) => match root.try_parse_from(words) {
+                    Err(err)
+                        if !matches!(
+                            err.inner(),
+                            CommandError::HelpRequested | CommandError::VersionRequested
+                        ) =>
+                    {
+                        err
+                    }
+                    _ => return None,
+                },
+                Err(err) => err,
+            };
+            Some(ExampleFailure {
+                command,
+                example: example.to_string(),
+                error,
+            })
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+/// Like [`verify_examples`], but panics with every failure listed
+#[track_caller]
+pub fn assert_examples(cmd: &impl AsRef<Command>) {
+    if let Err(failures) = verify_examples(cmd) {
+        let list: Vec<String> = failures.iter().map(|f| format!("  {}", f)).collect();
+        panic!(
+            "{} example(s) do not parse:\n{}",
+            failures.len(),
+            list.join("\n")
+        );
+    }
+}
+
+/// Split an example into words the way a POSIX shell would (without expansion)
+fn split_words(line: &str) -> Result<Vec<String>, CommandError> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => match chars.next() {
+                Some(next @ ('"' | '\\' | '$' | '`')) => word.push(next),
+                Some(next) => {
+                    word.push('\\');
+                    word.push(next);
+                }
+                None => word.push('\\'),
+            },
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(CommandError::Custom(
+            ErrorKind::InvalidValue,
+            "unterminated quote".to_string(),
+        ));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"app -m "fix: a \"b\"" 'c d' e\ f """#).unwrap(),
+            vec!["app", "-m", r#"fix: a "b""#, "c d", "e f", ""]
+        );
+        assert!(split_words("app 'open").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! # Text Utilities
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — text/mod.rs                                           ║
+//! ║  General-purpose string helpers                               ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! - [`similarity`]: edit distance and fuzzy matching, as used for
+//!   "did you mean" suggestions
+
+pub mod similarity;
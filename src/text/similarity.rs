@@ -0,0 +1,172 @@
+//! # String Similarity
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — text/similarity.rs                                    ║
+//! ║  Levenshtein and Jaro-Winkler for fuzzy matching              ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! All functions compare `char`s, so accented and CJK text is measured by
+//! character rather than by byte. [`closest_match`] ranks candidates by
+//! [`jaro_winkler`], which favours a shared prefix — a good fit for typos in
+//! command and flag names.
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::text::similarity::closest_match;
+//!
+//! let commands = ["build", "bench", "publish"];
+//! assert_eq!(closest_match("biuld", commands, 0.8), Some("build"));
+//! assert_eq!(closest_match("deploy", commands, 0.8), None);
+//! ```
+
+/// Number of single-character insertions, deletions and substitutions
+/// needed to turn `a` into `b`
+///
+/// ```
+/// use zfish::text::similarity::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // One row of the distance matrix, reused for every character of `a`
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Jaro similarity, from 0.0 (nothing in common) to 1.0 (identical)
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Characters match if equal and no further apart than this
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == *ca) {
+            b_matched[j] = true;
+            a_matches.push(*ca);
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(x, y)| x != y)
+        .count();
+
+    let m = a_matches.len() as f64;
+    let t = transpositions as f64 / 2.0;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - t) / m) / 3.0
+}
+
+/// Jaro-Winkler similarity: [`jaro`] boosted for a common prefix (up to 4
+/// characters)
+///
+/// ```
+/// use zfish::text::similarity::jaro_winkler;
+///
+/// assert!(jaro_winkler("install", "instal") > jaro_winkler("install", "nstall"));
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let similarity = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(x, y)| x == y)
+        .count();
+    similarity + prefix as f64 * 0.1 * (1.0 - similarity)
+}
+
+/// The candidate most similar to `target` by [`jaro_winkler`], if it scores
+/// at least `threshold`
+///
+/// Ties go to the earlier candidate. A threshold around `0.8` suggests
+/// typo fixes without offering unrelated names.
+pub fn closest_match<'a, I, S>(target: &str, candidates: I, threshold: f64) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a S>,
+    S: AsRef<str> + ?Sized + 'a,
+{
+    let mut best: Option<(&'a str, f64)> = None;
+    for candidate in candidates {
+        let candidate = candidate.as_ref();
+        let score = jaro_winkler(target, candidate);
+        if score >= threshold && best.is_none_or(|(_, top)| score > top) {
+            best = Some((candidate, score));
+        }
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("café", "cafe"), 1);
+        assert_eq!(levenshtein("commit", "commit"), 0);
+    }
+
+    #[test]
+    fn test_jaro_and_jaro_winkler() {
+        let close = |x: f64, y: f64| (x - y).abs() < 1e-3;
+        assert!(close(jaro("MARTHA", "MARHTA"), 0.944));
+        assert!(close(jaro_winkler("MARTHA", "MARHTA"), 0.961));
+        assert!(close(jaro_winkler("DIXON", "DICKSONX"), 0.813));
+        assert!(close(jaro("", ""), 1.0));
+        assert!(close(jaro("abc", ""), 0.0));
+        assert!(close(jaro("abc", "xyz"), 0.0));
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let names = vec![
+            "status".to_string(),
+            "stash".to_string(),
+            "show".to_string(),
+        ];
+        assert_eq!(closest_match("stats", &names, 0.8), Some("status"));
+        assert_eq!(closest_match("stsh", &names, 0.8), Some("stash"));
+        assert_eq!(closest_match("zzz", &names, 0.8), None);
+        assert_eq!(closest_match("x", Vec::<&str>::new(), 0.0), None);
+    }
+}
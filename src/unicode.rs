@@ -10,16 +10,100 @@
 //! - Keycap sequences (2-cell)
 //! - Text-default emoji that become emoji with VS-16 (plain=1, with VS-16=2)
 //!
+//! Terminals disagree on text-style emoji, so [`set_width_policy`] picks how
+//! wide they count for every table, prompt and line editor in the process;
+//! [`WidthPolicy::detect`] guesses from the environment.
+//!
+//! It also provides [`wrap`], which word-wraps text to a display width,
+//! [`WidthCache`] for measuring the same strings over and over (live
+//! dashboards re-rendering every tick), and [`nfc`] normalization.
+//!
 //! This logic purposefully has no external dependencies.
 
+use crate::unicode_tables::{
+    COMBINING_CLASSES, COMPOSITIONS, DECOMPOSITIONS, TEXT_PRESENTATION_EMOJI,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The process-wide [`WidthPolicy`], stored as its discriminant
+static WIDTH_POLICY: AtomicU8 = AtomicU8::new(WidthPolicy::Standard as u8);
+
+/// How wide emoji that default to text style (`✔`, `❤`, `☀`) are counted
+///
+/// Unicode draws them one cell wide unless VS-16 asks for emoji style, but
+/// terminals differ: some always draw them as wide emoji, others (tmux among
+/// them) ignore VS-16. Pick the policy that matches the terminal so columns
+/// line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum WidthPolicy {
+    /// One cell, two when followed by VS-16 (the Unicode rule)
+    #[default]
+    Standard,
+    /// Always two cells, like emoji-style emoji
+    WideEmoji,
+    /// Always one cell, even with VS-16
+    NarrowAmbiguous,
+}
+
+impl WidthPolicy {
+    /// Guesses the policy for the terminal zfish is running in
+    ///
+    /// Inside tmux or GNU screen, and on the legacy Windows console, VS-16 is
+    /// not honoured, so those get [`NarrowAmbiguous`](Self::NarrowAmbiguous);
+    /// everything else gets [`Standard`](Self::Standard).
+    pub fn detect() -> Self {
+        if crate::os::color_backend() == crate::os::ColorBackend::LegacyConsole {
+            return WidthPolicy::NarrowAmbiguous;
+        }
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        let multiplexed = var("TMUX").is_some()
+            || var("TERM")
+                .is_some_and(|term| term.starts_with("screen") || term.starts_with("tmux"));
+        if multiplexed {
+            WidthPolicy::NarrowAmbiguous
+        } else {
+            WidthPolicy::Standard
+        }
+    }
+}
+
+/// Sets the [`WidthPolicy`] used by every width calculation from now on
+///
+/// Tables measure cells when they are added, so set the policy first.
+pub fn set_width_policy(policy: WidthPolicy) {
+    WIDTH_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// The current [`WidthPolicy`] ([`Standard`](WidthPolicy::Standard) unless set)
+pub fn width_policy() -> WidthPolicy {
+    match WIDTH_POLICY.load(Ordering::Relaxed) {
+        x if x == WidthPolicy::WideEmoji as u8 => WidthPolicy::WideEmoji,
+        x if x == WidthPolicy::NarrowAmbiguous as u8 => WidthPolicy::NarrowAmbiguous,
+        _ => WidthPolicy::Standard,
+    }
+}
+
 /// Public API: compute display width of a string in terminal cells.
+///
+/// ANSI escape sequences (colors, styles, cursor movement) take no space.
 pub fn display_width(s: &str) -> usize {
+    if s.contains('\x1b') {
+        return display_width(&crate::style::strip_ansi(s));
+    }
+
+    let policy = width_policy();
     let mut width = 0;
     let chars: Vec<char> = s.chars().collect();
     let mut i = 0;
 
     while i < chars.len() {
-        let (char_width, consumed) = char_width_at(&chars, i);
+        let (char_width, consumed) = char_width_at(&chars, i, policy);
         width += char_width;
         i += consumed;
     }
@@ -27,8 +111,276 @@ pub fn display_width(s: &str) -> usize {
     width
 }
 
+/// Normalizes `s` to Unicode Normalization Form C (composed)
+///
+/// A letter typed as base + combining accent becomes the precomposed
+/// character, so both spellings compare equal. Already-normalized text is
+/// returned borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use zfish::unicode::nfc;
+///
+/// assert_eq!(nfc("cafe\u{301}"), "café");
+/// assert_eq!(nfc("\u{1100}\u{1161}"), "가");
+/// ```
+pub fn nfc(s: &str) -> Cow<'_, str> {
+    // Nothing below U+0300 decomposes or combines (U+00C0.. are already NFC)
+    if s.chars().all(|c| (c as u32) < 0x300) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut chars = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        decompose(c as u32, &mut chars);
+    }
+    reorder(&mut chars);
+    compose(&mut chars);
+
+    let normalized: String = chars.into_iter().filter_map(char::from_u32).collect();
+    if normalized == s {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+// Hangul syllables are composed arithmetically rather than from tables
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = 19 * HANGUL_N_COUNT;
+
+/// Append the full canonical decomposition of `cp`
+fn decompose(cp: u32, out: &mut Vec<u32>) {
+    if (HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT).contains(&cp) {
+        let index = cp - HANGUL_S_BASE;
+        out.push(HANGUL_L_BASE + index / HANGUL_N_COUNT);
+        out.push(HANGUL_V_BASE + (index % HANGUL_N_COUNT) / HANGUL_T_COUNT);
+        if !index.is_multiple_of(HANGUL_T_COUNT) {
+            out.push(HANGUL_T_BASE + index % HANGUL_T_COUNT);
+        }
+        return;
+    }
+    match DECOMPOSITIONS.binary_search_by_key(&cp, |&(c, _, _)| c) {
+        Ok(idx) => {
+            let (_, first, second) = DECOMPOSITIONS[idx];
+            decompose(first, out);
+            if second != 0 {
+                decompose(second, out);
+            }
+        }
+        Err(_) => out.push(cp),
+    }
+}
+
+/// Canonical combining class (0 for starters)
+fn combining_class(cp: u32) -> u8 {
+    let idx = COMBINING_CLASSES.partition_point(|&(_, last, _)| last < cp);
+    match COMBINING_CLASSES.get(idx) {
+        Some(&(first, _, class)) if first <= cp => class,
+        _ => 0,
+    }
+}
+
+/// Sort each run of combining marks by class, keeping equal classes in order
+fn reorder(chars: &mut [u32]) {
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let end = chars[start..]
+            .iter()
+            .position(|&c| combining_class(c) == 0)
+            .map_or(chars.len(), |offset| start + offset);
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+}
+
+/// The primary composite of a starter and a following character, if any
+fn compose_pair(first: u32, second: u32) -> Option<u32> {
+    let l_range = HANGUL_L_BASE..HANGUL_L_BASE + 19;
+    let v_range = HANGUL_V_BASE..HANGUL_V_BASE + HANGUL_V_COUNT;
+    if l_range.contains(&first) && v_range.contains(&second) {
+        let index =
+            (first - HANGUL_L_BASE) * HANGUL_N_COUNT + (second - HANGUL_V_BASE) * HANGUL_T_COUNT;
+        return Some(HANGUL_S_BASE + index);
+    }
+    let s_range = HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT;
+    if s_range.contains(&first)
+        && (first - HANGUL_S_BASE).is_multiple_of(HANGUL_T_COUNT)
+        && (HANGUL_T_BASE + 1..HANGUL_T_BASE + HANGUL_T_COUNT).contains(&second)
+    {
+        return Some(first + second - HANGUL_T_BASE);
+    }
+    COMPOSITIONS
+        .binary_search_by_key(&(first, second), |&(a, b, _)| (a, b))
+        .ok()
+        .map(|idx| COMPOSITIONS[idx].2)
+}
+
+/// Canonical composition of decomposed, reordered text, in place
+fn compose(chars: &mut Vec<u32>) {
+    let Some(&first) = chars.first() else {
+        return;
+    };
+    let mut starter = 0;
+    // Class of the last character kept; 256 blocks composition until the
+    // first starter when the text opens with a combining mark
+    let mut last_class: u16 = if combining_class(first) == 0 { 0 } else { 256 };
+    let mut kept = 1;
+
+    for i in 1..chars.len() {
+        let c = chars[i];
+        let class = u16::from(combining_class(c));
+        // Unblocked: nothing kept since the starter, or only lower classes
+        if (last_class == 0 || last_class < class)
+            && let Some(composite) = compose_pair(chars[starter], c)
+        {
+            chars[starter] = composite;
+            continue;
+        }
+        if class == 0 {
+            starter = kept;
+        }
+        last_class = class;
+        chars[kept] = c;
+        kept += 1;
+    }
+    chars.truncate(kept);
+}
+
+/// Remembers the display width of strings already measured
+///
+/// Lookups hash the string, which is much cheaper than re-measuring long or
+/// emoji-heavy text. Entries are never evicted; call [`clear`](Self::clear)
+/// when the set of strings changes. Changing the [`WidthPolicy`] empties the
+/// cache on the next lookup.
+///
+/// # Examples
+///
+/// ```
+/// use zfish::unicode::WidthCache;
+///
+/// let mut cache = WidthCache::new();
+/// assert_eq!(cache.width("状态 ✅"), 7);
+/// assert_eq!(cache.width("状态 ✅"), 7); // served from the cache
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WidthCache {
+    widths: HashMap<String, usize>,
+    /// Policy the remembered widths were measured with
+    policy: WidthPolicy,
+}
+
+impl WidthCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the display width of `s`, measuring it on first use
+    pub fn width(&mut self, s: &str) -> usize {
+        let policy = width_policy();
+        if policy != self.policy {
+            self.widths.clear();
+            self.policy = policy;
+        }
+        if let Some(&width) = self.widths.get(s) {
+            return width;
+        }
+        let width = display_width(s);
+        self.widths.insert(s.to_string(), width);
+        width
+    }
+
+    /// Number of distinct strings remembered
+    pub fn len(&self) -> usize {
+        self.widths.len()
+    }
+
+    /// Checks whether nothing has been measured yet
+    pub fn is_empty(&self) -> bool {
+        self.widths.is_empty()
+    }
+
+    /// Forgets every remembered width
+    pub fn clear(&mut self) {
+        self.widths.clear();
+    }
+}
+
+/// Word-wrap `text` into lines of at most `width` cells
+///
+/// Existing line breaks are kept, runs of whitespace collapse to one space,
+/// and words longer than `width` are split.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+
+        for word in paragraph.split_whitespace() {
+            let word_width = display_width(word);
+            if line_width > 0 && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            // Split words that cannot fit on a line of their own
+            let mut rest = word;
+            while display_width(rest) > width {
+                let end = match prefix_within(rest, width) {
+                    // A character wider than the line still has to go somewhere
+                    0 => rest.chars().next().map_or(rest.len(), char::len_utf8),
+                    end => end,
+                };
+                lines.push(rest[..end].to_string());
+                rest = &rest[end..];
+            }
+            line.push_str(rest);
+            line_width = display_width(rest);
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Longest prefix of `text` (in bytes, on a char boundary) at most `width` cells wide
+pub(crate) fn prefix_within(text: &str, width: usize) -> usize {
+    let mut end = 0;
+    for (idx, c) in text.char_indices() {
+        let next = idx + c.len_utf8();
+        if display_width(&text[..next]) > width {
+            break;
+        }
+        end = next;
+    }
+    end
+}
+
 /// Calculate the width of a character at a given position and return (width, chars_consumed)
-fn char_width_at(chars: &[char], i: usize) -> (usize, usize) {
+fn char_width_at(chars: &[char], i: usize, policy: WidthPolicy) -> (usize, usize) {
     let c = chars[i];
     let cp = c as u32;
 
@@ -37,7 +389,7 @@ fn char_width_at(chars: &[char], i: usize) -> (usize, usize) {
     }
 
     if is_text_default_emoji(cp) {
-        return handle_text_default_emoji(chars, i);
+        return handle_text_default_emoji(chars, i, policy);
     }
 
     if is_regional_indicator(cp) {
@@ -58,12 +410,19 @@ fn char_width_at(chars: &[char], i: usize) -> (usize, usize) {
     handle_regular_char(chars, i)
 }
 
-/// Handle text-default emoji (plain 1, with VS-16 => 2)
-fn handle_text_default_emoji(chars: &[char], i: usize) -> (usize, usize) {
-    if i + 1 < chars.len() && (chars[i + 1] as u32) == 0xFE0F {
-        (2, 2)
-    } else {
-        (1, 1)
+/// Handle text-default emoji (plain 1, with VS-16 or a skin tone => 2)
+///
+/// Once in emoji presentation it can start a ZWJ sequence (rainbow flag,
+/// heart on fire). The [`WidthPolicy`] can force either width.
+fn handle_text_default_emoji(chars: &[char], i: usize, policy: WidthPolicy) -> (usize, usize) {
+    let emoji_style = chars
+        .get(i + 1)
+        .is_some_and(|&next| next == '\u{FE0F}' || is_emoji_modifier(next as u32));
+    match policy {
+        WidthPolicy::WideEmoji => handle_wide_or_emoji(chars, i),
+        WidthPolicy::NarrowAmbiguous if emoji_style => (1, handle_wide_or_emoji(chars, i).1),
+        _ if emoji_style => handle_wide_or_emoji(chars, i),
+        _ => (1, 1),
     }
 }
 
@@ -149,6 +508,7 @@ fn is_zero_width(cp: u32) -> bool {
         0x200C | // ZWNJ
         0x200B | // ZW space
         0xFEFF | // ZW no-break space
+        0xE0001 | 0xE0020..=0xE007F | // tags (subdivision flags such as England's)
         0x0000..=0x001F | 0x007F..=0x009F // controls
     )
 }
@@ -209,7 +569,37 @@ fn is_wide_character(cp: u32) -> bool {
     )
 }
 
-#[inline]
 fn is_text_default_emoji(cp: u32) -> bool {
-    matches!(cp, 0x26A0 | 0x263A | 0x2709 | 0x260E | 0x270F)
+    let idx = TEXT_PRESENTATION_EMOJI.partition_point(|&(_, last)| last < cp);
+    TEXT_PRESENTATION_EMOJI
+        .get(idx)
+        .is_some_and(|&(first, _)| first <= cp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_policy_detect_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            WidthPolicy::detect_from(env(&[("TMUX", "/tmp/tmux-0/default,1,0")])),
+            WidthPolicy::NarrowAmbiguous
+        );
+        assert_eq!(
+            WidthPolicy::detect_from(env(&[("TERM", "screen-256color")])),
+            WidthPolicy::NarrowAmbiguous
+        );
+        assert_eq!(
+            WidthPolicy::detect_from(env(&[("TERM", "xterm-256color")])),
+            WidthPolicy::Standard
+        );
+    }
 }
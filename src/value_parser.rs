@@ -0,0 +1,497 @@
+//! # Built-in Value Parsers
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — value_parser.rs                                       ║
+//! ║  Typed parsers for common argument values                     ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! This module provides std-only parsers for the values almost every CLI ends
+//! up accepting:
+//! - Existing paths (`path_exists`)
+//! - URLs (`url`)
+//! - Human durations like `1h30m`, `90s`, `500ms` (`duration`)
+//! - Byte sizes like `512MiB`, `1.5GB`, `4k` (`byte_size`)
+//! - Numbers within bounds, such as port numbers (`in_range`)
+//!
+//! Passed to [`Arg::value_parser`](crate::command::Arg::value_parser), a parser
+//! rejects bad input while the command line is parsed and its result is read
+//! back with [`ArgMatches::get_one`](crate::command::ArgMatches::get_one).
+//! Each parser also has a newtype implementing [`FromStr`], so the value can be
+//! retrieved directly through [`ArgMatches::value_of_t`](crate::command::ArgMatches::value_of_t);
+//! use [`validate`] to check those values while parsing.
+//!
+//! ## Example
+//!
+//! ```
+//! use zfish::command::{App, Arg};
+//! use zfish::value_parser::{self, ByteSize, HumanDuration};
+//! use std::time::Duration;
+//!
+//! let matches = App::new("upload")
+//!     .arg(Arg::new("limit").long("limit").validator(value_parser::validate::<ByteSize>))
+//!     .arg(Arg::new("timeout").long("timeout").validator(value_parser::validate::<HumanDuration>))
+//!     .try_get_matches_from(vec!["upload", "--limit", "512MiB", "--timeout", "1h30m"])
+//!     .unwrap();
+//!
+//! let limit: ByteSize = matches.value_of_t("limit").unwrap();
+//! let timeout: HumanDuration = matches.value_of_t("timeout").unwrap();
+//! assert_eq!(limit.as_u64(), 512 * 1024 * 1024);
+//! assert_eq!(timeout.as_duration(), Duration::from_secs(5400));
+//!
+//! let matches = App::new("serve")
+//!     .arg(Arg::new("port").long("port").value_parser(value_parser::in_range(1..=65535u16)))
+//!     .try_get_matches_from(vec!["serve", "--port", "8080"])
+//!     .unwrap();
+//! assert_eq!(matches.get_one::<u16>("port"), Some(&8080));
+//! ```
+
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Adapts any [`FromStr`] type into a validator for [`Arg::validator`](crate::command::Arg::validator)
+///
+/// Example: `Arg::new("port").validator(value_parser::validate::<u16>)`
+pub fn validate<T>(value: &str) -> Result<(), String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Parses a path and checks that it exists on the file system
+pub fn path_exists(value: &str) -> Result<PathBuf, String> {
+    if value.is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let path = Path::new(value);
+    if path.exists() {
+        Ok(path.to_path_buf())
+    } else {
+        Err(format!("path '{}' does not exist", value))
+    }
+}
+
+/// Makes a parser for numbers (or any ordered [`FromStr`] type) within `range`
+///
+/// Example: `Arg::new("port").value_parser(value_parser::in_range(1..=65535u16))`
+pub fn in_range<T, R>(range: R) -> impl Fn(&str) -> Result<T, String> + Send + Sync + 'static
+where
+    T: FromStr + PartialOrd + fmt::Display,
+    T::Err: fmt::Display,
+    R: RangeBounds<T> + Send + Sync + 'static,
+{
+    move |value| {
+        let parsed: T = value
+            .parse()
+            .map_err(|e: T::Err| format!("invalid number '{}': {}", value, e))?;
+        if range.contains(&parsed) {
+            return Ok(parsed);
+        }
+        Err(format!("{} is not {}", value, describe_range(&range)))
+    }
+}
+
+/// Describes the values in `range`, such as `between 1 and 65535` or `at least 10`
+pub(crate) fn describe_range<T: fmt::Display>(range: &impl RangeBounds<T>) -> String {
+    if let (Bound::Included(low), Bound::Included(high)) = (range.start_bound(), range.end_bound())
+    {
+        return format!("between {} and {}", low, high);
+    }
+    let lower = match range.start_bound() {
+        Bound::Included(low) => Some(format!("at least {}", low)),
+        Bound::Excluded(low) => Some(format!("greater than {}", low)),
+        Bound::Unbounded => None,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(high) => Some(format!("at most {}", high)),
+        Bound::Excluded(high) => Some(format!("less than {}", high)),
+        Bound::Unbounded => None,
+    };
+    let parts: Vec<String> = lower.into_iter().chain(upper).collect();
+    parts.join(" and ")
+}
+
+/// Parses a URL of the form `scheme://[user@]host[:port][/path][?query][#fragment]`
+pub fn url(value: &str) -> Result<Url, String> {
+    Url::parse(value)
+}
+
+/// Parses a human duration such as `1h30m`, `90s`, `1.5h`, `250ms` or `2d 4h`
+///
+/// A bare number is interpreted as seconds. Supported units: `ns`, `us`/`µs`,
+/// `ms`, `s`, `m`, `h`, `d`, `w` (plus long forms like `sec`, `min`, `hours`).
+pub fn duration(value: &str) -> Result<Duration, String> {
+    let input = value.trim();
+    if input.is_empty() {
+        return Err("duration cannot be empty".to_string());
+    }
+
+    let too_large = || format!("invalid duration '{}'", value);
+    let mut total_nanos: u128 = 0;
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        rest = rest.trim_start();
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return Err(format!("invalid duration '{}': expected a number", value));
+        }
+        let number = &rest[..number_len];
+        rest = rest[number_len..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        // A bare number is the seconds component
+        let unit = match &rest[..unit_len] {
+            "" if number.len() == input.len() => "s",
+            unit => unit,
+        };
+        rest = &rest[unit_len..];
+
+        let nanos_per_unit = duration_unit_nanos(unit)
+            .ok_or_else(|| format!("invalid duration '{}': unknown unit '{}'", value, unit))?;
+
+        // The whole part is counted exactly; only a fraction like the `.5`
+        // of `1.5h` goes through `f64`
+        let bad_number = || format!("invalid duration '{}': bad number", value);
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if (whole.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+            return Err(bad_number());
+        }
+        let whole: u128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| too_large())?
+        };
+        let fraction: f64 = format!("0.{}", fraction)
+            .parse()
+            .map_err(|_| bad_number())?;
+        let fraction_nanos = (fraction * nanos_per_unit as f64).round() as u128;
+        let nanos = whole
+            .checked_mul(u128::from(nanos_per_unit))
+            .and_then(|nanos| nanos.checked_add(fraction_nanos))
+            .ok_or_else(too_large)?;
+        total_nanos = total_nanos.checked_add(nanos).ok_or_else(too_large)?;
+    }
+
+    let secs = u64::try_from(total_nanos / 1_000_000_000).map_err(|_| too_large())?;
+    Ok(Duration::new(secs, (total_nanos % 1_000_000_000) as u32))
+}
+
+/// Parses a byte size such as `512MiB`, `1.5GB`, `4k` or `1024`
+///
+/// Decimal units (`KB`, `MB`, `GB`, `TB`, `PB`) are powers of 1000; binary units
+/// (`KiB`, `MiB`, `GiB`, `TiB`, `PiB`) and the single-letter forms (`K`, `M`, `G`,
+/// `T`, `P`) are powers of 1024. Units are case-insensitive.
+pub fn byte_size(value: &str) -> Result<u64, String> {
+    let input = value.trim();
+    if input.is_empty() {
+        return Err("size cannot be empty".to_string());
+    }
+
+    let number_len = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    if number_len == 0 {
+        return Err(format!("invalid size '{}': expected a number", value));
+    }
+    let number = &input[..number_len];
+    let unit = input[number_len..].trim();
+
+    let multiplier = byte_unit_multiplier(unit)
+        .ok_or_else(|| format!("invalid size '{}': unknown unit '{}'", value, unit))?;
+    let too_large = || format!("invalid size '{}': value too large", value);
+
+    // Whole numbers are exact all the way up to `u64::MAX`
+    if !number.contains('.') {
+        let whole: u64 = number.parse().map_err(|_| too_large())?;
+        return whole.checked_mul(multiplier).ok_or_else(too_large);
+    }
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{}': bad number", value))?;
+    let bytes = (number * multiplier as f64).round();
+    // `u64::MAX as f64` rounds up to 2^64, which no `u64` can hold
+    if !bytes.is_finite() || bytes >= u64::MAX as f64 {
+        return Err(too_large());
+    }
+    Ok(bytes as u64)
+}
+
+/// Nanoseconds per duration unit
+fn duration_unit_nanos(unit: &str) -> Option<u64> {
+    const SEC: u64 = 1_000_000_000;
+    let nanos = match unit.to_ascii_lowercase().as_str() {
+        "ns" | "nsec" | "nanos" => 1,
+        "us" | "µs" | "usec" | "micros" => 1_000,
+        "ms" | "msec" | "millis" => 1_000_000,
+        "s" | "sec" | "secs" | "second" | "seconds" => SEC,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60 * SEC,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600 * SEC,
+        "d" | "day" | "days" => 86_400 * SEC,
+        "w" | "week" | "weeks" => 604_800 * SEC,
+        _ => return None,
+    };
+    Some(nanos)
+}
+
+/// Bytes per size unit
+fn byte_unit_multiplier(unit: &str) -> Option<u64> {
+    let multiplier = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "pb" => 1_000_000_000_000_000,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        "p" | "pib" => 1 << 50,
+        _ => return None,
+    };
+    Some(multiplier)
+}
+
+/// A path that was verified to exist when parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistingPath(PathBuf);
+
+impl ExistingPath {
+    /// Returns the path
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Consumes the wrapper and returns the inner `PathBuf`
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl FromStr for ExistingPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        path_exists(s).map(ExistingPath)
+    }
+}
+
+/// A duration parsed from human-friendly notation (`1h30m`, `90s`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HumanDuration(pub Duration);
+
+impl HumanDuration {
+    /// Returns the inner `Duration`
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        duration(s).map(HumanDuration)
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crate::humanize::format_duration(self.0))
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(value: HumanDuration) -> Self {
+        value.0
+    }
+}
+
+/// A byte count parsed from human-friendly notation (`512MiB`, `1.5GB`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// Returns the number of bytes
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crate::humanize::format_byte_size(self.0))
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        byte_size(s).map(ByteSize)
+    }
+}
+
+/// A parsed URL
+///
+/// This is a pragmatic parser for command-line input, not a full WHATWG URL
+/// implementation. The original string is preserved and returned by [`Url::as_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    raw: String,
+    scheme: String,
+    user: Option<String>,
+    host: String,
+    port: Option<u16>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Url {
+    /// Parses a URL string
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (scheme, rest) = value
+            .split_once("://")
+            .ok_or_else(|| format!("invalid URL '{}': missing scheme (e.g. https://)", value))?;
+
+        let valid_scheme = scheme
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if !valid_scheme {
+            return Err(format!("invalid URL '{}': bad scheme '{}'", value, scheme));
+        }
+
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((before, frag)) => (before, Some(frag.to_string())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((before, q)) => (before, Some(q.to_string())),
+            None => (rest, None),
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], rest[idx..].to_string()),
+            None => (rest, String::new()),
+        };
+        let (user, host_port) = match authority.rsplit_once('@') {
+            Some((user, host)) => (Some(user.to_string()), host),
+            None => (None, authority),
+        };
+        let (host, port) = Self::split_host_port(host_port, value)?;
+
+        if host.is_empty() && !scheme.eq_ignore_ascii_case("file") {
+            return Err(format!("invalid URL '{}': missing host", value));
+        }
+        if host.chars().any(|c| c.is_whitespace()) {
+            return Err(format!("invalid URL '{}': host contains whitespace", value));
+        }
+
+        Ok(Url {
+            raw: value.to_string(),
+            scheme: scheme.to_ascii_lowercase(),
+            user,
+            host: host.to_string(),
+            port,
+            path,
+            query,
+            fragment,
+        })
+    }
+
+    /// Split `host[:port]`, keeping bracketed IPv6 hosts intact
+    fn split_host_port<'a>(
+        host_port: &'a str,
+        value: &str,
+    ) -> Result<(&'a str, Option<u16>), String> {
+        let port_sep = if host_port.starts_with('[') {
+            host_port
+                .find(']')
+                .and_then(|end| host_port[end..].find(':').map(|i| end + i))
+        } else {
+            host_port.rfind(':')
+        };
+
+        match port_sep {
+            Some(idx) => {
+                let port = host_port[idx + 1..]
+                    .parse::<u16>()
+                    .map_err(|_| format!("invalid URL '{}': bad port", value))?;
+                Ok((&host_port[..idx], Some(port)))
+            }
+            None => Ok((host_port, None)),
+        }
+    }
+
+    /// Returns the original URL string
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the scheme in lowercase (e.g. `https`)
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Returns the user info before `@`, if any
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Returns the host (empty for `file:///` URLs)
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the explicit port, if any
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Returns the path (including the leading `/`, empty if absent)
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the query string without `?`, if any
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Returns the fragment without `#`, if any
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+}
+
+impl FromStr for Url {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Url::parse(s)
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
@@ -0,0 +1,65 @@
+//! # Shared Verbosity
+//!
+//! ```text
+//! ╔═══════════════════════════════════════════════════════════════╗
+//! ║  zfish — verbosity.rs                                          ║
+//! ║  One process-wide quiet/verbose setting for all output        ║
+//! ║  Copyright © 2025 Jeet Karena <karenajeet@proton.me>        ║
+//! ║  Licensed under MIT OR Apache-2.0                             ║
+//! ╚═══════════════════════════════════════════════════════════════╝
+//! ```
+//!
+//! [`App::verbosity_args`](crate::command::App::verbosity_args) stores the
+//! parsed `-q`/`-v` count here. Loggers without an explicit level and progress
+//! bars read it, so `-q` silences info logs and progress bars across the app
+//! without threading the value through every call site.
+//!
+//! The setting starts out unset, so nothing changes for apps that never opt in.
+//!
+//! ## Examples
+//!
+//! ```
+//! use zfish::verbosity;
+//!
+//! verbosity::set(-1);
+//! assert!(verbosity::is_quiet());
+//! verbosity::set(1);
+//! assert!(verbosity::is_verbose());
+//! verbosity::reset();
+//! assert_eq!(verbosity::get(), None);
+//! ```
+
+use std::sync::atomic::{AtomicI16, Ordering};
+
+/// Marker for "no verbosity has been set"
+const UNSET: i16 = i16::MIN;
+
+static VERBOSITY: AtomicI16 = AtomicI16::new(UNSET);
+
+/// Sets the process-wide verbosity (`-1` quiet, `0` normal, `1+` verbose)
+pub fn set(verbosity: i8) {
+    VERBOSITY.store(i16::from(verbosity), Ordering::Relaxed);
+}
+
+/// Gets the process-wide verbosity, if one has been set
+pub fn get() -> Option<i8> {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        UNSET => None,
+        value => i8::try_from(value).ok(),
+    }
+}
+
+/// Clears the process-wide verbosity back to unset
+pub fn reset() {
+    VERBOSITY.store(UNSET, Ordering::Relaxed);
+}
+
+/// Checks whether quiet mode (`-q`) is in effect
+pub fn is_quiet() -> bool {
+    get().is_some_and(|v| v < 0)
+}
+
+/// Checks whether verbose mode (`-v` or more) is in effect
+pub fn is_verbose() -> bool {
+    get().is_some_and(|v| v > 0)
+}
@@ -281,3 +281,22 @@ fn test_many_flags() {
     assert!(args.has_flag("flag99"));
     assert!(!args.has_flag("flag100"));
 }
+
+#[test]
+fn test_parse_from_iterator() {
+    let args = Args::parse_from(["prog", "input", "--file=a.txt", "-v"]);
+    assert_eq!(args.command, "prog");
+    assert_eq!(args.get_option("file").map(String::as_str), Some("a.txt"));
+    assert!(args.has_flag("v"));
+    assert_eq!(args.positional, ["input"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_parse_from_converts_invalid_utf8_lossily() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let name = std::ffi::OsString::from_vec(b"caf\xe9.txt".to_vec());
+    let args = Args::parse_from([std::ffi::OsString::from("prog"), name]);
+    assert_eq!(args.positional, ["caf\u{fffd}.txt"]);
+}
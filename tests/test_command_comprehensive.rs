@@ -8,7 +8,10 @@
 //! - Edge cases
 //! - Cross-platform compatibility
 
-use zfish::command::{App, Arg, ArgValue, Command, CommandError};
+use zfish::command::{
+    App, Arg, ArgErrors, ArgGroup, ArgMatches, ArgValue, Command, CommandError, CommandResult,
+    ErrorKind, FromArgMatches, HelpStyles,
+};
 
 // ============================================================================
 // Basic Argument Parsing Tests
@@ -207,6 +210,94 @@ fn test_custom_validator_invalid() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_value_range() {
+    let app = || {
+        App::new("serve")
+            .styles(HelpStyles::plain())
+            .arg(
+                Arg::new("port")
+                    .long("port")
+                    .about("Port to listen on")
+                    .value_range(1..=65535),
+            )
+            .arg(Arg::new("retries").long("retries").value_range(..10))
+    };
+
+    let matches = app()
+        .try_get_matches_from(vec!["serve", "--port", "8080", "--retries=9"])
+        .unwrap();
+    assert_eq!(matches.value_of("port"), Some("8080"));
+
+    let err = app()
+        .try_get_matches_from(vec!["serve", "--port", "70000"])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error: validation failed for 'port': 70000 must be between 1 and 65535"
+    );
+    let err = app()
+        .try_get_matches_from(vec!["serve", "--retries", "10"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::ValidationError("retries".to_string(), "10 must be less than 10".to_string())
+    );
+    let err = app()
+        .try_get_matches_from(vec!["serve", "--port", "http"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::ValidationError(
+            "port".to_string(),
+            "'http' is not a whole number".to_string()
+        )
+    );
+
+    let help = app().as_ref().generate_help();
+    assert!(help.contains("Port to listen on [between 1 and 65535]\n"));
+    assert!(help.contains(" [less than 10]\n"));
+}
+
+#[test]
+fn test_ignore_case_flags_and_values() {
+    let app = || {
+        App::new("test")
+            .arg(
+                Arg::new("level")
+                    .long("level")
+                    .possible_values(&["debug", "info", "warn"])
+                    .ignore_case(true),
+            )
+            .arg(
+                Arg::new("tags")
+                    .long("tags")
+                    .possible_values(&["Fast", "Slow"])
+                    .value_delimiter(',')
+                    .ignore_case(true),
+            )
+    };
+
+    let matches = app()
+        .try_get_matches_from(["test", "--LEVEL", "Debug", "--tags=fast,SLOW"])
+        .unwrap();
+    assert_eq!(matches.value_of("level"), Some("debug"));
+    assert_eq!(matches.values_of("tags").unwrap(), ["Fast", "Slow"]);
+
+    let err = app()
+        .try_get_matches_from(["test", "--level", "Trace"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValidationError);
+
+    // Without ignore_case, case still matters
+    let strict = App::new("test").arg(Arg::new("level").long("level").possible_values(&["debug"]));
+    assert!(
+        strict
+            .try_get_matches_from(["test", "--level", "DEBUG"])
+            .is_err()
+    );
+}
+
 // ============================================================================
 // Subcommand Tests
 // ============================================================================
@@ -289,6 +380,55 @@ fn test_unknown_subcommand() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_strict_positionals_reject_leftover_words() {
+    let app = || {
+        App::new("git")
+            .strict_positionals(true)
+            .subcommand(Command::new("status"))
+            .subcommand(
+                Command::new("add")
+                    .arg(Arg::new("path").index(0))
+                    .subcommand(Command::new("interactive")),
+            )
+            .subcommand(Command::new("commit").arg(Arg::new("paths").index(0).last(true)))
+    };
+
+    // Far from any subcommand, so not reported as a typo
+    let err = app().try_get_matches_from(["git", "xyzzy"]).unwrap_err();
+    assert_eq!(err, CommandError::UnknownSubcommand("xyzzy".to_string()));
+    assert!(matches!(
+        app().try_get_matches_from(["git", "stauts"]),
+        Err(CommandError::MisspelledSubcommand(_, _))
+    ));
+
+    // Subcommands inherit the setting; declared positionals still fill first
+    let err = app()
+        .try_get_matches_from(["git", "add", "a.txt", "b.txt"])
+        .unwrap_err();
+    assert_eq!(
+        err.inner(),
+        &CommandError::UnexpectedArgument("b.txt".to_string())
+    );
+    assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+    assert_eq!(err.message(), "unexpected argument 'b.txt'");
+    let err = app()
+        .try_get_matches_from(["git", "status", "now"])
+        .unwrap_err();
+    assert_eq!(
+        err.inner(),
+        &CommandError::UnexpectedArgument("now".to_string())
+    );
+
+    assert!(app().try_get_matches_from(["git", "add", "a.txt"]).is_ok());
+    // A variadic positional takes every extra word
+    assert!(
+        app()
+            .try_get_matches_from(["git", "commit", "a", "b", "c"])
+            .is_ok()
+    );
+}
+
 // ============================================================================
 // Help Generation Tests
 // ============================================================================
@@ -355,10 +495,230 @@ fn test_help_with_subcommands() {
     assert!(help.contains("Push to remote"));
 }
 
+#[test]
+fn test_help_bytes_matches_generate_help() {
+    let cmd = Command::new("git")
+        .about("Git version control")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .takes_value(false),
+        )
+        .arg(Arg::new("path").index(0).required(true))
+        .subcommand(Command::new("commit").alias("ci").about("Commit changes"));
+
+    let mut buf = b"prefix\n".to_vec();
+    cmd.help_bytes(&mut buf);
+    let expected = format!("prefix\n{}", cmd.generate_help());
+    assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    assert!(expected.contains("    commit (ci)"));
+}
+
+#[test]
+fn test_override_usage() {
+    let cmd = Command::new("cp")
+        .override_usage("cp [OPTIONS] <SRC>... <DEST>")
+        .styles(HelpStyles::plain())
+        .arg(Arg::new("paths").index(0).last(true));
+
+    assert!(
+        cmd.generate_help()
+            .contains("\nUSAGE:\n    cp [OPTIONS] <SRC>... <DEST>\n")
+    );
+
+    let err = cmd.try_parse_from(["cp", "--force"]).unwrap_err();
+    let rendered = cmd.format_error(err).to_string();
+    assert!(rendered.contains("\nUSAGE:\n    cp [OPTIONS] <SRC>... <DEST>\n"));
+}
+
+#[test]
+fn test_to_markdown() {
+    let app = App::new("myapp")
+        .about("Does things")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .takes_value(false)
+                .about("More output"),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .possible_values(&["auto", "never"])
+                .default_value("auto"),
+        )
+        .subcommand(
+            Command::new("build")
+                .about("Compile the project")
+                .arg(Arg::new("profile").index(0).required(true))
+                .example("myapp build dev"),
+        )
+        .subcommand(Command::new("remote").subcommand(Command::new("add")));
+
+    let page = app.to_markdown();
+    assert!(
+        page.starts_with("# myapp\n\nDoes things\n\n**Usage:** `myapp [OPTIONS] <COMMAND>`\n\n")
+    );
+    assert!(page.contains("| `-v`, `--verbose` | More output "));
+    assert!(page.contains(
+        "| `--color <COLOR>` | [default: `auto`] [possible values: `auto`, `never`] |\n"
+    ));
+    assert!(
+        page.contains(
+            "- [`build`](#myapp-build): Compile the project\n- [`remote`](#myapp-remote)\n"
+        )
+    );
+    assert!(page.contains(
+        "\n## myapp build\n\nCompile the project\n\n**Usage:** `myapp build <PROFILE>`\n"
+    ));
+    assert!(page.contains("| `<PROFILE>` | [required]  |\n"));
+    assert!(page.contains("**Examples:**\n\n```sh\nmyapp build dev\n```\n"));
+    assert!(page.contains("\n### myapp remote add\n\n**Usage:** `myapp remote add`\n"));
+    assert!(page.ends_with("`myapp remote add`\n"));
+}
+
+#[test]
+fn test_help_headings() {
+    let cmd = Command::new("fetch")
+        .styles(HelpStyles::plain())
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .help_heading("NETWORK OPTIONS"),
+        )
+        .arg(Arg::new("verbose").short('v').takes_value(false))
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .help_heading("NETWORK OPTIONS"),
+        )
+        .arg(Arg::new("output").long("output").help_heading("OUTPUT"));
+
+    let help = cmd.generate_help();
+    let section = |title: &str| help.find(&format!("\n{}\n", title)).unwrap();
+    let (plain, network, output) = (
+        section("OPTIONS:"),
+        section("NETWORK OPTIONS:"),
+        section("OUTPUT:"),
+    );
+    // Ungrouped options come first, then headings in order of first use
+    assert!(plain < network && network < output);
+    assert!(help[plain..network].contains("-v"));
+    let network_section = &help[network..output];
+    assert!(network_section.contains("--proxy") && network_section.contains("--timeout"));
+    assert!(help[output..].contains("--output"));
+
+    let page = cmd.to_markdown();
+    assert!(page.find("**Options:**").unwrap() < page.find("**NETWORK OPTIONS:**").unwrap());
+
+    // Without ungrouped options there is no OPTIONS section
+    let grouped = Command::new("fetch")
+        .arg(Arg::new("proxy").long("proxy").help_heading("NETWORK"))
+        .generate_help();
+    assert!(!grouped.contains("OPTIONS:"));
+    assert!(grouped.contains("NETWORK:"));
+}
+
+#[test]
+fn test_before_and_after_help() {
+    let cmd = Command::new("tar")
+        .about("Archive files")
+        .before_help("tar 1.35 - GNU archiver")
+        .after_help("EXAMPLES:\n    tar -cf archive.tar foo bar\n\nCopyright (C) 2023")
+        .styles(HelpStyles::plain())
+        .arg(Arg::new("file").short('f'));
+
+    let help = cmd.generate_help();
+    assert!(help.starts_with("tar 1.35 - GNU archiver\n\nArchive files\n\nUSAGE:\n"));
+    assert!(
+        help.ends_with(" \n\nEXAMPLES:\n    tar -cf archive.tar foo bar\n\nCopyright (C) 2023\n")
+    );
+
+    let bare = Command::new("tar").before_help("banner").generate_help();
+    assert!(bare.starts_with("banner\n\nUSAGE:\n"));
+}
+
 // ============================================================================
 // Version Tests
 // ============================================================================
 
+#[test]
+fn test_try_parse_from_is_side_effect_free() {
+    let cmd = Command::new("serve")
+        .version("2.1.0")
+        .arg(Arg::new("port").long("port").required(true));
+
+    let matches = cmd.try_parse_from(["serve", "--port", "80"]).unwrap();
+    assert_eq!(matches.value_of("port"), Some("80"));
+    assert_eq!(matches.indices_of("port"), Some(&[2][..]));
+
+    let help = cmd.format_error(cmd.try_parse_from(["serve", "-h"]).unwrap_err());
+    assert_eq!(help.exit_code(), 0);
+    assert_eq!(help.rendered(), format!("{}\n", cmd.generate_help()));
+
+    let version = cmd.format_error(cmd.try_parse_from(["serve", "-V"]).unwrap_err());
+    assert_eq!(version.exit_code(), 0);
+    assert_eq!(version.rendered(), "serve 2.1.0\n");
+
+    let missing = cmd.format_error(cmd.try_parse_from(["serve"]).unwrap_err());
+    assert_eq!(missing.exit_code(), 1);
+    assert!(missing.rendered().contains("USAGE:"));
+}
+
+#[test]
+fn test_error_exit_code_and_write_help() {
+    let app = App::new("serve")
+        .error_exit_code(2)
+        .arg(Arg::new("port").long("port").required(true));
+
+    let missing = app.error(ErrorKind::MissingArgument, "--port is required");
+    assert_eq!(missing.exit_code(), 2);
+    let help = app.as_ref().format_error(CommandError::HelpRequested);
+    assert_eq!(help.exit_code(), 0);
+
+    let mut out = Vec::new();
+    app.write_help(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        app.as_ref().generate_help()
+    );
+}
+
+#[test]
+fn test_error_formatter() {
+    fn compact(cmd: &Command, err: &CommandError) -> String {
+        format!(
+            "{}: {}\nusage: {}\n",
+            cmd.name(),
+            err.message(),
+            cmd.render_usage()
+        )
+    }
+
+    let app = App::new("serve")
+        .error_exit_code(2)
+        .error_formatter(compact)
+        .arg(Arg::new("port").long("port").required(true));
+
+    let missing = app
+        .as_ref()
+        .format_error(CommandError::MissingArgument("port".to_string()));
+    assert_eq!(
+        missing.rendered(),
+        "serve: the argument 'port' is required\nusage: serve [OPTIONS]\n"
+    );
+    assert_eq!(missing.exit_code(), 2);
+
+    // Help is still the generated help
+    let help = app.as_ref().format_error(CommandError::HelpRequested);
+    assert_eq!(
+        help.rendered(),
+        format!("{}\n", app.as_ref().generate_help())
+    );
+}
+
 #[test]
 fn test_version_flag() {
     let app = App::new("test").version("1.0.0");
@@ -403,6 +763,256 @@ fn test_unknown_short_flag() {
     }
 }
 
+#[test]
+fn test_misspelled_long_flag_suggests_the_closest() {
+    let app = || {
+        App::new("test")
+            .version("1.0.0")
+            .arg(Arg::new("verbose").long("verbose").takes_value(false))
+            .arg(Arg::new("output").long("output"))
+    };
+
+    let err = app()
+        .try_get_matches_from(vec!["test", "--verbos"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::MisspelledArgument("verbos".to_string(), "--verbose".to_string())
+    );
+    assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+    assert_eq!(err.suggestion(), Some("--verbose"));
+    assert_eq!(
+        err.to_string(),
+        "error: unknown argument 'verbos'; did you mean '--verbose'?"
+    );
+
+    let err = app()
+        .try_get_matches_from(vec!["test", "--outptu=a"])
+        .unwrap_err();
+    assert_eq!(err.suggestion(), Some("--output"));
+
+    // The builtin flags are candidates too
+    let err = app()
+        .try_get_matches_from(vec!["test", "--hlep"])
+        .unwrap_err();
+    assert_eq!(err.suggestion(), Some("--help"));
+    let err = app()
+        .try_get_matches_from(vec!["test", "--versoin"])
+        .unwrap_err();
+    assert_eq!(err.suggestion(), Some("--version"));
+}
+
+#[test]
+fn test_misspelled_subcommand_suggests_name_or_alias() {
+    let app = || {
+        App::new("test")
+            .subcommand(Command::new("build"))
+            .subcommand(Command::new("install").alias("add"))
+    };
+
+    let err = app()
+        .try_get_matches_from(vec!["test", "biuld"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::MisspelledSubcommand("biuld".to_string(), "build".to_string())
+    );
+    assert_eq!(err.kind(), ErrorKind::UnknownSubcommand);
+    assert_eq!(err.context(), ["biuld", "build"]);
+    assert_eq!(
+        err.to_string(),
+        "error: unknown subcommand 'biuld'; did you mean 'build'?"
+    );
+
+    let err = app().try_get_matches_from(vec!["test", "ad"]).unwrap_err();
+    assert_eq!(err.suggestion(), Some("add"));
+}
+
+#[test]
+fn test_misspelled_subcommand_is_a_value_when_positionals_exist() {
+    let app = App::new("test")
+        .arg(Arg::new("file").index(0))
+        .subcommand(Command::new("build"));
+
+    let matches = app.try_get_matches_from(vec!["test", "biuld"]).unwrap();
+    assert_eq!(matches.value_of("file"), Some("biuld"));
+}
+
+#[test]
+fn test_suggestion_is_rendered_with_usage() {
+    let app = App::new("test").arg(Arg::new("verbose").long("verbose").takes_value(false));
+    let err = app
+        .try_get_matches_from(vec!["test", "--verbos"])
+        .unwrap_err();
+    let rendered = Command::new("test")
+        .styles(HelpStyles::plain())
+        .format_error(err)
+        .to_string();
+    assert!(rendered.starts_with("error: unknown argument 'verbos'; did you mean '--verbose'?\n"));
+}
+
+#[test]
+fn test_error_kind_and_context() {
+    let app = App::new("test")
+        .arg(Arg::new("quiet").long("quiet").takes_value(false))
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .takes_value(false)
+                .conflicts_with("quiet"),
+        );
+
+    let err = app
+        .try_get_matches_from(vec!["test", "--verbose", "--quiet"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    assert_eq!(err.context(), vec!["verbose", "quiet"]);
+
+    let err = CommandError::InvalidValue("port".to_string(), "http".to_string());
+    assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    assert_eq!(err.context(), vec!["port", "http"]);
+
+    assert_eq!(CommandError::HelpRequested.kind(), ErrorKind::HelpRequested);
+    assert!(CommandError::HelpRequested.context().is_empty());
+}
+
+#[test]
+fn test_command_error_for_handlers() {
+    let cmd = Command::new("report").arg(Arg::new("start").long("start"));
+    let err = cmd.error(ErrorKind::InvalidValue, "--start must be before --end");
+
+    assert_eq!(
+        err.error(),
+        &CommandError::Custom(
+            ErrorKind::InvalidValue,
+            "--start must be before --end".to_string()
+        )
+    );
+    assert!(err.rendered().contains("error:"));
+    assert!(err.rendered().contains("--start must be before --end"));
+    assert!(err.rendered().contains("USAGE:\n    report [OPTIONS]"));
+    assert!(err.rendered().contains("For more information try --help"));
+}
+
+#[test]
+fn test_format_error_matches_parser_presentation() {
+    let app = App::new("test").arg(Arg::new("name").long("name").required(true));
+    let parse_err = app.clone().try_get_matches_from(vec!["test"]).unwrap_err();
+
+    let rendered = app.error(
+        ErrorKind::MissingArgument,
+        "the argument 'name' is required",
+    );
+    let parser_rendered = Command::new("test")
+        .arg(Arg::new("name").long("name").required(true))
+        .format_error(parse_err);
+    assert_eq!(rendered.rendered(), parser_rendered.rendered());
+}
+
+#[test]
+fn test_subcommand_errors_render_the_subcommand() {
+    let app = App::new("myapp")
+        .styles(HelpStyles::plain())
+        .arg(Arg::new("verbose").long("verbose").takes_value(false))
+        .subcommand(
+            Command::new("build")
+                .about("Build the project")
+                .arg(Arg::new("jobs").long("jobs").value_range(1..=8)),
+        );
+
+    let err = app
+        .clone()
+        .try_get_matches_from(["myapp", "build", "--jobs", "9"])
+        .unwrap_err();
+    assert_eq!(err.command_path(), ["myapp", "build"]);
+    let rendered = app.as_ref().format_error(err);
+    assert_eq!(rendered.exit_code(), 1);
+    assert!(
+        rendered
+            .rendered()
+            .contains("USAGE:\n    myapp build [OPTIONS]\n")
+    );
+    assert!(!rendered.rendered().contains("--verbose"));
+
+    // Help for a subcommand is that subcommand's help
+    let err = app
+        .clone()
+        .try_get_matches_from(["myapp", "build", "--help"])
+        .unwrap_err();
+    let rendered = app.as_ref().format_error(err);
+    assert_eq!(rendered.exit_code(), 0);
+    assert!(rendered.rendered().contains("Build the project"));
+    assert!(rendered.rendered().contains("--jobs"));
+    assert!(!rendered.rendered().contains("--verbose"));
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn styled_command() -> Command {
+    Command::new("test")
+        .arg(
+            Arg::new("input")
+                .index(0)
+                .required(true)
+                .about("Input file"),
+        )
+        .arg(
+            Arg::new("level")
+                .short('l')
+                .long("level")
+                .takes_value(true)
+                .default_value("3")
+                .about("Compression level"),
+        )
+        .subcommand(Command::new("init").about("Initialize"))
+}
+
+#[test]
+fn test_help_styles_plain_has_no_escapes() {
+    let cmd = styled_command().styles(HelpStyles::plain());
+    assert!(!cmd.generate_help().contains('\x1b'));
+
+    let err = cmd.error(ErrorKind::InvalidValue, "bad level");
+    assert!(!err.rendered().contains('\x1b'));
+    assert!(err.rendered().starts_with("error: bad level"));
+}
+
+#[test]
+fn test_help_styles_keep_column_alignment() {
+    let plain = styled_command().styles(HelpStyles::plain()).generate_help();
+    let colored = styled_command()
+        .styles(HelpStyles::colored())
+        .generate_help();
+    assert_eq!(strip_ansi(&colored), plain);
+    assert!(plain.contains("    -l, --level <LEVEL>       Compression level [default: 3]"));
+}
+
+#[test]
+fn test_help_styles_custom_palette() {
+    let styles = HelpStyles {
+        flag: Some(zfish::Color::Magenta),
+        ..HelpStyles::plain()
+    };
+    let app = App::new("test").styles(styles);
+    let err = app.error(ErrorKind::InvalidValue, "oops");
+    assert!(!err.rendered().contains('\x1b'));
+}
+
 // ============================================================================
 // Edge Cases Tests
 // ============================================================================
@@ -653,3 +1263,539 @@ fn test_special_characters_in_values() {
 
     assert_eq!(matches.value_of("pattern"), Some("*.rs"));
 }
+
+// ============================================================================
+// EXAMPLE TESTS
+// ============================================================================
+
+fn example_app() -> App {
+    App::new("myapp")
+        .example_checked("myapp --help")
+        .subcommand(
+            Command::new("build")
+                .arg(Arg::new("release").long("release"))
+                .arg(Arg::new("out").short('o').long("out").takes_value(true))
+                .example("myapp build --whatever")
+                .example_checked("myapp build --release -o 'dist dir'"),
+        )
+}
+
+#[test]
+fn test_examples_shown_in_help() {
+    let help = Command::new("build")
+        .example("myapp build --whatever")
+        .example_checked("myapp build --release")
+        .generate_help();
+    assert!(help.contains("EXAMPLES:\n    myapp build --whatever\n    myapp build --release"));
+}
+
+#[test]
+fn test_verify_examples_passes() {
+    assert!(zfish::testing::verify_examples(&example_app()).is_ok());
+    zfish::testing::assert_examples(&example_app());
+}
+
+#[test]
+fn test_verify_examples_reports_unknown_flags() {
+    let app = example_app().subcommand(
+        Command::new("deploy")
+            .example_checked("myapp deploy --dry-run")
+            .example_checked("myapp deploy 'oops"),
+    );
+
+    let failures = zfish::testing::verify_examples(&app).unwrap_err();
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].command, "myapp deploy");
+    assert_eq!(failures[0].example, "myapp deploy --dry-run");
+    assert!(matches!(
+        failures[0].error.inner(),
+        CommandError::UnknownArgument(_)
+    ));
+    assert_eq!(failures[1].error.kind(), ErrorKind::InvalidValue);
+}
+
+// ============================================================================
+// CLI MACRO TESTS
+// ============================================================================
+
+#[test]
+fn test_cli_macro_matches_builder() {
+    let from_macro = zfish::cli! {
+        "tool" {
+            version: "2.1.0",
+            about: "Does things",
+            args {
+                verbose { short: 'v', long: "verbose", flag, help: "More output" }
+                input { index: 0, required, help: "Input file" }
+            }
+        }
+    };
+    let by_hand = App::new("tool")
+        .version("2.1.0")
+        .about("Does things")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .takes_value(false)
+                .about("More output"),
+        )
+        .arg(
+            Arg::new("input")
+                .index(0)
+                .required(true)
+                .about("Input file"),
+        );
+
+    assert_eq!(
+        from_macro.as_ref().generate_help(),
+        by_hand.as_ref().generate_help()
+    );
+}
+
+#[test]
+fn test_cli_macro_subcommands() {
+    let app = zfish::cli! {
+        "git" {
+            subcommands {
+                remote {
+                    about: "Manage remotes",
+                    subcommands {
+                        add {
+                            args {
+                                name { index: 0, required }
+                                "fetch-all" { short: 'f', long: "fetch-all", flag, }
+                            }
+                        }
+                    },
+                    alias: "rem",
+                }
+                status {}
+            }
+        }
+    };
+
+    let matches = app
+        .clone()
+        .try_get_matches_from(["git", "rem", "add", "origin", "-f"])
+        .unwrap();
+    let (_, remote) = matches.subcommand().unwrap();
+    let add = remote.subcommand_matches("add").unwrap();
+    assert_eq!(add.value_of("name"), Some("origin"));
+    assert!(add.is_present("fetch-all"));
+
+    let err = app
+        .try_get_matches_from(["git", "remote", "add"])
+        .unwrap_err();
+    assert!(matches!(err.inner(), CommandError::MissingArgument(_)));
+    assert_eq!(err.command_path(), ["git", "remote", "add"]);
+}
+
+// ============================================================================
+// FROM ARG MATCHES TESTS
+// ============================================================================
+
+#[derive(Debug)]
+struct Limits {
+    jobs: u8,
+    timeout: Option<u32>,
+    ports: Vec<u16>,
+}
+
+impl FromArgMatches for Limits {
+    fn from_arg_matches(matches: &ArgMatches) -> CommandResult<Self> {
+        Ok(Limits {
+            jobs: matches.value_of_t("jobs")?,
+            timeout: matches.value_of_t_opt("timeout")?,
+            ports: matches.values_of_t("port")?,
+        })
+    }
+}
+
+fn limits_app() -> App {
+    App::new("run")
+        .arg(Arg::new("jobs").short('j'))
+        .arg(Arg::new("timeout").long("timeout"))
+        .arg(Arg::new("port").long("port").value_delimiter(','))
+}
+
+#[test]
+fn test_populate_settings_struct() {
+    let matches = limits_app()
+        .try_get_matches_from(["run", "-j", "4", "--timeout", "30", "--port", "80,443"])
+        .unwrap();
+    let limits: Limits = matches.populate().unwrap();
+    assert_eq!(limits.jobs, 4);
+    assert_eq!(limits.timeout, Some(30));
+    assert_eq!(limits.ports, [80, 443]);
+}
+
+#[test]
+fn test_populate_reports_first_bad_argument() {
+    let missing = limits_app().try_get_matches_from(["run"]).unwrap();
+    assert_eq!(
+        missing.populate::<Limits>().unwrap_err(),
+        CommandError::MissingArgument("jobs".to_string())
+    );
+
+    let bad = limits_app()
+        .try_get_matches_from(["run", "-j", "2", "--port", "80,http"])
+        .unwrap();
+    assert_eq!(
+        bad.populate::<Limits>().unwrap_err(),
+        CommandError::InvalidValue("port".to_string(), "http".to_string())
+    );
+}
+
+/// Like `Limits`, but reports every bad argument
+#[derive(Debug)]
+struct AllLimits(Limits);
+
+impl FromArgMatches for AllLimits {
+    fn from_arg_matches(matches: &ArgMatches) -> CommandResult<Self> {
+        let mut errors = ArgErrors::new();
+        let limits = Limits {
+            jobs: errors.check(matches.value_of_t("jobs")),
+            timeout: errors.check(matches.value_of_t_opt("timeout")),
+            ports: errors.check(matches.values_of_t("port")),
+        };
+        errors.finish(AllLimits(limits))
+    }
+}
+
+#[test]
+fn test_parse_into_settings_struct() {
+    let limits: AllLimits = limits_app()
+        .try_parse_into_from(["run", "-j", "4", "--port", "80"])
+        .unwrap();
+    assert_eq!(limits.0.jobs, 4);
+    assert_eq!(limits.0.timeout, None);
+    assert_eq!(limits.0.ports, [80]);
+
+    // Parse errors come back before any conversion
+    let err = limits_app()
+        .try_parse_into_from::<AllLimits, _, _>(["run", "--nope"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn test_arg_errors_reports_every_bad_argument() {
+    let err = limits_app()
+        .try_parse_into_from::<AllLimits, _, _>(["run", "--timeout", "soon", "--port", "80,http"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::Multiple(vec![
+            CommandError::MissingArgument("jobs".to_string()),
+            CommandError::InvalidValue("timeout".to_string(), "soon".to_string()),
+            CommandError::InvalidValue("port".to_string(), "http".to_string()),
+        ])
+    );
+    assert_eq!(err.kind(), ErrorKind::MissingArgument);
+    assert_eq!(err.context(), ["jobs", "timeout", "soon", "port", "http"]);
+
+    let rendered = limits_app()
+        .styles(HelpStyles::plain())
+        .as_ref()
+        .format_error(err)
+        .to_string();
+    assert!(rendered.starts_with(
+        "error: the argument 'jobs' is required\n\
+         error: invalid value 'soon' for 'timeout'\n\
+         error: invalid value 'http' for 'port'\n\nUSAGE:"
+    ));
+
+    // A single failure is returned as it is
+    let err = limits_app()
+        .try_parse_into_from::<AllLimits, _, _>(["run", "-j", "x"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::InvalidValue("jobs".to_string(), "x".to_string())
+    );
+}
+
+// ============================================================================
+// NON-UTF-8 ARGUMENT TESTS
+// ============================================================================
+
+#[cfg(unix)]
+fn not_utf8(bytes: &[u8]) -> std::ffi::OsString {
+    use std::os::unix::ffi::OsStringExt;
+    std::ffi::OsString::from_vec(bytes.to_vec())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_invalid_utf8_values_are_kept_exactly() {
+    let app = || {
+        App::new("cp")
+            .arg(Arg::new("src").index(0).allow_invalid_utf8(true))
+            .arg(Arg::new("dest").long("dest").allow_invalid_utf8(true))
+            .arg(
+                Arg::new("skip")
+                    .long("skip")
+                    .value_delimiter(',')
+                    .allow_invalid_utf8(true),
+            )
+    };
+
+    let matches = app()
+        .try_get_matches_from([
+            not_utf8(b"cp"),
+            not_utf8(b"caf\xe9.txt"),
+            not_utf8(b"--dest=out\xff"),
+            "--skip".into(),
+            not_utf8(b"a\x80,b"),
+        ])
+        .unwrap();
+    assert_eq!(matches.value_of_os("src"), Some(&*not_utf8(b"caf\xe9.txt")));
+    assert_eq!(matches.value_of("src"), None);
+    assert_eq!(matches.value_of_os("dest"), Some(&*not_utf8(b"out\xff")));
+    assert_eq!(
+        matches.values_of_os("skip").unwrap(),
+        [&*not_utf8(b"a\x80"), std::ffi::OsStr::new("b")]
+    );
+
+    // Valid values stay strings, and value_of_os reads them too
+    let matches = app()
+        .try_get_matches_from([not_utf8(b"cp"), "caf\u{e9}".into(), "--dest=out".into()])
+        .unwrap();
+    assert_eq!(matches.value_of("src"), Some("caf\u{e9}"));
+    assert_eq!(matches.value_of_os("dest"), Some("out".as_ref()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_invalid_utf8_is_an_error_unless_allowed() {
+    let err = Command::new("cat")
+        .arg(Arg::new("file").index(0))
+        .try_parse_from([not_utf8(b"cat"), not_utf8(b"\xfe")])
+        .unwrap_err();
+    assert_eq!(err, CommandError::InvalidUtf8("file".to_string()));
+    assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    assert_eq!(err.message(), "the value of 'file' is not valid UTF-8");
+}
+
+#[test]
+fn test_escape_characters_in_valid_arguments_are_left_alone() {
+    // The private use characters that stand for invalid bytes are ordinary
+    // text when every argument is valid UTF-8
+    let matches = Command::new("echo")
+        .arg(Arg::new("text").index(0))
+        .try_parse_from(["echo", "\u{10ff80}\u{10ffff}"])
+        .unwrap();
+    assert_eq!(matches.value_of("text"), Some("\u{10ff80}\u{10ffff}"));
+}
+
+// ============================================================================
+// UNICODE NORMALIZATION TESTS
+// ============================================================================
+
+#[test]
+fn test_normalize_unicode_values() {
+    let app = || {
+        App::new("tag")
+            .arg(
+                Arg::new("lang")
+                    .long("lang")
+                    .possible_values(&["français", "español"])
+                    .normalize_unicode(true),
+            )
+            .arg(Arg::new("name").index(0).normalize_unicode(true))
+            .arg(Arg::new("raw").long("raw"))
+    };
+
+    // "c" + U+0327 COMBINING CEDILLA and "n" + U+0303 COMBINING TILDE
+    let matches = app()
+        .try_get_matches_from([
+            "tag",
+            "--lang",
+            "franc\u{327}ais",
+            "Ren\u{303}e",
+            "--raw",
+            "e\u{301}",
+        ])
+        .unwrap();
+    assert_eq!(matches.value_of("lang"), Some("français"));
+    assert_eq!(matches.value_of("name"), Some("Reñe"));
+    assert_eq!(matches.value_of("raw"), Some("e\u{301}"));
+
+    let err = App::new("tag")
+        .arg(Arg::new("lang").long("lang").possible_values(&["español"]))
+        .try_get_matches_from(["tag", "--lang", "espan\u{303}ol"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValidationError);
+}
+
+// ============================================================================
+// DEFINITION CHECK TESTS
+// ============================================================================
+
+#[test]
+fn test_debug_assert_accepts_valid_definition() {
+    App::new("myapp")
+        .version("1.0")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .global(true)
+                .takes_value(false),
+        )
+        .subcommand(
+            Command::new("build")
+                .arg(Arg::new("json").long("json").conflicts_with("yaml"))
+                .arg(Arg::new("yaml").long("yaml"))
+                .group(ArgGroup::new("format").args(&["json", "yaml"]))
+                .arg(Arg::new("verbose").short('v').takes_value(false))
+                .arg(Arg::new("out").short('o').requires("verbose"))
+                .arg(Arg::new("input").index(0)),
+        )
+        .debug_assert();
+}
+
+#[test]
+fn test_debug_assert_reports_every_problem() {
+    let cmd = Command::new("myapp")
+        .version("1.0")
+        .arg(Arg::new("verbose").short('v').global(true))
+        .arg(Arg::new("version-info").short('V'))
+        .arg(Arg::new("out").short('o').requires("format"))
+        .group(
+            ArgGroup::new("mode")
+                .args(&["fast", "out"])
+                .conflicts_with("quiet"),
+        )
+        .subcommand(
+            Command::new("build")
+                .arg(Arg::new("value").short('v'))
+                .arg(Arg::new("src").index(0))
+                .arg(Arg::new("dest").index(0).conflicts_with("missing")),
+        );
+
+    let message = *std::panic::catch_unwind(|| cmd.debug_assert())
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    for problem in [
+        "7 problem(s)",
+        "myapp: 'version-info' uses -V, which is taken by the built-in flag",
+        "myapp: 'out' requires unknown argument 'format'",
+        "myapp: group 'mode' has unknown member 'fast'",
+        "myapp: group 'mode' conflicts_with unknown argument or group 'quiet'",
+        "myapp build: 'value' and 'verbose' both use -v",
+        "myapp build: 'src' and 'dest' both use position 0",
+        "myapp build: 'dest' conflicts_with unknown argument 'missing'",
+    ] {
+        assert!(
+            message.contains(problem),
+            "{:?} not in {}",
+            problem,
+            message
+        );
+    }
+}
+
+#[test]
+fn test_debug_assert_checks_conditional_requirements() {
+    let cmd = Command::new("fetch")
+        .arg(Arg::new("token").required_unless_present("anonymous"))
+        .arg(Arg::new("host").required_if_eq("mode", "remote"));
+
+    let message = *std::panic::catch_unwind(|| cmd.debug_assert())
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(
+        message.contains("fetch: 'token' required_unless_present unknown argument 'anonymous'")
+    );
+    assert!(message.contains("fetch: 'host' required_if_eq unknown argument 'mode'"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_debug_builds_check_the_definition_when_parsing() {
+    let app = App::new("tool")
+        .arg(Arg::new("verbose").short('v'))
+        .arg(Arg::new("version-info").short('v'));
+    let cmd = app.as_ref().clone();
+
+    for parse in [
+        Box::new(move || drop(app.try_get_matches_from(["tool"]))) as Box<dyn FnOnce()>,
+        Box::new(move || drop(cmd.try_parse_from(["tool"]))),
+    ] {
+        let message = *std::panic::catch_unwind(std::panic::AssertUnwindSafe(parse))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert!(message.contains("tool: 'verbose' and 'version-info' both use -v"));
+    }
+}
+
+// ============================================================================
+// DUPLICATE OCCURRENCE TESTS
+// ============================================================================
+
+#[test]
+fn test_repeated_single_value_option_is_an_error() {
+    let app = || App::new("tool").arg(Arg::new("output").short('o').long("output"));
+
+    let err = app()
+        .try_get_matches_from(["tool", "--output", "a", "-o", "b"])
+        .unwrap_err();
+    assert_eq!(err, CommandError::DuplicateArgument("output".to_string()));
+    assert_eq!(err.kind(), ErrorKind::DuplicateArgument);
+    assert_eq!(
+        err.to_string(),
+        "error: the argument 'output' was given more than once"
+    );
+
+    let err = app()
+        .try_get_matches_from(["tool", "--output=a", "--output=b"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateArgument);
+}
+
+#[test]
+fn test_overrides_with_self_keeps_last_value() {
+    let matches = App::new("tool")
+        .arg(Arg::new("color").long("color").overrides_with_self(true))
+        .arg(Arg::new("tag").long("tag").multiple(true))
+        .try_get_matches_from([
+            "tool",
+            "--color",
+            "auto",
+            "--tag",
+            "a",
+            "--color=never",
+            "--tag",
+            "b",
+        ])
+        .unwrap();
+    assert_eq!(matches.value_of("color"), Some("never"));
+    assert_eq!(
+        matches.values_of("tag"),
+        Some(&["a".to_string(), "b".to_string()][..])
+    );
+}
+
+#[test]
+fn test_subcommand_may_override_inherited_global_once() {
+    let app = || {
+        App::new("tool")
+            .arg(Arg::new("config").long("config").global(true))
+            .subcommand(Command::new("run"))
+    };
+
+    let matches = app()
+        .try_get_matches_from(["tool", "--config", "a", "run", "--config", "b"])
+        .unwrap();
+    let run = matches.subcommand_matches("run").unwrap();
+    assert_eq!(run.value_of("config"), Some("b"));
+
+    let err = app()
+        .try_get_matches_from(["tool", "run", "--config", "a", "--config", "b"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateArgument);
+}
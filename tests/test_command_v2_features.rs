@@ -9,8 +9,12 @@
 //! - Value delimiters
 //! - Command aliases
 //! - Argument groups
+//! - Command settings
 
-use zfish::command::{App, Arg, Command};
+use zfish::command::{
+    App, Arg, ArgAction, ArgGroup, Command, CommandError, ErrorKind, HelpStyles, Setting,
+    ValueSource,
+};
 
 // ============================================================================
 // POSITIONAL ARGUMENTS TESTS
@@ -214,6 +218,103 @@ fn test_priority_cli_env_default() {
     }
 }
 
+#[test]
+fn test_default_value_if() {
+    let app = || {
+        App::new("report")
+            .arg(Arg::new("format").long("format").default_value("text"))
+            .arg(Arg::new("color").long("color").takes_value(false))
+            .arg(
+                Arg::new("style")
+                    .long("style")
+                    .default_value_if("format", Some("json"), "pretty")
+                    .default_value_if("color", None, "fancy")
+                    .default_value("plain"),
+            )
+    };
+
+    let style = |args: Vec<&str>| {
+        let matches = app().try_get_matches_from(args).unwrap();
+        matches.value_of("style").map(String::from)
+    };
+    assert_eq!(style(vec!["report"]).as_deref(), Some("plain"));
+    assert_eq!(
+        style(vec!["report", "--format", "json"]).as_deref(),
+        Some("pretty")
+    );
+    assert_eq!(style(vec!["report", "--color"]).as_deref(), Some("fancy"));
+    // The first matching condition wins
+    assert_eq!(
+        style(vec!["report", "--color", "--format", "json"]).as_deref(),
+        Some("pretty")
+    );
+    // A given value always wins
+    assert_eq!(
+        style(vec!["report", "--format", "json", "--style", "compact"]).as_deref(),
+        Some("compact")
+    );
+}
+
+#[test]
+fn test_default_value_if_sees_other_defaults() {
+    let app = App::new("report")
+        .arg(
+            Arg::new("style")
+                .long("style")
+                .default_value_if("format", Some("json"), "pretty"),
+        )
+        .arg(Arg::new("format").long("format").default_value("json"));
+
+    let matches = app.try_get_matches_from(vec!["report"]).unwrap();
+    assert_eq!(matches.value_of("style"), Some("pretty"));
+}
+
+#[test]
+fn test_value_source() {
+    unsafe {
+        std::env::set_var("TEST_VALUE_SOURCE_TOKEN", "secret");
+    }
+    let app = || {
+        App::new("deploy")
+            .arg(
+                Arg::new("token")
+                    .long("token")
+                    .env("TEST_VALUE_SOURCE_TOKEN"),
+            )
+            .arg(
+                Arg::new("region")
+                    .long("region")
+                    .default_value("eu")
+                    .global(true),
+            )
+            .arg(Arg::new("tag").long("tag"))
+            .subcommand(Command::new("run"))
+    };
+
+    let matches = app().try_get_matches_from(vec!["deploy", "run"]).unwrap();
+    assert_eq!(matches.value_source("token"), Some(ValueSource::EnvVar));
+    assert_eq!(matches.value_source("region"), Some(ValueSource::Default));
+    assert_eq!(matches.value_source("tag"), None);
+    let run = matches.subcommand_matches("run").unwrap();
+    assert_eq!(run.value_source("region"), Some(ValueSource::Default));
+
+    let matches = app()
+        .try_get_matches_from(vec!["deploy", "--token", "t", "run", "--region", "us"])
+        .unwrap();
+    assert_eq!(
+        matches.value_source("token"),
+        Some(ValueSource::CommandLine)
+    );
+    assert_eq!(
+        matches.value_source("region"),
+        Some(ValueSource::CommandLine)
+    );
+
+    unsafe {
+        std::env::remove_var("TEST_VALUE_SOURCE_TOKEN");
+    }
+}
+
 // ============================================================================
 // ARGUMENT DEPENDENCIES TESTS (requires)
 // ============================================================================
@@ -282,6 +383,76 @@ fn test_requires_not_present_when_not_used() {
     assert!(!sub.is_present("format"));
 }
 
+#[test]
+fn test_required_unless_present() {
+    let app = || {
+        App::new("fetch")
+            .arg(
+                Arg::new("token")
+                    .long("token")
+                    .required_unless_present("anonymous")
+                    .required_unless_present("netrc"),
+            )
+            .arg(Arg::new("anonymous").long("anonymous").takes_value(false))
+            .arg(Arg::new("netrc").long("netrc"))
+    };
+
+    assert_eq!(
+        app().try_get_matches_from(vec!["fetch"]).unwrap_err(),
+        CommandError::MissingArgument("token".to_string())
+    );
+    assert!(
+        app()
+            .try_get_matches_from(vec!["fetch", "--token", "abc"])
+            .is_ok()
+    );
+    assert!(
+        app()
+            .try_get_matches_from(vec!["fetch", "--anonymous"])
+            .is_ok()
+    );
+    assert!(
+        app()
+            .try_get_matches_from(vec!["fetch", "--netrc", "~/.netrc"])
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_required_if_eq() {
+    let app = || {
+        App::new("sync")
+            .arg(Arg::new("mode").long("mode").default_value("local"))
+            .arg(
+                Arg::new("host")
+                    .long("host")
+                    .required_if_eq("mode", "remote"),
+            )
+    };
+
+    assert!(app().try_get_matches_from(vec!["sync"]).is_ok());
+    assert_eq!(
+        app()
+            .try_get_matches_from(vec!["sync", "--mode", "remote"])
+            .unwrap_err(),
+        CommandError::MissingArgument("host".to_string())
+    );
+    let matches = app()
+        .try_get_matches_from(vec!["sync", "--mode", "remote", "--host", "example.com"])
+        .unwrap();
+    assert_eq!(matches.value_of("host"), Some("example.com"));
+
+    // A default value can trigger the condition too
+    let remote_default = App::new("sync")
+        .arg(Arg::new("mode").long("mode").default_value("remote"))
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .required_if_eq("mode", "remote"),
+        );
+    assert!(remote_default.try_get_matches_from(vec!["sync"]).is_err());
+}
+
 // ============================================================================
 // ARGUMENT CONFLICTS TESTS
 // ============================================================================
@@ -429,12 +600,169 @@ fn test_alias_with_arguments() {
     assert_eq!(sub.value_of("file"), Some("input.txt"));
 }
 
+// ============================================================================
+// GLOBAL ARGUMENTS TESTS
+// ============================================================================
+
+fn global_app() -> App {
+    App::new("test")
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .takes_value(false)
+                .global(true),
+        )
+        .subcommand(
+            Command::new("remote").subcommand(Command::new("add").arg(Arg::new("name").index(0))),
+        )
+}
+
+#[test]
+fn test_global_arg_after_subcommand() {
+    let matches =
+        global_app().get_matches_from(vec!["test", "remote", "add", "origin", "--verbose"]);
+    assert!(matches.is_flag_set("verbose"));
+
+    let remote = matches.subcommand_matches("remote").unwrap();
+    assert!(remote.is_flag_set("verbose"));
+    let add = remote.subcommand_matches("add").unwrap();
+    assert!(add.is_flag_set("verbose"));
+    assert_eq!(add.value_of("name"), Some("origin"));
+}
+
+#[test]
+fn test_global_arg_before_subcommand() {
+    let matches = global_app().get_matches_from(vec!["test", "--verbose", "remote", "add"]);
+    assert!(matches.is_flag_set("verbose"));
+
+    let add = matches
+        .subcommand_matches("remote")
+        .and_then(|m| m.subcommand_matches("add"))
+        .unwrap();
+    assert!(add.is_flag_set("verbose"));
+}
+
+#[test]
+fn test_dry_run_flag() {
+    let app = App::new("test")
+        .dry_run_flag()
+        .subcommand(Command::new("deploy"));
+
+    let matches = app
+        .clone()
+        .get_matches_from(vec!["test", "deploy", "--dry-run"]);
+    assert!(matches.is_dry_run());
+    assert!(matches.subcommand_matches("deploy").unwrap().is_dry_run());
+
+    let matches = app.get_matches_from(vec!["test", "deploy"]);
+    assert!(!matches.is_dry_run());
+}
+
 // ============================================================================
 // ARGUMENT GROUPS TESTS
 // ============================================================================
 
-// Note: ArgGroup tests disabled - groups are for subcommands, not App level
-// These tests would need to be refactored to work with subcommands
+fn auth_app(group: ArgGroup) -> App {
+    App::new("login")
+        .arg(Arg::new("user").long("user"))
+        .arg(Arg::new("password").long("password"))
+        .arg(Arg::new("token").long("token"))
+        .arg(Arg::new("anonymous").long("anonymous").takes_value(false))
+        .group(group.args(&["user", "password"]))
+}
+
+#[test]
+fn test_group_members_are_exclusive_by_default() {
+    let err = auth_app(ArgGroup::new("auth"))
+        .try_get_matches_from(["login", "--user", "me", "--password", "pw"])
+        .unwrap_err();
+    assert!(
+        matches!(err, CommandError::ArgumentConflict(ref a, ref b) if a == "user" && b == "password")
+    );
+
+    let matches = auth_app(ArgGroup::new("auth").multiple(true))
+        .try_get_matches_from(["login", "--user", "me", "--password", "pw"])
+        .unwrap();
+    assert_eq!(matches.value_of("password"), Some("pw"));
+}
+
+#[test]
+fn test_group_requires_all() {
+    let app = || auth_app(ArgGroup::new("auth").requires_all(true));
+
+    let err = app()
+        .try_get_matches_from(["login", "--password", "pw"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingDependency);
+    assert_eq!(
+        err.to_string(),
+        "error: the argument 'password' requires 'user'"
+    );
+
+    assert!(app().try_get_matches_from(["login"]).is_ok());
+    assert!(
+        app()
+            .try_get_matches_from(["login", "--user", "me", "--password", "pw"])
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_group_conflicts_with_arg_or_group() {
+    let app = || {
+        auth_app(
+            ArgGroup::new("auth")
+                .multiple(true)
+                .conflicts_with("anonymous"),
+        )
+        .group(ArgGroup::new("key").arg("token").conflicts_with("auth"))
+    };
+
+    let err = app()
+        .try_get_matches_from(["login", "--anonymous", "--user", "me"])
+        .unwrap_err();
+    assert!(
+        matches!(err, CommandError::ArgumentConflict(ref a, ref b) if a == "user" && b == "anonymous")
+    );
+
+    // A group name stands for every member of that group
+    let err = app()
+        .try_get_matches_from(["login", "--token", "t", "--password", "pw"])
+        .unwrap_err();
+    assert!(
+        matches!(err, CommandError::ArgumentConflict(ref a, ref b) if a == "token" && b == "password")
+    );
+
+    assert!(
+        app()
+            .try_get_matches_from(["login", "--token", "t"])
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_group_rules_in_subcommands() {
+    let app = App::new("git").subcommand(
+        Command::new("commit")
+            .arg(Arg::new("all").short('a').takes_value(false))
+            .arg(Arg::new("patch").short('p').takes_value(false))
+            .group(
+                ArgGroup::new("stage")
+                    .args(&["all", "patch"])
+                    .required(true),
+            ),
+    );
+
+    let err = app
+        .clone()
+        .try_get_matches_from(["git", "commit"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingArgument);
+    assert!(
+        app.try_get_matches_from(["git", "commit", "-a", "-p"])
+            .is_err()
+    );
+}
 
 // ============================================================================
 // COMBINED FEATURES TESTS
@@ -493,3 +821,568 @@ fn test_variadic_with_delimiter() {
     let files: Vec<String> = vec!["f1.txt".to_string(), "f2.txt".to_string()];
     assert_eq!(sub.values_of("files"), Some(files.as_slice()));
 }
+
+#[test]
+fn test_output_format_arg() {
+    use zfish::table::OutputFormat;
+
+    let app = App::new("test")
+        .output_format_arg(&["table", "json", "csv"])
+        .subcommand(Command::new("list"));
+
+    let matches = app.clone().get_matches_from(vec!["test", "list"]);
+    assert_eq!(matches.output_format(), Some(OutputFormat::Table));
+
+    let matches = app
+        .clone()
+        .get_matches_from(vec!["test", "list", "--format", "json"]);
+    assert_eq!(matches.output_format(), Some(OutputFormat::Json));
+    assert_eq!(
+        matches.subcommand_matches("list").unwrap().output_format(),
+        Some(OutputFormat::Json)
+    );
+
+    assert!(
+        app.try_get_matches_from(vec!["test", "--format", "yaml"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_occurrences_of_absent_once_and_repeated() {
+    let app = || {
+        App::new("test")
+            .arg(Arg::new("force").long("force").takes_value(false))
+            .arg(Arg::new("tag").short('t').multiple(true))
+            .arg(Arg::new("level").long("level").default_value("1"))
+    };
+
+    let matches = app().try_get_matches_from(vec!["test"]).unwrap();
+    assert_eq!(matches.occurrences_of("force"), 0);
+    assert_eq!(matches.occurrences_of("tag"), 0);
+    // A default value is present but was not given
+    assert_eq!(matches.value_of("level"), Some("1"));
+    assert_eq!(matches.occurrences_of("level"), 0);
+
+    let matches = app()
+        .try_get_matches_from(vec!["test", "--force", "-t", "a", "-t", "b", "--level=2"])
+        .unwrap();
+    assert_eq!(matches.occurrences_of("force"), 1);
+    assert_eq!(matches.occurrences_of("tag"), 2);
+    assert_eq!(matches.occurrences_of("level"), 1);
+    assert_eq!(matches.occurrences_of("unknown"), 0);
+}
+
+#[test]
+fn test_count_action() {
+    let app = || {
+        App::new("test")
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .long("verbose")
+                    .action(ArgAction::Count),
+            )
+            .arg(Arg::new("include").short('I').action(ArgAction::Append))
+    };
+
+    let matches = app()
+        .try_get_matches_from(vec!["test", "-vvv", "--verbose"])
+        .unwrap();
+    assert_eq!(matches.get_count("verbose"), 4);
+    assert!(matches.is_flag_set("verbose"));
+
+    let matches = app()
+        .try_get_matches_from(vec!["test", "-I", "a", "-I", "b"])
+        .unwrap();
+    assert_eq!(matches.get_count("verbose"), 0);
+    assert_eq!(matches.values_of("include").unwrap(), &["a", "b"]);
+}
+
+#[test]
+fn test_verbosity_args() {
+    let app = App::new("test")
+        .verbosity_args()
+        .subcommand(Command::new("build"));
+
+    let matches = app.clone().get_matches_from(vec!["test"]);
+    assert_eq!(matches.verbosity(), 0);
+
+    let matches = app
+        .clone()
+        .get_matches_from(vec!["test", "-vv", "build", "--verbose"]);
+    assert_eq!(matches.occurrences_of("verbose"), 3);
+    assert_eq!(matches.verbosity(), 3);
+    assert_eq!(matches.subcommand_matches("build").unwrap().verbosity(), 3);
+
+    let matches = app.clone().get_matches_from(vec!["test", "build", "-q"]);
+    assert_eq!(matches.verbosity(), -1);
+
+    assert!(app.try_get_matches_from(vec!["test", "-q", "-v"]).is_err());
+}
+
+#[test]
+fn test_indices_of_interleaved_options() {
+    let app = App::new("cc")
+        .arg(Arg::new("include").short('I').multiple(true))
+        .arg(Arg::new("lib").short('L').multiple(true))
+        .arg(Arg::new("static").long("static").takes_value(false))
+        .arg(Arg::new("input").index(0));
+
+    let matches = app
+        .try_get_matches_from(vec![
+            "cc", "-I", "a", "-L", "b", "--static", "-I", "c", "main.c",
+        ])
+        .unwrap();
+
+    assert_eq!(matches.indices_of("include"), Some(&[2, 7][..]));
+    assert_eq!(matches.indices_of("lib"), Some(&[4][..]));
+    assert_eq!(matches.indices_of("static"), Some(&[5][..]));
+    assert_eq!(matches.indices_of("input"), Some(&[8][..]));
+    assert_eq!(matches.indices_of("missing"), None);
+}
+
+#[test]
+fn test_indices_of_in_subcommand() {
+    let app = App::new("git").subcommand(
+        Command::new("commit").arg(Arg::new("message").short('m').overrides_with_self(true)),
+    );
+
+    let matches = app
+        .try_get_matches_from(vec!["git", "commit", "-m", "wip", "-m", "fix"])
+        .unwrap();
+    let sub = matches.subcommand_matches("commit").unwrap();
+    assert_eq!(sub.indices_of("message"), Some(&[3, 5][..]));
+    assert_eq!(sub.value_of("message"), Some("fix"));
+}
+
+// ============================================================================
+// SETTINGS TESTS
+// ============================================================================
+
+#[test]
+fn test_trailing_var_arg() {
+    let app = App::new("exec")
+        .setting(Setting::TrailingVarArg)
+        .arg(Arg::new("verbose").short('v').takes_value(false))
+        .arg(Arg::new("program").index(0))
+        .arg(Arg::new("args").last(true));
+
+    let matches = app
+        .try_get_matches_from(vec!["exec", "-v", "ls", "-la", "--color", "/tmp"])
+        .unwrap();
+    assert!(matches.is_present("verbose"));
+    assert_eq!(matches.value_of("program"), Some("ls"));
+    assert_eq!(
+        matches.values_of("args").unwrap(),
+        &["-la", "--color", "/tmp"]
+    );
+}
+
+#[test]
+fn test_raw_args_after_double_dash() {
+    let app = || {
+        App::new("myapp").subcommand(
+            Command::new("exec")
+                .arg(Arg::new("verbose").short('v').takes_value(false))
+                .trailing_var_arg("args"),
+        )
+    };
+
+    let matches = app()
+        .try_get_matches_from([
+            "myapp",
+            "exec",
+            "-v",
+            "--",
+            "cargo",
+            "test",
+            "--release",
+            "-v",
+        ])
+        .unwrap();
+    let exec = matches.subcommand_matches("exec").unwrap();
+    assert!(exec.is_present("verbose"));
+    assert_eq!(
+        exec.values_of("args").unwrap(),
+        ["cargo", "test", "--release", "-v"]
+    );
+    assert_eq!(exec.indices_of("args").unwrap(), [4, 5, 6, 7]);
+
+    // A bare `--` gives an empty list; without one there is nothing
+    let matches = app().try_get_matches_from(["myapp", "exec", "--"]).unwrap();
+    let exec = matches.subcommand_matches("exec").unwrap();
+    assert_eq!(exec.values_of("args").unwrap(), [] as [&str; 0]);
+    let matches = app().try_get_matches_from(["myapp", "exec"]).unwrap();
+    assert!(
+        !matches
+            .subcommand_matches("exec")
+            .unwrap()
+            .is_present("args")
+    );
+
+    // Flags before `--` are still checked
+    assert!(
+        app()
+            .try_get_matches_from(["myapp", "exec", "--nope", "--", "ls"])
+            .is_err()
+    );
+
+    let exec = Command::new("exec")
+        .arg(Arg::new("verbose").short('v').takes_value(false))
+        .trailing_var_arg("args");
+    assert_eq!(exec.render_usage(), "exec [OPTIONS] [-- <ARGS>...]");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_raw_args_keep_invalid_utf8() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let name = OsString::from_vec(b"caf\xe9".to_vec());
+    let matches = App::new("run")
+        .trailing_var_arg("args")
+        .try_get_matches_from([
+            OsString::from("run"),
+            "--".into(),
+            "ls".into(),
+            name.clone(),
+        ])
+        .unwrap();
+    assert_eq!(
+        matches.values_of_os("args").unwrap(),
+        [OsString::from("ls").as_os_str(), name.as_os_str()]
+    );
+}
+
+#[test]
+fn test_allow_hyphen_values() {
+    let app = App::new("seek")
+        .arg(Arg::new("offset").long("offset"))
+        .setting(Setting::AllowHyphenValues);
+    let matches = app
+        .try_get_matches_from(vec!["seek", "--offset", "-5"])
+        .unwrap();
+    assert_eq!(matches.value_of("offset"), Some("-5"));
+
+    let strict = App::new("seek").arg(Arg::new("offset").long("offset"));
+    assert!(
+        strict
+            .try_get_matches_from(vec!["seek", "--offset", "-5"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_arg_allow_hyphen_values() {
+    let app = || {
+        App::new("seek")
+            .arg(Arg::new("offset").long("offset").allow_hyphen_values(true))
+            .arg(
+                Arg::new("output")
+                    .short('o')
+                    .long("output")
+                    .allow_hyphen_values(true),
+            )
+            .arg(Arg::new("whence").long("whence"))
+    };
+
+    let matches = app()
+        .try_get_matches_from(vec!["seek", "--offset", "-5", "-o", "-"])
+        .unwrap();
+    assert_eq!(matches.value_of("offset"), Some("-5"));
+    assert_eq!(matches.value_of("output"), Some("-"));
+
+    let matches = app()
+        .try_get_matches_from(vec!["seek", "-o", "--offset"])
+        .unwrap();
+    assert_eq!(matches.value_of("output"), Some("--offset"));
+    assert!(!matches.is_present("offset"));
+
+    // Only the options that opt in take hyphen values
+    assert!(matches!(
+        app().try_get_matches_from(vec!["seek", "--whence", "-1"]),
+        Err(CommandError::UnknownArgument(_))
+    ));
+}
+
+#[test]
+fn test_arg_required_else_help() {
+    let app = App::new("tool")
+        .setting(Setting::ArgRequiredElseHelp)
+        .arg(Arg::new("input").index(0));
+    assert!(matches!(
+        app.try_get_matches_from(vec!["tool"]),
+        Err(CommandError::HelpRequested)
+    ));
+}
+
+fn settings_app() -> App {
+    App::new("git")
+        .setting(Setting::SubcommandRequired | Setting::AllowHyphenValues)
+        .subcommand(
+            Command::new("remote")
+                .subcommand(Command::new("add").arg(Arg::new("name").long("name"))),
+        )
+}
+
+#[test]
+fn test_settings_inherited_by_subcommands() {
+    let err = settings_app()
+        .try_get_matches_from(vec!["git"])
+        .unwrap_err();
+    assert!(matches!(err, CommandError::MissingSubcommand(ref path) if path == &["git"]));
+
+    let err = settings_app()
+        .try_get_matches_from(vec!["git", "remote"])
+        .unwrap_err();
+    assert!(
+        matches!(err.inner(), CommandError::MissingSubcommand(path) if path == &["git", "remote"])
+    );
+
+    let matches = settings_app()
+        .try_get_matches_from(vec!["git", "remote", "add", "--name", "-x"])
+        .unwrap();
+    let add = matches
+        .subcommand_matches("remote")
+        .and_then(|m| m.subcommand_matches("add"))
+        .unwrap();
+    assert_eq!(add.value_of("name"), Some("-x"));
+}
+
+#[test]
+fn test_subcommand_required() {
+    let cmd = Command::new("git")
+        .styles(HelpStyles::plain())
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes")
+                .subcommand(Command::new("add").about("Add a remote")),
+        );
+    assert!(cmd.is_set(Setting::SubcommandRequired));
+
+    let err = cmd.try_parse_from(["git", "remote"]).unwrap_err();
+    assert_eq!(
+        err.inner(),
+        &CommandError::MissingSubcommand(vec!["git".to_string(), "remote".to_string()])
+    );
+    let rendered = cmd.format_error(err);
+    assert_eq!(rendered.exit_code(), 1);
+    // The help of the command that needs a subcommand follows the error
+    assert!(
+        rendered
+            .rendered()
+            .starts_with("error: 'git remote' requires a subcommand\n\nManage remotes\n")
+    );
+    assert!(rendered.rendered().contains("add"));
+    assert!(rendered.rendered().contains("Add a remote"));
+
+    assert!(cmd.try_parse_from(["git", "remote", "add"]).is_ok());
+    let optional = cmd.subcommand_required(false);
+    assert!(
+        optional
+            .try_parse_from(["git"])
+            .unwrap()
+            .subcommand()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_subcommand_required_with_duplicate_names() {
+    let add = |arg: &str| {
+        Command::new("add")
+            .subcommand_required(true)
+            .subcommand(Command::new(arg))
+    };
+    let cmd = Command::new("git")
+        .styles(HelpStyles::plain())
+        .subcommand(Command::new("remote").subcommand(add("x")))
+        .subcommand(Command::new("config").subcommand(add("y")));
+
+    let err = cmd.try_parse_from(["git", "config", "add"]).unwrap_err();
+    assert_eq!(err.context(), ["git", "config", "add"]);
+    let rendered = cmd.format_error(err);
+    // The help is that of `config add`, not the first `add` in the tree
+    assert!(
+        rendered
+            .rendered()
+            .starts_with("error: 'git config add' requires a subcommand\n")
+    );
+    assert!(rendered.rendered().contains("    y"));
+    assert!(!rendered.rendered().contains("    x"));
+}
+
+#[test]
+fn test_setting_query() {
+    let cmd = Command::new("run").setting(Setting::TrailingVarArg);
+    assert!(cmd.is_set(Setting::TrailingVarArg));
+    assert!(!cmd.is_set(Setting::InteractiveFallback));
+
+    let cmd = cmd.interactive_fallback(true);
+    assert!(cmd.is_set(Setting::InteractiveFallback));
+    assert!(
+        !cmd.interactive_fallback(false)
+            .is_set(Setting::InteractiveFallback)
+    );
+}
+
+fn infer_app() -> App {
+    App::new("cargo")
+        .version("1.0.0")
+        .infer_long_args(true)
+        .infer_subcommands(true)
+        .arg(Arg::new("verbose").long("verbose").takes_value(false))
+        .arg(Arg::new("verify").long("verify").takes_value(false))
+        .arg(Arg::new("color").long("color"))
+        .subcommand(Command::new("build").alias("compile"))
+        .subcommand(Command::new("bench").arg(Arg::new("jobs").long("jobs")))
+        .subcommand(Command::new("clean"))
+}
+
+#[test]
+fn test_infer_long_args() {
+    let matches = infer_app()
+        .try_get_matches_from(vec!["cargo", "--verb", "--col=auto"])
+        .unwrap();
+    assert!(matches.is_present("verbose"));
+    assert_eq!(matches.value_of("color"), Some("auto"));
+
+    let err = infer_app()
+        .try_get_matches_from(vec!["cargo", "--ver"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::AmbiguousArgument(
+            "ver".to_string(),
+            vec![
+                "--verbose".to_string(),
+                "--verify".to_string(),
+                "--version".to_string()
+            ]
+        )
+    );
+    assert_eq!(
+        err.message(),
+        "ambiguous argument 'ver'; could be '--verbose', '--verify' or '--version'"
+    );
+    assert!(matches!(
+        infer_app().try_get_matches_from(vec!["cargo", "--he"]),
+        Err(CommandError::HelpRequested)
+    ));
+
+    // Inherited by subcommands
+    let matches = infer_app()
+        .try_get_matches_from(vec!["cargo", "bench", "--j", "4"])
+        .unwrap();
+    assert_eq!(
+        matches
+            .subcommand_matches("bench")
+            .unwrap()
+            .value_of("jobs"),
+        Some("4")
+    );
+
+    // Off by default
+    let strict = App::new("cargo").arg(Arg::new("verbose").long("verbose").takes_value(false));
+    assert!(matches!(
+        strict.try_get_matches_from(vec!["cargo", "--verb"]),
+        Err(CommandError::UnknownArgument(_))
+    ));
+}
+
+#[test]
+fn test_infer_subcommands() {
+    let matches = infer_app()
+        .try_get_matches_from(vec!["cargo", "cl"])
+        .unwrap();
+    assert_eq!(matches.subcommand_name(), Some("clean"));
+
+    // Prefixes of aliases count, and the full name is recorded
+    let matches = infer_app()
+        .try_get_matches_from(vec!["cargo", "comp"])
+        .unwrap();
+    assert_eq!(matches.subcommand_name(), Some("build"));
+
+    let err = infer_app()
+        .try_get_matches_from(vec!["cargo", "b"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::AmbiguousSubcommand(
+            "b".to_string(),
+            vec!["build".to_string(), "bench".to_string()]
+        )
+    );
+    assert_eq!(err.kind(), ErrorKind::UnknownSubcommand);
+}
+
+// ============================================================================
+// MULTICALL TESTS
+// ============================================================================
+
+fn multicall_app() -> App {
+    App::new("box")
+        .multicall(true)
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .takes_value(false)
+                .global(true),
+        )
+        .subcommand(Command::new("init").arg(Arg::new("level").index(0)))
+        .subcommand(Command::new("shutdown").alias("halt"))
+}
+
+#[test]
+fn test_multicall_dispatches_on_program_name() {
+    let matches = multicall_app()
+        .try_get_matches_from(vec!["/sbin/init", "-v", "3"])
+        .unwrap();
+    assert_eq!(matches.subcommand_name(), Some("init"));
+    let init = matches.subcommand_matches("init").unwrap();
+    assert_eq!(init.value_of("level"), Some("3"));
+    assert!(matches.is_flag_set("verbose"));
+    // argv indices still count from the program name
+    assert_eq!(init.indices_of("level"), Some(&[2][..]));
+
+    let matches = multicall_app().try_get_matches_from(vec!["halt"]).unwrap();
+    assert_eq!(matches.subcommand_name(), Some("halt"));
+}
+
+#[test]
+fn test_multicall_under_own_name_parses_normally() {
+    let matches = multicall_app()
+        .try_get_matches_from(vec!["/bin/box", "init", "5"])
+        .unwrap();
+    assert_eq!(
+        matches
+            .subcommand_matches("init")
+            .unwrap()
+            .value_of("level"),
+        Some("5")
+    );
+}
+
+#[test]
+fn test_multicall_unknown_program_name() {
+    assert_eq!(
+        multicall_app()
+            .try_get_matches_from(vec!["reboot"])
+            .unwrap_err(),
+        CommandError::UnknownSubcommand("reboot".to_string())
+    );
+    assert_eq!(
+        multicall_app()
+            .try_get_matches_from(vec!["int"])
+            .unwrap_err()
+            .suggestion(),
+        Some("init")
+    );
+
+    // Without multicall the program name is never looked at
+    let plain = App::new("box").subcommand(Command::new("init"));
+    assert!(plain.try_get_matches_from(vec!["reboot"]).is_ok());
+}
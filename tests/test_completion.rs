@@ -0,0 +1,109 @@
+//! Tests for filesystem path completion and line editor history
+
+use std::fs;
+use std::path::PathBuf;
+use zfish::completion::{Completer, PathCompleter};
+use zfish::line_editor::History;
+
+/// Creates a fresh scratch directory with a few files and folders
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("zfish_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::create_dir_all(dir.join("data")).unwrap();
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::write(dir.join("deploy.sh"), "").unwrap();
+    fs::write(dir.join("README.md"), "").unwrap();
+    fs::write(dir.join(".env"), "").unwrap();
+    dir
+}
+
+#[test]
+fn test_directories_first_with_trailing_slash() {
+    let dir = scratch_dir("dirs_first");
+    let base = format!("{}/", dir.display());
+
+    let candidates = PathCompleter::new().complete(&format!("{}d", base));
+    assert_eq!(
+        candidates,
+        vec![
+            format!("{}data/", base),
+            format!("{}docs/", base),
+            format!("{}deploy.sh", base),
+        ]
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_hidden_entries_and_dirs_only() {
+    let dir = scratch_dir("hidden");
+    let base = format!("{}/", dir.display());
+
+    let all = PathCompleter::new().complete(&base);
+    assert_eq!(all.len(), 4, "{:?}", all);
+    assert!(all.iter().all(|c| !c[base.len()..].starts_with('.')));
+
+    let hidden = PathCompleter::new().complete(&format!("{}.", base));
+    assert_eq!(
+        hidden,
+        vec![format!("{}.git/", base), format!("{}.env", base)]
+    );
+
+    let dirs = PathCompleter::directories().complete(&base);
+    assert_eq!(
+        dirs,
+        vec![format!("{}data/", base), format!("{}docs/", base)]
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_missing_directory_and_closure_completer() {
+    assert!(
+        PathCompleter::new()
+            .complete("definitely/not/here/x")
+            .is_empty()
+    );
+
+    let fixed = |word: &str| vec![format!("{}!", word)];
+    assert_eq!(fixed.complete("hi"), vec!["hi!".to_string()]);
+}
+
+// ============================================================================
+// HISTORY TESTS
+// ============================================================================
+
+#[test]
+fn test_history_dedup_and_max_len() {
+    let mut history = History::new().max_len(3);
+    for line in ["ls", "  ", "pwd", "ls", "cd src", "make"] {
+        history.push(line);
+    }
+    assert_eq!(history.entries(), ["ls", "cd src", "make"]);
+}
+
+#[test]
+fn test_history_file_round_trip() {
+    let dir = scratch_dir("history");
+    let path = dir.join(".myapp_history");
+
+    let mut history = History::load(&path).unwrap();
+    assert!(history.entries().is_empty());
+    history.push("status");
+    history.push("deploy --prod");
+    history.save().unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "status\ndeploy --prod\n"
+    );
+
+    fs::write(&path, "a\nb\na\n\nc\n").unwrap();
+    let reloaded = History::load(&path).unwrap().max_len(2);
+    assert_eq!(reloaded.entries(), ["a", "c"]);
+    assert_eq!(reloaded.path(), Some(path.as_path()));
+
+    assert!(History::load(&dir).is_err());
+}
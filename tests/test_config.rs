@@ -0,0 +1,195 @@
+//! Tests for config-file argument defaults (`App::config_file`)
+
+use std::fs;
+use std::path::PathBuf;
+use zfish::command::{App, Arg, ArgAction, Command, CommandError, ErrorKind, ValueSource};
+
+/// Writes `text` to a fresh config file named after the test
+fn config_file(name: &str, text: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("zfish_config_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    fs::write(&path, text).unwrap();
+    path
+}
+
+fn build_app() -> App {
+    App::new("myapp")
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .possible_values(&["auto", "always", "never"]),
+        )
+        .arg(Arg::new("jobs").long("jobs").default_value("1"))
+        .arg(Arg::new("user").long("user").env("ZFISH_CONFIG_TEST_USER"))
+        .arg(Arg::new("force").long("force").action(ArgAction::SetTrue))
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .action(ArgAction::Append),
+        )
+        .arg(Arg::new("remote-url").long("url").global(true))
+        .subcommand(Command::new("remote").subcommand(
+            Command::new("add").arg(Arg::new("name").long("name").default_value("origin")),
+        ))
+}
+
+// ============================================================================
+// Precedence Tests
+// ============================================================================
+
+#[test]
+fn test_config_values_fill_missing_args() {
+    let path = config_file(
+        "fill",
+        "color = \"always\"\njobs = 4\nforce = true\ninclude = [\"src\", \"tests\"]\n",
+    );
+    let app = || build_app().config_file(&path);
+
+    let matches = app().try_get_matches_from(["myapp"]).unwrap();
+    assert_eq!(matches.value_of("color"), Some("always"));
+    assert_eq!(matches.value_of("jobs"), Some("4"));
+    assert!(matches.is_present("force"));
+    assert_eq!(matches.values_of("include").unwrap(), ["src", "tests"]);
+    assert_eq!(matches.value_source("jobs"), Some(ValueSource::ConfigFile));
+    assert_eq!(matches.value_source("force"), Some(ValueSource::ConfigFile));
+
+    // The command line always wins
+    let matches = app()
+        .try_get_matches_from(["myapp", "--jobs", "8", "--include", "docs"])
+        .unwrap();
+    assert_eq!(matches.value_of("jobs"), Some("8"));
+    assert_eq!(matches.values_of("include").unwrap(), ["docs"]);
+    assert_eq!(matches.value_source("jobs"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn test_config_ranks_below_env_and_above_default() {
+    let path = config_file("rank", "user = \"config\"\n");
+    let app = || build_app().config_file(&path);
+
+    let matches = app().try_get_matches_from(["myapp"]).unwrap();
+    assert_eq!(matches.value_of("user"), Some("config"));
+    assert_eq!(matches.value_of("jobs"), Some("1"));
+    assert_eq!(matches.value_source("jobs"), Some(ValueSource::Default));
+
+    unsafe {
+        std::env::set_var("ZFISH_CONFIG_TEST_USER", "env");
+    }
+    let matches = app().try_get_matches_from(["myapp"]).unwrap();
+    unsafe {
+        std::env::remove_var("ZFISH_CONFIG_TEST_USER");
+    }
+    assert_eq!(matches.value_of("user"), Some("env"));
+    assert_eq!(matches.value_source("user"), Some(ValueSource::EnvVar));
+}
+
+#[test]
+fn test_config_keys_may_be_long_flags() {
+    let path = config_file("long", "url = \"https://example.com\"\n");
+    let matches = build_app()
+        .config_file(&path)
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert_eq!(matches.value_of("remote-url"), Some("https://example.com"));
+}
+
+#[test]
+fn test_config_satisfies_required_args() {
+    let app = |path: &PathBuf| {
+        App::new("myapp")
+            .config_file(path)
+            .arg(Arg::new("token").long("token").required(true))
+    };
+
+    let path = config_file("required", "token = \"abc\"\n");
+    let matches = app(&path).try_get_matches_from(["myapp"]).unwrap();
+    assert_eq!(matches.value_of("token"), Some("abc"));
+    assert_eq!(matches.value_source("token"), Some(ValueSource::ConfigFile));
+
+    let path = config_file("required_missing", "other = 1\n");
+    let err = app(&path).try_get_matches_from(["myapp"]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingArgument);
+}
+
+// ============================================================================
+// Section Tests
+// ============================================================================
+
+#[test]
+fn test_subcommand_sections() {
+    let path = config_file(
+        "sections",
+        "name = \"top-level\"\n[remote.add]\nname = \"upstream\"\nurl = \"https://nested\"\n\
+         [remote]\nurl = \"https://parent\"\n",
+    );
+    let app = || build_app().config_file(&path);
+
+    let matches = app()
+        .try_get_matches_from(["myapp", "remote", "add"])
+        .unwrap();
+    let (_, remote) = matches.subcommand().unwrap();
+    let (_, add) = remote.subcommand().unwrap();
+    assert_eq!(add.value_of("name"), Some("upstream"));
+    // A global argument takes the value of the nearest section that sets it
+    assert_eq!(add.value_of("remote-url"), Some("https://nested"));
+
+    let matches = app().try_get_matches_from(["myapp", "remote"]).unwrap();
+    let (_, remote) = matches.subcommand().unwrap();
+    assert_eq!(remote.value_of("remote-url"), Some("https://parent"));
+}
+
+// ============================================================================
+// Error Tests
+// ============================================================================
+
+#[test]
+fn test_missing_config_file_is_ignored() {
+    let path = std::env::temp_dir().join("zfish_config_does_not_exist/config.toml");
+    let matches = build_app()
+        .config_file(path)
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert_eq!(matches.value_of("jobs"), Some("1"));
+}
+
+#[test]
+fn test_malformed_config_file_is_an_error() {
+    let path = config_file("malformed", "jobs = 4\nthis is not toml\n");
+    let err = build_app()
+        .config_file(&path)
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidConfig);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "error: invalid config file '{}': line 2: expected 'key = value'",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn test_config_values_are_validated() {
+    let path = config_file("invalid", "color = \"purple\"\n");
+    let err = build_app()
+        .config_file(&path)
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert!(matches!(err, CommandError::ValidationError(ref name, _) if name == "color"));
+
+    let path = config_file("not_bool", "force = \"yes\"\n");
+    let err = build_app()
+        .config_file(&path)
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert!(matches!(err, CommandError::ValidationError(ref name, _) if name == "force"));
+
+    let path = config_file("list", "jobs = [1, 2]\n");
+    let err = build_app()
+        .config_file(&path)
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert!(matches!(err, CommandError::ValidationError(ref name, _) if name == "jobs"));
+}
@@ -0,0 +1,157 @@
+//! Tests for `.env` reading (`env::read_dotenv`, `App::dotenv_file`) and
+//! `Arg::env` fallbacks
+//!
+//! None of these change the environment or the working directory: the app
+//! reads its file by path and only ever reads the environment.
+
+use std::fs;
+use std::path::PathBuf;
+use zfish::command::{App, Arg, CommandError, ErrorKind, ValueSource};
+use zfish::env;
+
+/// Creates a fresh scratch directory holding `.env` with `text`
+fn dotenv_dir(name: &str, text: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("zfish_env_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(".env"), text).unwrap();
+    dir
+}
+
+// ============================================================================
+// read_dotenv Tests
+// ============================================================================
+
+#[test]
+fn test_read_dotenv() {
+    let dir = dotenv_dir(
+        "read",
+        "export ZFISH_ENV_TEST_TOKEN=\"abc def\"\nJOBS=1\nJOBS=4\n",
+    );
+
+    let vars = env::read_dotenv(dir.join(".env")).unwrap();
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars["ZFISH_ENV_TEST_TOKEN"], "abc def");
+    // The last assignment wins
+    assert_eq!(vars["JOBS"], "4");
+    // Reading leaves the environment alone
+    assert!(std::env::var_os("ZFISH_ENV_TEST_TOKEN").is_none());
+}
+
+#[test]
+fn test_read_dotenv_errors() {
+    let missing = env::read_dotenv("/nonexistent/zfish/.env").unwrap_err();
+    assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+
+    let dir = dotenv_dir("malformed", "ZFISH_ENV_TEST_BEFORE=1\nnot a pair\n");
+    let err = env::read_dotenv(dir.join(".env")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "line 2: expected 'KEY=VALUE'");
+}
+
+// ============================================================================
+// App::dotenv_file Tests
+// ============================================================================
+
+#[test]
+fn test_app_dotenv_feeds_env_fallbacks() {
+    let app = |path: PathBuf| {
+        App::new("myapp").dotenv_file(path).arg(
+            Arg::new("config")
+                .long("config")
+                .env("ZFISH_ENV_TEST_CONFIG"),
+        )
+    };
+
+    // Without a .env file there is nothing to read
+    let empty = std::env::temp_dir().join(format!("zfish_env_none_{}", std::process::id()));
+    let matches = app(empty.join(".env"))
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert!(!matches.is_present("config"));
+
+    let dir = dotenv_dir("app", "ZFISH_ENV_TEST_CONFIG='~/.myapp/config'\n");
+    let matches = app(dir.join(".env"))
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert_eq!(matches.value_of("config"), Some("~/.myapp/config"));
+    assert_eq!(matches.value_source("config"), Some(ValueSource::EnvVar));
+    assert!(std::env::var_os("ZFISH_ENV_TEST_CONFIG").is_none());
+
+    // The command line still wins over the file
+    let matches = app(dir.join(".env"))
+        .try_get_matches_from(["myapp", "--config", "cli"])
+        .unwrap();
+    assert_eq!(matches.value_of("config"), Some("cli"));
+
+    let bad = dotenv_dir("app_bad", "ZFISH_ENV_TEST_OTHER=\"open\n");
+    let path = bad.join(".env");
+    let err = app(path.clone())
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidConfig);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "error: invalid config file '{}': line 1: unterminated string: missing closing '\"'",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn test_app_dotenv_ranks_below_the_real_environment() {
+    // PATH is set for every test process, so the file cannot override it
+    let path = std::env::var("PATH").unwrap();
+    let dir = dotenv_dir("ranking", "PATH=/from/dotenv\n");
+    let matches = App::new("myapp")
+        .dotenv_file(dir.join(".env"))
+        .arg(Arg::new("path").long("path").env("PATH"))
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert_eq!(matches.value_of("path"), Some(path.as_str()));
+}
+
+#[test]
+fn test_app_dotenv_values_are_validated() {
+    let app = |path: PathBuf| {
+        App::new("myapp").dotenv_file(path).arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .env("ZFISH_ENV_TEST_JOBS")
+                .value_range(1..=8),
+        )
+    };
+
+    let dir = dotenv_dir("range_ok", "ZFISH_ENV_TEST_JOBS=4\n");
+    let matches = app(dir.join(".env"))
+        .try_get_matches_from(["myapp"])
+        .unwrap();
+    assert_eq!(matches.value_of("jobs"), Some("4"));
+    assert_eq!(matches.value_source("jobs"), Some(ValueSource::EnvVar));
+
+    let dir = dotenv_dir("range_bad", "ZFISH_ENV_TEST_JOBS=9\n");
+    let err = app(dir.join(".env"))
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValidationError);
+    assert_eq!(
+        err,
+        CommandError::ValidationError("jobs".to_string(), "9 must be between 1 and 8".to_string())
+    );
+}
+
+#[test]
+fn test_app_env_values_are_validated() {
+    // PATH is set for every test process and is never one of these
+    let err = App::new("myapp")
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .env("PATH")
+                .possible_values(&["fast", "slow"]),
+        )
+        .try_get_matches_from(["myapp"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValidationError);
+    assert_eq!(err.context(), vec!["mode"]);
+}
@@ -0,0 +1,53 @@
+//! Tests for confirm-before-overwrite file writing
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use zfish::command::App;
+use zfish::fsutil::{self, WriteOutcome};
+
+fn scratch_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("zfish_fsutil_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// One test, because `--yes` is a process-wide setting
+#[test]
+fn test_write_checked_outcomes() {
+    let path = scratch_file("config.toml");
+
+    assert_eq!(
+        fsutil::write_checked(&path, "a = 1\n").unwrap(),
+        WriteOutcome::Created
+    );
+    assert_eq!(
+        fsutil::write_checked(&path, "a = 1\n").unwrap(),
+        WriteOutcome::Unchanged
+    );
+
+    // Nobody can be asked when stdin is not a terminal, so the file is kept
+    if !std::io::stdin().is_terminal() {
+        let outcome = fsutil::write_checked(&path, "a = 2\n").unwrap();
+        assert_eq!(outcome, WriteOutcome::Skipped);
+        assert!(!outcome.is_written());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1\n");
+    }
+
+    let app = || App::new("gen").yes_flag();
+    let matches = app().try_get_matches_from(["gen", "-y"]).unwrap();
+    assert!(matches.assume_yes());
+    assert!(fsutil::assume_yes());
+    assert_eq!(
+        fsutil::write_checked(&path, "a = 3\n").unwrap(),
+        WriteOutcome::Overwritten
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a = 3\n");
+
+    app().try_get_matches_from(["gen"]).unwrap();
+    assert!(!fsutil::assume_yes());
+
+    assert!(fsutil::write_checked(path.parent().unwrap(), "x").is_err());
+}
@@ -0,0 +1,71 @@
+//! Round-trip tests for the humanize module
+
+use std::time::Duration;
+use zfish::humanize::{format_byte_size, format_duration, parse_byte_size, parse_duration};
+use zfish::value_parser::{ByteSize, HumanDuration};
+
+#[test]
+fn test_format_duration_components() {
+    assert_eq!(format_duration(Duration::from_secs(90)), "1m 30s");
+    assert_eq!(format_duration(Duration::from_secs(5400)), "1h 30m");
+    assert_eq!(format_duration(Duration::from_secs(86_400 + 1)), "1d 1s");
+    assert_eq!(format_duration(Duration::from_millis(2500)), "2s 500ms");
+    assert_eq!(format_duration(Duration::from_nanos(1_001)), "1us 1ns");
+    assert_eq!(format_duration(Duration::ZERO), "0s");
+}
+
+#[test]
+fn test_duration_round_trip() {
+    let samples = [
+        Duration::ZERO,
+        Duration::from_secs(1),
+        Duration::from_secs(59),
+        Duration::from_secs(3601),
+        Duration::from_secs(7 * 86_400 + 5),
+        Duration::from_millis(1),
+        Duration::from_millis(61_250),
+        Duration::from_micros(1_500),
+        // Too many digits for an f64 nanosecond total
+        Duration::new(200 * 86_400 + 3661, 123_456_789),
+        Duration::new(u64::MAX, 999_999_999),
+    ];
+
+    for duration in samples {
+        let text = format_duration(duration);
+        assert_eq!(parse_duration(&text).unwrap(), duration, "via '{}'", text);
+    }
+}
+
+#[test]
+fn test_format_byte_size_units() {
+    assert_eq!(format_byte_size(0), "0 B");
+    assert_eq!(format_byte_size(1023), "1023 B");
+    assert_eq!(format_byte_size(1024), "1 KiB");
+    assert_eq!(format_byte_size(512 * 1024 * 1024), "512 MiB");
+    assert_eq!(format_byte_size(1536 * 1024 * 1024), "1.5 GiB");
+    assert_eq!(format_byte_size(1_000_000), "976.56 KiB");
+    // Values that round up to 1024 move to the next unit
+    assert_eq!(format_byte_size(1_048_575), "1 MiB");
+    assert_eq!(format_byte_size((1 << 30) - 1), "1 GiB");
+    assert_eq!(format_byte_size(1_048_570), "1023.99 KiB");
+}
+
+#[test]
+fn test_byte_size_round_trip_exact_multiples() {
+    let samples = [0, 1, 999, 1024, 4096, 512 << 20, 3 << 30, 2 << 40];
+
+    for bytes in samples {
+        let text = format_byte_size(bytes);
+        assert_eq!(parse_byte_size(&text).unwrap(), bytes, "via '{}'", text);
+    }
+}
+
+#[test]
+fn test_newtype_display_uses_humanize() {
+    let timeout: HumanDuration = "90s".parse().unwrap();
+    assert_eq!(timeout.to_string(), "1m 30s");
+
+    let limit: ByteSize = "512MiB".parse().unwrap();
+    assert_eq!(limit.to_string(), "512 MiB");
+    assert_eq!(limit.to_string().parse::<ByteSize>().unwrap(), limit);
+}
@@ -0,0 +1,59 @@
+//! Tests for the stdin-dash convention
+
+use std::io::{BufRead, Read};
+use zfish::command::{App, Arg};
+use zfish::io::{FileOrStdin, InputSource};
+
+#[test]
+fn test_file_or_stdin_parse() {
+    assert_eq!("-".parse::<FileOrStdin>(), Ok(FileOrStdin::Stdin));
+    assert_eq!(
+        "data.csv".parse::<FileOrStdin>(),
+        Ok(FileOrStdin::Path("data.csv".into()))
+    );
+    assert!("".parse::<FileOrStdin>().is_err());
+
+    assert!(FileOrStdin::Stdin.is_stdin());
+    assert_eq!(FileOrStdin::Stdin.path(), None);
+    assert_eq!(FileOrStdin::Stdin.to_string(), "-");
+}
+
+#[test]
+fn test_positional_dash_is_stdin() {
+    let app = App::new("cat").arg(Arg::new("file").index(0).allow_stdin_dash(true));
+    let matches = app.try_get_matches_from(vec!["cat", "-"]).unwrap();
+
+    let input: FileOrStdin = matches.value_of_t("file").unwrap();
+    assert_eq!(input, FileOrStdin::Stdin);
+}
+
+#[test]
+fn test_option_dash_requires_opt_in() {
+    let app = App::new("tool")
+        .arg(Arg::new("input").long("input").allow_stdin_dash(true))
+        .arg(Arg::new("output").long("output"));
+
+    let matches = app
+        .try_get_matches_from(vec!["tool", "--input", "-", "--output", "-"])
+        .unwrap();
+    assert_eq!(matches.value_of("input"), Some("-"));
+    assert_eq!(matches.value_of("output"), None);
+}
+
+#[test]
+fn test_open_file_source() {
+    let input: FileOrStdin = "Cargo.toml".parse().unwrap();
+    let mut source = input.open().unwrap();
+    assert!(matches!(source, InputSource::File(_)));
+
+    let mut first = String::new();
+    source.read_line(&mut first).unwrap();
+    assert_eq!(first.trim(), "[package]");
+
+    let mut rest = String::new();
+    source.read_to_string(&mut rest).unwrap();
+    assert!(rest.contains("name = \"zfish\""));
+
+    let missing: FileOrStdin = "no/such/file".parse().unwrap();
+    assert!(missing.open().is_err());
+}
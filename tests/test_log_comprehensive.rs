@@ -214,3 +214,136 @@ fn test_logger_multiple_instances() {
     logger2.debug("Logger 2");
     logger3.info("Logger 3");
 }
+
+#[test]
+fn test_logger_dry_run_prefix() {
+    let logger = Logger::new().dry_run_prefix(true);
+    logger.info("Would delete 3 files");
+    logger.warn("Would overwrite config");
+
+    let logger = Logger::new().level(Level::Debug).dry_run_prefix(false);
+    logger.debug("Not a dry run");
+}
+
+#[test]
+fn test_level_from_verbosity() {
+    assert_eq!(Level::from_verbosity(-1), Level::Error);
+    assert_eq!(Level::from_verbosity(i8::MIN), Level::Error);
+    assert_eq!(Level::from_verbosity(0), Level::Info);
+    assert_eq!(Level::from_verbosity(1), Level::Debug);
+    assert_eq!(Level::from_verbosity(5), Level::Debug);
+
+    let logger = Logger::from_verbosity(-1);
+    logger.info("Hidden when quiet");
+    logger.error("Always shown");
+}
+
+#[test]
+fn test_logger_dedup_and_throttle() {
+    let logger = Logger::new()
+        .dedup_window(std::time::Duration::from_secs(1))
+        .max_per_second(10);
+    for _ in 0..100 {
+        logger.warn("connection refused, retrying");
+    }
+    for i in 0..100 {
+        logger.info(&format!("item {}", i));
+    }
+    // Pending "repeated"/"suppressed" notices are printed when dropped
+    drop(logger);
+}
+
+#[test]
+fn test_logger_scoped() {
+    let logger = Logger::new().level(Level::Debug).dry_run_prefix(true);
+    let deploy = logger.scoped("deploy");
+    deploy.info("pushing image");
+    let build = deploy.scoped("build");
+    build.debug("compiling");
+    logger.info("done");
+}
+
+/// A cloneable in-memory writer for checking what a logger wrote
+#[derive(Clone, Default)]
+struct Buffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Buffer {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[test]
+fn test_logger_writer_shared_with_scopes() {
+    let buffer = Buffer::default();
+    let logger = Logger::new().level(Level::Info).writer(buffer.clone());
+    logger.info("starting");
+    logger.scoped("deploy").warn("slow");
+    logger.debug("hidden");
+
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("starting"));
+    assert!(lines[1].ends_with("deploy ▸ slow"));
+}
+
+#[test]
+fn test_global_logger_from_threads() {
+    let buffer = Buffer::default();
+    assert!(zfish::log::init(
+        Logger::new().level(Level::Debug).writer(buffer.clone())
+    ));
+    assert!(!zfish::log::init(Logger::new()));
+
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                for j in 0..25 {
+                    zfish::log::info(&format!("thread {} line {}", i, j));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    zfish::log::logger().debug("done");
+
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 101);
+    assert!(lines.iter().take(100).all(|line| line.contains("thread ")));
+    assert!(lines[100].ends_with("done"));
+}
+
+#[test]
+fn test_logger_highlight_rules_keep_text() {
+    use zfish::style::Color;
+
+    let buffer = Buffer::default();
+    let logger = Logger::new()
+        .writer(buffer.clone())
+        .highlight_rule("*.rs", Color::Cyan)
+        .highlight_rule("*ms", Color::Yellow);
+    logger.info("checked src/lib.rs in 12ms");
+
+    // Colours may be disabled here; either way the words come through intact
+    let line = &buffer.lines()[0];
+    for word in ["checked", "src/lib.rs", "12ms"] {
+        assert!(line.contains(word), "{:?}", line);
+    }
+}
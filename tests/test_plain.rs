@@ -0,0 +1,123 @@
+//! Tests for plain mode (ZFISH_PLAIN=1)
+//!
+//! The environment variable is read by the code under test, so the check
+//! re-runs this test binary with it set and inspects everything written.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use zfish::progress::{DrawTarget, ProgressBar};
+use zfish::prompt::Prompt;
+use zfish::style::Color;
+use zfish::table::{Table, TextBox};
+use zfish::tasks::TaskRunner;
+use zfish::term::Frame;
+
+/// Set for the child process, which does the drawing
+const CHILD_VAR: &str = "ZFISH_PLAIN_TEST_CHILD";
+
+/// Runs `draw_everything` in a child with a color-capable `TERM`
+fn run_child(plain: bool) -> Vec<u8> {
+    let mut command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args([
+            "--exact",
+            "draw_everything",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD_VAR, "1")
+        .env("TERM", "xterm-256color")
+        .env("COLORTERM", "truecolor")
+        .env_remove("NO_COLOR")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if plain {
+        command.env("ZFISH_PLAIN", "1");
+    } else {
+        command.env_remove("ZFISH_PLAIN");
+    }
+
+    let mut child = command.spawn().unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"my-app\n2\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "child failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut bytes = output.stdout;
+    bytes.extend_from_slice(&output.stderr);
+    bytes
+}
+
+#[test]
+fn draw_everything() {
+    if std::env::var_os(CHILD_VAR).is_none() {
+        return;
+    }
+
+    println!("{}", Color::Red.paint("colored"));
+
+    let mut table = Table::new(vec!["Name", "Size"]);
+    table.add_row(vec!["a.txt", "12 B"]);
+    table.print();
+    TextBox::new("boxed").print();
+
+    zfish::log::Logger::new()
+        .scoped("deploy")
+        .warn("scoped warning");
+
+    let mut bar = ProgressBar::new(4).draw_target(DrawTarget::Stdout);
+    for _ in 0..4 {
+        bar.inc(1);
+    }
+    bar.finish("copied");
+
+    let mut frame = Frame::new();
+    frame.line("frame one");
+    frame.flush().unwrap();
+    frame.line("frame two");
+    frame.flush().unwrap();
+    frame.finish().unwrap();
+
+    TaskRunner::new()
+        .draw_target(DrawTarget::Stdout)
+        .task("Build", |_| Ok::<(), String>(()))
+        .run()
+        .unwrap();
+
+    let name = Prompt::input("Project name").unwrap();
+    let color = Prompt::select("Color", &["red", "green"]).unwrap();
+    println!("answers: {} {}", name, color);
+}
+
+#[test]
+fn test_plain_mode_writes_no_escapes() {
+    let output = run_child(true);
+    let text = String::from_utf8_lossy(&output);
+
+    assert!(!output.contains(&0x1b), "escape sequence in:\n{}", text);
+    assert!(output.is_ascii(), "non-ASCII output:\n{}", text);
+    assert!(text.contains("+-------+------+"));
+    assert!(text.contains("deploy > "));
+    assert!(text.contains("copied"));
+    // Only the last frame is printed
+    assert!(text.contains("frame two\n"));
+    assert!(!text.contains("frame one"));
+    assert!(text.contains("answers: my-app 1"));
+}
+
+#[test]
+fn test_escapes_are_written_without_plain_mode() {
+    // The child really would draw escapes and box characters otherwise
+    let output = run_child(false);
+    assert!(output.contains(&0x1b));
+    assert!(!output.is_ascii());
+}
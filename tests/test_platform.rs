@@ -0,0 +1,29 @@
+//! Tests for platform capability reporting
+
+use zfish::platform::Platform;
+
+#[test]
+fn test_capabilities_report() {
+    let caps = Platform::capabilities();
+
+    #[cfg(all(
+        feature = "raw",
+        any(target_os = "linux", target_os = "macos", windows)
+    ))]
+    {
+        assert!(caps.hidden_password_input);
+        assert!(caps.raw_mode);
+        assert!(caps.size_query);
+    }
+    assert_eq!(caps.interactive(), caps.raw_mode && caps.stdin_is_terminal);
+
+    let report = caps.to_string();
+    assert!(report.starts_with(&format!("platform: {}\n", Platform::name())));
+    assert!(report.contains("raw mode: "));
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+    assert!(report.contains(&format!(
+        "legacy console: {}\n",
+        yes_no(caps.legacy_console)
+    )));
+    assert!(report.ends_with(&format!("plain mode: {}", yes_no(caps.plain))));
+}
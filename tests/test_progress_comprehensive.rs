@@ -155,3 +155,105 @@ fn test_progress_bar_width_chain() {
         .width(70);
     assert_eq!(pb.width, 70);
 }
+
+#[test]
+fn test_progress_bar_finish_and_clear() {
+    let mut pb = ProgressBar::new(10);
+    pb.set(4);
+    pb.finish_and_clear();
+    assert_eq!(pb.position(), 10);
+}
+
+#[test]
+fn test_progress_bar_finish_at_current_pos() {
+    let mut pb = ProgressBar::new(10);
+    pb.inc(3);
+    pb.finish_at_current_pos();
+    assert_eq!(pb.position(), 3);
+}
+
+#[test]
+fn test_progress_bar_abandon_with_message() {
+    let mut pb = ProgressBar::new(10);
+    pb.set(7);
+    pb.abandon_with_message("connection lost");
+    assert_eq!(pb.position(), 7);
+}
+
+#[test]
+fn test_progress_bar_for_duration_completes() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    let done = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&done);
+    let handle = ProgressBar::for_duration(Duration::from_millis(50))
+        .on_complete(move || flag.store(true, Ordering::SeqCst))
+        .start();
+    assert!(handle.wait());
+    assert!(done.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_progress_bar_for_duration_cancel() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    let done = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&done);
+    let started = Instant::now();
+    let handle = ProgressBar::for_duration(Duration::from_secs(30))
+        .on_complete(move || flag.store(true, Ordering::SeqCst))
+        .start();
+    assert!(!handle.is_finished());
+    assert!(!handle.cancel());
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(!done.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_progress_bar_title_progress() {
+    let mut pb = ProgressBar::new(10);
+    pb.set_title_progress(true);
+    pb.set(5);
+    pb.set(6);
+    pb.set_title_progress(false);
+    pb.set_title_progress(true);
+    pb.finish("done");
+    assert_eq!(pb.position(), 10);
+
+    // Dropping a bar mid-way must not panic while clearing the indicator
+    let mut pb = ProgressBar::new(3);
+    pb.set_title_progress(true);
+    pb.inc(1);
+    drop(pb);
+}
+
+#[test]
+fn test_progress_bar_notify_on_finish() {
+    // stdout is captured by the test harness, so no sequence is written
+    let mut pb = ProgressBar::new(4).notify_on_finish("Build finished");
+    pb.inc(4);
+    pb.finish("4 crates compiled");
+    pb.finish_and_clear();
+    assert_eq!(pb.position(), 4);
+    assert!(zfish::term::notify("Build finished", "").is_ok());
+}
+
+#[test]
+fn test_progress_bar_draw_target() {
+    use zfish::DrawTarget;
+
+    assert_eq!(DrawTarget::default(), DrawTarget::Stderr);
+    let mut pb = ProgressBar::new(2).draw_target(DrawTarget::Stdout);
+    pb.inc(1);
+    pb.finish("done");
+    assert_eq!(pb.position(), 2);
+
+    let handle = ProgressBar::for_duration(std::time::Duration::from_millis(10))
+        .draw_target(DrawTarget::Stderr)
+        .start();
+    assert!(handle.wait());
+}
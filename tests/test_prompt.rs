@@ -51,3 +51,28 @@ fn test_password_input() {
     // Don't assert specific values since this is an interactive test
     assert!(!result.is_empty());
 }
+
+#[test]
+#[ignore]
+fn test_select_prompt() {
+    println!("Please pick 'green' (by number or name) when prompted:");
+    let result = Prompt::select("Favourite colour?", &["red", "green", "blue"]).unwrap();
+    println!("You picked index {}", result);
+
+    assert_eq!(result, 1);
+}
+
+#[test]
+#[ignore]
+fn test_optional_prompts() {
+    println!("Please press Esc (or enter nothing) at each prompt:");
+    let name = Prompt::input_opt("Nickname (optional):").unwrap();
+    println!("You entered: {:?}", name);
+    assert_eq!(name, None);
+
+    let colour = Prompt::select_opt("Favourite colour?", &["red", "green", "blue"]).unwrap();
+    println!("You picked: {:?}", colour);
+
+    let confirmed = Prompt::confirm_opt("Subscribe?", false).unwrap();
+    println!("You answered: {:?}", confirmed);
+}
@@ -0,0 +1,248 @@
+//! Tests for shell completion script generation
+
+use std::process::Command as Process;
+use zfish::command::{App, Arg, Command, ValueHint};
+use zfish::shell::Shell;
+
+fn app() -> App {
+    App::new("myapp")
+        .version("1.0.0")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .about("When to use color")
+                .possible_values(&["auto", "always", "never"])
+                .global(true),
+        )
+        .subcommand(
+            Command::new("build")
+                .about("Compile the project")
+                .alias("b")
+                .arg(Arg::new("release").long("release").takes_value(false))
+                .arg(
+                    Arg::new("out-dir")
+                        .short('o')
+                        .long("out-dir")
+                        .value_hint(ValueHint::DirPath),
+                )
+                .arg(
+                    Arg::new("profile")
+                        .index(0)
+                        .possible_values(&["dev", "bench"]),
+                )
+                .arg(
+                    Arg::new("target")
+                        .long("target")
+                        .possible_values(&["my host", "it's", "$HOME"]),
+                ),
+        )
+        .subcommand(Command::new("remote").subcommand(Command::new("add")))
+}
+
+/// What bash offers for `line` (the cursor at the end), or `None` without bash
+fn complete(line: &str) -> Option<Vec<String>> {
+    let script = app().generate_completions(Shell::Bash);
+    let mut words: Vec<&str> = line.split(' ').collect();
+    if line.ends_with(' ') {
+        words.pop();
+        words.push("");
+    }
+    let quoted: Vec<String> = words.iter().map(|w| format!("'{}'", w)).collect();
+    let driver = format!(
+        "{}\nCOMP_WORDS=({})\nCOMP_CWORD={}\n_myapp\nprintf '%s\\n' \"${{COMPREPLY[@]}}\"\n",
+        script,
+        quoted.join(" "),
+        words.len() - 1
+    );
+    let output = Process::new("bash")
+        .args(["--norc", "--noprofile", "-c", &driver])
+        .output()
+        .ok()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut offered: Vec<String> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect();
+    offered.sort();
+    Some(offered)
+}
+
+// ============================================================================
+// SCRIPT TESTS
+// ============================================================================
+
+#[test]
+fn test_bash_script_covers_the_tree() {
+    let script = app().generate_completions(Shell::Bash);
+    assert!(script.starts_with("_myapp() {\n"));
+    assert!(script.ends_with("complete -F _myapp -o bashdefault -o default myapp\n"));
+    assert!(script.contains("\"myapp,build\"|\"myapp,b\") cmd=\"myapp__build\" ;;"));
+    assert!(script.contains("\"myapp__remote,add\") cmd=\"myapp__remote__add\" ;;"));
+    assert!(script.contains("_myapp_words 'auto' 'always' 'never'"));
+    assert!(script.contains("compgen -d"));
+
+    // App and Command produce the same script
+    let command = Command::new("tool").arg(Arg::new("x").long("x"));
+    assert_eq!(
+        App::new("tool")
+            .arg(Arg::new("x").long("x"))
+            .generate_completions(Shell::Bash),
+        command.generate_completions(Shell::Bash)
+    );
+}
+
+// ============================================================================
+// BASH BEHAVIOUR TESTS
+// ============================================================================
+
+#[test]
+fn test_bash_completes_subcommands_and_flags() {
+    let Some(top) = complete("myapp ") else {
+        return; // bash not installed
+    };
+    assert_eq!(top, ["build", "remote"]);
+    assert_eq!(
+        complete("myapp --").unwrap(),
+        ["--color", "--help", "--verbose", "--version"]
+    );
+    assert_eq!(complete("myapp remote ").unwrap(), ["add"]);
+}
+
+#[test]
+fn test_bash_completes_values() {
+    if complete("myapp ").is_none() {
+        return;
+    }
+    assert_eq!(complete("myapp --color a").unwrap(), ["always", "auto"]);
+    // Globals are inherited, aliases are followed, positional values offered
+    assert_eq!(complete("myapp b --color n").unwrap(), ["never"]);
+    assert_eq!(complete("myapp build ").unwrap(), ["bench", "dev"]);
+    assert_eq!(
+        complete("myapp build --").unwrap(),
+        ["--color", "--help", "--out-dir", "--release", "--target"]
+    );
+    // An option's value is not mistaken for a subcommand
+    assert_eq!(
+        complete("myapp --color build ").unwrap(),
+        ["build", "remote"]
+    );
+}
+
+#[test]
+fn test_bash_completes_values_with_spaces_and_quotes() {
+    if complete("myapp ").is_none() {
+        return;
+    }
+    // Each value is one word, escaped for the command line
+    assert_eq!(
+        complete("myapp build --target ").unwrap(),
+        ["\\$HOME", "it\\'s", "my\\ host"]
+    );
+    assert_eq!(complete("myapp build --target m").unwrap(), ["my\\ host"]);
+}
+
+// ============================================================================
+// OTHER SHELLS
+// ============================================================================
+
+#[test]
+fn test_zsh_script() {
+    let script = app().generate_completions(Shell::Zsh);
+    assert!(script.starts_with("#compdef myapp\n"));
+    assert!(script.contains("'--color=[When to use color]:color:(auto always never)' \\\n"));
+    assert!(script.contains("build|b) _myapp__build ;;"));
+    assert!(script.contains("'build:Compile the project'"));
+    assert!(script.contains("'(-o --out-dir)--out-dir=:out-dir:_files -/' \\\n"));
+    assert!(script.contains("':profile:(dev bench)' \\\n"));
+    assert!(script.contains("_myapp__remote__add() {"));
+    assert!(script.ends_with("    compdef _myapp myapp\nfi\n"));
+}
+
+#[test]
+fn test_fish_script() {
+    let script = app().generate_completions(Shell::Fish);
+    assert!(script.starts_with("function __fish_myapp_path\n"));
+    assert!(script.contains("            case 'myapp,build' 'myapp,b'\n"));
+    assert!(script.contains(
+        "complete -c myapp -n 'test (__fish_myapp_path) = myapp' -l 'color' -r -f -a 'auto always never' -d 'When to use color'\n"
+    ));
+    assert!(script.contains(
+        "complete -c myapp -n 'test (__fish_myapp_path) = myapp' -f -a 'build' -d 'Compile the project'\n"
+    ));
+    assert!(script.contains("= myapp__build' -f -a 'dev bench'\n"));
+}
+
+#[test]
+fn test_powershell_script() {
+    let script = app().generate_completions(Shell::PowerShell);
+    assert!(script.contains("Register-ArgumentCompleter -Native -CommandName 'myapp'"));
+    assert!(script.contains("'myapp,b' { $path = 'myapp;build'; break }"));
+    assert!(script.contains("'myapp,--color' { 'auto', 'always', 'never'; break }"));
+    assert!(script.contains("'myapp;build,--out-dir' { return }"));
+    assert!(script.contains(
+        "[CompletionResult]::new('build', 'build', [CompletionResultType]::ParameterValue, 'Compile the project')"
+    ));
+}
+
+// ============================================================================
+// VALUE HINTS
+// ============================================================================
+
+fn hints_app() -> App {
+    App::new("remote")
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .value_hint(ValueHint::Hostname),
+        )
+        .arg(
+            Arg::new("exec")
+                .long("exec")
+                .value_hint(ValueHint::CommandName),
+        )
+}
+
+#[test]
+fn test_hostname_and_command_hints() {
+    let bash = hints_app().generate_completions(Shell::Bash);
+    assert!(bash.contains("compgen -A hostname"));
+    assert!(bash.contains("compgen -c"));
+
+    let zsh = hints_app().generate_completions(Shell::Zsh);
+    assert!(zsh.contains("'--host=:host:_hosts' \\\n"));
+    assert!(zsh.contains("'--exec=:exec:_command_names -e' \\\n"));
+
+    let fish = hints_app().generate_completions(Shell::Fish);
+    assert!(fish.contains("-l 'host' -r -f -a '(__fish_print_hostnames)'\n"));
+    assert!(fish.contains("-l 'exec' -r -f -a '(__fish_complete_command)'\n"));
+
+    let powershell = hints_app().generate_completions(Shell::PowerShell);
+    assert!(powershell.contains("'remote,--host' { return }"));
+    assert!(powershell.contains(
+        "'remote,--exec' { (Get-Command -Name \"$wordToComplete*\").Name | Sort-Object -Unique; break }"
+    ));
+}
+
+#[test]
+fn test_print_completions_writes_the_script() {
+    for shell in Shell::ALL {
+        let mut out = Vec::new();
+        app().print_completions(*shell, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            app().generate_completions(*shell)
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Tests for App::run_from and the end-of-run summary report
+
+use std::sync::{Arc, Mutex};
+use zfish::command::{App, Arg};
+use zfish::summary::Report;
+
+static LAST_REPORT: Mutex<Option<Report>> = Mutex::new(None);
+
+fn record(report: &Report) {
+    *LAST_REPORT.lock().unwrap() = Some(report.clone());
+}
+
+fn app() -> App {
+    App::new("sync")
+        .arg(Arg::new("fail").long("fail").takes_value(false))
+        .error_exit_code(3)
+        .summary(record)
+}
+
+fn run(args: &[&str]) -> i32 {
+    app().run_from(args.iter().copied(), |matches| {
+        zfish::log::warn("skipped a locked file");
+        if matches.is_present("fail") {
+            Err("remote hung up")
+        } else {
+            Ok(())
+        }
+    })
+}
+
+// One test, because the log counters are process-wide
+#[test]
+fn test_run_from_reports_outcome() {
+    assert_eq!(run(&["sync"]), 0);
+    let report = LAST_REPORT.lock().unwrap().take().unwrap();
+    assert!(!report.failed());
+    assert!(report.warnings >= 1);
+    assert!(report.headline().starts_with("Finished with "));
+    assert!(report.render().contains(&report.headline()));
+
+    assert_eq!(run(&["sync", "--fail"]), 3);
+    let report = LAST_REPORT.lock().unwrap().take().unwrap();
+    assert_eq!(report.exit_code, 3);
+    assert!(report.headline().starts_with("Failed with 1 error and "));
+    assert_eq!(report.error_messages, ["remote hung up"]);
+    assert!(report.render().contains("error: remote hung up"));
+
+    // Parse errors and help exit without a summary
+    assert_eq!(run(&["sync", "--bogus"]), 3);
+    assert_eq!(run(&["sync", "--help"]), 0);
+    assert!(LAST_REPORT.lock().unwrap().is_none());
+}
+
+#[test]
+fn test_summary_hook_captures_state() {
+    let calls = Arc::new(Mutex::new(0));
+    let seen = Arc::clone(&calls);
+    // Only successful runs, so the process-wide log counters are untouched
+    let app = App::new("sync").summary(move |_: &Report| *seen.lock().unwrap() += 1);
+
+    assert_eq!(app.clone().run_from(["sync"], |_| Ok::<(), &str>(())), 0);
+    assert_eq!(app.run_from(["sync"], |_| Ok::<(), &str>(())), 0);
+    assert_eq!(*calls.lock().unwrap(), 2);
+}
@@ -1,6 +1,6 @@
 //! Comprehensive tests for the table module
 
-use zfish::table::{Alignment, BoxStyle, Table};
+use zfish::table::{Alignment, BoxStyle, OutputFormat, Table, TableError};
 
 #[test]
 fn test_table_creation() {
@@ -257,3 +257,458 @@ fn test_financial_report_table() {
     table.add_row(vec!["TOTAL", "$37,000", "$24,000", "$13,000"]);
     table.print();
 }
+
+fn export_table() -> Table {
+    let mut table = Table::new(vec!["Name", "Note"]);
+    table.add_row(vec!["Alice", "says \"hi\""]);
+    table.add_row(vec!["Bob", "a, b"]);
+    table
+}
+
+#[test]
+fn test_render_matches_layout() {
+    let mut table = export_table();
+    table.set_box_style(BoxStyle::Ascii).set_indent(2);
+    let rendered = table.render();
+
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], "  +-------+-----------+");
+    assert_eq!(lines[1], "  | Name  | Note      |");
+    assert_eq!(lines[3], "  | Alice | says \"hi\" |");
+}
+
+#[test]
+fn test_footer_separator_single_row() {
+    let mut table = Table::new(vec!["Total"]);
+    table.set_footer_separator(true);
+    table.add_row(vec!["42"]);
+    assert_eq!(table.render().lines().count(), 5);
+}
+
+#[test]
+fn test_to_json_escapes() {
+    assert_eq!(
+        export_table().to_json(),
+        "[\n  {\"Name\": \"Alice\", \"Note\": \"says \\\"hi\\\"\"},\n  {\"Name\": \"Bob\", \"Note\": \"a, b\"}\n]\n"
+    );
+    assert_eq!(Table::new(vec!["Empty"]).to_json(), "[]\n");
+}
+
+#[test]
+fn test_to_csv_quotes() {
+    assert_eq!(
+        export_table().to_csv(),
+        "Name,Note\nAlice,\"says \"\"hi\"\"\"\nBob,\"a, b\"\n"
+    );
+}
+
+#[test]
+fn test_to_yaml() {
+    assert_eq!(
+        export_table().to_yaml(),
+        "- Name: \"Alice\"\n  Note: \"says \\\"hi\\\"\"\n- Name: \"Bob\"\n  Note: \"a, b\"\n"
+    );
+}
+
+#[test]
+fn test_to_markdown() {
+    assert_eq!(
+        export_table().to_markdown(),
+        "| Name  | Note      |\n\
+         | ----- | --------- |\n\
+         | Alice | says \"hi\" |\n\
+         | Bob   | a, b      |\n"
+    );
+}
+
+#[test]
+fn test_to_markdown_escapes_and_aligns() {
+    let mut table = Table::new(vec!["Op", "Count", "Mid"]);
+    table.add_row(vec!["\x1b[1ma|b\x1b[0m", "12", "two\nlines"]);
+    table.add_row(vec!["x", "3", ""]);
+    table.set_column_alignment(1, Alignment::Right);
+    table.set_column_alignment(2, Alignment::Center);
+
+    assert_eq!(
+        table.to_markdown(),
+        "| Op   | Count | Mid          |\n\
+         | ---- | ----: | :----------: |\n\
+         | a\\|b | 12    | two<br>lines |\n\
+         | x    | 3     |              |\n"
+    );
+    assert_eq!(Table::new(vec!["A"]).to_markdown(), "| A   |\n| --- |\n");
+}
+
+#[test]
+fn test_output_format_parse_and_render_as() {
+    assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+    assert_eq!("yml".parse::<OutputFormat>(), Ok(OutputFormat::Yaml));
+    assert!("xml".parse::<OutputFormat>().is_err());
+    assert_eq!(OutputFormat::Csv.to_string(), "csv");
+
+    let table = export_table();
+    assert_eq!(table.render_as(OutputFormat::Csv), table.to_csv());
+    assert_eq!(table.render_as(OutputFormat::Table), table.render());
+}
+
+#[test]
+fn test_table_aligns_pre_styled_cells() {
+    let mut table = Table::new(vec!["Status", "Name"]);
+    table.add_row(vec!["\x1b[32mok\x1b[0m", "alpha"]);
+    table.add_row(vec!["failed", "beta"]);
+    let rendered = table.render();
+    let widths: Vec<usize> = rendered
+        .lines()
+        .map(zfish::unicode::display_width)
+        .collect();
+    assert!(widths.windows(2).all(|w| w[0] == w[1]), "{:?}", widths);
+
+    // Machine-readable formats carry no escape sequences
+    assert!(table.to_csv().starts_with("Status,Name\nok,alpha\n"));
+    assert!(!table.to_json().contains('\x1b'));
+}
+
+// ============================================================================
+// INGESTION TESTS
+// ============================================================================
+
+#[test]
+fn test_from_csv_str_with_headers() {
+    let data = "name,notes\r\nalpha,\"multi\nline, \"\"quoted\"\"\"\r\n\r\nbeta,\n";
+    let table = Table::from_csv_str(data, true).unwrap();
+    assert_eq!(
+        table.to_json(),
+        "[\n  {\"name\": \"alpha\", \"notes\": \"multi\\nline, \\\"quoted\\\"\"},\n  {\"name\": \"beta\", \"notes\": \"\"}\n]\n"
+    );
+}
+
+#[test]
+fn test_from_csv_str_without_headers() {
+    let table = Table::from_csv_str("1,2\n3,4", false).unwrap();
+    assert_eq!(table.to_csv(), "Column 1,Column 2\n1,2\n3,4\n");
+
+    let empty = Table::from_csv_str("", true).unwrap();
+    assert_eq!(empty.to_csv(), "\n");
+}
+
+#[test]
+fn test_from_csv_str_errors() {
+    let err = Table::from_csv_str("a,b\n1,2\n3\n", true).unwrap_err();
+    assert_eq!(err.to_string(), "line 3: expected 2 fields, found 1");
+
+    let err = Table::from_csv_str("a,b\n\"open,2\n", true).unwrap_err();
+    assert_eq!(
+        err,
+        zfish::table::TableError::Parse {
+            line: 2,
+            message: "unterminated quoted field".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_from_json_array_str() {
+    let data = r#"[
+        {"name": "zfish", "stars": 42, "tags": ["cli", "tui"], "fork": false},
+        {"name": "café \"x\"", "license": null, "stars": -1.5e3}
+    ]"#;
+    let table = Table::from_json_array_str(data).unwrap();
+    assert_eq!(
+        table.to_csv(),
+        "name,stars,tags,fork,license\n\
+         zfish,42,\"[\"\"cli\"\",\"\"tui\"\"]\",false,\n\
+         \"café \"\"x\"\"\",-1.5e3,,,\n"
+    );
+
+    let empty = Table::from_json_array_str(" [ ] ").unwrap();
+    assert_eq!(empty.to_json(), "[]\n");
+}
+
+#[test]
+fn test_from_json_array_str_errors() {
+    let err = Table::from_json_array_str("{\"a\": 1}").unwrap_err();
+    assert_eq!(err.to_string(), "line 1: expected a JSON array of objects");
+
+    let err = Table::from_json_array_str("[\n  {\"a\": 1},\n  2\n]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: expected every array element to be an object"
+    );
+
+    let err = Table::from_json_array_str("[{\"a\": tru}]").unwrap_err();
+    assert_eq!(err.to_string(), "line 1: expected a JSON value");
+
+    assert!(Table::from_json_array_str("[{\"a\": 1}] x").is_err());
+    assert!(Table::from_json_array_str("[{\"a\": \"open}]").is_err());
+}
+
+// ============================================================================
+// TEXT BOX TESTS
+// ============================================================================
+
+#[test]
+fn test_wrap_by_display_width() {
+    use zfish::unicode::wrap;
+
+    assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+    assert_eq!(wrap("one\n\ntwo  three", 20), ["one", "", "two three"]);
+    assert_eq!(wrap("abcdefgh", 3), ["abc", "def", "gh"]);
+    assert_eq!(wrap("日本語テキスト", 5), ["日本", "語テ", "キス", "ト"]);
+    assert_eq!(wrap("", 5), [""]);
+}
+
+#[test]
+fn test_text_box_multiline_alignment_and_padding() {
+    use zfish::table::TextBox;
+
+    let rendered = TextBox::new("left\nright side")
+        .box_style(BoxStyle::Ascii)
+        .alignment(Alignment::Right)
+        .padding(2)
+        .max_width(80)
+        .render();
+    assert_eq!(
+        rendered,
+        "+--------------+\n|        left  |\n|  right side  |\n+--------------+\n"
+    );
+}
+
+#[test]
+fn test_text_box_fits_max_width() {
+    use zfish::table::TextBox;
+
+    let text = "a long message that would overflow a narrow terminal window";
+    let rendered = TextBox::new(text).max_width(20).render();
+    for line in rendered.lines() {
+        assert!(zfish::unicode::display_width(line) <= 20, "{:?}", line);
+    }
+    assert!(rendered.lines().count() > 3);
+}
+
+#[test]
+fn test_join_tables_side_by_side() {
+    use zfish::table::{join_horizontal, join_vertical};
+
+    let mut left = Table::new(vec!["Service"]);
+    left.add_row(vec!["api"]);
+    left.add_row(vec!["worker"]);
+    left.set_box_style(BoxStyle::Ascii).set_indent(0);
+    let mut right = Table::new(vec!["Up"]);
+    right.set_box_style(BoxStyle::Ascii).set_indent(0);
+
+    let dashboard = join_horizontal(&left.render(), &right.render(), 2);
+    let lines: Vec<&str> = dashboard.lines().collect();
+    assert_eq!(lines.len(), left.render().lines().count());
+    assert_eq!(lines[0], "+---------+  +----+");
+    assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+
+    let stacked = join_vertical("\x1b[1mtitle\x1b[0m\n", &dashboard, 1);
+    let lines: Vec<&str> = stacked.lines().collect();
+    assert_eq!(lines[1].trim(), "");
+    assert_eq!(
+        zfish::unicode::display_width(lines[0]),
+        zfish::unicode::display_width(lines[2])
+    );
+}
+
+// ============================================================================
+// LIVE TABLE TESTS
+// ============================================================================
+
+#[test]
+fn test_live_table_updates() {
+    let mut table = Table::new(vec!["Job", "State"]);
+    table.set_box_style(BoxStyle::Ascii).set_indent(0);
+    table.add_row(vec!["build", "waiting"]);
+
+    let mut live = table.live().unwrap();
+    live.update_cell(0, 1, "ok").unwrap();
+    live.push_row(vec!["deploy", "queued"]).unwrap();
+    // Out of range positions are ignored
+    live.update_cell(5, 0, "nope").unwrap();
+    live.update_cell(0, 9, "nope").unwrap();
+
+    let table = live.finish().unwrap();
+    assert_eq!(
+        table.render(),
+        "+--------+--------+\n\
+         | Job    | State  |\n\
+         +--------+--------+\n\
+         | build  | ok     |\n\
+         | deploy | queued |\n\
+         +--------+--------+\n"
+    );
+}
+
+#[test]
+fn test_live_table_columns_shrink() {
+    let mut table = Table::new(vec!["Id"]);
+    table.set_box_style(BoxStyle::Ascii).set_indent(0);
+    table.add_row(vec!["a-very-long-id"]);
+
+    let mut live = table.live().unwrap();
+    live.update_cell(0, 0, "7").unwrap();
+    assert_eq!(
+        live.table().render(),
+        "+----+\n| Id |\n+----+\n| 7  |\n+----+\n"
+    );
+}
+
+// ============================================================================
+// AUTO ALIGNMENT TESTS
+// ============================================================================
+
+#[test]
+fn test_auto_align_numeric_columns() {
+    let mut table = Table::new(vec!["Name", "Total", "Code"]);
+    table
+        .set_box_style(BoxStyle::Ascii)
+        .set_indent(0)
+        .set_auto_align(true);
+    table.add_row(vec!["Alice", "1,200", "A1"]);
+    table.add_row(vec!["Bob", "-35", "7"]);
+    table.add_row(vec!["Carol", "", "B2"]);
+
+    assert_eq!(
+        table.render(),
+        "+-------+-------+------+\n\
+         | Name  | Total | Code |\n\
+         +-------+-------+------+\n\
+         | Alice | 1,200 | A1   |\n\
+         | Bob   |   -35 | 7    |\n\
+         | Carol |       | B2   |\n\
+         +-------+-------+------+\n"
+    );
+
+    // Explicit alignments win, and turning it off restores left alignment
+    table.set_column_alignment(1, Alignment::Center);
+    assert!(table.render().contains("| Bob   |  -35  |"));
+    let mut plain = Table::new(vec!["N"]);
+    plain.set_box_style(BoxStyle::Ascii).set_indent(0);
+    plain.add_row(vec!["1"]);
+    plain.add_row(vec!["10"]);
+    assert!(plain.render().contains("| 1  |"));
+}
+
+// ============================================================================
+// TABLE BUILDER TESTS
+// ============================================================================
+
+#[test]
+fn test_table_builder_matches_add_row() {
+    let built = Table::builder(["Name", "Size"])
+        .add_row(["Cargo.toml", "1.2K"])
+        .rows([["src", "-"], ["README.md", "14K"]])
+        .build();
+
+    let mut manual = Table::new(vec!["Name", "Size"]);
+    manual.add_row(vec!["Cargo.toml", "1.2K"]);
+    manual.add_row(vec!["src", "-"]);
+    manual.add_row(vec!["README.md", "14K"]);
+    assert_eq!(built.render(), manual.render());
+
+    let mut table: Table = Table::builder::<1>(["Id"]).add_row(["7"]).into();
+    table.set_box_style(BoxStyle::Ascii).set_indent(0);
+    assert_eq!(table.render(), "+----+\n| Id |\n+----+\n| 7  |\n+----+\n");
+}
+
+#[test]
+fn test_try_add_row_rejects_wrong_cell_count() {
+    let mut table = Table::new(vec!["Name", "Size"]);
+    assert_eq!(table.try_add_row(vec!["Cargo.toml", "1.2K"]), Ok(()));
+
+    let err = table.try_add_row(vec!["src"]).unwrap_err();
+    assert_eq!(
+        err,
+        TableError::ColumnCountMismatch {
+            expected: 2,
+            got: 1
+        }
+    );
+    assert_eq!(err.to_string(), "expected 2 cells in row, got 1");
+    assert!(table.try_add_row(vec!["a", "b", "c"]).is_err());
+
+    // Rejected rows are not added
+    assert_eq!(table.to_csv(), "Name,Size\nCargo.toml,1.2K\n");
+}
+
+// ============================================================================
+// LAYOUT TESTS
+// ============================================================================
+
+#[test]
+fn test_layout_matches_render() {
+    let mut table = Table::new(vec!["Name", "Size"]);
+    table.set_auto_align(true).set_footer_separator(true);
+    table.add_row(vec!["Cargo.toml", "1.2K"]);
+    table.add_row(vec!["日本語", "14"]);
+    table.add_row(vec!["Total", "15"]);
+
+    assert_eq!(table.column_widths(), [10, 4]);
+    assert_eq!(table.row_count(), 3);
+
+    let layout = table.layout();
+    let rendered = table.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), layout.line_count);
+    assert!(
+        lines
+            .iter()
+            .all(|line| zfish::unicode::display_width(line) == layout.width)
+    );
+    assert_eq!(layout.alignments, [Alignment::Left, Alignment::Right]);
+
+    // Each offset is where that column's header text starts
+    let header = lines[1];
+    for (offset, name) in layout.column_offsets.iter().zip(["Name", "Size"]) {
+        let from_offset: String = header.chars().skip(*offset).collect();
+        assert!(from_offset.starts_with(name), "{:?}", from_offset);
+    }
+}
+
+// ============================================================================
+// EMOJI WIDTH TESTS
+// ============================================================================
+
+#[test]
+fn test_text_presentation_emoji_width() {
+    use zfish::unicode::display_width;
+
+    // Text-default emoji are one cell, two with VS-16
+    for plain in ["\u{2714}", "\u{00A9}", "\u{2764}", "\u{1F5A5}", "\u{26F0}"] {
+        assert_eq!(display_width(plain), 1, "{:?}", plain);
+        assert_eq!(
+            display_width(&format!("{}\u{FE0F}", plain)),
+            2,
+            "{:?}",
+            plain
+        );
+    }
+    // ...while emoji-presentation ones are always two
+    assert_eq!(display_width("\u{2705}"), 2);
+    // A skin tone also switches to emoji presentation
+    assert_eq!(display_width("\u{261D}\u{1F3FB}"), 2);
+    // Keycaps are unaffected
+    assert_eq!(display_width("1\u{FE0F}\u{20E3}"), 2);
+    assert_eq!(display_width("#1"), 2);
+}
+
+#[test]
+fn test_emoji_sequences_width() {
+    use zfish::unicode::display_width;
+
+    // Rainbow flag and heart on fire start with text-default emoji
+    assert_eq!(display_width("\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}"), 2);
+    assert_eq!(display_width("\u{2764}\u{FE0F}\u{200D}\u{1F525}"), 2);
+    // Subdivision flags: black flag + tag characters + cancel tag
+    let england = "\u{1F3F4}\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}";
+    assert_eq!(display_width(england), 2);
+    assert_eq!(display_width(&format!("|{}|", england)), 4);
+
+    let mut table = Table::new(vec!["Flag", "Team"]);
+    table.add_row(vec![england, "England"]);
+    table.add_row(vec!["\u{2714}", "ok"]);
+    let widths: Vec<usize> = table.render().lines().map(display_width).collect();
+    assert!(widths.windows(2).all(|w| w[0] == w[1]), "{:?}", widths);
+}
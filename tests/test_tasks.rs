@@ -0,0 +1,130 @@
+//! Tests for TaskRunner checklists
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use zfish::tasks::{TaskRunner, TaskStatus};
+
+// ============================================================================
+// SEQUENTIAL TESTS
+// ============================================================================
+
+#[test]
+fn test_tasks_run_in_order() {
+    let order = std::sync::Mutex::new(Vec::new());
+    let outcomes = TaskRunner::new()
+        .task("Fetch", |ctx| {
+            order.lock().unwrap().push(ctx.name().to_string());
+            ctx.set_message("downloading");
+            Ok::<(), String>(())
+        })
+        .task("Build", |ctx| {
+            order.lock().unwrap().push(ctx.name().to_string());
+            Ok::<(), String>(())
+        })
+        .run()
+        .unwrap();
+
+    assert_eq!(*order.lock().unwrap(), ["Fetch", "Build"]);
+    let names: Vec<&str> = outcomes.iter().map(|o| o.name.as_str()).collect();
+    assert_eq!(names, ["Fetch", "Build"]);
+    assert!(outcomes.iter().all(|o| o.status == TaskStatus::Succeeded));
+}
+
+#[test]
+fn test_failure_skips_remaining_tasks() {
+    let err = TaskRunner::new()
+        .task("Fetch", |_| Ok::<(), &str>(()))
+        .task("Build", |ctx| {
+            ctx.logger().debug("compiling 12 crates");
+            ctx.logger().error("linker exited with 1");
+            Err("linker not found")
+        })
+        .task("Test", |_| -> Result<(), &str> { panic!("must not run") })
+        .run()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "task 'Build' failed: linker not found");
+    let statuses: Vec<&TaskStatus> = err.outcomes().iter().map(|o| &o.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            &TaskStatus::Succeeded,
+            &TaskStatus::Failed("linker not found".to_string()),
+            &TaskStatus::Skipped,
+        ]
+    );
+
+    let build = &err.outcomes()[1];
+    assert!(build.log.contains("compiling 12 crates"));
+    assert!(build.log.contains("linker exited with 1"));
+    assert_eq!(err.outcomes()[2].elapsed, Duration::ZERO);
+}
+
+#[test]
+fn test_output_is_kept_with_the_log() {
+    let outcomes = TaskRunner::new()
+        .task("Build", |ctx| {
+            ctx.logger().info("starting");
+            writeln!(ctx.output(), "   Compiling zfish")?;
+            ctx.logger().info("done");
+            Ok::<(), std::io::Error>(())
+        })
+        .run()
+        .unwrap();
+
+    let log = &outcomes[0].log;
+    let compiling = log.find("   Compiling zfish\n").unwrap();
+    assert!(log.find("starting").unwrap() < compiling);
+    assert!(compiling < log.find("done").unwrap());
+}
+
+#[test]
+fn test_panic_is_a_failure() {
+    let err = TaskRunner::new()
+        .task("Explode", |_| -> Result<(), String> { panic!("boom") })
+        .run()
+        .unwrap_err();
+    assert_eq!(
+        err.outcomes()[0].status,
+        TaskStatus::Failed("panicked: boom".to_string())
+    );
+    // The panic message goes to the task's output, not over the checklist
+    assert!(err.outcomes()[0].log.contains("panicked at"));
+    assert!(err.outcomes()[0].log.contains("boom"));
+}
+
+#[test]
+fn test_empty_runner_succeeds() {
+    assert_eq!(TaskRunner::new().run().unwrap(), []);
+}
+
+// ============================================================================
+// PARALLEL TESTS
+// ============================================================================
+
+#[test]
+fn test_jobs_run_tasks_in_parallel() {
+    let running = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let task = |_: &zfish::tasks::TaskContext| {
+        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        running.fetch_sub(1, Ordering::SeqCst);
+        Ok::<(), String>(())
+    };
+
+    let outcomes = TaskRunner::new()
+        .jobs(4)
+        .task("one", task)
+        .task("two", task)
+        .task("three", task)
+        .task("four", task)
+        .run()
+        .unwrap();
+
+    assert_eq!(outcomes.len(), 4);
+    assert!(peak.load(Ordering::SeqCst) > 1);
+}
@@ -45,3 +45,54 @@ fn test_cursor_movement() {
     Terminal::move_cursor(20, 1).unwrap();
     println!("Back at the bottom");
 }
+
+// ============================================================================
+// FRAME TESTS
+// ============================================================================
+
+fn render(frame: &mut zfish::term::Frame) -> String {
+    let mut out = Vec::new();
+    frame.render_to(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_frame_first_render_draws_every_line() {
+    let mut frame = zfish::term::Frame::new();
+    frame.line("one").line("two");
+    assert_eq!(render(&mut frame), "\r\x1b[2Kone\r\n\r\x1b[2Ktwo");
+}
+
+#[test]
+fn test_frame_redraws_only_changed_lines() {
+    let mut frame = zfish::term::Frame::new();
+    frame.line("a: 1").line("b: 1").line("c: 1");
+    render(&mut frame);
+
+    // Unchanged frame: nothing written at all
+    frame.line("a: 1").line("b: 1").line("c: 1");
+    assert_eq!(render(&mut frame), "");
+
+    // Cursor sits on the last row; only the middle line changes
+    frame.line("a: 1").line("b: 2").line("c: 1");
+    assert_eq!(render(&mut frame), "\x1b[1A\r\x1b[2Kb: 2");
+
+    frame.line("a: 2").line("b: 2").line("c: 2");
+    assert_eq!(
+        render(&mut frame),
+        "\x1b[1A\r\x1b[2Ka: 2\x1b[2B\r\x1b[2Kc: 2"
+    );
+}
+
+#[test]
+fn test_frame_grows_and_blanks_dropped_rows() {
+    let mut frame = zfish::term::Frame::new();
+    frame.line("x");
+    render(&mut frame);
+
+    frame.line("x").line("y");
+    assert_eq!(render(&mut frame), "\r\n\r\x1b[2Ky");
+
+    frame.line("x");
+    assert_eq!(render(&mut frame), "\r\x1b[2K");
+}
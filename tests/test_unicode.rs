@@ -0,0 +1,34 @@
+//! Tests for the process-wide width policy
+//!
+//! Kept in their own test binary because the policy is global state.
+
+use zfish::table::Table;
+use zfish::unicode::{WidthCache, WidthPolicy, display_width, set_width_policy, width_policy};
+
+#[test]
+fn test_width_policy_applies_everywhere() {
+    let check = "\u{2714}";
+    let check_emoji = "\u{2714}\u{FE0F}";
+    let mut cache = WidthCache::new();
+
+    assert_eq!(width_policy(), WidthPolicy::Standard);
+    assert_eq!((display_width(check), display_width(check_emoji)), (1, 2));
+    assert_eq!(cache.width(check), 1);
+
+    set_width_policy(WidthPolicy::WideEmoji);
+    assert_eq!(width_policy(), WidthPolicy::WideEmoji);
+    assert_eq!((display_width(check), display_width(check_emoji)), (2, 2));
+    // The cache notices the change instead of serving stale widths
+    assert_eq!(cache.width(check), 2);
+
+    set_width_policy(WidthPolicy::NarrowAmbiguous);
+    assert_eq!((display_width(check), display_width(check_emoji)), (1, 1));
+    // Emoji-style emoji and CJK are not ambiguous
+    assert_eq!(display_width("\u{2705}日本"), 6);
+
+    let mut table = Table::new(vec!["x"]);
+    table.add_row(vec![check_emoji]);
+    assert_eq!(table.column_widths(), [1]);
+
+    set_width_policy(WidthPolicy::Standard);
+}
@@ -0,0 +1,386 @@
+//! Tests for the built-in value parsers and the typed getter
+
+use std::time::Duration;
+use zfish::command::{App, Arg, CommandError};
+use zfish::value_parser::{self, ByteSize, ExistingPath, HumanDuration, Url};
+
+// ============================================================================
+// Duration Tests
+// ============================================================================
+
+#[test]
+fn test_duration_compound() {
+    assert_eq!(
+        value_parser::duration("1h30m").unwrap(),
+        Duration::from_secs(5400)
+    );
+    assert_eq!(
+        value_parser::duration("2d 4h").unwrap(),
+        Duration::from_secs(2 * 86_400 + 4 * 3600)
+    );
+}
+
+#[test]
+fn test_duration_units_and_bare_seconds() {
+    assert_eq!(
+        value_parser::duration("250ms").unwrap(),
+        Duration::from_millis(250)
+    );
+    assert_eq!(
+        value_parser::duration("90").unwrap(),
+        Duration::from_secs(90)
+    );
+    assert_eq!(
+        value_parser::duration("1.5h").unwrap(),
+        Duration::from_secs(5400)
+    );
+    assert_eq!(
+        value_parser::duration("10 minutes").unwrap(),
+        Duration::from_secs(600)
+    );
+}
+
+#[test]
+fn test_duration_invalid() {
+    assert!(value_parser::duration("").is_err());
+    assert!(value_parser::duration("abc").is_err());
+    assert!(value_parser::duration("5 parsecs").is_err());
+    assert!(value_parser::duration("-5s").is_err());
+    assert!(value_parser::duration("-5").is_err());
+    assert!(value_parser::duration("1e3").is_err());
+    assert!(value_parser::duration("1h 30").is_err());
+}
+
+#[test]
+fn test_duration_bare_seconds_are_exact() {
+    assert_eq!(
+        value_parser::duration("9007199254740993").unwrap(),
+        Duration::from_secs(9_007_199_254_740_993)
+    );
+    assert_eq!(
+        value_parser::duration("18446744073709551615").unwrap(),
+        Duration::from_secs(u64::MAX)
+    );
+    assert_eq!(
+        value_parser::duration("0.25").unwrap(),
+        Duration::from_millis(250)
+    );
+    assert!(value_parser::duration("18446744073709551616").is_err());
+}
+
+// ============================================================================
+// Byte Size Tests
+// ============================================================================
+
+#[test]
+fn test_byte_size_binary_and_decimal() {
+    assert_eq!(
+        value_parser::byte_size("512MiB").unwrap(),
+        512 * 1024 * 1024
+    );
+    assert_eq!(value_parser::byte_size("1.5GB").unwrap(), 1_500_000_000);
+    assert_eq!(value_parser::byte_size("4k").unwrap(), 4096);
+    assert_eq!(value_parser::byte_size("1024").unwrap(), 1024);
+    assert_eq!(value_parser::byte_size("10 kb").unwrap(), 10_000);
+}
+
+#[test]
+fn test_byte_size_invalid() {
+    assert!(value_parser::byte_size("").is_err());
+    assert!(value_parser::byte_size("MiB").is_err());
+    assert!(value_parser::byte_size("12 bananas").is_err());
+    assert!(value_parser::byte_size("99999999999PiB").is_err());
+}
+
+#[test]
+fn test_byte_size_u64_boundary() {
+    assert_eq!(
+        value_parser::byte_size("18446744073709551615").unwrap(),
+        u64::MAX
+    );
+    assert_eq!(value_parser::byte_size("16383PiB").unwrap(), 16383 << 50);
+    // 2^64 is one past the largest size
+    assert!(value_parser::byte_size("18446744073709551616").is_err());
+    assert!(value_parser::byte_size("16384PiB").is_err());
+    assert!(value_parser::byte_size("16384.0PiB").is_err());
+}
+
+// ============================================================================
+// URL Tests
+// ============================================================================
+
+#[test]
+fn test_url_components() {
+    let url = value_parser::url("https://user@example.com:8443/api/v1?x=1#top").unwrap();
+    assert_eq!(url.scheme(), "https");
+    assert_eq!(url.user(), Some("user"));
+    assert_eq!(url.host(), "example.com");
+    assert_eq!(url.port(), Some(8443));
+    assert_eq!(url.path(), "/api/v1");
+    assert_eq!(url.query(), Some("x=1"));
+    assert_eq!(url.fragment(), Some("top"));
+    assert_eq!(
+        url.to_string(),
+        "https://user@example.com:8443/api/v1?x=1#top"
+    );
+}
+
+#[test]
+fn test_url_ipv6_and_file() {
+    let url: Url = "http://[::1]:8080/".parse().unwrap();
+    assert_eq!(url.host(), "[::1]");
+    assert_eq!(url.port(), Some(8080));
+
+    let file = value_parser::url("file:///etc/hosts").unwrap();
+    assert_eq!(file.host(), "");
+    assert_eq!(file.path(), "/etc/hosts");
+}
+
+#[test]
+fn test_url_invalid() {
+    assert!(value_parser::url("example.com").is_err());
+    assert!(value_parser::url("https://").is_err());
+    assert!(value_parser::url("https://host:notaport").is_err());
+    assert!(value_parser::url("1http://host").is_err());
+}
+
+// ============================================================================
+// Path Tests
+// ============================================================================
+
+#[test]
+fn test_path_exists() {
+    assert!(value_parser::path_exists("Cargo.toml").is_ok());
+    assert!(value_parser::path_exists("definitely/not/here.txt").is_err());
+    assert!(value_parser::path_exists("").is_err());
+}
+
+// ============================================================================
+// Range Tests
+// ============================================================================
+
+#[test]
+fn test_in_range() {
+    let port = value_parser::in_range(1..=65535u32);
+    assert_eq!(port("8080"), Ok(8080));
+    assert_eq!(
+        port("70000"),
+        Err("70000 is not between 1 and 65535".to_string())
+    );
+    assert!(
+        port("http")
+            .unwrap_err()
+            .starts_with("invalid number 'http'")
+    );
+
+    let ratio = value_parser::in_range(0.0..1.0);
+    assert_eq!(ratio("0.5"), Ok(0.5));
+    assert_eq!(
+        ratio("1"),
+        Err("1 is not at least 0 and less than 1".to_string())
+    );
+    assert_eq!(
+        value_parser::in_range(10..)("3"),
+        Err("3 is not at least 10".to_string())
+    );
+}
+
+// ============================================================================
+// Integration with Arg and ArgMatches
+// ============================================================================
+
+#[test]
+fn test_validate_rejects_at_parse_time() {
+    let app = App::new("test").arg(
+        Arg::new("limit")
+            .long("limit")
+            .validator(value_parser::validate::<ByteSize>),
+    );
+
+    let result = app.try_get_matches_from(vec!["test", "--limit", "lots"]);
+    assert!(matches!(result, Err(CommandError::ValidationError(name, _)) if name == "limit"));
+}
+
+#[test]
+fn test_validator_closure() {
+    let reserved: Vec<String> = ["root", "admin"].map(String::from).into();
+    let app = App::new("test").arg(Arg::new("user").long("user").validator(move |v| {
+        if reserved.iter().any(|r| r == v) {
+            Err(format!("'{}' is reserved", v))
+        } else {
+            Ok(())
+        }
+    }));
+    let app = || app.clone();
+
+    assert!(
+        app()
+            .try_get_matches_from(vec!["test", "--user", "jo"])
+            .is_ok()
+    );
+    let err = app()
+        .try_get_matches_from(vec!["test", "--user", "root"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::ValidationError("user".to_string(), "'root' is reserved".to_string())
+    );
+}
+
+#[test]
+fn test_value_parser_typed_values() {
+    let app = || {
+        App::new("test")
+            .arg(
+                Arg::new("port")
+                    .long("port")
+                    .value_parser(value_parser::in_range(1..=65535u16)),
+            )
+            .arg(
+                Arg::new("config")
+                    .long("config")
+                    .value_parser(value_parser::path_exists),
+            )
+            .arg(
+                Arg::new("timeout")
+                    .long("timeout")
+                    .default_value("30s")
+                    .value_parser(value_parser::duration),
+            )
+            .arg(
+                Arg::new("include")
+                    .long("include")
+                    .value_delimiter(',')
+                    .value_parser(|v: &str| Ok(v.len())),
+            )
+    };
+
+    let matches = app()
+        .try_get_matches_from(vec![
+            "test",
+            "--port",
+            "8080",
+            "--config",
+            "Cargo.toml",
+            "--include",
+            "a,bcd",
+        ])
+        .unwrap();
+    assert_eq!(matches.get_one::<u16>("port"), Some(&8080));
+    assert_eq!(matches.value_of("port"), Some("8080"));
+    assert!(
+        matches
+            .get_one::<std::path::PathBuf>("config")
+            .unwrap()
+            .ends_with("Cargo.toml")
+    );
+    // Defaults go through the parser too
+    assert_eq!(
+        matches.get_one::<Duration>("timeout"),
+        Some(&Duration::from_secs(30))
+    );
+    assert_eq!(matches.get_many::<usize>("include"), [&1, &3]);
+
+    // The wrong type and absent arguments give nothing
+    assert_eq!(matches.get_one::<u32>("port"), None);
+    assert_eq!(matches.get_one::<u16>("missing"), None);
+    assert!(matches.get_many::<u16>("missing").is_empty());
+
+    let err = app()
+        .try_get_matches_from(vec!["test", "--port", "0"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::ValidationError(
+            "port".to_string(),
+            "0 is not between 1 and 65535".to_string()
+        )
+    );
+    assert!(
+        app()
+            .try_get_matches_from(vec!["test", "--config", "missing.toml"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_value_of_t_typed_values() {
+    let app = App::new("test")
+        .arg(Arg::new("timeout").long("timeout"))
+        .arg(Arg::new("limit").long("limit"))
+        .arg(Arg::new("config").long("config"))
+        .arg(Arg::new("port").long("port"));
+
+    let matches = app
+        .try_get_matches_from(vec![
+            "test",
+            "--timeout",
+            "90s",
+            "--limit",
+            "2MiB",
+            "--config",
+            "Cargo.toml",
+            "--port",
+            "8080",
+        ])
+        .unwrap();
+
+    let timeout: HumanDuration = matches.value_of_t("timeout").unwrap();
+    assert_eq!(timeout.as_duration(), Duration::from_secs(90));
+    let limit: ByteSize = matches.value_of_t("limit").unwrap();
+    assert_eq!(limit.as_u64(), 2 * 1024 * 1024);
+    let config: ExistingPath = matches.value_of_t("config").unwrap();
+    assert!(config.as_path().ends_with("Cargo.toml"));
+    let port: u16 = matches.value_of_t("port").unwrap();
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn test_value_of_t_errors() {
+    let app = App::new("test").arg(Arg::new("port").long("port"));
+    let matches = app
+        .try_get_matches_from(vec!["test", "--port", "http"])
+        .unwrap();
+
+    assert_eq!(
+        matches.value_of_t::<u16>("port"),
+        Err(CommandError::InvalidValue(
+            "port".to_string(),
+            "http".to_string()
+        ))
+    );
+    assert_eq!(
+        matches.value_of_t::<u16>("missing"),
+        Err(CommandError::MissingArgument("missing".to_string()))
+    );
+}
+
+#[test]
+fn test_values_of_t() {
+    let app = || {
+        App::new("test").arg(
+            Arg::new("port")
+                .long("port")
+                .multiple(true)
+                .value_delimiter(','),
+        )
+    };
+
+    let matches = app()
+        .try_get_matches_from(vec!["test", "--port", "80,443"])
+        .unwrap();
+    assert_eq!(matches.values_of_t::<u16>("port"), Ok(vec![80, 443]));
+
+    let matches = app()
+        .try_get_matches_from(vec!["test", "--port", "80,http"])
+        .unwrap();
+    let err = matches.values_of_t::<u16>("port").unwrap_err();
+    assert_eq!(
+        err,
+        CommandError::InvalidValue("port".to_string(), "http".to_string())
+    );
+    assert_eq!(err.to_string(), "error: invalid value 'http' for 'port'");
+
+    let matches = app().try_get_matches_from(vec!["test"]).unwrap();
+    assert_eq!(matches.values_of_t::<u16>("port"), Ok(Vec::new()));
+}
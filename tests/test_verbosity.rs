@@ -0,0 +1,42 @@
+//! Tests for the shared verbosity setting
+//!
+//! Everything lives in one test because the setting is process-wide.
+
+use zfish::command::App;
+use zfish::log::Logger;
+use zfish::progress::ProgressBar;
+use zfish::verbosity;
+
+#[test]
+fn test_shared_verbosity_lifecycle() {
+    assert_eq!(verbosity::get(), None);
+    assert!(!verbosity::is_quiet());
+
+    verbosity::set(2);
+    assert_eq!(verbosity::get(), Some(2));
+    verbosity::set(i8::MIN);
+    assert_eq!(verbosity::get(), Some(i8::MIN));
+    verbosity::reset();
+    assert_eq!(verbosity::get(), None);
+
+    // Apps without the convention bundle leave the setting alone
+    App::new("test").try_get_matches_from(vec!["test"]).unwrap();
+    assert_eq!(verbosity::get(), None);
+
+    let app = App::new("test").verbosity_args();
+    app.clone()
+        .try_get_matches_from(vec!["test", "-vv"])
+        .unwrap();
+    assert_eq!(verbosity::get(), Some(2));
+
+    app.try_get_matches_from(vec!["test", "--quiet"]).unwrap();
+    assert!(verbosity::is_quiet());
+
+    // Quiet mode: these must be silent and must not panic
+    Logger::new().info("suppressed");
+    let mut bar = ProgressBar::new(10);
+    bar.inc(5);
+    bar.finish("suppressed");
+
+    verbosity::reset();
+}